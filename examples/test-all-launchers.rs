@@ -291,14 +291,14 @@ async fn create_test_mrpack(mrpack_path: &PathBuf) -> Result<()> {
         }
     });
 
-    zip.start_file("modrinth.index.json", zip::write::FileOptions::default())?;
+    zip.start_file("modrinth.index.json", zip::write::SimpleFileOptions::default())?;
     zip.write_all(serde_json::to_string_pretty(&index)?.as_bytes())?;
 
     // Add overrides
-    zip.add_directory("overrides/", zip::write::FileOptions::default())?;
-    zip.add_directory("overrides/config/", zip::write::FileOptions::default())?;
+    zip.add_directory("overrides/", zip::write::SimpleFileOptions::default())?;
+    zip.add_directory("overrides/config/", zip::write::SimpleFileOptions::default())?;
 
-    zip.start_file("overrides/config/test.toml", zip::write::FileOptions::default())?;
+    zip.start_file("overrides/config/test.toml", zip::write::SimpleFileOptions::default())?;
     zip.write_all(b"# Test configuration file\nenabled = true\n")?;
 
     zip.finish()?;
@@ -336,20 +336,22 @@ async fn print_test_summary(base_dir: &PathBuf) -> Result<()> {
 }
 
 /// Count files recursively in a directory
-async fn count_files_recursive(dir: &PathBuf) -> Result<usize> {
-    let mut count = 0;
-    let mut entries = fs::read_dir(dir).await?;
-
-    while let Some(entry) = entries.next_entry().await? {
-        let path = entry.path();
-        if path.is_dir() {
-            count += count_files_recursive(&path).await?;
-        } else {
-            count += 1;
+fn count_files_recursive(dir: &PathBuf) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<usize>> + '_>> {
+    Box::pin(async move {
+        let mut count = 0;
+        let mut entries = fs::read_dir(dir).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.is_dir() {
+                count += count_files_recursive(&path).await?;
+            } else {
+                count += 1;
+            }
         }
-    }
 
-    Ok(count)
+        Ok(count)
+    })
 }
 
 
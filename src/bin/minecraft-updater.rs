@@ -2,7 +2,13 @@ use std::path::PathBuf;
 use clap::{Parser, Subcommand};
 use serde_json;
 use tracing::{info, error};
-use minecraft_installer::updater::{MinecraftUpdater, InstanceInfo, UpdateResult};
+use minecraft_installer::download::HttpConfig;
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::logging::{self, LogFormat};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+use minecraft_installer::launcher_support::{LauncherManager, LauncherType, OverrideOptions, OverrideOrder, OverridePolicy};
+use minecraft_installer::updater::{confirm_update, infer_modpack_type, pack_supports_loader, MinecraftUpdater, InstanceDiff, InstanceInfo, ModInfo, StdinPrompt, UpdateResult};
 
 #[derive(Parser)]
 #[command(name = "minecraft-updater")]
@@ -11,6 +17,27 @@ use minecraft_installer::updater::{MinecraftUpdater, InstanceInfo, UpdateResult}
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Include mods in subfolders of the mods directory (e.g. mods/optional/)
+    #[arg(long, global = true)]
+    recursive_mods: bool,
+    /// Proxy for GitHub, Modrinth and modpack downloads, e.g. http://proxy:8080 or socks5://127.0.0.1:1080
+    #[arg(long, value_name = "URL", global = true)]
+    proxy: Option<String>,
+    /// Extra directory to search for launchers (repeatable)
+    #[arg(long = "launcher-path", value_name = "DIR", global = true)]
+    launcher_paths: Vec<PathBuf>,
+    /// Log line format (human, json); separate from each command's --format
+    #[arg(long, value_name = "FORMAT", default_value = "human", global = true)]
+    log_format: LogFormat,
+    /// Whether pack overrides replace existing files when updating (overwrite, skip-existing)
+    #[arg(long, value_name = "POLICY", default_value = "skip-existing", global = true)]
+    overrides: OverridePolicy,
+    /// Extract pack overrides before or after the mods are updated (before-mods, after-mods)
+    #[arg(long, value_name = "ORDER", default_value = "before-mods", global = true)]
+    overrides_order: OverrideOrder,
+    /// Stream progress events (one JSON object per line) to stderr while updating
+    #[arg(long, global = true)]
+    progress: bool,
 }
 
 #[derive(Subcommand)]
@@ -20,52 +47,142 @@ enum Commands {
         /// Output format (json, pretty, compact)
         #[arg(short, long, default_value = "compact")]
         format: String,
-        /// Filter by specific launcher (optional)
+        /// Filter by specific launcher (optional, e.g. PrismLauncher, XMCL, ModrinthApp)
         #[arg(long)]
-        launcher: Option<String>,
+        launcher: Option<LauncherType>,
     },
     /// Update mods for a specific instance
     Update {
         /// Path to the instance directory
         #[arg(short, long)]
         instance_path: PathBuf,
-        /// Modpack type (neoforge, fabric)
+        /// Modpack type (neoforge, fabric); detected from the instance's loader when omitted
         #[arg(short, long)]
-        modpack_type: String,
+        modpack_type: Option<String>,
         /// Specific version to download (e.g., "0.0.18", default: latest)
         #[arg(short, long)]
         version: Option<String>,
         /// Output format (json, pretty)
         #[arg(short, long, default_value = "json")]
         format: String,
+        /// Show what would change without modifying the instance
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Interactive instance selection and update
     Interactive {
         /// Modpack type (neoforge, fabric)
         #[arg(short, long)]
         modpack_type: String,
-        /// Specific launcher to use (optional)
+        /// Specific launcher to use (optional, e.g. PrismLauncher, XMCL, ModrinthApp)
         #[arg(long)]
-        launcher: Option<String>,
+        launcher: Option<LauncherType>,
+        /// Apply the update without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Update all instances of a specific modpack type
     UpdateAll {
-        /// Modpack type (neoforge, fabric)
+        /// Modpack type (neoforge, fabric); each instance's own loader decides when omitted
         #[arg(short, long)]
-        modpack_type: String,
+        modpack_type: Option<String>,
+        /// Output format (json, pretty)
+        #[arg(short, long, default_value = "json")]
+        format: String,
+    },
+    /// Check whether a newer updater binary has been released (does not download it)
+    CheckUpdate,
+    /// List every mod in an instance with its version, id and size
+    Mods {
+        /// Path to the instance directory
+        #[arg(short, long)]
+        instance_path: PathBuf,
+        /// Output format (json, pretty)
+        #[arg(short, long, default_value = "json")]
+        format: String,
+    },
+    /// Compare two instances' mods: ones only in either instance and ones in different versions
+    Diff {
+        /// Path to the first instance directory, e.g. the broken one
+        #[arg(long)]
+        instance_a: PathBuf,
+        /// Path to the second instance directory, e.g. a known-good one
+        #[arg(long)]
+        instance_b: PathBuf,
         /// Output format (json, pretty)
         #[arg(short, long, default_value = "json")]
         format: String,
     },
+    /// Check an instance's mods for newer versions on Modrinth (read-only)
+    CheckMods {
+        /// Path to the instance directory
+        #[arg(short, long)]
+        instance_path: PathBuf,
+    },
+    /// Install a single Modrinth mod into an instance
+    InstallMod {
+        /// Path to the instance directory
+        #[arg(short, long)]
+        instance_path: PathBuf,
+        /// Modrinth project slug or id, e.g. sodium
+        #[arg(short, long)]
+        project: String,
+        /// Modrinth version id, or "latest" for the newest compatible version
+        #[arg(short, long, default_value = "latest")]
+        version: String,
+        /// Minecraft version (defaults to the instance's)
+        #[arg(long)]
+        minecraft_version: Option<String>,
+        /// Mod loader, e.g. fabric or neoforge (defaults to the instance's)
+        #[arg(long)]
+        loader: Option<String>,
+    },
+    /// Enable or disable a single mod without deleting it (renames to/from .jar.disabled)
+    ToggleMod {
+        /// Path to the instance directory
+        #[arg(short, long)]
+        instance_path: PathBuf,
+        /// Mod filename, e.g. sodium-0.5.3.jar
+        #[arg(long = "mod", value_name = "FILENAME")]
+        filename: String,
+        /// Re-enable the mod
+        #[arg(long, conflicts_with = "disable", required_unless_present = "disable")]
+        enable: bool,
+        /// Disable the mod
+        #[arg(long)]
+        disable: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
-
     let cli = Cli::parse();
-    let updater = MinecraftUpdater::new();
+
+    // Initialize logging
+    logging::subscriber(cli.log_format, EnvFilter::from_default_env()).init();
+    let mut http = HttpConfig::default();
+    if let Some(proxy) = &cli.proxy {
+        http = http.with_proxy(proxy)?;
+    }
+    let mut updater = MinecraftUpdater::new()
+        .with_http_config(http)
+        .with_recursive_mods(cli.recursive_mods)
+        .with_launcher_search_paths(cli.launcher_paths)
+        .with_override_options(
+            OverrideOptions::update()
+                .with_order(cli.overrides_order)
+                .with_policy(cli.overrides),
+        );
+    let progress = cli.progress.then(|| {
+        let mut events = updater.subscribe_progress();
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                if let Ok(line) = serde_json::to_string(&event) {
+                    eprintln!("{}", line);
+                }
+            }
+        })
+    });
 
     match cli.command {
         Commands::Scan { format, launcher } => {
@@ -73,7 +190,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(mut instances) => {
                     // Filter by launcher if specified
                     if let Some(target_launcher) = launcher {
-                        instances.retain(|instance| instance.launcher_type.to_lowercase() == target_launcher.to_lowercase());
+                        instances.retain(|instance| instance.launcher_type == target_launcher.as_str());
                     }
                     match format.as_str() {
                         "json" => {
@@ -91,13 +208,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                 }
-                Err(e) => {
-                    error!("Failed to scan instances: {}", e);
-                    std::process::exit(1);
-                }
+                Err(e) => exit_with_error(&format, "Failed to scan instances", &e),
             }
         }
-        Commands::Interactive { modpack_type, launcher } => {
+        Commands::Interactive { modpack_type, launcher, yes } => {
             match updater.scan_instances().await {
                 Ok(instances) => {
                     let mut filtered_instances: Vec<_> = instances.iter()
@@ -106,7 +220,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     
                     // Filter by launcher if specified
                     if let Some(target_launcher) = &launcher {
-                        filtered_instances.retain(|instance| instance.launcher_type == *target_launcher);
+                        filtered_instances.retain(|instance| instance.launcher_type == target_launcher.as_str());
                     }
                     
                     if filtered_instances.is_empty() {
@@ -126,14 +240,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let launcher_names: Vec<String> = by_launcher.keys().cloned().collect();
                     for (i, launcher_name) in launcher_names.iter().enumerate() {
                         let count = by_launcher[launcher_name].len();
-                        let icon = match launcher_name.as_str() {
-                            "AstralRinth" => "📱",
-                            "ModrinthApp" => "📱", 
-                            "XMCL" => "📁",
-                            "PrismLauncher" => "📁",
-                            "Official Minecraft" => "📁",
-                            _ => "📦",
-                        };
+                        let icon = launcher_icon(launcher_name);
                         println!("{}. {} {} ({})", i + 1, icon, launcher_name, count);
                     }
                     
@@ -164,12 +271,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             if let Ok(choice2) = input2.trim().parse::<usize>() {
                                 if choice2 > 0 && choice2 <= launcher_instances.len() {
                                     let selected_instance = launcher_instances[choice2 - 1];
+                                    let instance_path = PathBuf::from(&selected_instance.instance_path);
+
+                                    // Show what would change before anything is deleted
+                                    let plan = match updater.plan_instance_update(&instance_path, &modpack_type, None).await {
+                                        Ok(plan) => plan,
+                                        Err(e) => {
+                                            error!("Failed to plan update: {}", e);
+                                            std::process::exit(1);
+                                        }
+                                    };
+                                    if !confirm_update(&plan, &mut StdinPrompt, yes) {
+                                        println!("❌ Update cancelled");
+                                        return Ok(());
+                                    }
+
                                     println!("\n🔄 Updating {}...", selected_instance.name);
-                                    
-                                    match updater.update_instance_mods(
-                                        &PathBuf::from(&selected_instance.instance_path),
-                                        &modpack_type
-                                    ).await {
+                                    match updater.update_instance_mods(&instance_path, &modpack_type).await {
                                         Ok(result) => {
                                             print_update_result_pretty(&result);
                                         }
@@ -196,8 +314,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
-        Commands::Update { instance_path, modpack_type, version, format } => {
-            match updater.update_instance_mods_version(&instance_path, &modpack_type, version.as_deref()).await {
+        Commands::Update { instance_path, modpack_type, version, format, dry_run } => {
+            let modpack_type = match modpack_type {
+                Some(modpack_type) => modpack_type,
+                None => match updater.detect_modpack_type(&instance_path).await {
+                    Ok(detected) => {
+                        println!("🔍 Detected {} instance", detected);
+                        detected.to_string()
+                    }
+                    Err(e) => exit_with_error(&format, "Failed to detect modpack type", &e),
+                },
+            };
+            let update = if dry_run {
+                updater.plan_instance_update(&instance_path, &modpack_type, version.as_deref()).await
+            } else {
+                updater.update_instance_mods_version(&instance_path, &modpack_type, version.as_deref()).await
+            };
+            match update {
                 Ok(result) => {
                     match format.as_str() {
                         "json" => {
@@ -212,10 +345,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                 }
-                Err(e) => {
-                    error!("Failed to update instance: {}", e);
-                    std::process::exit(1);
-                }
+                Err(e) => exit_with_error(&format, "Failed to update instance", &e),
             }
         }
         Commands::UpdateAll { modpack_type, format } => {
@@ -224,17 +354,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let mut results = Vec::new();
 
                     for instance in instances {
+                        // Without an explicit type, every NeoForge and Fabric instance gets its own pack
+                        let instance_type = match &modpack_type {
+                            Some(modpack_type) => modpack_type.as_str(),
+                            None => match infer_modpack_type(&instance.mod_loader) {
+                                Ok(detected) => detected,
+                                Err(_) => continue,
+                            },
+                        };
+
                         // Only update instances that match the modpack type
-                        if should_update_instance(&instance, &modpack_type) {
+                        if should_update_instance(&instance, instance_type) {
                             match updater.update_instance_mods(
                                 &PathBuf::from(&instance.instance_path),
-                                &modpack_type
+                                instance_type
                             ).await {
                                 Ok(result) => results.push(result),
                                 Err(e) => {
                                     error!("Failed to update instance {}: {}", instance.name, e);
                                 }
                             }
+                        } else if instance_type == "neoforge" && instance.mod_loader.eq_ignore_ascii_case("forge") {
+                            println!("⚠️  Skipping {}: Forge instances can't run the NeoForge modpack", instance.name);
                         }
                     }
 
@@ -251,28 +392,138 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                 }
+                Err(e) => exit_with_error(&format, "Failed to scan instances", &e),
+            }
+        }
+        Commands::CheckUpdate => {
+            match updater.check_binary_update().await {
+                Ok(update) => {
+                    println!("{}", serde_json::to_string_pretty(&update)?);
+                }
                 Err(e) => {
-                    error!("Failed to scan instances: {}", e);
+                    error!("Failed to check for updates: {}", e);
                     std::process::exit(1);
                 }
             }
         }
+        Commands::Mods { instance_path, format } => {
+            match updater.list_instance_mods(&instance_path).await {
+                Ok(mods) => {
+                    match format.as_str() {
+                        "json" => {
+                            println!("{}", serde_json::to_string_pretty(&mods)?);
+                        }
+                        "pretty" => {
+                            print_mods_pretty(&mods);
+                        }
+                        _ => {
+                            eprintln!("Invalid format: {}. Use 'json' or 'pretty'", format);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                Err(e) => exit_with_error(&format, "Failed to list mods", &e),
+            }
+        }
+        Commands::Diff { instance_a, instance_b, format } => {
+            match updater.diff_instances(&instance_a, &instance_b).await {
+                Ok(diff) => {
+                    match format.as_str() {
+                        "json" => {
+                            println!("{}", serde_json::to_string_pretty(&diff)?);
+                        }
+                        "pretty" => {
+                            print_instance_diff_pretty(&diff);
+                        }
+                        _ => {
+                            eprintln!("Invalid format: {}. Use 'json' or 'pretty'", format);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                Err(e) => exit_with_error(&format, "Failed to compare instances", &e),
+            }
+        }
+        Commands::CheckMods { instance_path } => {
+            match updater.check_mod_updates(&instance_path).await {
+                Ok(updates) => {
+                    println!("{}", serde_json::to_string_pretty(&updates)?);
+                }
+                Err(e) => {
+                    error!("Failed to check mod updates: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::InstallMod { instance_path, project, version, minecraft_version, loader } => {
+            let instance = if minecraft_version.is_none() || loader.is_none() {
+                updater.analyze_instance(&instance_path).await.ok().flatten()
+            } else {
+                None
+            };
+            let minecraft_version = minecraft_version.or_else(|| instance.as_ref().map(|i| i.minecraft_version.clone()));
+            let loader = loader.or_else(|| instance.as_ref().map(|i| i.mod_loader.to_lowercase()));
+            let (Some(minecraft_version), Some(loader)) = (minecraft_version, loader) else {
+                eprintln!("Could not detect the instance's Minecraft version and loader; pass --minecraft-version and --loader");
+                std::process::exit(1);
+            };
+
+            match LauncherManager::new()
+                .install_modrinth_mod(&instance_path, &project, &version, &minecraft_version, &loader)
+                .await
+            {
+                Ok(path) => {
+                    println!("{}", serde_json::json!({ "project": project, "path": path }));
+                }
+                Err(e) => {
+                    error!("Failed to install mod: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::ToggleMod { instance_path, filename, enable, .. } => {
+            match LauncherManager::new().set_mod_enabled(&instance_path, &filename, enable).await {
+                Ok(path) => {
+                    println!("{}", serde_json::json!({ "enabled": enable, "path": path }));
+                }
+                Err(e) => {
+                    error!("Failed to toggle mod: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    // Closing the stream lets the printer drain what's left before exiting
+    drop(updater);
+    if let Some(printer) = progress {
+        let _ = printer.await;
     }
 
     Ok(())
 }
 
+/// Report a failed command and exit; `--format json` gets an object with a stable `error_code`
+fn exit_with_error(format: &str, context: &str, e: &MinecraftInstallerError) -> ! {
+    if format == "json" {
+        println!("{}", serde_json::json!({ "error_code": e.error_code(), "message": e.to_string() }));
+    } else {
+        error!("{}: {}", context, e);
+    }
+    std::process::exit(1);
+}
+
 /// Determine if an instance should be updated based on modpack type
 fn should_update_instance(instance: &InstanceInfo, modpack_type: &str) -> bool {
-    match modpack_type {
-        "neoforge" => {
-            instance.mod_loader.to_lowercase().contains("neoforge") ||
-            instance.mod_loader.to_lowercase().contains("forge")
-        }
-        "fabric" => {
-            instance.mod_loader.to_lowercase().contains("fabric")
-        }
-        _ => false,
+    pack_supports_loader(modpack_type, &instance.mod_loader)
+}
+
+/// Icon shown next to a launcher's canonical name
+fn launcher_icon(launcher_name: &str) -> &'static str {
+    match launcher_name.parse::<LauncherType>() {
+        Ok(LauncherType::AstralRinth | LauncherType::ModrinthApp) => "📱",
+        Ok(LauncherType::Unknown | LauncherType::Other) | Err(_) => "📦",
+        Ok(_) => "📁",
     }
 }
 
@@ -288,18 +539,12 @@ fn print_instances_compact(instances: &[InstanceInfo]) {
     }
 
     // Sort launchers for consistent display
-    let launcher_order = vec![
-        ("AstralRinth", "📱"),
-        ("ModrinthApp", "📱"), 
-        ("XMCL", "📁"),
-        ("PrismLauncher", "📁"),
-        ("Official Minecraft", "📁"),
-    ];
-    
+    let launcher_order: Vec<&str> = LauncherType::ALL.iter().map(LauncherType::as_str).collect();
+
     // Print known launchers first
-    for (launcher_name, icon) in &launcher_order {
+    for launcher_name in &launcher_order {
         if let Some(launcher_instances) = by_launcher.get(*launcher_name) {
-            println!("{} {} ({})", icon, launcher_name, launcher_instances.len());
+            println!("{} {} ({})", launcher_icon(launcher_name), launcher_name, launcher_instances.len());
             
             for instance in launcher_instances {
                 let mod_loader_info = if let Some(version) = &instance.mod_loader_version {
@@ -321,7 +566,7 @@ fn print_instances_compact(instances: &[InstanceInfo]) {
     
     // Print any remaining launchers
     for (launcher_type, launcher_instances) in &by_launcher {
-        if !launcher_order.iter().any(|(name, _)| name == launcher_type) {
+        if !launcher_order.contains(&launcher_type.as_str()) {
             println!("📦 {} ({})", launcher_type, launcher_instances.len());
             
             for instance in launcher_instances {
@@ -358,14 +603,7 @@ fn print_instances_pretty(instances: &[InstanceInfo]) {
 
     // Print each launcher group
     for (launcher_type, launcher_instances) in by_launcher {
-        let icon = match launcher_type.as_str() {
-            "AstralRinth" => "📱",
-            "ModrinthApp" => "📱", 
-            "XMCL" => "📁",
-            "PrismLauncher" => "📁",
-            "Official Minecraft" => "📁",
-            _ => "📦",
-        };
+        let icon = launcher_icon(&launcher_type);
         
         println!("{} {} ({})", icon, launcher_type, launcher_instances.len());
         println!("{}", "─".repeat(40));
@@ -390,6 +628,46 @@ fn print_instances_pretty(instances: &[InstanceInfo]) {
     }
 }
 
+/// Print an instance's mods in a pretty format
+fn print_mods_pretty(mods: &[ModInfo]) {
+    println!("🧩 Mods ({})", mods.len());
+    println!("═══════════════════════════════════════");
+
+    for mod_info in mods {
+        let origin = if mod_info.is_user_mod { "user" } else { "modpack" };
+        println!("  • {} {}", mod_info.name, mod_info.version.as_deref().unwrap_or("?"));
+        println!("     File: {} ({} KB)", mod_info.filename, mod_info.file_size / 1024);
+        if let Some(mod_id) = &mod_info.mod_id {
+            println!("     Mod ID: {}", mod_id);
+        }
+        println!("     Source: {}", origin);
+    }
+}
+
+/// Print an instance diff in a pretty format
+fn print_instance_diff_pretty(diff: &InstanceDiff) {
+    println!("🔍 Instance Diff");
+    println!("═══════════════════════════════════════");
+
+    println!("\n⬅️  Only in A ({}):", diff.only_in_a.len());
+    for filename in &diff.only_in_a {
+        println!("  • {}", filename);
+    }
+    println!("\n➡️  Only in B ({}):", diff.only_in_b.len());
+    for filename in &diff.only_in_b {
+        println!("  • {}", filename);
+    }
+    println!("\n🔄 Different Versions ({}):", diff.version_differs.len());
+    for mod_diff in &diff.version_differs {
+        println!(
+            "  • {}: {} → {}",
+            mod_diff.name,
+            mod_diff.version_a.as_deref().unwrap_or(&mod_diff.filename_a),
+            mod_diff.version_b.as_deref().unwrap_or(&mod_diff.filename_b)
+        );
+    }
+}
+
 /// Print update result in a pretty format
 fn print_update_result_pretty(result: &UpdateResult) {
     println!("🔄 Update Result: {}", result.instance_name);
@@ -402,6 +680,9 @@ fn print_update_result_pretty(result: &UpdateResult) {
     }
 
     println!("📝 {}", result.message);
+    if result.dry_run {
+        println!("🔍 Dry run: no changes were made");
+    }
 
     if !result.updated_mods.is_empty() {
         println!("\n🔄 Updated Mods ({}):", result.updated_mods.len());
@@ -424,6 +705,20 @@ fn print_update_result_pretty(result: &UpdateResult) {
         }
     }
 
+    if !result.pinned_mods.is_empty() {
+        println!("\n📌 Pinned Mods ({}):", result.pinned_mods.len());
+        for mod_name in &result.pinned_mods {
+            println!("   • {}", mod_name);
+        }
+    }
+
+    if !result.already_current.is_empty() {
+        println!("\n✓ Already Current ({}):", result.already_current.len());
+        for mod_name in &result.already_current {
+            println!("   • {}", mod_name);
+        }
+    }
+
     if !result.errors.is_empty() {
         println!("\n❌ Errors ({}):", result.errors.len());
         for error in &result.errors {
@@ -0,0 +1,114 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::error::{MinecraftInstallerError, Result};
+
+/// Name of the defaults file looked up in the user's config directory
+pub const CONFIG_FILE: &str = "minecraft-installer.toml";
+
+/// Points at a config file to use instead of the default location
+pub const CONFIG_PATH_ENV: &str = "MINECRAFT_INSTALLER_CONFIG";
+
+/// Prefix of the environment variables that override the config file, e.g. `MINECRAFT_INSTALLER_INSTALL_DIR`
+pub const ENV_PREFIX: &str = "MINECRAFT_INSTALLER_";
+
+/// Persistent defaults for options that would otherwise have to be passed on every run
+///
+/// Every field is optional; layers are combined with [`Config::merge`] so the CLI beats the
+/// environment, which beats the file, which beats the built-in defaults.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub install_dir: Option<PathBuf>,
+    /// Launcher name as accepted by `--target-launcher`, e.g. "prism" or "ModrinthApp"
+    pub target_launcher: Option<String>,
+    pub java_home: Option<PathBuf>,
+    /// Parallel mrpack file downloads
+    pub download_concurrency: Option<usize>,
+    pub proxy: Option<String>,
+    /// Download timeout in seconds
+    pub timeout: Option<u64>,
+}
+
+impl Config {
+    /// `minecraft-installer.toml` in the platform config directory, or the path in `MINECRAFT_INSTALLER_CONFIG`
+    pub fn default_path() -> Option<PathBuf> {
+        match std::env::var_os(CONFIG_PATH_ENV).filter(|p| !p.is_empty()) {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir().map(|dir| dir.join("minecraft-installer").join(CONFIG_FILE)),
+        }
+    }
+
+    /// Parse a config file's contents
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| MinecraftInstallerError::Validation(format!("Invalid config: {}", e)))
+    }
+
+    /// Read the config file at `path`; a missing file is an empty config
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!("No config file at {}", path.display());
+                return Ok(Self::default());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Self::from_toml(&content).map_err(|e| {
+            MinecraftInstallerError::Validation(format!("{}: {}", path.display(), e))
+        })
+    }
+
+    /// Overrides from `MINECRAFT_INSTALLER_*` environment variables
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Overrides from `MINECRAFT_INSTALLER_*` pairs; empty values are ignored
+    pub fn from_vars<I, K, V>(vars: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let mut config = Self::default();
+        for (key, value) in vars {
+            let Some(name) = key.as_ref().strip_prefix(ENV_PREFIX) else { continue };
+            let value: String = value.into();
+            if value.is_empty() {
+                continue;
+            }
+
+            match name {
+                "INSTALL_DIR" => config.install_dir = Some(PathBuf::from(value)),
+                "TARGET_LAUNCHER" => config.target_launcher = Some(value),
+                "JAVA_HOME" => config.java_home = Some(PathBuf::from(value)),
+                "DOWNLOAD_CONCURRENCY" => config.download_concurrency = Some(parse_number(key.as_ref(), &value)?),
+                "PROXY" => config.proxy = Some(value),
+                "TIMEOUT" => config.timeout = Some(parse_number(key.as_ref(), &value)?),
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+
+    /// Combine two layers, preferring the values set in `overrides`
+    pub fn merge(self, overrides: Config) -> Config {
+        Config {
+            install_dir: overrides.install_dir.or(self.install_dir),
+            target_launcher: overrides.target_launcher.or(self.target_launcher),
+            java_home: overrides.java_home.or(self.java_home),
+            download_concurrency: overrides.download_concurrency.or(self.download_concurrency),
+            proxy: overrides.proxy.or(self.proxy),
+            timeout: overrides.timeout.or(self.timeout),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T> {
+    value.trim().parse().map_err(|_| {
+        MinecraftInstallerError::Validation(format!("{} must be a number, got '{}'", key, value))
+    })
+}
@@ -2,15 +2,102 @@ use std::path::{Path, PathBuf};
 use tokio::fs;
 use crate::error::{MinecraftInstallerError, Result};
 
+/// Names of the directories `DirectoryManager` lays out; an empty name puts that directory at its parent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryLayout {
+    /// Game directory under the base dir; `""` to install straight into an existing `.minecraft`
+    pub minecraft: String,
+    pub versions: String,
+    pub libraries: String,
+    pub assets: String,
+    pub java: String,
+    pub logs: String,
+    pub instances: String,
+    pub shared_assets: String,
+    /// Scratch space for archives (e.g. Java) that are unpacked and then deleted
+    pub downloads: String,
+}
+
+impl Default for DirectoryLayout {
+    fn default() -> Self {
+        Self {
+            minecraft: "minecraft".to_string(),
+            versions: "versions".to_string(),
+            libraries: "libraries".to_string(),
+            assets: "assets".to_string(),
+            java: "java".to_string(),
+            logs: "logs".to_string(),
+            instances: "instances".to_string(),
+            shared_assets: "shared-assets".to_string(),
+            downloads: "downloads".to_string(),
+        }
+    }
+}
+
+/// `parent/name`, or `parent` itself for an empty name
+fn subdir(parent: PathBuf, name: &str) -> PathBuf {
+    if name.is_empty() {
+        parent
+    } else {
+        parent.join(name)
+    }
+}
+
 /// Directory structure manager for Minecraft installation
 #[derive(Debug, Clone)]
 pub struct DirectoryManager {
     pub base_dir: PathBuf,
+    layout: DirectoryLayout,
+    shared_assets: bool,
+    shared_assets_store: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
 }
 
 impl DirectoryManager {
     pub fn new(base_dir: PathBuf) -> Self {
-        Self { base_dir }
+        Self { base_dir, layout: DirectoryLayout::default(), shared_assets: false, shared_assets_store: None, cache_dir: None }
+    }
+
+    /// Keep regenerable files (the shared asset store, downloaded archives) under `cache_dir` instead of the base dir
+    ///
+    /// Installed data stays under the base dir, so backups of it don't pick up anything that can be downloaded again.
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    /// Use different directory names than the default `minecraft/`, `java/`, `instances/`, ... layout
+    pub fn with_layout(mut self, layout: DirectoryLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// The directory names this manager lays out
+    pub fn layout(&self) -> &DirectoryLayout {
+        &self.layout
+    }
+
+    /// Keep asset objects in a content-addressed store and hardlink them into the assets dir
+    pub fn link_shared_assets(mut self) -> Self {
+        self.shared_assets = true;
+        self
+    }
+
+    /// Like [`Self::link_shared_assets`], with the store at `store` so several install dirs can share it
+    pub fn link_shared_assets_at(mut self, store: PathBuf) -> Self {
+        self.shared_assets = true;
+        self.shared_assets_store = Some(store);
+        self
+    }
+
+    /// Whether asset objects are linked from the shared store
+    pub fn shares_assets(&self) -> bool {
+        self.shared_assets
+    }
+
+    /// Root of the regenerable files; the base dir unless `with_cache_dir` moved them
+    pub fn cache_dir(&self) -> PathBuf {
+        self.cache_dir.clone().unwrap_or_else(|| self.base_dir.clone())
     }
 
     /// Initialize all required directories
@@ -43,12 +130,12 @@ impl DirectoryManager {
 
     /// Get the main Minecraft directory
     pub fn minecraft_dir(&self) -> PathBuf {
-        self.base_dir.join("minecraft")
+        subdir(self.base_dir.clone(), &self.layout.minecraft)
     }
 
     /// Get the versions directory
     pub fn versions_dir(&self) -> PathBuf {
-        self.minecraft_dir().join("versions")
+        subdir(self.minecraft_dir(), &self.layout.versions)
     }
 
     /// Get the directory for a specific version
@@ -61,6 +148,11 @@ impl DirectoryManager {
         self.version_dir(version).join(format!("{}.jar", version))
     }
 
+    /// Dedicated server JAR, run from the version directory
+    pub fn server_jar(&self, version: &str) -> PathBuf {
+        self.version_dir(version).join("server.jar")
+    }
+
     /// Get the version JSON file path
     pub fn version_json(&self, version: &str) -> PathBuf {
         self.version_dir(version).join(format!("{}.json", version))
@@ -68,12 +160,12 @@ impl DirectoryManager {
 
     /// Get the libraries directory
     pub fn libraries_dir(&self) -> PathBuf {
-        self.minecraft_dir().join("libraries")
+        subdir(self.minecraft_dir(), &self.layout.libraries)
     }
 
     /// Get the assets directory
     pub fn assets_dir(&self) -> PathBuf {
-        self.minecraft_dir().join("assets")
+        subdir(self.minecraft_dir(), &self.layout.assets)
     }
 
     /// Get the assets index directory
@@ -86,15 +178,35 @@ impl DirectoryManager {
         self.assets_dir().join("objects")
     }
 
+    /// Get the directory legacy asset indexes are laid out in by asset name
+    pub fn virtual_assets_dir(&self, index_id: &str) -> PathBuf {
+        self.assets_dir().join("virtual").join(index_id)
+    }
+
     /// Get the path for a specific asset object
     pub fn asset_object_path(&self, hash: &str) -> PathBuf {
         let prefix = &hash[..2];
         self.assets_objects_dir().join(prefix).join(hash)
     }
 
+    /// Get the content-addressed store used by `link_shared_assets`; under the cache dir unless `link_shared_assets_at` moved it
+    pub fn shared_assets_dir(&self) -> PathBuf {
+        self.shared_assets_store.clone().unwrap_or_else(|| subdir(self.cache_dir(), &self.layout.shared_assets))
+    }
+
+    /// Get the scratch directory archives are downloaded to before they are unpacked
+    pub fn downloads_dir(&self) -> PathBuf {
+        subdir(self.cache_dir(), &self.layout.downloads)
+    }
+
+    /// Get the path for an asset object in the shared store
+    pub fn shared_asset_path(&self, hash: &str) -> PathBuf {
+        self.shared_assets_dir().join(hash)
+    }
+
     /// Get the Java installations directory
     pub fn java_dir(&self) -> PathBuf {
-        self.base_dir.join("java")
+        subdir(self.base_dir.clone(), &self.layout.java)
     }
 
     /// Get the Java installation directory for a specific version
@@ -104,12 +216,12 @@ impl DirectoryManager {
 
     /// Get the logs directory
     pub fn logs_dir(&self) -> PathBuf {
-        self.base_dir.join("logs")
+        subdir(self.base_dir.clone(), &self.layout.logs)
     }
 
     /// Get the instances directory (for game instances/profiles)
     pub fn instances_dir(&self) -> PathBuf {
-        self.base_dir.join("instances")
+        subdir(self.base_dir.clone(), &self.layout.instances)
     }
 
     /// Get the instance directory for a specific instance
@@ -122,6 +234,11 @@ impl DirectoryManager {
         self.version_dir(version).join("natives")
     }
 
+    /// Lockfile held while an install writes to this directory
+    pub fn install_lock(&self) -> PathBuf {
+        self.base_dir.join(".installer.lock")
+    }
+
     /// Get the launcher profiles file path
     pub fn launcher_profiles(&self) -> PathBuf {
         self.minecraft_dir().join("launcher_profiles.json")
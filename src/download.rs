@@ -1,15 +1,19 @@
-use std::collections::HashMap;
-use std::path::Path;
-use reqwest::Client;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use reqwest::{Client, ClientBuilder};
 use serde::{Deserialize, Serialize};
 use sha1_smol::{Sha1, Digest};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use tracing::{debug, info, warn};
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use tokio_util::sync::CancellationToken;
 
 use crate::error::{MinecraftInstallerError, Result};
 use crate::directories::DirectoryManager;
+use crate::events::{Phase, PhaseTotal, ProgressReporter};
 
 /// Minecraft version manifest from Mojang
 #[derive(Deserialize, Debug)]
@@ -33,6 +37,9 @@ pub struct VersionInfo {
     pub time: String,
     #[serde(rename = "releaseTime")]
     pub release_time: String,
+    /// SHA1 of the version JSON (only present in version_manifest_v2.json)
+    #[serde(default)]
+    pub sha1: Option<String>,
 }
 
 /// Detailed version information
@@ -128,6 +135,12 @@ pub struct Arguments {
 #[derive(Deserialize, Debug)]
 pub struct AssetIndexData {
     pub objects: HashMap<String, AssetObject>,
+    /// Legacy (1.6) indexes: the game reads assets by name from `assets/virtual/<index>/`
+    #[serde(rename = "virtual", default)]
+    pub is_virtual: bool,
+    /// Pre-1.6 indexes: the game reads assets by name from `<game dir>/resources/`
+    #[serde(default)]
+    pub map_to_resources: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -136,24 +149,335 @@ pub struct AssetObject {
     pub size: u64,
 }
 
+/// Result of checking asset objects on disk against a stored asset index
+#[derive(Debug, Default)]
+pub struct AssetVerification {
+    pub checked: usize,
+    /// Asset names whose object file does not exist
+    pub missing: Vec<String>,
+    /// Asset names whose object file has the wrong size or hash
+    pub corrupt: Vec<String>,
+}
+
+impl AssetVerification {
+    /// True when every asset object is present with the expected hash
+    pub fn is_intact(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+}
+
+/// What a planned download is used for
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadKind {
+    Client,
+    Library,
+    Native,
+    AssetIndex,
+    Asset,
+}
+
+/// Which parts of a version are downloaded besides the client jar and version JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadOptions {
+    pub download_assets: bool,
+    pub download_libraries: bool,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self { download_assets: true, download_libraries: true }
+    }
+}
+
+/// A single file the installer would fetch
+#[derive(Serialize, Debug, Clone)]
+pub struct PlannedDownload {
+    pub kind: DownloadKind,
+    /// Destination relative to the `minecraft` directory
+    pub path: String,
+    pub url: String,
+    pub size: u64,
+    pub sha1: String,
+}
+
+/// The full set of files needed to install a version
+#[derive(Serialize, Debug)]
+pub struct DownloadPlan {
+    pub version: String,
+    pub files: Vec<PlannedDownload>,
+}
+
+/// Files of an installed version that are missing or fail their hash check
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    pub checked: usize,
+    pub missing: Vec<PlannedDownload>,
+    pub corrupt: Vec<PlannedDownload>,
+}
+
+impl VerifyReport {
+    /// True when every checked file is present with the expected hash
+    pub fn is_intact(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+}
+
+/// Asset object downloads listed in an asset index, one per unique hash
+fn asset_files(index_data: AssetIndexData) -> Vec<PlannedDownload> {
+    let mut objects: Vec<_> = index_data.objects.into_values().collect();
+    objects.sort_by(|a, b| a.hash.cmp(&b.hash));
+    objects.dedup_by(|a, b| a.hash == b.hash);
+
+    objects.into_iter()
+        .map(|asset| PlannedDownload {
+            kind: DownloadKind::Asset,
+            path: format!("assets/objects/{}/{}", &asset.hash[..2], asset.hash),
+            url: format!("https://resources.download.minecraft.net/{}/{}", &asset.hash[..2], asset.hash),
+            size: asset.size,
+            sha1: asset.hash,
+        })
+        .collect()
+}
+
+/// Connect and whole-request limits for the HTTP clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpTimeouts {
+    pub connect: Duration,
+    pub total: Duration,
+}
+
+impl Default for HttpTimeouts {
+    fn default() -> Self {
+        Self { connect: Duration::from_secs(10), total: Duration::from_secs(300) }
+    }
+}
+
+impl HttpTimeouts {
+    /// Apply both limits to a client builder
+    pub fn apply(&self, builder: ClientBuilder) -> ClientBuilder {
+        builder.connect_timeout(self.connect).timeout(self.total)
+    }
+}
+
+/// Token bucket that caps the combined speed of every download sharing it
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bytes_per_sec: u64,
+    bucket: Arc<tokio::sync::Mutex<(f64, Instant)>>,
+}
+
+impl RateLimiter {
+    /// Allow `bytes_per_sec` on average, with bursts of up to one second's worth; the bucket starts full
+    pub fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1);
+        Self { bytes_per_sec, bucket: Arc::new(tokio::sync::Mutex::new((bytes_per_sec as f64, Instant::now()))) }
+    }
+
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// Wait until `bytes` more may be taken; the lock is held while waiting so callers queue up in turn
+    pub async fn acquire(&self, bytes: u64) {
+        let rate = self.bytes_per_sec as f64;
+        let mut bucket = self.bucket.lock().await;
+        let (tokens, last) = &mut *bucket;
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * rate).min(rate);
+        *last = now;
+
+        *tokens -= bytes as f64;
+        if *tokens < 0.0 {
+            tokio::time::sleep(Duration::from_secs_f64(-*tokens / rate)).await;
+            *tokens = 0.0;
+            *last = Instant::now();
+        }
+    }
+}
+
+/// Timeouts and proxy applied to every HTTP client (Mojang, Adoptium, Modrinth, Fabric and GitHub)
+///
+/// Without an explicit proxy, clients honour `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY`.
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+    pub timeouts: HttpTimeouts,
+    proxy: Option<reqwest::Proxy>,
+    rate_limit: Option<RateLimiter>,
+}
+
+impl HttpConfig {
+    pub fn with_timeouts(mut self, timeouts: HttpTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Route all traffic through an `http://`, `https://`, `socks5://` or `socks5h://` proxy
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|e| {
+            MinecraftInstallerError::Validation(format!("Invalid proxy URL {}: {}", proxy_url, e))
+        })?;
+        self.proxy = Some(proxy);
+        Ok(self)
+    }
+
+    pub fn has_proxy(&self) -> bool {
+        self.proxy.is_some()
+    }
+
+    /// Cap Mojang and Adoptium downloads at `bytes_per_sec` combined; 0 means unlimited.
+    /// Clones of this config share one budget.
+    pub fn with_max_rate(mut self, bytes_per_sec: u64) -> Self {
+        self.rate_limit = (bytes_per_sec > 0).then(|| RateLimiter::new(bytes_per_sec));
+        self
+    }
+
+    pub fn rate_limit(&self) -> Option<&RateLimiter> {
+        self.rate_limit.as_ref()
+    }
+
+    /// Apply the timeouts and proxy to a client builder
+    pub fn apply(&self, builder: ClientBuilder) -> ClientBuilder {
+        let builder = self.timeouts.apply(builder);
+        match &self.proxy {
+            Some(proxy) => builder.proxy(proxy.clone()),
+            None => builder,
+        }
+    }
+
+    /// Build a client with the timeouts and proxy; every HTTP client is made here and requests may still override the User-Agent
+    pub fn client(&self) -> Client {
+        self.apply(Client::builder().user_agent("MinecraftInstaller/0.1.0")).build().expect("Failed to create HTTP client")
+    }
+}
+
 /// Download manager for Minecraft files
 pub struct DownloadManager {
     client: Client,
     dirs: DirectoryManager,
+    offline_cache: Option<PathBuf>,
+    progress: ProgressReporter,
+    cancel: CancellationToken,
+    http: HttpConfig,
+    /// Hash every asset object on install instead of trusting ones already on disk at the indexed size
+    rehash_assets: bool,
+    bars: MultiProgress,
+    /// Byte progress across every phase of the current install
+    overall: ProgressBar,
 }
 
 impl DownloadManager {
     pub fn new(dirs: DirectoryManager) -> Self {
-        let client = Client::builder()
-            .user_agent("MinecraftInstaller/0.1.0")
-            .build()
-            .expect("Failed to create HTTP client");
+        Self {
+            client: HttpConfig::default().client(),
+            dirs,
+            offline_cache: None,
+            progress: ProgressReporter::default(),
+            cancel: CancellationToken::new(),
+            http: HttpConfig::default(),
+            rehash_assets: false,
+            bars: MultiProgress::new(),
+            overall: ProgressBar::hidden(),
+        }
+    }
+
+    /// Replace the default connect (10s) and total (300s) request timeouts
+    pub fn with_timeouts(self, timeouts: HttpTimeouts) -> Self {
+        let http = self.http.clone().with_timeouts(timeouts);
+        self.with_http_config(&http)
+    }
+
+    /// Replace the default timeouts, proxy settings and rate limit
+    pub fn with_http_config(mut self, http: &HttpConfig) -> Self {
+        self.client = http.client();
+        self.http = http.clone();
+        self
+    }
+
+    /// Resolve every download from a local cache instead of the network.
+    ///
+    /// Files are looked up as `<cache>/<sha1>`; the version manifest is read from
+    /// `<cache>/version_manifest.json` and must carry per-version `sha1`s (v2 format).
+    pub fn with_offline_cache(mut self, cache_dir: PathBuf) -> Self {
+        self.offline_cache = Some(cache_dir);
+        self
+    }
+
+    /// Store asset objects once under `<base>/shared-assets` and hardlink them into place
+    pub fn with_shared_assets(mut self) -> Self {
+        self.dirs = self.dirs.link_shared_assets();
+        self
+    }
+
+    /// Like [`Self::with_shared_assets`], with the store at `store`
+    pub fn with_shared_assets_at(mut self, store: PathBuf) -> Self {
+        self.dirs = self.dirs.link_shared_assets_at(store);
+        self
+    }
 
-        Self { client, dirs }
+    /// Re-hash asset objects that are already present instead of only checking their size
+    pub fn with_asset_rehash(mut self) -> Self {
+        self.rehash_assets = true;
+        self
+    }
+
+    /// Where client, library and asset progress events are sent
+    pub fn set_progress_reporter(&mut self, progress: ProgressReporter) {
+        self.progress = progress;
+    }
+
+    /// Abort downloads with [`MinecraftInstallerError::Cancelled`] once `cancel` is tripped
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<()> {
+        if self.cancel.is_cancelled() {
+            return Err(MinecraftInstallerError::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Fail with every hash that is missing from the offline cache, if one is configured
+    fn check_offline_cache<'a>(&self, hashes: impl IntoIterator<Item = &'a str>) -> Result<()> {
+        let Some(cache_dir) = &self.offline_cache else {
+            return Ok(());
+        };
+
+        let mut missing: Vec<&str> = hashes
+            .into_iter()
+            .filter(|hash| !cache_dir.join(hash).is_file())
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        missing.sort_unstable();
+        missing.dedup();
+        Err(MinecraftInstallerError::DownloadFailed(format!(
+            "{} file(s) missing from offline cache {}: {}",
+            missing.len(),
+            cache_dir.display(),
+            missing.join(", ")
+        )))
     }
 
     /// Get the version manifest from Mojang
     pub async fn get_version_manifest(&self) -> Result<VersionManifest> {
+        if let Some(cache_dir) = &self.offline_cache {
+            let manifest_path = cache_dir.join("version_manifest.json");
+            info!("Reading Minecraft version manifest from {}", manifest_path.display());
+            let data = fs::read(&manifest_path).await.map_err(|e| {
+                MinecraftInstallerError::DownloadFailed(format!(
+                    "Version manifest not found in offline cache at {}: {}",
+                    manifest_path.display(),
+                    e
+                ))
+            })?;
+            return Ok(serde_json::from_slice(&data)?);
+        }
+
         info!("Fetching Minecraft version manifest...");
         let url = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
 
@@ -172,6 +496,18 @@ impl DownloadManager {
 
     /// Get detailed version information
     pub async fn get_version_details(&self, version_info: &VersionInfo) -> Result<VersionDetails> {
+        if let Some(cache_dir) = &self.offline_cache {
+            let sha1 = version_info.sha1.as_deref().ok_or_else(|| {
+                MinecraftInstallerError::DownloadFailed(format!(
+                    "Offline manifest has no sha1 for {}; use version_manifest_v2.json",
+                    version_info.id
+                ))
+            })?;
+            self.check_offline_cache([sha1])?;
+            let data = fs::read(cache_dir.join(sha1)).await?;
+            return Ok(serde_json::from_slice(&data)?);
+        }
+
         info!("Fetching details for Minecraft {}...", version_info.id);
 
         let response = self.client.get(&version_info.url).send().await?;
@@ -186,6 +522,204 @@ impl DownloadManager {
         Ok(details)
     }
 
+    /// Fetch and parse an asset index without writing it to disk
+    async fn fetch_asset_index(&self, asset_index: &AssetIndex) -> Result<AssetIndexData> {
+        let index_path = self.dirs.assets_index_dir().join(format!("{}.json", asset_index.id));
+        if index_path.exists() && self.calculate_sha1(&index_path).await? == asset_index.sha1 {
+            return Ok(serde_json::from_slice(&fs::read(index_path).await?)?);
+        }
+
+        if let Some(cache_dir) = &self.offline_cache {
+            self.check_offline_cache([asset_index.sha1.as_str()])?;
+            return Ok(serde_json::from_slice(&fs::read(cache_dir.join(&asset_index.sha1)).await?)?);
+        }
+
+        let response = self.client.get(&asset_index.url).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Failed to fetch asset index {}: HTTP {}",
+                asset_index.id,
+                response.status()
+            )));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// The client, library, native and asset index files for a version
+    fn version_files(&self, version_details: &VersionDetails) -> Vec<PlannedDownload> {
+        let id = &version_details.id;
+        let client = &version_details.downloads.client;
+        let mut files = vec![PlannedDownload {
+            kind: DownloadKind::Client,
+            path: format!("versions/{}/{}.jar", id, id),
+            url: client.url.clone(),
+            size: client.size,
+            sha1: client.sha1.clone(),
+        }];
+
+        let native_key = format!("natives-{}", self.get_os_name());
+        for library in version_details.libraries.iter().filter(|l| self.should_include_library(l)) {
+            if let Some(artifact) = &library.downloads.artifact {
+                files.push(PlannedDownload {
+                    kind: DownloadKind::Library,
+                    path: format!("libraries/{}", artifact.path),
+                    url: artifact.url.clone(),
+                    size: artifact.size,
+                    sha1: artifact.sha1.clone(),
+                });
+            }
+            if let Some(native) = library.downloads.classifiers.as_ref().and_then(|c| c.get(&native_key)) {
+                files.push(PlannedDownload {
+                    kind: DownloadKind::Native,
+                    path: format!("versions/{}/natives/{}.jar", id, library.name.replace(':', "_")),
+                    url: native.url.clone(),
+                    size: native.size,
+                    sha1: native.sha1.clone(),
+                });
+            }
+        }
+
+        let asset_index = &version_details.asset_index;
+        files.push(PlannedDownload {
+            kind: DownloadKind::AssetIndex,
+            path: format!("assets/indexes/{}.json", asset_index.id),
+            url: asset_index.url.clone(),
+            size: asset_index.size,
+            sha1: asset_index.sha1.clone(),
+        });
+
+        files
+    }
+
+    /// Bytes each phase of an install with `options` downloads, read from the version JSON alone
+    pub fn phase_totals(&self, version_details: &VersionDetails, options: DownloadOptions) -> Vec<PhaseTotal> {
+        let files = self.version_files(version_details);
+        let sum = |kinds: &[DownloadKind]| -> u64 {
+            files.iter().filter(|file| kinds.contains(&file.kind)).map(|file| file.size).sum()
+        };
+
+        let mut totals = vec![PhaseTotal { phase: Phase::Client, bytes: sum(&[DownloadKind::Client]) }];
+        if options.download_libraries {
+            totals.push(PhaseTotal { phase: Phase::Libraries, bytes: sum(&[DownloadKind::Library, DownloadKind::Native]) });
+        }
+        if options.download_assets {
+            let asset_index = &version_details.asset_index;
+            totals.push(PhaseTotal { phase: Phase::Assets, bytes: asset_index.size + asset_index.total_size });
+        }
+        totals
+    }
+
+    /// Show one overall bar above the per-phase bars and report the planned totals
+    pub fn start_overall_progress(&self, version_details: &VersionDetails, options: DownloadOptions) {
+        let totals = self.phase_totals(version_details, options);
+        self.overall.reset();
+        self.overall.set_length(totals.iter().map(|total| total.bytes).sum());
+        self.overall.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.green/white}] {bytes}/{total_bytes} ({eta})")
+                .expect("Invalid progress bar template")
+                .progress_chars("#>-"),
+        );
+        self.overall.set_message(format!("Total {}", version_details.id));
+        self.bars.add(self.overall.clone());
+        self.progress.for_instance(&version_details.id).planned(totals);
+    }
+
+    /// Complete the overall bar started by `start_overall_progress`
+    pub fn finish_overall_progress(&self) {
+        self.overall.finish_with_message("✓ All components downloaded");
+        self.bars.remove(&self.overall);
+    }
+
+    /// Resolve every file `download_client`, `download_libraries` and `download_assets` would fetch
+    pub async fn download_plan(&self, version_details: &VersionDetails) -> Result<DownloadPlan> {
+        self.download_plan_with_options(version_details, DownloadOptions::default()).await
+    }
+
+    /// Resolve the files an install with `options` fetches; the asset index is only read when assets are included
+    pub async fn download_plan_with_options(
+        &self,
+        version_details: &VersionDetails,
+        options: DownloadOptions,
+    ) -> Result<DownloadPlan> {
+        let mut files = self.version_files(version_details);
+        files.retain(|file| match file.kind {
+            DownloadKind::Library | DownloadKind::Native => options.download_libraries,
+            DownloadKind::AssetIndex | DownloadKind::Asset => options.download_assets,
+            DownloadKind::Client => true,
+        });
+        if options.download_assets {
+            let index_data = self.fetch_asset_index(&version_details.asset_index).await?;
+            files.extend(asset_files(index_data));
+        }
+
+        Ok(DownloadPlan { version: version_details.id.clone(), files })
+    }
+
+    /// Re-hash an installed version's client, libraries, asset index and asset objects.
+    ///
+    /// Natives are skipped because their jars are deleted after extraction. Asset objects
+    /// are only checked when the stored asset index itself is intact.
+    pub async fn verify_installation(&self, version_details: &VersionDetails) -> Result<VerifyReport> {
+        let minecraft_dir = self.dirs.minecraft_dir();
+        let mut report = VerifyReport::default();
+        let mut files: Vec<_> = self.version_files(version_details)
+            .into_iter()
+            .filter(|f| f.kind != DownloadKind::Native)
+            .collect();
+
+        let index_path = self.dirs.assets_index_dir().join(format!("{}.json", version_details.asset_index.id));
+        if index_path.exists() && self.calculate_sha1(&index_path).await? == version_details.asset_index.sha1 {
+            let index_data: AssetIndexData = serde_json::from_slice(&fs::read(&index_path).await?)?;
+            files.extend(asset_files(index_data));
+        }
+
+        for file in files {
+            report.checked += 1;
+            let path = minecraft_dir.join(&file.path);
+
+            let metadata = match fs::metadata(&path).await {
+                Ok(metadata) => metadata,
+                Err(_) => {
+                    report.missing.push(file);
+                    continue;
+                }
+            };
+
+            if metadata.len() != file.size || self.calculate_sha1(&path).await? != file.sha1 {
+                debug!("Corrupt file {}", path.display());
+                report.corrupt.push(file);
+            }
+        }
+
+        info!(
+            "Verified {} files for {}: {} missing, {} corrupt",
+            report.checked,
+            version_details.id,
+            report.missing.len(),
+            report.corrupt.len()
+        );
+        Ok(report)
+    }
+
+    /// Re-download the missing and corrupt files from a verification report
+    pub async fn repair(&self, report: &VerifyReport) -> Result<()> {
+        let bad_files: Vec<_> = report.missing.iter().chain(&report.corrupt).collect();
+        self.check_offline_cache(bad_files.iter().map(|f| f.sha1.as_str()))?;
+
+        let minecraft_dir = self.dirs.minecraft_dir();
+        for file in bad_files {
+            info!("Repairing {}", file.path);
+            self.download_file_with_progress(&file.url, &minecraft_dir.join(&file.path), Some(&file.sha1), None).await?;
+        }
+        Ok(())
+    }
+
+    /// Download `url` to `path`, checking it against `expected_sha1` when one is published
+    pub async fn download_to(&self, url: &str, path: &Path, expected_sha1: Option<&str>) -> Result<()> {
+        self.download_file_with_progress(url, path, expected_sha1, None).await
+    }
+
     /// Download a file with progress tracking
     async fn download_file_with_progress(
         &self,
@@ -194,6 +728,8 @@ impl DownloadManager {
         expected_sha1: Option<&str>,
         progress_bar: Option<&ProgressBar>,
     ) -> Result<()> {
+        self.check_cancelled()?;
+
         // Check if file already exists and is valid
         if let Some(sha1) = expected_sha1 {
             if path.exists() {
@@ -213,48 +749,75 @@ impl DownloadManager {
             fs::create_dir_all(parent).await?;
         }
 
-        // Download the file
-        let response = self.client.get(url).send().await?;
-        if !response.status().is_success() {
-            return Err(MinecraftInstallerError::DownloadFailed(format!(
-                "HTTP {} for {}",
-                response.status(),
-                url
-            )));
-        }
-
-        let total_size = response.content_length().unwrap_or(0);
-        if let Some(pb) = progress_bar {
-            pb.set_length(total_size);
-        }
+        if let Some(cache_dir) = &self.offline_cache {
+            let sha1 = expected_sha1.ok_or_else(|| {
+                MinecraftInstallerError::DownloadFailed(format!("No SHA1 to resolve {} offline", url))
+            })?;
+            self.check_offline_cache([sha1])?;
+            let size = fs::copy(cache_dir.join(sha1), path).await?;
+            if let Some(pb) = progress_bar {
+                pb.set_length(size);
+                pb.set_position(size);
+            }
+        } else {
+            // Download the file
+            let response = tokio::select! {
+                response = self.client.get(url).send() => response?,
+                _ = self.cancel.cancelled() => return Err(MinecraftInstallerError::Cancelled),
+            };
+            if !response.status().is_success() {
+                return Err(MinecraftInstallerError::DownloadFailed(format!(
+                    "HTTP {} for {}",
+                    response.status(),
+                    url
+                )));
+            }
 
-        let mut file = fs::File::create(path).await?;
-        let mut downloaded = 0u64;
-        let mut stream = response.bytes_stream();
+            let total_size = response.content_length().unwrap_or(0);
+            if let Some(pb) = progress_bar {
+                pb.set_length(total_size);
+            }
 
-        use futures::StreamExt;
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            file.write_all(&chunk).await?;
-            downloaded += chunk.len() as u64;
+            let mut file = fs::File::create(path).await?;
+            let mut downloaded = 0u64;
+            let mut stream = response.bytes_stream();
+
+            use futures::StreamExt;
+            loop {
+                let chunk = tokio::select! {
+                    chunk = stream.next() => chunk,
+                    _ = self.cancel.cancelled() => {
+                        // Don't leave a truncated file behind for the next run to trip over
+                        drop(file);
+                        let _ = fs::remove_file(path).await;
+                        return Err(MinecraftInstallerError::Cancelled);
+                    }
+                };
+                let Some(chunk) = chunk else { break };
+                let chunk = chunk?;
+                if let Some(limit) = self.http.rate_limit() {
+                    limit.acquire(chunk.len() as u64).await;
+                }
+                file.write_all(&chunk).await?;
+                downloaded += chunk.len() as u64;
 
-            if let Some(pb) = progress_bar {
-                pb.set_position(downloaded);
+                if let Some(pb) = progress_bar {
+                    pb.set_position(downloaded);
+                }
             }
-        }
 
-        file.sync_all().await?;
+            file.sync_all().await?;
+        }
 
         // Verify SHA1 if provided
         if let Some(expected_sha1) = expected_sha1 {
             let actual_sha1 = self.calculate_sha1(path).await?;
             if actual_sha1 != expected_sha1 {
-                return Err(MinecraftInstallerError::Validation(format!(
-                    "SHA1 mismatch for {}: expected {}, got {}",
-                    path.display(),
-                    expected_sha1,
-                    actual_sha1
-                )));
+                return Err(MinecraftInstallerError::HashMismatch {
+                    path: path.to_path_buf(),
+                    expected: expected_sha1.to_string(),
+                    actual: actual_sha1,
+                });
             }
         }
 
@@ -272,11 +835,13 @@ impl DownloadManager {
     /// Download the Minecraft client
     pub async fn download_client(&self, version_details: &VersionDetails) -> Result<()> {
         info!("Downloading Minecraft client {}...", version_details.id);
+        let progress = self.progress.for_instance(&version_details.id);
+        progress.phase_started(Phase::Client);
 
         let client_download = &version_details.downloads.client;
         let jar_path = self.dirs.version_jar(&version_details.id);
 
-        let progress_bar = ProgressBar::new(client_download.size);
+        let progress_bar = self.bars.add(ProgressBar::new(client_download.size));
         progress_bar.set_style(
             ProgressStyle::default_bar()
                 .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
@@ -293,18 +858,56 @@ impl DownloadManager {
         ).await?;
 
         progress_bar.finish_with_message(format!("✓ Client {} downloaded", version_details.id));
+        self.overall.inc(client_download.size);
+        progress.progress(Phase::Client, Some(jar_path.display().to_string()), client_download.size, 1, 1);
 
         // Save version JSON
         let version_json_path = self.dirs.version_json(&version_details.id);
         let version_json = serde_json::to_string_pretty(version_details)?;
         fs::write(version_json_path, version_json).await?;
 
+        progress.phase_completed(Phase::Client);
         Ok(())
     }
 
+    /// Download the dedicated server JAR into the version directory
+    pub async fn download_server(&self, version_details: &VersionDetails) -> Result<PathBuf> {
+        let server_download = version_details.downloads.server.as_ref().ok_or_else(|| {
+            MinecraftInstallerError::InvalidVersion(format!("Minecraft {} has no server download", version_details.id))
+        })?;
+
+        info!("Downloading Minecraft server {}...", version_details.id);
+        let progress = self.progress.for_instance(&version_details.id);
+        progress.phase_started(Phase::Server);
+
+        let jar_path = self.dirs.server_jar(&version_details.id);
+        let progress_bar = self.bars.add(ProgressBar::new(server_download.size));
+        progress_bar.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
+                .expect("Invalid progress bar template")
+                .progress_chars("#>-"),
+        );
+        progress_bar.set_message(format!("Server {}", version_details.id));
+
+        self.download_file_with_progress(
+            &server_download.url,
+            &jar_path,
+            Some(&server_download.sha1),
+            Some(&progress_bar),
+        ).await?;
+
+        progress_bar.finish_with_message(format!("✓ Server {} downloaded", version_details.id));
+        progress.progress(Phase::Server, Some(jar_path.display().to_string()), server_download.size, 1, 1);
+        progress.phase_completed(Phase::Server);
+        Ok(jar_path)
+    }
+
     /// Download libraries
     pub async fn download_libraries(&self, version_details: &VersionDetails) -> Result<()> {
         info!("Downloading libraries for {}...", version_details.id);
+        let progress = self.progress.for_instance(&version_details.id);
+        progress.phase_started(Phase::Libraries);
 
         let mut valid_libraries = Vec::new();
         for library in &version_details.libraries {
@@ -312,13 +915,27 @@ impl DownloadManager {
                 valid_libraries.push(library);
             }
         }
+        // Rule-excluded libraries are skipped on purpose; everything left is required to launch
+        let excluded = version_details.libraries.len() - valid_libraries.len();
+        if excluded > 0 {
+            debug!("Skipping {} libraries excluded by rules on {}", excluded, self.get_os_name());
+        }
 
         if valid_libraries.is_empty() {
             info!("No libraries to download");
+            progress.phase_completed(Phase::Libraries);
             return Ok(());
         }
 
-        let progress_bar = ProgressBar::new(valid_libraries.len() as u64);
+        let os_name = self.get_os_name();
+        let native_key = format!("natives-{}", os_name);
+        self.check_offline_cache(valid_libraries.iter().flat_map(|library| {
+            let artifact = library.downloads.artifact.as_ref();
+            let native = library.downloads.classifiers.as_ref().and_then(|c| c.get(&native_key));
+            artifact.into_iter().chain(native).map(|a| a.sha1.as_str())
+        }))?;
+
+        let progress_bar = self.bars.add(ProgressBar::new(valid_libraries.len() as u64));
         progress_bar.set_style(
             ProgressStyle::default_bar()
                 .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} libraries")
@@ -327,7 +944,11 @@ impl DownloadManager {
         );
         progress_bar.set_message("Libraries");
 
-        for library in valid_libraries {
+        let total = valid_libraries.len() as u64;
+        let mut failed = Vec::new();
+        for (i, library) in valid_libraries.into_iter().enumerate() {
+            self.check_cancelled()?;
+            let mut bytes = 0;
             if let Some(artifact) = &library.downloads.artifact {
                 let lib_path = self.dirs.libraries_dir().join(&artifact.path);
 
@@ -337,15 +958,20 @@ impl DownloadManager {
                     Some(&artifact.sha1),
                     None,
                 ).await {
-                    Ok(_) => debug!("Downloaded library: {}", library.name),
-                    Err(e) => warn!("Failed to download library {}: {}", library.name, e),
+                    Ok(_) => {
+                        debug!("Downloaded library: {}", library.name);
+                        bytes += artifact.size;
+                    }
+                    Err(e) => {
+                        warn!("Failed to download library {}: {}", library.name, e);
+                        failed.push(format!("{} ({})", library.name, e));
+                    }
                 }
             }
 
             // Download natives if present
             if let Some(classifiers) = &library.downloads.classifiers {
-                let os_name = self.get_os_name();
-                if let Some(native) = classifiers.get(&format!("natives-{}", os_name)) {
+                if let Some(native) = classifiers.get(&native_key) {
                     let natives_dir = self.dirs.natives_dir(&version_details.id);
                     let native_path = natives_dir.join(format!("{}.jar", library.name.replace(':', "_")));
 
@@ -356,26 +982,47 @@ impl DownloadManager {
                         None,
                     ).await {
                         Ok(_) => {
+                            bytes += native.size;
                             // Extract native library
                             if let Err(e) = self.extract_native(&native_path, &natives_dir).await {
                                 warn!("Failed to extract native {}: {}", library.name, e);
                             }
                         }
-                        Err(e) => warn!("Failed to download native {}: {}", library.name, e),
+                        Err(e) => {
+                            warn!("Failed to download native {}: {}", library.name, e);
+                            failed.push(format!("{} natives ({})", library.name, e));
+                        }
                     }
                 }
             }
 
             progress_bar.inc(1);
+            self.overall.inc(bytes);
+            progress.progress(Phase::Libraries, Some(library.name.clone()), bytes, i as u64 + 1, total);
+        }
+        self.check_cancelled()?;
+
+        // The game can't start without them, so don't report the install as done
+        if !failed.is_empty() {
+            progress_bar.abandon_with_message("✗ Libraries incomplete");
+            return Err(MinecraftInstallerError::DownloadFailed(format!(
+                "{} required librar{} failed: {}",
+                failed.len(),
+                if failed.len() == 1 { "y" } else { "ies" },
+                failed.join(", ")
+            )));
         }
 
         progress_bar.finish_with_message("✓ Libraries downloaded");
+        progress.phase_completed(Phase::Libraries);
         Ok(())
     }
 
     /// Download assets
     pub async fn download_assets(&self, version_details: &VersionDetails) -> Result<()> {
         info!("Downloading assets for {}...", version_details.id);
+        let progress = self.progress.for_instance(&version_details.id);
+        progress.phase_started(Phase::Assets);
 
         // Download asset index
         let asset_index = &version_details.asset_index;
@@ -387,16 +1034,44 @@ impl DownloadManager {
             Some(&asset_index.sha1),
             None,
         ).await?;
+        self.overall.inc(asset_index.size);
 
         // Parse asset index
         let index_data: AssetIndexData = serde_json::from_slice(&fs::read(index_path).await?)?;
 
         if index_data.objects.is_empty() {
             info!("No assets to download");
+            progress.phase_completed(Phase::Assets);
             return Ok(());
         }
 
-        let progress_bar = ProgressBar::new(index_data.objects.len() as u64);
+        // Objects already on disk at the indexed size are taken as installed without hashing them,
+        // which makes re-running an install cheap; --rehash-assets opts back into full checks
+        let mut present = HashSet::new();
+        if !self.rehash_assets {
+            for (name, asset) in &index_data.objects {
+                let mut paths = vec![self.dirs.asset_object_path(&asset.hash)];
+                if self.dirs.shares_assets() {
+                    paths.push(self.dirs.shared_asset_path(&asset.hash));
+                }
+                let mut on_disk = true;
+                for path in &paths {
+                    on_disk &= fs::metadata(path).await.is_ok_and(|m| m.is_file() && m.len() == asset.size);
+                }
+                if on_disk {
+                    present.insert(name.clone());
+                }
+            }
+            debug!("{} of {} assets already present", present.len(), index_data.objects.len());
+        }
+
+        self.check_offline_cache(
+            index_data.objects.iter()
+                .filter(|(name, _)| !present.contains(*name))
+                .map(|(_, asset)| asset.hash.as_str()),
+        )?;
+
+        let progress_bar = self.bars.add(ProgressBar::new(index_data.objects.len() as u64));
         progress_bar.set_style(
             ProgressStyle::default_bar()
                 .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} assets")
@@ -405,8 +1080,19 @@ impl DownloadManager {
         );
         progress_bar.set_message("Assets");
 
+        // Legacy versions read assets by their real names instead of from the hashed objects
+        let mut named_dirs = Vec::new();
+        if index_data.is_virtual || index_data.map_to_resources {
+            named_dirs.push(self.dirs.virtual_assets_dir(&asset_index.id));
+        }
+        if index_data.map_to_resources {
+            named_dirs.push(self.dirs.instance_dir(&format!("minecraft-installer-{}", version_details.id)).join("resources"));
+        }
+
         // Download assets
-        for (_name, asset) in index_data.objects {
+        let total = index_data.objects.len() as u64;
+        for (i, (name, asset)) in index_data.objects.into_iter().enumerate() {
+            self.check_cancelled()?;
             let asset_path = self.dirs.asset_object_path(&asset.hash);
             let asset_url = format!(
                 "https://resources.download.minecraft.net/{}/{}",
@@ -414,23 +1100,122 @@ impl DownloadManager {
                 asset.hash
             );
 
-            match self.download_file_with_progress(
-                &asset_url,
-                &asset_path,
-                Some(&asset.hash),
-                None,
-            ).await {
-                Ok(_) => {}
-                Err(e) => warn!("Failed to download asset {}: {}", asset.hash, e),
+            let download = if present.contains(&name) {
+                Ok(())
+            } else if self.dirs.shares_assets() {
+                self.download_shared_asset(&asset_url, &asset.hash, &asset_path).await
+            } else {
+                self.download_file_with_progress(&asset_url, &asset_path, Some(&asset.hash), None).await
+            };
+            let bytes = match download {
+                Ok(_) => asset.size,
+                Err(e) => {
+                    warn!("Failed to download asset {}: {}", asset.hash, e);
+                    0
+                }
+            };
+
+            if bytes > 0 || asset.size == 0 {
+                for dir in &named_dirs {
+                    if let Err(e) = self.copy_named_asset(&asset_path, dir, &name, asset.size).await {
+                        warn!("Failed to place legacy asset {}: {}", name, e);
+                    }
+                }
             }
 
             progress_bar.inc(1);
+            self.overall.inc(bytes);
+            progress.progress(Phase::Assets, Some(name), bytes, i as u64 + 1, total);
         }
+        self.check_cancelled()?;
 
         progress_bar.finish_with_message("✓ Assets downloaded");
+        progress.phase_completed(Phase::Assets);
+        Ok(())
+    }
+
+    /// Copy a hashed asset object to `<dir>/<name>` for versions that read assets by name
+    async fn copy_named_asset(&self, object_path: &Path, dir: &Path, name: &str, size: u64) -> Result<()> {
+        let relative = Path::new(name);
+        if !relative.components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+            return Err(MinecraftInstallerError::Validation(format!("Unsafe asset name: {}", name)));
+        }
+
+        let target = dir.join(relative);
+        if fs::metadata(&target).await.is_ok_and(|m| m.len() == size) {
+            return Ok(());
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::copy(object_path, &target).await?;
+        Ok(())
+    }
+
+    /// Download an asset object into the shared store and link it to `asset_path`, copying if hardlinks fail
+    async fn download_shared_asset(&self, url: &str, hash: &str, asset_path: &Path) -> Result<()> {
+        let store_path = self.dirs.shared_asset_path(hash);
+        self.download_file_with_progress(url, &store_path, Some(hash), None).await?;
+
+        if let Some(parent) = asset_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        // Replace any standalone copy so every install points at the same inode
+        if asset_path.exists() {
+            fs::remove_file(asset_path).await?;
+        }
+        if let Err(e) = fs::hard_link(&store_path, asset_path).await {
+            debug!("Hardlinking {} failed ({}), copying instead", hash, e);
+            fs::copy(&store_path, asset_path).await?;
+        }
         Ok(())
     }
 
+    /// Verify asset objects against the stored asset index without touching the network
+    pub async fn verify_assets(&self, asset_index_id: &str) -> Result<AssetVerification> {
+        let index_path = self.dirs.assets_index_dir().join(format!("{}.json", asset_index_id));
+        if !index_path.exists() {
+            return Err(MinecraftInstallerError::Validation(format!(
+                "Asset index {} not found at {}",
+                asset_index_id,
+                index_path.display()
+            )));
+        }
+
+        let index_data: AssetIndexData = serde_json::from_slice(&fs::read(&index_path).await?)?;
+
+        let mut objects: Vec<_> = index_data.objects.into_iter().collect();
+        objects.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut verification = AssetVerification::default();
+        for (name, asset) in objects {
+            verification.checked += 1;
+            let asset_path = self.dirs.asset_object_path(&asset.hash);
+
+            let metadata = match fs::metadata(&asset_path).await {
+                Ok(metadata) => metadata,
+                Err(_) => {
+                    verification.missing.push(name);
+                    continue;
+                }
+            };
+
+            // A size mismatch is enough to flag the object without hashing it
+            if metadata.len() != asset.size || self.calculate_sha1(&asset_path).await? != asset.hash {
+                debug!("Corrupt asset {} at {}", name, asset_path.display());
+                verification.corrupt.push(name);
+            }
+        }
+
+        info!(
+            "Verified {} assets: {} missing, {} corrupt",
+            verification.checked,
+            verification.missing.len(),
+            verification.corrupt.len()
+        );
+        Ok(verification)
+    }
+
     /// Check if a library should be included based on rules
     fn should_include_library(&self, library: &Library) -> bool {
         if let Some(rules) = &library.rules {
@@ -1,5 +1,8 @@
+use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::launcher_support::LauncherType;
+
 pub type Result<T> = std::result::Result<T, MinecraftInstallerError>;
 
 #[derive(Error, Debug)]
@@ -8,7 +11,7 @@ pub enum MinecraftInstallerError {
     Io(#[from] std::io::Error),
 
     #[error("HTTP request error: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(#[source] reqwest::Error),
 
     #[error("JSON parsing error: {0}")]
     Json(#[from] serde_json::Error),
@@ -39,6 +42,53 @@ pub enum MinecraftInstallerError {
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Hash mismatch for {}: expected {expected}, got {actual}", path.display())]
+    HashMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Unsupported launcher: {0}")]
+    UnsupportedLauncher(LauncherType),
+}
+
+impl MinecraftInstallerError {
+    /// Stable identifier for front-ends that branch on the kind of failure instead of its message
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            MinecraftInstallerError::Io(_) => "IO_ERROR",
+            MinecraftInstallerError::Http(_) => "HTTP_ERROR",
+            MinecraftInstallerError::Json(_) => "JSON_ERROR",
+            MinecraftInstallerError::Zip(_) => "ZIP_ERROR",
+            MinecraftInstallerError::InvalidVersion(_) => "INVALID_VERSION",
+            MinecraftInstallerError::InvalidLoader(_) => "INVALID_LOADER",
+            MinecraftInstallerError::DownloadFailed(_) => "DOWNLOAD_FAILED",
+            MinecraftInstallerError::InstallationFailed(_) => "INSTALLATION_FAILED",
+            MinecraftInstallerError::JavaInstallationFailed(_) => "JAVA_INSTALLATION_FAILED",
+            MinecraftInstallerError::FileSystem(_) => "FILE_SYSTEM_ERROR",
+            MinecraftInstallerError::Network(_) => "NETWORK_ERROR",
+            MinecraftInstallerError::Validation(_) => "VALIDATION_ERROR",
+            MinecraftInstallerError::Cancelled => "CANCELLED",
+            MinecraftInstallerError::HashMismatch { .. } => "HASH_MISMATCH",
+            MinecraftInstallerError::UnsupportedLauncher(_) => "UNSUPPORTED_LAUNCHER",
+        }
+    }
+}
+
+impl From<reqwest::Error> for MinecraftInstallerError {
+    fn from(error: reqwest::Error) -> Self {
+        // A stalled server surfaces as a plain network failure rather than a request error
+        if error.is_timeout() {
+            MinecraftInstallerError::Network(format!("Request timed out: {}", error))
+        } else {
+            MinecraftInstallerError::Http(error)
+        }
+    }
 }
 
 
@@ -0,0 +1,117 @@
+use serde::Serialize;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// A stage of an install or update, as reported in `ProgressEvent`s
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Java,
+    Client,
+    Server,
+    Libraries,
+    Assets,
+    Profile,
+    Mrpack,
+    Overrides,
+    Update,
+}
+
+/// Bytes a phase is expected to download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PhaseTotal {
+    pub phase: Phase,
+    pub bytes: u64,
+}
+
+/// A typed progress or log event, tagged with `event` when serialized
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// Sent before any download starts; `total_bytes` is the sum of every phase total
+    Planned {
+        instance: Option<String>,
+        phases: Vec<PhaseTotal>,
+        total_bytes: u64,
+    },
+    PhaseStarted {
+        phase: Phase,
+        instance: Option<String>,
+    },
+    /// One item of a phase finished; `bytes` is the size of `file` when known
+    Progress {
+        phase: Phase,
+        instance: Option<String>,
+        file: Option<String>,
+        bytes: u64,
+        current: u64,
+        total: u64,
+        percent: f64,
+    },
+    PhaseCompleted {
+        phase: Phase,
+        instance: Option<String>,
+    },
+    Log {
+        instance: Option<String>,
+        message: String,
+    },
+}
+
+/// Sending half of a progress stream; a reporter without a subscriber drops every event
+#[derive(Debug, Clone, Default)]
+pub struct ProgressReporter {
+    sender: Option<UnboundedSender<ProgressEvent>>,
+    instance: Option<String>,
+}
+
+impl ProgressReporter {
+    /// Create a reporter and the receiver its events arrive on
+    pub fn channel() -> (Self, UnboundedReceiver<ProgressEvent>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (Self { sender: Some(sender), instance: None }, receiver)
+    }
+
+    /// A reporter that tags every event with the given instance (or version) name
+    pub fn for_instance(&self, instance: impl Into<String>) -> Self {
+        Self { sender: self.sender.clone(), instance: Some(instance.into()) }
+    }
+
+    /// Send an event; a closed or missing receiver is not an error
+    pub fn emit(&self, event: ProgressEvent) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Announce the byte totals of the phases about to run
+    pub fn planned(&self, phases: Vec<PhaseTotal>) {
+        let total_bytes = phases.iter().map(|total| total.bytes).sum();
+        self.emit(ProgressEvent::Planned { instance: self.instance.clone(), phases, total_bytes });
+    }
+
+    pub fn phase_started(&self, phase: Phase) {
+        self.emit(ProgressEvent::PhaseStarted { phase, instance: self.instance.clone() });
+    }
+
+    pub fn phase_completed(&self, phase: Phase) {
+        self.emit(ProgressEvent::PhaseCompleted { phase, instance: self.instance.clone() });
+    }
+
+    /// Report item `current` of `total`, with the file it concerned and its size
+    pub fn progress(&self, phase: Phase, file: Option<String>, bytes: u64, current: u64, total: u64) {
+        let percent = if total == 0 { 100.0 } else { current as f64 * 100.0 / total as f64 };
+        self.emit(ProgressEvent::Progress {
+            phase,
+            instance: self.instance.clone(),
+            file,
+            bytes,
+            current,
+            total,
+            percent,
+        });
+    }
+
+    pub fn log(&self, message: impl Into<String>) {
+        self.emit(ProgressEvent::Log { instance: self.instance.clone(), message: message.into() });
+    }
+}
@@ -0,0 +1,140 @@
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::download::HttpConfig;
+use crate::error::{MinecraftInstallerError, Result};
+use crate::loader::LoaderListing;
+
+/// Default Fabric meta API base URL
+pub const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
+
+/// Quilt meta API base URL; it serves loader lists in the same shape as Fabric's
+pub const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
+
+/// Loader version written when the meta API can't be reached
+pub const FALLBACK_FABRIC_LOADER: &str = "0.15.11";
+
+#[derive(Deserialize)]
+struct LoaderEntry {
+    loader: LoaderVersion,
+}
+
+#[derive(Deserialize)]
+struct LoaderVersion {
+    version: String,
+    #[serde(default)]
+    stable: bool,
+}
+
+/// Turns loader specs like "stable" or "latest" into a concrete Fabric loader version
+#[derive(Clone)]
+pub struct FabricResolver {
+    client: Client,
+    base_url: String,
+}
+
+impl FabricResolver {
+    pub fn new() -> Self {
+        Self::with_base_url(FABRIC_META_URL)
+    }
+
+    /// Create a resolver against a different meta host (e.g. a mirror or a test server)
+    pub fn with_base_url(base_url: &str) -> Self {
+        let client = HttpConfig::default().client();
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Route requests through the configured proxy and timeouts
+    pub fn with_http_config(mut self, http: &HttpConfig) -> Self {
+        self.client = http.client();
+        self
+    }
+
+    /// Resolve a loader version for a Minecraft version
+    ///
+    /// Concrete versions are returned as-is. "stable", "latest" or no spec at all are
+    /// looked up on the meta API, falling back to [`FALLBACK_FABRIC_LOADER`] when offline.
+    pub async fn resolve(&self, version_spec: Option<&str>, mc_version: &str) -> String {
+        let spec = version_spec.unwrap_or("stable");
+        if !matches!(spec, "" | "stable" | "latest" | "recommended") {
+            return spec.to_string();
+        }
+
+        match self.fetch_loader_version(spec == "latest", mc_version).await {
+            Ok(version) => version,
+            Err(e) => {
+                debug!("Falling back to Fabric loader {}: {}", FALLBACK_FABRIC_LOADER, e);
+                FALLBACK_FABRIC_LOADER.to_string()
+            }
+        }
+    }
+
+    /// Every loader version for a Minecraft version, newest first
+    ///
+    /// The first stable one is recommended; Quilt's meta has no stable flag, so there it is the first without a pre-release suffix.
+    pub async fn list_versions(&self, mc_version: &str) -> Result<Vec<LoaderListing>> {
+        let entries = self.fetch_loader_entries(mc_version).await?;
+        let recommended = entries.iter()
+            .position(|e| e.loader.stable)
+            .or_else(|| entries.iter().position(|e| !e.loader.version.contains('-')));
+
+        Ok(entries.into_iter()
+            .enumerate()
+            .map(|(i, e)| LoaderListing { version: e.loader.version, recommended: Some(i) == recommended })
+            .collect())
+    }
+
+    /// The launcher version JSON for `loader_version` on `mc_version`, as the meta API serves it
+    ///
+    /// It holds only the loader's libraries, main class and arguments and inherits the rest from `mc_version`.
+    pub async fn profile_json(&self, loader_version: &str, mc_version: &str) -> Result<serde_json::Value> {
+        let url = format!("{}/versions/loader/{}/{}/profile/json", self.base_url, mc_version, loader_version);
+        debug!("Fetching loader profile: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Loader meta returned {} for {}", response.status(), url
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    async fn fetch_loader_entries(&self, mc_version: &str) -> Result<Vec<LoaderEntry>> {
+        let url = format!("{}/versions/loader/{}", self.base_url, mc_version);
+        debug!("Resolving Fabric loader: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Fabric meta returned {} for {}", response.status(), url
+            )));
+        }
+
+        // Entries come newest first
+        Ok(response.json().await?)
+    }
+
+    async fn fetch_loader_version(&self, latest: bool, mc_version: &str) -> Result<String> {
+        let entries = self.fetch_loader_entries(mc_version).await?;
+        entries.iter()
+            .find(|e| latest || e.loader.stable)
+            .or_else(|| entries.first())
+            .map(|e| e.loader.version.clone())
+            .ok_or_else(|| MinecraftInstallerError::InvalidVersion(format!(
+                "No Fabric loader available for Minecraft {}", mc_version
+            )))
+    }
+}
+
+impl Default for FabricResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
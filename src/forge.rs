@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::download::{Artifact, HttpConfig, Library};
+use crate::error::{MinecraftInstallerError, Result};
+use crate::loader::LoaderListing;
+
+/// Default Forge maven, which hosts the installer JARs
+pub const FORGE_MAVEN_URL: &str = "https://maven.minecraftforge.net";
+
+/// Default list of recommended/latest Forge builds per Minecraft version
+pub const FORGE_PROMOTIONS_URL: &str = "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+
+#[derive(Deserialize)]
+struct Promotions {
+    promos: HashMap<String, String>,
+}
+
+/// Turns loader specs like "recommended" or "latest" into a concrete Forge version and its installer URL
+#[derive(Clone)]
+pub struct ForgeResolver {
+    client: Client,
+    maven_url: String,
+    promotions_url: String,
+    /// Every Forge build per Minecraft version, published next to the promotions file
+    metadata_url: String,
+}
+
+impl ForgeResolver {
+    pub fn new() -> Self {
+        Self::with_urls(FORGE_MAVEN_URL, FORGE_PROMOTIONS_URL)
+    }
+
+    /// Create a resolver against a different maven and promotions file (e.g. a mirror or a test server)
+    pub fn with_urls(maven_url: &str, promotions_url: &str) -> Self {
+        let client = HttpConfig::default().client();
+
+        let metadata_url = match promotions_url.rsplit_once('/') {
+            Some((dir, _)) => format!("{}/maven-metadata.json", dir),
+            None => "maven-metadata.json".to_string(),
+        };
+
+        Self {
+            client,
+            maven_url: maven_url.trim_end_matches('/').to_string(),
+            promotions_url: promotions_url.to_string(),
+            metadata_url,
+        }
+    }
+
+    /// Route requests through the configured proxy and timeouts
+    pub fn with_http_config(mut self, http: &HttpConfig) -> Self {
+        self.client = http.client();
+        self
+    }
+
+    /// Resolve a Forge version for a Minecraft version
+    ///
+    /// Concrete versions ("47.2.0" or "1.20.1-47.2.0") are returned as-is. "stable", "recommended"
+    /// or no spec use the recommended build and fall back to the latest one; "latest" always uses the latest.
+    pub async fn resolve(&self, version_spec: Option<&str>, mc_version: &str) -> Result<String> {
+        let spec = version_spec.unwrap_or("stable");
+        if !matches!(spec, "" | "stable" | "latest" | "recommended") {
+            let prefix = format!("{}-", mc_version);
+            return Ok(spec.strip_prefix(&prefix).unwrap_or(spec).to_string());
+        }
+
+        debug!("Resolving Forge version: {}", self.promotions_url);
+        let response = self.client.get(&self.promotions_url).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Forge promotions returned {} for {}", response.status(), self.promotions_url
+            )));
+        }
+
+        let promotions: Promotions = response.json().await?;
+        let latest = promotions.promos.get(&format!("{}-latest", mc_version));
+        let chosen = if spec == "latest" {
+            latest
+        } else {
+            promotions.promos.get(&format!("{}-recommended", mc_version)).or(latest)
+        };
+        chosen.cloned().ok_or_else(|| MinecraftInstallerError::InvalidLoader(format!(
+            "Forge is not available for Minecraft {}", mc_version
+        )))
+    }
+
+    /// Every Forge version for a Minecraft version, newest first, with the one `resolve` picks by default recommended
+    pub async fn list_versions(&self, mc_version: &str) -> Result<Vec<LoaderListing>> {
+        debug!("Listing Forge versions: {}", self.metadata_url);
+        let response = self.client.get(&self.metadata_url).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Forge maven metadata returned {} for {}", response.status(), self.metadata_url
+            )));
+        }
+
+        // Builds are listed oldest first as "<mc>-<forge>"
+        let mut metadata: HashMap<String, Vec<String>> = response.json().await?;
+        let builds = metadata.remove(mc_version).ok_or_else(|| MinecraftInstallerError::InvalidLoader(format!(
+            "Forge is not available for Minecraft {}", mc_version
+        )))?;
+        let recommended = self.resolve(None, mc_version).await.ok();
+
+        let prefix = format!("{}-", mc_version);
+        Ok(builds.iter()
+            .rev()
+            .map(|build| build.strip_prefix(&prefix).unwrap_or(build).to_string())
+            .map(|version| LoaderListing { recommended: recommended.as_deref() == Some(version.as_str()), version })
+            .collect())
+    }
+
+    /// Maven URL of the installer JAR for `forge_version` on `mc_version`
+    pub fn installer_url(&self, mc_version: &str, forge_version: &str) -> String {
+        format!(
+            "{0}/net/minecraftforge/forge/{1}-{2}/forge-{1}-{2}-installer.jar",
+            self.maven_url, mc_version, forge_version
+        )
+    }
+}
+
+impl Default for ForgeResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Deserialize)]
+struct VersionLibraries {
+    #[serde(default)]
+    libraries: Vec<Library>,
+}
+
+/// `install_profile.json` and the `version.json` it points at, as shipped inside a Forge installer JAR
+#[derive(Deserialize, Debug)]
+pub struct ForgeInstallProfile {
+    /// Version id the installer writes under `versions/`, e.g. "1.20.1-forge-47.2.0"
+    pub version: String,
+    pub minecraft: String,
+    /// Libraries the installer's processors need
+    #[serde(default)]
+    pub libraries: Vec<Library>,
+    #[serde(default)]
+    pub processors: Vec<serde_json::Value>,
+    /// The launcher version JSON, written to `versions/<version>/<version>.json`
+    #[serde(skip)]
+    pub version_json: serde_json::Value,
+    #[serde(skip)]
+    game_libraries: Vec<Library>,
+}
+
+impl ForgeInstallProfile {
+    /// Read both profiles out of a Forge installer JAR
+    pub fn from_installer(jar: &Path) -> Result<Self> {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(jar)?)?;
+        let mut read_entry = |name: &str| -> Result<String> {
+            let mut entry = archive.by_name(name).map_err(|_| MinecraftInstallerError::InvalidLoader(format!(
+                "{} has no {}; is it a Forge installer?", jar.display(), name
+            )))?;
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+            Ok(content)
+        };
+
+        let install_profile = read_entry("install_profile.json")?;
+        let json_entry = serde_json::from_str::<serde_json::Value>(&install_profile)?
+            .get("json")
+            .and_then(|j| j.as_str())
+            .unwrap_or("/version.json")
+            .trim_start_matches('/')
+            .to_string();
+        let version_json = read_entry(&json_entry)?;
+        Self::from_json(&install_profile, &version_json)
+    }
+
+    /// Parse the two profile documents; pre-1.13 installers (with `versionInfo`) are rejected
+    pub fn from_json(install_profile: &str, version_json: &str) -> Result<Self> {
+        let raw: serde_json::Value = serde_json::from_str(install_profile)?;
+        if raw.get("versionInfo").is_some() {
+            return Err(MinecraftInstallerError::InvalidLoader(
+                "Legacy Forge installers (Minecraft 1.12.2 and older) are not supported".to_string(),
+            ));
+        }
+
+        let mut profile: Self = serde_json::from_value(raw)?;
+        profile.version_json = serde_json::from_str(version_json)?;
+        profile.game_libraries = serde_json::from_value::<VersionLibraries>(profile.version_json.clone())?.libraries;
+        Ok(profile)
+    }
+
+    /// Every library to download: the processors' first, then the game's, each path once
+    ///
+    /// Artifacts without a URL (the patched client JAR) are produced by the processors and left out.
+    pub fn libraries(&self) -> Vec<&Artifact> {
+        let mut seen = HashSet::new();
+        self.libraries.iter()
+            .chain(&self.game_libraries)
+            .filter_map(|library| library.downloads.artifact.as_ref())
+            .filter(|artifact| !artifact.url.is_empty())
+            .filter(|artifact| seen.insert(artifact.path.as_str()))
+            .collect()
+    }
+
+    /// Whether the installer itself has to run to patch the client
+    pub fn needs_processors(&self) -> bool {
+        !self.processors.is_empty()
+    }
+}
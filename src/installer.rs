@@ -1,23 +1,83 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
 use tracing::{info, error};
 
 use crate::error::{MinecraftInstallerError, Result};
 use crate::directories::DirectoryManager;
-use crate::download::{DownloadManager, VersionManifest};
+use crate::download::{AssetVerification, DownloadKind, DownloadManager, DownloadOptions, DownloadPlan, HttpConfig, HttpTimeouts, VerifyReport, VersionDetails, VersionManifest};
+use crate::fabric::{FabricResolver, QUILT_META_URL};
+use crate::forge::{ForgeInstallProfile, ForgeResolver};
+use crate::neoforge::NeoForgeResolver;
 use crate::java::JavaManager;
+use crate::loader::LoaderListing;
+use crate::lock::InstallLock;
+use crate::manifest::InstallManifest;
+use crate::events::{Phase, ProgressEvent, ProgressReporter};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio_util::sync::CancellationToken;
+
+/// What `install_minecraft` put on disk
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallReport {
+    pub version: String,
+    pub loader: String,
+    pub loader_version: String,
+    /// Major version of the Java runtime that will run the game; `None` when the install was skipped
+    pub java_version: Option<u32>,
+    pub client_jar_path: PathBuf,
+    pub library_count: usize,
+    pub asset_count: usize,
+    /// Bytes on disk for the client, version JSON, libraries, asset index and asset objects
+    pub total_bytes: u64,
+    pub libraries_skipped: bool,
+    pub assets_skipped: bool,
+}
+
+/// A manifest entry as shown by `list_versions`
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionListing {
+    pub id: String,
+    pub version_type: String,
+    pub release_time: String,
+    pub installed: bool,
+}
+
+/// Render loader versions as the `--list-loaders` table, marking the recommended one
+pub fn format_loader_versions(loader: &str, mc_version: &str, versions: &[LoaderListing]) -> String {
+    let mut out = format!("\n🧩 {} versions for Minecraft {}\n", loader, mc_version);
+    out.push_str("═════════════════════════════════\n");
+    for listing in versions {
+        if listing.recommended {
+            out.push_str(&format!("{:20} ⭐ recommended\n", listing.version));
+        } else {
+            out.push_str(&format!("{}\n", listing.version));
+        }
+    }
+    out.push_str("\nUse --loader-version <version> to install a specific version\n");
+    out
+}
 
 /// Main Minecraft installer
 pub struct MinecraftInstaller {
     dirs: DirectoryManager,
     download_manager: DownloadManager,
     java_manager: JavaManager,
+    forge_resolver: ForgeResolver,
+    fabric_resolver: FabricResolver,
+    quilt_resolver: FabricResolver,
+    neoforge_resolver: NeoForgeResolver,
+    http: HttpConfig,
+    progress: ProgressReporter,
 }
 
 impl MinecraftInstaller {
     /// Create a new Minecraft installer
     pub async fn new(install_dir: PathBuf) -> Result<Self> {
-        let dirs = DirectoryManager::new(install_dir);
+        Self::with_directories(DirectoryManager::new(install_dir)).await
+    }
 
+    /// Create an installer that uses `dirs`, e.g. one built with a custom `DirectoryLayout`
+    pub async fn with_directories(dirs: DirectoryManager) -> Result<Self> {
         // Initialize directories
         dirs.init().await?;
 
@@ -28,9 +88,109 @@ impl MinecraftInstaller {
             dirs,
             download_manager,
             java_manager,
+            forge_resolver: ForgeResolver::new(),
+            fabric_resolver: FabricResolver::new(),
+            quilt_resolver: FabricResolver::with_base_url(QUILT_META_URL),
+            neoforge_resolver: NeoForgeResolver::new(),
+            http: HttpConfig::default(),
+            progress: ProgressReporter::default(),
         })
     }
 
+    /// Stop client, library and asset downloads with `MinecraftInstallerError::Cancelled` once `cancel` is tripped
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.download_manager = self.download_manager.with_cancellation(cancel);
+        self
+    }
+
+    /// Install from a pre-populated download cache (`<cache>/<sha1>`) without touching the network
+    pub fn with_offline_cache(mut self, cache_dir: PathBuf) -> Self {
+        self.download_manager = self.download_manager.with_offline_cache(cache_dir);
+        self
+    }
+
+    /// Share asset objects between versions through hardlinks into `<install dir>/shared-assets`
+    pub fn with_shared_assets(mut self) -> Self {
+        self.dirs = self.dirs.link_shared_assets();
+        self.download_manager = self.download_manager.with_shared_assets();
+        self
+    }
+
+    /// Share asset objects through hardlinks into `store`, which other install dirs can point at too
+    pub fn with_shared_assets_at(mut self, store: PathBuf) -> Self {
+        self.dirs = self.dirs.link_shared_assets_at(store.clone());
+        self.download_manager = self.download_manager.with_shared_assets_at(store);
+        self
+    }
+
+    /// Hash every asset object on install; by default objects already present at the right size are kept as-is
+    pub fn with_asset_rehash(mut self) -> Self {
+        self.download_manager = self.download_manager.with_asset_rehash();
+        self
+    }
+
+    /// Run on the Java in `java_home` instead of auto-installing one; installs fail if it is too old
+    pub fn with_java_home(mut self, java_home: PathBuf) -> Self {
+        self.java_manager = self.java_manager.with_override(java_home);
+        self
+    }
+
+    /// Limit how long a download or loader lookup may take to connect and to finish
+    pub fn with_timeouts(self, timeouts: HttpTimeouts) -> Self {
+        let http = self.http.clone().with_timeouts(timeouts);
+        self.with_http_config(&http)
+    }
+
+    /// Timeouts and proxy for Mojang and Adoptium downloads and loader lookups
+    pub fn with_http_config(mut self, http: &HttpConfig) -> Self {
+        self.http = http.clone();
+        self.download_manager = self.download_manager.with_http_config(http);
+        self.java_manager = self.java_manager.with_http_config(http);
+        self.forge_resolver = self.forge_resolver.with_http_config(http);
+        self.fabric_resolver = self.fabric_resolver.with_http_config(http);
+        self.quilt_resolver = self.quilt_resolver.with_http_config(http);
+        self.neoforge_resolver = self.neoforge_resolver.with_http_config(http);
+        self
+    }
+
+    /// Look up Forge versions and installers through `resolver` (e.g. a mirror) instead of the official maven
+    pub fn with_forge_resolver(mut self, resolver: ForgeResolver) -> Self {
+        self.forge_resolver = resolver;
+        self
+    }
+
+    /// List Fabric loader versions from a different meta host
+    pub fn with_fabric_resolver(mut self, resolver: FabricResolver) -> Self {
+        self.fabric_resolver = resolver;
+        self
+    }
+
+    /// List Quilt loader versions from a different meta host
+    pub fn with_quilt_resolver(mut self, resolver: FabricResolver) -> Self {
+        self.quilt_resolver = resolver;
+        self
+    }
+
+    /// List NeoForge versions from a different maven
+    pub fn with_neoforge_resolver(mut self, resolver: NeoForgeResolver) -> Self {
+        self.neoforge_resolver = resolver;
+        self
+    }
+
+    /// Stream typed progress events for every later install; replaces any earlier subscriber
+    pub fn subscribe_progress(&mut self) -> UnboundedReceiver<ProgressEvent> {
+        let (progress, receiver) = ProgressReporter::channel();
+        self.download_manager.set_progress_reporter(progress.clone());
+        self.progress = progress;
+        receiver
+    }
+
+    /// Delete downloaded JREs that no installed Minecraft version needs any more and that contain none of
+    /// the `in_use` Java paths; returns the removed directories
+    pub async fn prune_java(&self, in_use: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        self.java_manager.prune_unused_except(&[], in_use).await
+    }
+
     /// Install Minecraft
     pub async fn install_minecraft(
         &self,
@@ -38,25 +198,32 @@ impl MinecraftInstaller {
         loader: &str,
         loader_version: &str,
         force: bool,
-    ) -> Result<()> {
+    ) -> Result<InstallReport> {
+        self.install_minecraft_with_options(version, loader, loader_version, force, DownloadOptions::default()).await
+    }
+
+    /// Install Minecraft, leaving out libraries or assets (e.g. for a dedicated server or a dependency fetch)
+    pub async fn install_minecraft_with_options(
+        &self,
+        version: &str,
+        loader: &str,
+        loader_version: &str,
+        force: bool,
+        options: DownloadOptions,
+    ) -> Result<InstallReport> {
         info!("Starting Minecraft {} installation", version);
+        let _lock = InstallLock::acquire(&self.dirs.install_lock())?;
+        let progress = self.progress.for_instance(version);
 
         // Check if already installed
         if !force && self.dirs.is_version_installed(version).await {
             info!("Minecraft {} is already installed", version);
-            return Ok(());
+            progress.log(format!("Minecraft {} is already installed", version));
+            let version_details: VersionDetails = serde_json::from_slice(&tokio::fs::read(self.dirs.version_json(version)).await?)?;
+            return self.build_install_report(&version_details, loader, loader_version, None, options).await;
         }
 
-        // Get version manifest
-        let manifest = self.download_manager.get_version_manifest().await?;
-
-        // Find the requested version
-        let version_info = manifest.versions.iter()
-            .find(|v| v.id == version)
-            .ok_or_else(|| MinecraftInstallerError::InvalidVersion(version.to_string()))?;
-
-        // Get detailed version information
-        let version_details = self.download_manager.get_version_details(version_info).await?;
+        let version_details = self.resolve_version(version).await?;
 
         // Determine required Java version
         let required_java = version_details.java_version
@@ -67,12 +234,13 @@ impl MinecraftInstaller {
         info!("Minecraft {} requires Java {}", version, required_java);
 
         // Ensure Java is installed
-        let _java_path = self.java_manager.ensure_java(required_java).await?;
+        progress.phase_started(Phase::Java);
+        let (java_path, java_version) = self.java_manager.ensure_java(required_java).await?;
+        progress.phase_completed(Phase::Java);
 
-        // Install mod loader if not vanilla
-        if loader != "vanilla" {
+        if !matches!(loader, "vanilla" | "forge") {
             return Err(MinecraftInstallerError::InvalidLoader(format!(
-                "Mod loader '{}' is not yet supported. Only 'vanilla' is currently supported.",
+                "Mod loader '{}' is not yet supported. Supported loaders: vanilla, forge.",
                 loader
             )));
         }
@@ -80,26 +248,216 @@ impl MinecraftInstaller {
         // Download Minecraft components
         info!("Downloading Minecraft components...");
 
+        self.download_manager.start_overall_progress(&version_details, options);
+
         // Download client JAR and save version JSON
         self.download_manager.download_client(&version_details).await?;
 
         // Download libraries
-        self.download_manager.download_libraries(&version_details).await?;
+        if options.download_libraries {
+            self.download_manager.download_libraries(&version_details).await?;
+        } else {
+            info!("Skipping libraries");
+        }
 
         // Download assets
-        self.download_manager.download_assets(&version_details).await?;
+        if options.download_assets {
+            self.download_manager.download_assets(&version_details).await?;
+        } else {
+            info!("Skipping assets");
+        }
+        self.download_manager.finish_overall_progress();
+
+        let (version_id, loader_version) = if loader == "forge" {
+            self.install_forge(version, loader_version, &java_path).await?
+        } else {
+            (version.to_string(), loader_version.to_string())
+        };
 
         // Create launcher profile
-        self.create_launcher_profile(version).await?;
+        progress.phase_started(Phase::Profile);
+        self.create_launcher_profile(version, &version_id).await?;
+        InstallManifest::new(version, loader, (loader != "vanilla").then_some(loader_version.as_str()))
+            .write(&self.dirs.instance_dir(&format!("minecraft-installer-{}", version)))
+            .await?;
+        progress.phase_completed(Phase::Profile);
+
+        let report = self.build_install_report(&version_details, loader, &loader_version, Some(java_version), options).await?;
 
         info!("✓ Minecraft {} installation completed successfully!", version);
-        self.print_installation_summary(version).await?;
+        progress.log(format!("Minecraft {} installation completed", version));
+        self.print_installation_summary(&report);
 
-        Ok(())
+        Ok(report)
+    }
+
+    /// Set up a dedicated server: Java, the verified server JAR, and `eula.txt`/`server.properties` stubs next to it.
+    /// Client libraries and assets are not downloaded; existing stub files are left alone.
+    pub async fn install_server(&self, version: &str) -> Result<PathBuf> {
+        info!("Starting Minecraft {} server installation", version);
+        let progress = self.progress.for_instance(version);
+
+        let version_details = self.resolve_version(version).await?;
+        if version_details.downloads.server.is_none() {
+            return Err(MinecraftInstallerError::InvalidVersion(format!(
+                "Minecraft {} has no server download", version
+            )));
+        }
+
+        let required_java = version_details.java_version
+            .as_ref()
+            .map(|jv| jv.major_version)
+            .unwrap_or(8);
+        progress.phase_started(Phase::Java);
+        self.java_manager.ensure_java(required_java).await?;
+        progress.phase_completed(Phase::Java);
+
+        let server_jar = self.download_manager.download_server(&version_details).await?;
+        let server_dir = self.dirs.version_dir(version);
+
+        let eula_path = server_dir.join("eula.txt");
+        if !eula_path.exists() {
+            // Accepting the EULA is the server owner's call, so the stub leaves it off
+            tokio::fs::write(
+                &eula_path,
+                "#By changing the setting below to TRUE you are indicating your agreement to our EULA (https://aka.ms/MinecraftEULA).\neula=false\n",
+            ).await?;
+        }
+        let properties_path = server_dir.join("server.properties");
+        if !properties_path.exists() {
+            tokio::fs::write(
+                &properties_path,
+                format!("#Minecraft server properties\nmotd=Minecraft {} Server\nserver-port=25565\nmax-players=20\nonline-mode=true\n", version),
+            ).await?;
+        }
+
+        info!("✓ Minecraft {} server installed at {}", version, server_jar.display());
+        progress.log(format!("Minecraft {} server installation completed", version));
+        Ok(server_jar)
+    }
+
+    /// Count what landed on disk for an installed version
+    async fn build_install_report(
+        &self,
+        version_details: &VersionDetails,
+        loader: &str,
+        loader_version: &str,
+        java_version: Option<u32>,
+        options: DownloadOptions,
+    ) -> Result<InstallReport> {
+        let plan = self.download_manager.download_plan_with_options(version_details, options).await?;
+        let minecraft_dir = self.dirs.minecraft_dir();
+
+        let mut report = InstallReport {
+            version: version_details.id.clone(),
+            loader: loader.to_string(),
+            loader_version: loader_version.to_string(),
+            java_version,
+            client_jar_path: self.dirs.version_jar(&version_details.id),
+            library_count: 0,
+            asset_count: 0,
+            total_bytes: 0,
+            libraries_skipped: !options.download_libraries,
+            assets_skipped: !options.download_assets,
+        };
+
+        if let Ok(metadata) = tokio::fs::metadata(self.dirs.version_json(&version_details.id)).await {
+            report.total_bytes += metadata.len();
+        }
+
+        for file in &plan.files {
+            // Native jars are removed once extracted, so only count what is actually there
+            let Ok(metadata) = tokio::fs::metadata(minecraft_dir.join(&file.path)).await else {
+                continue;
+            };
+            report.total_bytes += metadata.len();
+            match file.kind {
+                DownloadKind::Library => report.library_count += 1,
+                DownloadKind::Asset => report.asset_count += 1,
+                _ => {}
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Look up a version in the manifest and fetch its details
+    async fn resolve_version(&self, version: &str) -> Result<VersionDetails> {
+        let manifest = self.download_manager.get_version_manifest().await?;
+
+        let version_info = manifest.versions.iter()
+            .find(|v| v.id == version)
+            .ok_or_else(|| MinecraftInstallerError::InvalidVersion(version.to_string()))?;
+
+        self.download_manager.get_version_details(version_info).await
+    }
+
+    /// Resolve the files installing a version would download, without downloading them
+    pub async fn download_plan(&self, version: &str) -> Result<DownloadPlan> {
+        let version_details = self.resolve_version(version).await?;
+        self.download_manager.download_plan(&version_details).await
+    }
+
+    /// Install Forge on top of the already-downloaded vanilla `version`: its libraries and version JSON,
+    /// then the installer run headlessly with `java` to patch the client. Returns the version id and Forge version.
+    pub async fn install_forge(&self, version: &str, loader_version: &str, java: &Path) -> Result<(String, String)> {
+        let resolver = &self.forge_resolver;
+        let forge_version = resolver.resolve(Some(loader_version), version).await?;
+        info!("Installing Forge {} for Minecraft {}", forge_version, version);
+
+        let installer_jar = self.dirs.version_dir(version).join(format!("forge-{}-{}-installer.jar", version, forge_version));
+        self.download_manager
+            .download_to(&resolver.installer_url(version, &forge_version), &installer_jar, None)
+            .await
+            .map_err(|e| MinecraftInstallerError::InvalidLoader(format!(
+                "Forge {} is not available for Minecraft {}: {}", forge_version, version, e
+            )))?;
+        let profile = ForgeInstallProfile::from_installer(&installer_jar)?;
+
+        for artifact in profile.libraries() {
+            let path = self.dirs.libraries_dir().join(&artifact.path);
+            self.download_manager.download_to(&artifact.url, &path, Some(&artifact.sha1)).await?;
+        }
+
+        let version_json = self.dirs.version_json(&profile.version);
+        if let Some(parent) = version_json.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&version_json, serde_json::to_string_pretty(&profile.version_json)?).await?;
+
+        if profile.needs_processors() {
+            // The installer refuses to run without a launcher profile file to add itself to
+            let profiles_path = self.dirs.launcher_profiles();
+            if !profiles_path.exists() {
+                tokio::fs::write(&profiles_path, r#"{"profiles": {}}"#).await?;
+            }
+
+            info!("Running Forge installer processors...");
+            let output = tokio::process::Command::new(java)
+                .arg("-jar")
+                .arg(&installer_jar)
+                .arg("--installClient")
+                .arg(self.dirs.minecraft_dir())
+                .current_dir(self.dirs.version_dir(version))
+                .output()
+                .await?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let tail: Vec<_> = stderr.lines().rev().take(5).collect();
+                return Err(MinecraftInstallerError::InstallationFailed(format!(
+                    "Forge installer exited with {}: {}",
+                    output.status,
+                    tail.into_iter().rev().collect::<Vec<_>>().join("\n")
+                )));
+            }
+        }
+
+        let _ = tokio::fs::remove_file(&installer_jar).await;
+        Ok((profile.version, forge_version))
     }
 
     /// Create launcher profile JSON
-    async fn create_launcher_profile(&self, version: &str) -> Result<()> {
+    async fn create_launcher_profile(&self, version: &str, version_id: &str) -> Result<()> {
         use serde_json::json;
 
         let profile_id = format!("minecraft-installer-{}", version);
@@ -115,7 +473,7 @@ impl MinecraftInstaller {
                     "created": chrono::Utc::now().to_rfc3339(),
                     "icon": "Crafting_Table",
                     "lastUsed": chrono::Utc::now().to_rfc3339(),
-                    "lastVersionId": version,
+                    "lastVersionId": version_id,
                     "name": format!("Minecraft {}", version),
                     "type": "custom",
                     "gameDir": instance_dir.to_string_lossy()
@@ -145,39 +503,42 @@ impl MinecraftInstaller {
     }
 
     /// Print installation summary
-    async fn print_installation_summary(&self, version: &str) -> Result<()> {
+    pub fn print_installation_summary(&self, report: &InstallReport) {
+        let version = &report.version;
         let minecraft_dir = self.dirs.minecraft_dir();
         let version_dir = self.dirs.version_dir(version);
 
-        // Calculate installation size
-        let total_size = self.dirs.get_directory_size(&minecraft_dir).await?;
-        let size_mb = total_size / 1024 / 1024;
-
         println!("\n🎮 Minecraft Installation Summary");
         println!("═══════════════════════════════════");
         println!("Version: {}", version);
-        println!("Installation Size: {} MB", size_mb);
+        if let Some(java_version) = report.java_version {
+            println!("Java: {}", java_version);
+        }
+        println!("Installation Size: {} MB", report.total_bytes / 1024 / 1024);
         println!("Installation Directory: {}", minecraft_dir.display());
         println!("Version Directory: {}", version_dir.display());
 
         // Check what was installed
-        let client_jar = self.dirs.version_jar(version);
         let version_json = self.dirs.version_json(version);
         let launcher_profiles = self.dirs.launcher_profiles();
 
         println!("\nInstalled Components:");
-        println!("✓ Client JAR: {}", client_jar.display());
+        println!("✓ Client JAR: {}", report.client_jar_path.display());
         println!("✓ Version JSON: {}", version_json.display());
         println!("✓ Launcher Profile: {}", launcher_profiles.display());
 
         let libraries_dir = self.dirs.libraries_dir();
-        if libraries_dir.exists() {
-            println!("✓ Libraries: {}", libraries_dir.display());
+        if report.libraries_skipped {
+            println!("⏭ Libraries: skipped");
+        } else if libraries_dir.exists() {
+            println!("✓ Libraries ({}): {}", report.library_count, libraries_dir.display());
         }
 
         let assets_dir = self.dirs.assets_dir();
-        if assets_dir.exists() {
-            println!("✓ Assets: {}", assets_dir.display());
+        if report.assets_skipped {
+            println!("⏭ Assets: skipped");
+        } else if assets_dir.exists() {
+            println!("✓ Assets ({}): {}", report.asset_count, assets_dir.display());
         }
 
         println!("\n📋 Next Steps:");
@@ -185,14 +546,48 @@ impl MinecraftInstaller {
         println!("2. Import the launcher_profiles.json file");
         println!("3. Select the '{}' profile", format!("minecraft-installer-{}", version));
         println!("4. Launch and enjoy Minecraft {}!", version);
+    }
 
-        Ok(())
+    /// Versions from the manifest, newest first, filtered by type; `limit` 0 returns all of them
+    pub async fn get_versions(&self, version_type: Option<&str>, limit: usize) -> Result<Vec<VersionListing>> {
+        let manifest = self.download_manager.get_version_manifest().await?;
+        Ok(self.version_listings(&manifest, version_type, limit).await)
+    }
+
+    async fn version_listings(&self, manifest: &VersionManifest, version_type: Option<&str>, limit: usize) -> Vec<VersionListing> {
+        let limit = if limit == 0 { usize::MAX } else { limit };
+
+        let mut versions = Vec::new();
+        for version in manifest.versions.iter()
+            .filter(|v| version_type.is_none_or(|filter_type| v.version_type == filter_type))
+            .take(limit)
+        {
+            versions.push(VersionListing {
+                id: version.id.clone(),
+                version_type: version.version_type.clone(),
+                release_time: version.release_time.clone(),
+                installed: self.dirs.is_version_installed(&version.id).await,
+            });
+        }
+        versions
     }
 
-    /// List available Minecraft versions
-    pub async fn list_versions(&self, version_type: Option<&str>) -> Result<()> {
+    /// List available Minecraft versions as a table, or as JSON with `format` "json"
+    pub async fn list_versions(&self, version_type: Option<&str>, limit: usize, format: &str) -> Result<()> {
         info!("Fetching available Minecraft versions...");
 
+        if format == "json" {
+            let versions = self.get_versions(version_type, limit).await?;
+            println!("{}", serde_json::to_string_pretty(&versions)?);
+            return Ok(());
+        }
+        if format != "table" {
+            return Err(MinecraftInstallerError::Validation(format!(
+                "Invalid format: {}. Use 'table' or 'json'",
+                format
+            )));
+        }
+
         let manifest = self.download_manager.get_version_manifest().await?;
 
         println!("\n🎮 Available Minecraft Versions");
@@ -200,24 +595,14 @@ impl MinecraftInstaller {
         println!("Latest Release: {}", manifest.latest.release);
         println!("Latest Snapshot: {}", manifest.latest.snapshot);
 
-        // Filter versions by type
-        let filtered_versions: Vec<_> = manifest.versions.iter()
-            .filter(|v| {
-                if let Some(filter_type) = version_type {
-                    v.version_type == filter_type
-                } else {
-                    true
-                }
-            })
-            .take(20) // Show only the latest 20 versions
-            .collect();
+        let filtered_versions = self.version_listings(&manifest, version_type, limit).await;
 
         println!("\nRecent Versions ({}):",
             version_type.unwrap_or("all types"));
         println!("─────────────────────────────────");
 
         for version in filtered_versions {
-            let status = if self.dirs.is_version_installed(&version.id).await {
+            let status = if version.installed {
                 "✓ Installed"
             } else {
                 ""
@@ -234,6 +619,83 @@ impl MinecraftInstaller {
         Ok(())
     }
 
+    /// Versions of `loader` (fabric, quilt, forge, neoforge) available for a Minecraft version, newest first
+    pub async fn loader_versions(&self, loader: &str, mc_version: &str) -> Result<Vec<LoaderListing>> {
+        match loader.to_lowercase().as_str() {
+            "fabric" => self.fabric_resolver.list_versions(mc_version).await,
+            "quilt" => self.quilt_resolver.list_versions(mc_version).await,
+            "forge" => self.forge_resolver.list_versions(mc_version).await,
+            "neoforge" => self.neoforge_resolver.list_versions(mc_version).await,
+            _ => Err(MinecraftInstallerError::InvalidLoader(format!(
+                "{} has no versions to list; use fabric, quilt, forge or neoforge", loader
+            ))),
+        }
+    }
+
+    /// Print the loader versions available for a Minecraft version (table or json), `limit` 0 showing all
+    pub async fn list_loader_versions(&self, loader: &str, mc_version: &str, limit: usize, format: &str) -> Result<()> {
+        if format != "table" && format != "json" {
+            return Err(MinecraftInstallerError::Validation(format!(
+                "Invalid format: {}. Use 'table' or 'json'",
+                format
+            )));
+        }
+
+        info!("Fetching {} versions for Minecraft {}...", loader, mc_version);
+        let mut versions = self.loader_versions(loader, mc_version).await?;
+        if limit > 0 {
+            versions.truncate(limit);
+        }
+
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&versions)?);
+        } else {
+            print!("{}", format_loader_versions(loader, mc_version, &versions));
+        }
+        Ok(())
+    }
+
+    /// Check an installed version's assets against its asset index, offline
+    pub async fn verify_assets(&self, version: &str) -> Result<AssetVerification> {
+        let version_json = self.dirs.version_json(version);
+        if !version_json.exists() {
+            return Err(MinecraftInstallerError::InvalidVersion(format!(
+                "{} is not installed",
+                version
+            )));
+        }
+
+        let version_details: VersionDetails = serde_json::from_slice(&tokio::fs::read(version_json).await?)?;
+        self.download_manager.verify_assets(&version_details.asset_index.id).await
+    }
+
+    /// Re-hash an installed version's client JAR, libraries and assets against its stored version JSON
+    pub async fn verify_installation(&self, version: &str) -> Result<VerifyReport> {
+        let version_json = self.dirs.version_json(version);
+        if !version_json.exists() {
+            return Err(MinecraftInstallerError::InvalidVersion(format!(
+                "{} is not installed",
+                version
+            )));
+        }
+
+        let version_details: VersionDetails = serde_json::from_slice(&tokio::fs::read(version_json).await?)?;
+        self.download_manager.verify_installation(&version_details).await
+    }
+
+    /// Re-download only the files a verification flagged, then verify again
+    pub async fn repair_installation(&self, version: &str, report: &VerifyReport) -> Result<VerifyReport> {
+        self.download_manager.repair(report).await?;
+        let report = self.verify_installation(version).await?;
+        if report.is_intact() {
+            return Ok(report);
+        }
+
+        // A repaired asset index exposes asset objects that could not be checked before
+        self.download_manager.repair(&report).await?;
+        self.verify_installation(version).await
+    }
+
     /// Get installation directory
     pub fn get_install_dir(&self) -> &PathBuf {
         &self.dirs.base_dir
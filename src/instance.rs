@@ -0,0 +1,1093 @@
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::{info, debug};
+use crate::error::Result;
+use crate::launcher_support::{minecraft_base_dir, official_profiles_path, LauncherManager, LauncherType, PrismConfig};
+
+/// Instance information for display in Electron app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceInfo {
+    pub name: String,
+    pub launcher_type: String,
+    pub launcher_path: String,
+    pub instance_path: String,
+    pub minecraft_version: String,
+    pub mod_loader: String,
+    pub mod_loader_version: Option<String>,
+    pub mod_count: usize,
+    pub mods: Vec<ModInfo>,
+    pub has_automodpack: bool,
+    pub server_info: Option<ServerInfo>,
+    pub last_updated: Option<String>,
+}
+
+/// Mod information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModInfo {
+    pub name: String,
+    pub filename: String,
+    pub version: Option<String>,
+    pub mod_id: Option<String>,
+    pub is_user_mod: bool, // true if added by user, false if from modpack
+    pub file_size: u64,
+    pub last_modified: String,
+    /// SHA-1 from the install manifest, when the jar on disk still matches its entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+}
+
+/// Server information from automodpack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub server_ip: String,
+    pub server_port: u16,
+    pub fingerprint: String,
+    pub server_name: String,
+}
+
+/// ATLauncher also uses instance.json, but nests its own metadata under "launcher"
+fn is_atlauncher_instance(instance_path: &Path) -> bool {
+    std::fs::read_to_string(instance_path.join("instance.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|data| data["launcher"].is_object())
+}
+
+/// Mod loader component uids in a Prism/MultiMC `mmc-pack.json`
+const MMC_LOADER_UIDS: [(&str, &str); 4] = [
+    ("net.fabricmc.fabric-loader", "Fabric"),
+    ("org.quiltmc.quilt-loader", "Quilt"),
+    ("net.neoforged", "NeoForge"),
+    ("net.minecraftforge", "Forge"),
+];
+
+/// GDLauncher Next keeps each instance's settings in a `config.json` with a `loader` object
+fn is_gdlauncher_instance(instance_path: &Path) -> bool {
+    std::fs::read_to_string(instance_path.join("config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|data| data["loader"].is_object())
+}
+
+/// A row from the AstralRinth/ModrinthApp `profiles` table
+struct AppDbProfile {
+    name: String,
+    game_version: String,
+    mod_loader: String,
+    mod_loader_version: Option<String>,
+    modified: Option<i64>,
+}
+
+/// Look up a profile in the launcher's app.db by its folder name (the `path` column)
+fn read_app_db_profile(launcher_path: &Path, folder_name: &str) -> Option<AppDbProfile> {
+    let db_path = launcher_path.join("app.db");
+    if !db_path.exists() {
+        return None;
+    }
+
+    let conn = rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| debug!("Could not open {}: {}", db_path.display(), e))
+        .ok()?;
+
+    // SELECT * so older schemas without mod_loader_version still work
+    conn.query_row("SELECT * FROM profiles WHERE path = ?", [folder_name], |row| {
+        Ok(AppDbProfile {
+            name: row.get("name")?,
+            game_version: row.get("game_version")?,
+            mod_loader: row.get("mod_loader")?,
+            mod_loader_version: row.get("mod_loader_version").ok().flatten(),
+            modified: row.get("modified").ok().flatten(),
+        })
+    })
+    .map_err(|e| debug!("No app.db profile for {}: {}", folder_name, e))
+    .ok()
+}
+
+/// Path of a jar relative to the mods dir, using `/` separators
+pub(crate) fn relative_mod_path(mods_dir: &Path, mod_path: &Path) -> String {
+    mod_path.strip_prefix(mods_dir)
+        .unwrap_or(mod_path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The mod id declared in a jar's Fabric, Quilt or (Neo)Forge metadata
+pub(crate) fn read_jar_mod_id(jar_path: &Path) -> Option<String> {
+    read_jar_metadata_field(jar_path, "id", "modId")
+}
+
+/// The mod version declared in a jar's metadata; Forge's `${file.jarVersion}` placeholders don't count
+pub(crate) fn read_jar_mod_version(jar_path: &Path) -> Option<String> {
+    read_jar_metadata_field(jar_path, "version", "version").filter(|version| !version.starts_with('$'))
+}
+
+/// `json_key` from fabric.mod.json (or quilt.mod.json's `quilt_loader`), else `toml_key` from (neoforge.)mods.toml
+fn read_jar_metadata_field(jar_path: &Path, json_key: &str, toml_key: &str) -> Option<String> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(jar_path).ok()?).ok()?;
+    let mut read_entry = |name: &str| -> Option<String> {
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name(name).ok()?, &mut content).ok()?;
+        Some(content)
+    };
+
+    if let Some(content) = read_entry("fabric.mod.json") {
+        let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+        return json[json_key].as_str().map(str::to_string);
+    }
+    if let Some(content) = read_entry("quilt.mod.json") {
+        let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+        return json["quilt_loader"][json_key].as_str().map(str::to_string);
+    }
+    let toml = read_entry("META-INF/neoforge.mods.toml").or_else(|| read_entry("META-INF/mods.toml"))?;
+    toml.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(toml_key)?.trim_start().strip_prefix('=')?;
+        Some(value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Format an epoch timestamp as RFC3339; values this large are milliseconds, otherwise seconds
+fn timestamp_to_rfc3339(timestamp: i64) -> Option<String> {
+    let datetime = if timestamp > 100_000_000_000 {
+        chrono::DateTime::from_timestamp_millis(timestamp)
+    } else {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+    };
+    datetime.map(|t| t.to_rfc3339())
+}
+
+fn file_modified_rfc3339(path: &Path) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
+}
+
+/// Map a lowercase loader id (as stored by Modrinth-style launchers) to its display name
+fn loader_display_name(loader: &str) -> String {
+    match loader.to_lowercase().as_str() {
+        "neoforge" => "NeoForge".to_string(),
+        "fabric" => "Fabric".to_string(),
+        "forge" => "Forge".to_string(),
+        "quilt" => "Quilt".to_string(),
+        "vanilla" => "Vanilla".to_string(),
+        _ => loader.to_string(),
+    }
+}
+
+/// Reduce a mod's file or display name to the key used to match it across pack versions
+///
+/// Only the text before the first `$` (bundled jars) counts. Words separated by `-`, `_` or `+` are kept
+/// up to the first version-like word (`0.6.0`, `v3`, `mc1.21.1`) or, after the first word, a loader
+/// name, so "sodium-extra-0.6.0.jar" and "sodium-extra-0.7.0+mc1.21.1.jar" both become "sodium-extra".
+pub fn normalize_mod_name(name: &str) -> String {
+    let name = name.to_lowercase();
+    let name = name.strip_suffix(".disabled").unwrap_or(&name);
+    let name = name.strip_suffix(".jar").unwrap_or(name);
+    let name = name.split('$').next().unwrap_or(name);
+
+    let is_version = |word: &str| {
+        let digits = word.strip_prefix("mc").or_else(|| word.strip_prefix('v')).unwrap_or(word);
+        digits.starts_with(|c: char| c.is_ascii_digit())
+    };
+    let is_loader = |word: &str| matches!(word, "fabric" | "forge" | "neoforge" | "quilt");
+
+    let mut end = 0;
+    for (i, word) in name.split(['-', '_', '+']).enumerate() {
+        if i > 0 && (is_version(word) || is_loader(word)) {
+            break;
+        }
+        end += word.len() + usize::from(i > 0);
+    }
+    name[..end].to_string()
+}
+
+/// Reads instance metadata and installed mods from every supported launcher layout
+pub struct InstanceScanner<'a> {
+    launcher_manager: &'a LauncherManager,
+    recursive_mods: bool,
+}
+
+impl<'a> InstanceScanner<'a> {
+    /// Scan the launchers `launcher_manager` detects, including its extra search paths
+    pub fn new(launcher_manager: &'a LauncherManager) -> Self {
+        Self { launcher_manager, recursive_mods: false }
+    }
+
+    /// Also count jars in subfolders of the mods directory (e.g. `mods/optional/`)
+    pub fn with_recursive_mods(mut self, recursive: bool) -> Self {
+        self.recursive_mods = recursive;
+        self
+    }
+
+    /// Scan all launchers and return instance information
+    pub async fn scan_instances(&self) -> Result<Vec<InstanceInfo>> {
+        info!("🔍 Scanning for Minecraft instances...");
+
+        let mut instances = Vec::new();
+        let detected_launchers = self.launcher_manager.detect_launchers().await;
+
+        for (launcher_type, launcher_path) in detected_launchers {
+            instances.extend(self.scan_launcher(&launcher_type, &launcher_path).await?);
+        }
+
+        info!("🎯 Total instances found: {}", instances.len());
+        Ok(instances)
+    }
+
+    /// Scan the instances of a single launcher
+    pub async fn scan_launcher(&self, launcher_type: &LauncherType, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        match launcher_type {
+            LauncherType::AstralRinth => {
+                info!("📱 Scanning AstralRinth database at: {}", launcher_path.display());
+                let profiles = self.scan_astralrinth_profiles(launcher_path).await?;
+                info!("✅ Found {} AstralRinth instances", profiles.len());
+                Ok(profiles)
+            }
+            LauncherType::ModrinthApp => {
+                info!("📱 Scanning ModrinthApp database at: {}", launcher_path.display());
+                let profiles = self.scan_astralrinth_profiles(launcher_path).await?;
+                info!("✅ Found {} ModrinthApp instances", profiles.len());
+                Ok(profiles)
+            }
+            LauncherType::XMCL => {
+                info!("📁 Scanning XMCL folders at: {}", launcher_path.display());
+                let xmcl_instances = self.scan_xmcl_instances(launcher_path).await?;
+                info!("✅ Found {} XMCL instances", xmcl_instances.len());
+                Ok(xmcl_instances)
+            }
+            LauncherType::Prism | LauncherType::PrismCracked => {
+                info!("📁 Scanning PrismLauncher folders at: {}", launcher_path.display());
+                let prism_instances = self.scan_prism_instances(launcher_path, launcher_type).await?;
+                info!("✅ Found {} PrismLauncher instances", prism_instances.len());
+                Ok(prism_instances)
+            }
+            LauncherType::Official => {
+                info!("📁 Scanning Official Minecraft profiles at: {}", launcher_path.display());
+                let official_instances = self.scan_official_instances(launcher_path).await?;
+                info!("✅ Found {} Official Minecraft instances", official_instances.len());
+                Ok(official_instances)
+            }
+            LauncherType::MultiMC => {
+                info!("📁 Scanning MultiMC folders at: {}", launcher_path.display());
+                let mmc_instances = self.scan_mmc_instances(launcher_path).await?;
+                info!("✅ Found {} MultiMC instances", mmc_instances.len());
+                Ok(mmc_instances)
+            }
+            LauncherType::ATLauncher => {
+                info!("📁 Scanning ATLauncher folders at: {}", launcher_path.display());
+                let atlauncher_instances = self.scan_atlauncher_instances(launcher_path).await?;
+                info!("✅ Found {} ATLauncher instances", atlauncher_instances.len());
+                Ok(atlauncher_instances)
+            }
+            LauncherType::GDLauncher => {
+                info!("📁 Scanning GDLauncher folders at: {}", launcher_path.display());
+                let gdlauncher_instances = self.scan_gdlauncher_instances(launcher_path).await?;
+                info!("✅ Found {} GDLauncher instances", gdlauncher_instances.len());
+                Ok(gdlauncher_instances)
+            }
+            _ => {
+                debug!("⏭️  Skipping unsupported launcher type: {}", launcher_type);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Analyze a single instance directory, detecting its launcher layout from the files present
+    pub async fn analyze_instance(&self, instance_path: &Path) -> Result<Option<InstanceInfo>> {
+        // Instances live at <launcher>/<instances|profiles>/<name>
+        let launcher_path = instance_path.parent()
+            .and_then(|p| p.parent())
+            .unwrap_or(instance_path)
+            .to_path_buf();
+
+        if instance_path.join("instance.json").exists() {
+            if is_atlauncher_instance(instance_path) {
+                self.analyze_atlauncher_instance(instance_path, &launcher_path).await
+            } else {
+                self.analyze_xmcl_instance(instance_path, &launcher_path).await
+            }
+        } else if instance_path.join("mmc-pack.json").exists() {
+            self.analyze_prism_instance(instance_path, &launcher_path).await
+        } else if is_gdlauncher_instance(instance_path) {
+            self.analyze_gdlauncher_instance(instance_path, &launcher_path).await
+        } else if instance_path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()) == Some("profiles") {
+            self.analyze_astralrinth_profile(instance_path, &launcher_path).await
+        } else if let Some((official_path, profile_id)) = self.launcher_manager.find_official_profile(instance_path).await {
+            let profiles_content = fs::read_to_string(official_profiles_path(&official_path)).await?;
+            let profiles_data: serde_json::Value = serde_json::from_str(&profiles_content)?;
+            self.analyze_official_profile(&profile_id, &profiles_data["profiles"][&profile_id], &official_path).await
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Scan AstralRinth/ModrinthApp profiles
+    async fn scan_astralrinth_profiles(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        let mut instances = Vec::new();
+        let profiles_dir = launcher_path.join("profiles");
+
+        if !profiles_dir.exists() {
+            return Ok(instances);
+        }
+
+        let mut entries = fs::read_dir(&profiles_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let profile_path = entry.path();
+            if profile_path.is_dir() {
+                if let Some(instance_info) = self.analyze_astralrinth_profile(&profile_path, launcher_path).await? {
+                    instances.push(instance_info);
+                }
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Analyze a single AstralRinth profile
+    async fn analyze_astralrinth_profile(
+        &self,
+        profile_path: &Path,
+        launcher_path: &Path,
+    ) -> Result<Option<InstanceInfo>> {
+        // AstralRinth stores profile info in the database, not in profile.json files
+        let folder_name = profile_path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Unknown")
+            .to_string();
+
+        let (profile_name, minecraft_version, mod_loader, mod_loader_version, last_updated) =
+            match read_app_db_profile(launcher_path, &folder_name) {
+                Some(row) => (
+                    row.name,
+                    row.game_version,
+                    loader_display_name(&row.mod_loader),
+                    row.mod_loader_version,
+                    row.modified.and_then(timestamp_to_rfc3339),
+                ),
+                None => {
+                    // No database row; fall back to guessing from the folder name
+                    let lower = folder_name.to_lowercase();
+                    let mod_loader = if lower.contains("neoforge") {
+                        "NeoForge"
+                    } else if lower.contains("fabric") {
+                        "Fabric"
+                    } else if lower.contains("forge") {
+                        "Forge"
+                    } else {
+                        "Unknown"
+                    };
+
+                    let minecraft_version = folder_name.split('-')
+                        .find(|part| part.starts_with("1.") && part.len() >= 3)
+                        .unwrap_or("Unknown")
+                        .to_string();
+
+                    (folder_name.clone(), minecraft_version, mod_loader.to_string(), None, None)
+                }
+            };
+
+        // Analyze mods
+        let mods_dir = profile_path.join("mods");
+        let (mods, mod_count) = if mods_dir.exists() {
+            let mods = self.analyze_mods_directory(&mods_dir).await?;
+            (mods.clone(), mods.len())
+        } else {
+            (Vec::new(), 0)
+        };
+
+        // Check for automodpack
+        let has_automodpack = profile_path.join("automodpack-known-hosts.json").exists();
+        let server_info = if has_automodpack {
+            self.extract_server_info(profile_path).await.ok()
+        } else {
+            None
+        };
+
+        // Determine launcher type based on the launcher path
+        let launcher_type = if launcher_path.to_string_lossy().contains("ModrinthApp") {
+            LauncherType::ModrinthApp
+        } else {
+            LauncherType::AstralRinth
+        };
+
+        Ok(Some(InstanceInfo {
+            name: profile_name,
+            launcher_type: launcher_type.to_string(),
+            launcher_path: launcher_path.to_string_lossy().to_string(),
+            instance_path: profile_path.to_string_lossy().to_string(),
+            minecraft_version,
+            mod_loader,
+            mod_loader_version,
+            mod_count,
+            mods,
+            has_automodpack,
+            server_info,
+            last_updated,
+        }))
+    }
+
+    /// Scan XMCL instances
+    async fn scan_xmcl_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        let mut instances = Vec::new();
+        let instances_dir = launcher_path.join("instances");
+
+        if !instances_dir.exists() {
+            return Ok(instances);
+        }
+
+        let mut entries = fs::read_dir(&instances_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let instance_path = entry.path();
+            if instance_path.is_dir() {
+                if let Some(instance_info) = self.analyze_xmcl_instance(&instance_path, launcher_path).await? {
+                    instances.push(instance_info);
+                }
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Analyze a single XMCL instance
+    async fn analyze_xmcl_instance(
+        &self,
+        instance_path: &Path,
+        launcher_path: &Path,
+    ) -> Result<Option<InstanceInfo>> {
+        let instance_json_path = instance_path.join("instance.json");
+        if !instance_json_path.exists() {
+            return Ok(None);
+        }
+
+        let instance_content = fs::read_to_string(&instance_json_path).await?;
+        let instance_data: serde_json::Value = serde_json::from_str(&instance_content)?;
+
+        let name = instance_data["name"].as_str().unwrap_or("Unknown").to_string();
+        let minecraft_version = instance_data["runtime"]["minecraft"].as_str().unwrap_or("Unknown").to_string();
+        let last_updated = instance_data["lastPlayedDate"].as_i64()
+            .filter(|&t| t > 0)
+            .and_then(timestamp_to_rfc3339);
+        
+        // Determine mod loader from XMCL runtime structure
+        let (mod_loader, mod_loader_version) = if let Some(neo_forged) = instance_data["runtime"]["neoForged"].as_str() {
+            if !neo_forged.is_empty() {
+                ("NeoForge".to_string(), Some(neo_forged.to_string()))
+            } else if let Some(fabric_loader) = instance_data["runtime"]["fabricLoader"].as_str() {
+                if !fabric_loader.is_empty() {
+                    ("Fabric".to_string(), Some(fabric_loader.to_string()))
+                } else if let Some(forge) = instance_data["runtime"]["forge"].as_str() {
+                    if !forge.is_empty() {
+                        ("Forge".to_string(), Some(forge.to_string()))
+                    } else {
+                        ("Unknown".to_string(), None)
+                    }
+                } else {
+                    ("Unknown".to_string(), None)
+                }
+            } else if let Some(forge) = instance_data["runtime"]["forge"].as_str() {
+                if !forge.is_empty() {
+                    ("Forge".to_string(), Some(forge.to_string()))
+                } else {
+                    ("Unknown".to_string(), None)
+                }
+            } else {
+                ("Unknown".to_string(), None)
+            }
+        } else if let Some(fabric_loader) = instance_data["runtime"]["fabricLoader"].as_str() {
+            if !fabric_loader.is_empty() {
+                ("Fabric".to_string(), Some(fabric_loader.to_string()))
+            } else {
+                ("Unknown".to_string(), None)
+            }
+        } else if let Some(forge) = instance_data["runtime"]["forge"].as_str() {
+            if !forge.is_empty() {
+                ("Forge".to_string(), Some(forge.to_string()))
+            } else {
+                ("Unknown".to_string(), None)
+            }
+        } else {
+            ("Unknown".to_string(), None)
+        };
+
+        // Analyze mods
+        let mods_dir = instance_path.join("mods");
+        let (mods, mod_count) = if mods_dir.exists() {
+            let mods = self.analyze_mods_directory(&mods_dir).await?;
+            (mods.clone(), mods.len())
+        } else {
+            (Vec::new(), 0)
+        };
+
+        // Check for automodpack
+        let has_automodpack = instance_path.join("automodpack-known-hosts.json").exists();
+        let server_info = if has_automodpack {
+            self.extract_server_info(instance_path).await.ok()
+        } else {
+            None
+        };
+
+        Ok(Some(InstanceInfo {
+            name,
+            launcher_type: LauncherType::XMCL.to_string(),
+            launcher_path: launcher_path.to_string_lossy().to_string(),
+            instance_path: instance_path.to_string_lossy().to_string(),
+            minecraft_version,
+            mod_loader,
+            mod_loader_version,
+            mod_count,
+            mods,
+            has_automodpack,
+            server_info,
+            last_updated,
+        }))
+    }
+
+    /// Scan PrismLauncher instances
+    async fn scan_prism_instances(&self, launcher_path: &Path, launcher_type: &LauncherType) -> Result<Vec<InstanceInfo>> {
+        let instances_dir = PrismConfig::load(launcher_path).instance_dir;
+        self.scan_mmc_style_instances(&instances_dir, launcher_path, launcher_type).await
+    }
+
+    /// Scan MultiMC instances, which share Prism's instance.cfg/mmc-pack.json format
+    async fn scan_mmc_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        let instances_dir = PrismConfig::load_file(launcher_path, "multimc.cfg").instance_dir;
+        self.scan_mmc_style_instances(&instances_dir, launcher_path, &LauncherType::MultiMC).await
+    }
+
+    async fn scan_mmc_style_instances(
+        &self,
+        instances_dir: &Path,
+        launcher_path: &Path,
+        launcher_type: &LauncherType,
+    ) -> Result<Vec<InstanceInfo>> {
+        let mut instances = Vec::new();
+
+        if !instances_dir.exists() {
+            return Ok(instances);
+        }
+
+        let mut entries = fs::read_dir(&instances_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let instance_path = entry.path();
+            if instance_path.is_dir() {
+                if let Some(instance_info) = self.analyze_mmc_style_instance(&instance_path, launcher_path, launcher_type).await? {
+                    instances.push(instance_info);
+                }
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Analyze a single PrismLauncher instance
+    async fn analyze_prism_instance(
+        &self,
+        instance_path: &Path,
+        launcher_path: &Path,
+    ) -> Result<Option<InstanceInfo>> {
+        self.analyze_mmc_style_instance(instance_path, launcher_path, &LauncherType::Prism).await
+    }
+
+    /// Analyze a single PrismLauncher/MultiMC instance
+    async fn analyze_mmc_style_instance(
+        &self,
+        instance_path: &Path,
+        launcher_path: &Path,
+        launcher_type: &LauncherType,
+    ) -> Result<Option<InstanceInfo>> {
+        let instance_cfg_path = instance_path.join("instance.cfg");
+        let mmc_pack_path = instance_path.join("mmc-pack.json");
+
+        if !instance_cfg_path.exists() || !mmc_pack_path.exists() {
+            return Ok(None);
+        }
+
+        // Read instance name from instance.cfg
+        let instance_cfg_content = fs::read_to_string(&instance_cfg_path).await?;
+        let name = instance_cfg_content
+            .lines()
+            .find(|line| line.starts_with("name="))
+            .and_then(|line| line.split('=').nth(1))
+            .unwrap_or("Unknown")
+            .to_string();
+
+        // lastLaunchTime is epoch millis; instances never launched fall back to the pack file's mtime
+        let last_updated = instance_cfg_content
+            .lines()
+            .find_map(|line| line.strip_prefix("lastLaunchTime="))
+            .and_then(|value| value.trim().parse::<i64>().ok())
+            .filter(|&t| t > 0)
+            .and_then(timestamp_to_rfc3339)
+            .or_else(|| file_modified_rfc3339(&mmc_pack_path));
+
+        // Read pack info from mmc-pack.json
+        let mmc_pack_content = fs::read_to_string(&mmc_pack_path).await?;
+        let mmc_pack_data: serde_json::Value = serde_json::from_str(&mmc_pack_content)?;
+
+        let components = mmc_pack_data["components"].as_array().map(Vec::as_slice).unwrap_or_default();
+        let component = |uid: &str| components.iter().find(|c| c["uid"].as_str() == Some(uid));
+        let component_version = |c: &serde_json::Value| {
+            c["version"].as_str().or_else(|| c["cachedVersion"].as_str()).map(|v| v.to_string())
+        };
+
+        let minecraft_version = component("net.minecraft")
+            .and_then(component_version)
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        // Match loaders by component uid; display names like "NeoForge" contain "Forge"
+        let (mod_loader, mod_loader_version) = MMC_LOADER_UIDS.iter()
+            .find_map(|(uid, loader)| component(uid).map(|c| (loader.to_string(), component_version(c))))
+            .unwrap_or_else(|| ("Unknown".to_string(), None));
+
+        let minecraft_dir = minecraft_base_dir(instance_path);
+
+        // Analyze mods
+        let mods_dir = minecraft_dir.join("mods");
+        let (mods, mod_count) = if mods_dir.exists() {
+            let mods = self.analyze_mods_directory(&mods_dir).await?;
+            (mods.clone(), mods.len())
+        } else {
+            (Vec::new(), 0)
+        };
+
+        // Check for automodpack
+        let has_automodpack = minecraft_dir.join("automodpack-known-hosts.json").exists();
+        let server_info = if has_automodpack {
+            self.extract_server_info(&minecraft_dir).await.ok()
+        } else {
+            None
+        };
+
+        Ok(Some(InstanceInfo {
+            name,
+            launcher_type: launcher_type.to_string(),
+            launcher_path: launcher_path.to_string_lossy().to_string(),
+            instance_path: instance_path.to_string_lossy().to_string(),
+            minecraft_version,
+            mod_loader,
+            mod_loader_version,
+            mod_count,
+            mods,
+            has_automodpack,
+            server_info,
+            last_updated,
+        }))
+    }
+
+    /// Scan ATLauncher instances
+    async fn scan_atlauncher_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        let mut instances = Vec::new();
+        let instances_dir = launcher_path.join("instances");
+
+        if !instances_dir.exists() {
+            return Ok(instances);
+        }
+
+        let mut entries = fs::read_dir(&instances_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let instance_path = entry.path();
+            if instance_path.is_dir() {
+                if let Some(instance_info) = self.analyze_atlauncher_instance(&instance_path, launcher_path).await? {
+                    instances.push(instance_info);
+                }
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Analyze a single ATLauncher instance
+    async fn analyze_atlauncher_instance(
+        &self,
+        instance_path: &Path,
+        launcher_path: &Path,
+    ) -> Result<Option<InstanceInfo>> {
+        let instance_json_path = instance_path.join("instance.json");
+        if !instance_json_path.exists() {
+            return Ok(None);
+        }
+
+        let instance_content = fs::read_to_string(&instance_json_path).await?;
+        let instance_data: serde_json::Value = serde_json::from_str(&instance_content)?;
+        let launcher = &instance_data["launcher"];
+
+        let name = launcher["name"].as_str()
+            .or_else(|| instance_path.file_name().and_then(|n| n.to_str()))
+            .unwrap_or("Unknown")
+            .to_string();
+        // ATLauncher's instance.json embeds the version json, so "id" is the Minecraft version
+        let minecraft_version = instance_data["id"].as_str()
+            .or_else(|| instance_data["minecraftVersion"].as_str())
+            .unwrap_or("Unknown")
+            .to_string();
+
+        let (mod_loader, mod_loader_version) = match launcher["loaderVersion"]["type"].as_str() {
+            Some(loader_type) if !loader_type.is_empty() => (
+                loader_type.to_string(),
+                launcher["loaderVersion"]["version"].as_str().map(|v| v.to_string()),
+            ),
+            _ => ("Vanilla".to_string(), None),
+        };
+
+        // Analyze mods
+        let mods_dir = instance_path.join("mods");
+        let (mods, mod_count) = if mods_dir.exists() {
+            let mods = self.analyze_mods_directory(&mods_dir).await?;
+            (mods.clone(), mods.len())
+        } else {
+            (Vec::new(), 0)
+        };
+
+        // Check for automodpack
+        let has_automodpack = instance_path.join("automodpack-known-hosts.json").exists();
+        let server_info = if has_automodpack {
+            self.extract_server_info(instance_path).await.ok()
+        } else {
+            None
+        };
+
+        Ok(Some(InstanceInfo {
+            name,
+            launcher_type: LauncherType::ATLauncher.to_string(),
+            launcher_path: launcher_path.to_string_lossy().to_string(),
+            instance_path: instance_path.to_string_lossy().to_string(),
+            minecraft_version,
+            mod_loader,
+            mod_loader_version,
+            mod_count,
+            mods,
+            has_automodpack,
+            server_info,
+            last_updated: None,
+        }))
+    }
+
+    /// Scan GDLauncher Next instances
+    async fn scan_gdlauncher_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        let mut instances = Vec::new();
+        let instances_dir = launcher_path.join("instances");
+
+        if !instances_dir.exists() {
+            return Ok(instances);
+        }
+
+        let mut entries = fs::read_dir(&instances_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let instance_path = entry.path();
+            if instance_path.is_dir() {
+                if let Some(instance_info) = self.analyze_gdlauncher_instance(&instance_path, launcher_path).await? {
+                    instances.push(instance_info);
+                }
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Analyze a single GDLauncher Next instance
+    async fn analyze_gdlauncher_instance(
+        &self,
+        instance_path: &Path,
+        launcher_path: &Path,
+    ) -> Result<Option<InstanceInfo>> {
+        let config_path = instance_path.join("config.json");
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let config_content = fs::read_to_string(&config_path).await?;
+        let config_data: serde_json::Value = serde_json::from_str(&config_content)?;
+        let loader = &config_data["loader"];
+
+        // GDLauncher has no display name; the folder name is what it shows
+        let name = instance_path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Unknown")
+            .to_string();
+        let minecraft_version = loader["mcVersion"].as_str().unwrap_or("Unknown").to_string();
+
+        let (mod_loader, mod_loader_version) = match loader["loaderType"].as_str().map(str::to_lowercase).as_deref() {
+            Some("fabric") => ("Fabric".to_string(), loader["loaderVersion"].as_str().map(|v| v.to_string())),
+            Some("quilt") => ("Quilt".to_string(), loader["loaderVersion"].as_str().map(|v| v.to_string())),
+            Some("neoforge") => ("NeoForge".to_string(), loader["loaderVersion"].as_str().map(|v| v.to_string())),
+            Some("forge") => ("Forge".to_string(), loader["loaderVersion"].as_str().map(|v| v.to_string())),
+            Some("vanilla") | None => ("Vanilla".to_string(), None),
+            Some(_) => ("Unknown".to_string(), None),
+        };
+
+        // Analyze mods
+        let mods_dir = instance_path.join("mods");
+        let (mods, mod_count) = if mods_dir.exists() {
+            let mods = self.analyze_mods_directory(&mods_dir).await?;
+            (mods.clone(), mods.len())
+        } else {
+            (Vec::new(), 0)
+        };
+
+        // Check for automodpack
+        let has_automodpack = instance_path.join("automodpack-known-hosts.json").exists();
+        let server_info = if has_automodpack {
+            self.extract_server_info(instance_path).await.ok()
+        } else {
+            None
+        };
+
+        Ok(Some(InstanceInfo {
+            name,
+            launcher_type: LauncherType::GDLauncher.to_string(),
+            launcher_path: launcher_path.to_string_lossy().to_string(),
+            instance_path: instance_path.to_string_lossy().to_string(),
+            minecraft_version,
+            mod_loader,
+            mod_loader_version,
+            mod_count,
+            mods,
+            has_automodpack,
+            server_info,
+            last_updated: config_data["lastPlayed"].as_i64()
+                .filter(|&t| t > 0)
+                .and_then(timestamp_to_rfc3339),
+        }))
+    }
+
+    /// Scan Official Minecraft Launcher instances
+    async fn scan_official_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        let mut instances = Vec::new();
+        let profiles_path = official_profiles_path(launcher_path);
+
+        if !profiles_path.exists() {
+            return Ok(instances);
+        }
+
+        let profiles_content = fs::read_to_string(&profiles_path).await?;
+        let profiles_data: serde_json::Value = serde_json::from_str(&profiles_content)?;
+
+        if let Some(profiles) = profiles_data["profiles"].as_object() {
+            for (profile_id, profile) in profiles {
+                if let Some(instance_info) = self.analyze_official_profile(profile_id, profile, launcher_path).await? {
+                    instances.push(instance_info);
+                }
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Analyze a single Official Minecraft profile
+    async fn analyze_official_profile(
+        &self,
+        _profile_id: &str,
+        profile: &serde_json::Value,
+        launcher_path: &Path,
+    ) -> Result<Option<InstanceInfo>> {
+        let name = profile["name"].as_str().unwrap_or("Unknown").to_string();
+        let minecraft_version = profile["lastVersionId"].as_str().unwrap_or("Unknown").to_string();
+
+        // Official launcher doesn't have mod loaders by default
+        let mod_loader = "Vanilla".to_string();
+
+        // Check if this is a modded profile by looking for mods directory
+        let game_dir = profile["gameDir"].as_str()
+            .map(|s| PathBuf::from(s))
+            .unwrap_or_else(|| launcher_path.to_path_buf());
+
+        let mods_dir = game_dir.join("mods");
+        let (mods, mod_count) = if mods_dir.exists() {
+            let mods = self.analyze_mods_directory(&mods_dir).await?;
+            (mods.clone(), mods.len())
+        } else {
+            (Vec::new(), 0)
+        };
+
+        // Check for automodpack
+        let has_automodpack = game_dir.join("automodpack-known-hosts.json").exists();
+        let server_info = if has_automodpack {
+            self.extract_server_info(&game_dir).await.ok()
+        } else {
+            None
+        };
+
+        Ok(Some(InstanceInfo {
+            name,
+            launcher_type: LauncherType::Official.to_string(),
+            launcher_path: launcher_path.to_string_lossy().to_string(),
+            instance_path: game_dir.to_string_lossy().to_string(),
+            minecraft_version,
+            mod_loader,
+            mod_loader_version: None,
+            mod_count,
+            mods,
+            has_automodpack,
+            server_info,
+            last_updated: profile["lastUsed"].as_str()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&chrono::Utc).to_rfc3339()),
+        }))
+    }
+
+    /// Analyze mods in a directory
+    pub(crate) async fn analyze_mods_directory(&self, mods_dir: &Path) -> Result<Vec<ModInfo>> {
+        let mut mods = Vec::new();
+
+        for mod_path in self.collect_mod_jars(mods_dir).await? {
+            if let Some(mut mod_info) = self.analyze_mod_file(&mod_path).await? {
+                mod_info.filename = relative_mod_path(mods_dir, &mod_path);
+                mods.push(mod_info);
+            }
+        }
+
+        Ok(mods)
+    }
+
+    /// List the jars in a mods directory, descending into subfolders when recursive scanning is on
+    pub(crate) async fn collect_mod_jars(&self, mods_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut jars = Vec::new();
+        let mut pending = vec![mods_dir.to_path_buf()];
+
+        while let Some(dir) = pending.pop() {
+            let mut entries = fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.is_dir() {
+                    if self.recursive_mods {
+                        pending.push(path);
+                    }
+                } else if path.extension().and_then(|s| s.to_str()) == Some("jar") {
+                    jars.push(path);
+                }
+            }
+        }
+
+        // Directory order varies by filesystem; sorting keeps same-name mods resolving the same way every run
+        jars.sort();
+        Ok(jars)
+    }
+
+    /// Analyze a single mod file
+    async fn analyze_mod_file(&self, mod_path: &Path) -> Result<Option<ModInfo>> {
+        let filename = mod_path.file_name().unwrap().to_string_lossy().to_string();
+        let metadata = fs::metadata(mod_path).await?;
+        let file_size = metadata.len();
+        let last_modified = metadata.modified()?.duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        // Try to extract mod information from JAR
+        let (name, version, mod_id) = self.extract_mod_metadata(mod_path).await?;
+
+        // Determine if this is a user mod (not from a known modpack)
+        let is_user_mod = self.is_user_mod(&filename, &name);
+
+        Ok(Some(ModInfo {
+            name,
+            filename,
+            version,
+            mod_id,
+            is_user_mod,
+            file_size,
+            last_modified: chrono::DateTime::from_timestamp(last_modified as i64, 0)
+                .unwrap_or_default()
+                .to_rfc3339(),
+            sha1: None,
+        }))
+    }
+
+    /// Extract mod metadata from JAR file
+    async fn extract_mod_metadata(&self, mod_path: &Path) -> Result<(String, Option<String>, Option<String>)> {
+        // Name and version come from the filename; only the mod id is read from the jar's metadata
+        let filename = mod_path.file_name().unwrap().to_string_lossy();
+
+        // Extract name from filename (remove version numbers)
+        let name = filename
+            .replace(".jar", "")
+            .split('-')
+            .next()
+            .unwrap_or(&filename)
+            .to_string();
+
+        // Try to extract version from filename
+        let version = self.extract_version_from_filename(&filename);
+
+        Ok((name, version, read_jar_mod_id(mod_path)))
+    }
+
+    /// Extract version from filename
+    fn extract_version_from_filename(&self, filename: &str) -> Option<String> {
+        // Look for version patterns like -1.0.0, -1.0, etc.
+        let parts: Vec<&str> = filename.split('-').collect();
+        if parts.len() > 1 {
+            let last_part = parts.last().unwrap().replace(".jar", "");
+            if last_part.chars().any(|c| c.is_ascii_digit()) {
+                return Some(last_part);
+            }
+        }
+        None
+    }
+
+    /// Determine if a mod is user-added
+    fn is_user_mod(&self, filename: &str, name: &str) -> bool {
+        // Known modpack mods that should be updated
+        let known_modpack_mods = [
+            "sodium", "iris", "lithium", "phosphor", "fabric-api", "neoforge",
+            "jei", "jade", "wthit", "modmenu", "cloth-config", "auto-config",
+        ];
+
+        let lowercase_name = name.to_lowercase();
+        let lowercase_filename = filename.to_lowercase();
+
+        // Check if it's a known modpack mod
+        for known_mod in &known_modpack_mods {
+            if lowercase_name.contains(known_mod) || lowercase_filename.contains(known_mod) {
+                return false; // This is a modpack mod, not a user mod
+            }
+        }
+
+        // If it doesn't match known modpack patterns, assume it's user-added
+        true
+    }
+
+    /// Extract server information from automodpack files
+    async fn extract_server_info(&self, instance_path: &Path) -> Result<ServerInfo> {
+        let known_hosts_path = instance_path.join("automodpack-known-hosts.json");
+        let servers_dat_path = instance_path.join("servers.dat");
+
+        let mut server_ip = "Unknown".to_string();
+        let mut server_port = 25565;
+        let mut fingerprint = "Unknown".to_string();
+
+        // Read from automodpack-known-hosts.json
+        if known_hosts_path.exists() {
+            let content = fs::read_to_string(&known_hosts_path).await?;
+            if let Ok(hosts_data) = serde_json::from_str::<serde_json::Value>(&content) {
+                if let Some(hosts) = hosts_data["hosts"].as_object() {
+                    for (address, fp) in hosts {
+                        // Keys are servers.dat addresses: the port is only there when it isn't 25565
+                        match address.rsplit_once(':').and_then(|(host, port)| Some((host, port.parse().ok()?))) {
+                            Some((host, port)) => {
+                                server_ip = host.to_string();
+                                server_port = port;
+                            }
+                            None => server_ip = address.clone(),
+                        }
+                        fingerprint = fp.as_str().unwrap_or("Unknown").to_string();
+                        break; // Take the first server
+                    }
+                }
+            }
+        }
+
+        // Try to extract port from servers.dat if available
+        if servers_dat_path.exists() {
+            // This would require parsing NBT format - simplified for now
+            debug!("servers.dat found but NBT parsing not implemented");
+        }
+
+        Ok(ServerInfo {
+            server_ip,
+            server_port,
+            fingerprint,
+            server_name: "NAHA Server".to_string(),
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::time::Duration;
 use tokio::process::Command;
 use tokio::fs;
 use reqwest::Client;
@@ -9,6 +10,7 @@ use indicatif::{ProgressBar, ProgressStyle};
 
 use crate::error::{MinecraftInstallerError, Result};
 use crate::directories::DirectoryManager;
+use crate::download::{HttpConfig, HttpTimeouts};
 
 #[derive(Deserialize, Debug)]
 struct AdoptiumRelease {
@@ -30,24 +32,91 @@ struct AdoptiumPackage {
     size: u64,
 }
 
+/// A Java runtime found on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaInstallation {
+    pub path: PathBuf,
+    pub version: u32,
+    /// JVM architecture from `os.arch`, normalized to `std::env::consts::ARCH` names;
+    /// `None` if the JVM didn't report it
+    pub arch: Option<String>,
+}
+
+impl JavaInstallation {
+    /// Whether this JVM can load natives built for `target_arch`. An unreported architecture is trusted.
+    pub fn matches_arch(&self, target_arch: &str) -> bool {
+        self.arch.as_deref().is_none_or(|arch| arch == normalize_arch(target_arch))
+    }
+}
+
+/// Map JVM `os.arch` values (amd64, arm64, i386, ...) onto Rust's architecture names
+pub fn normalize_arch(arch: &str) -> &str {
+    match arch.trim() {
+        "amd64" | "x86_64" | "x64" => "x86_64",
+        "aarch64" | "arm64" => "aarch64",
+        "x86" | "i386" | "i486" | "i586" | "i686" => "x86",
+        "arm" | "armv7" | "armv7l" => "arm",
+        other => other,
+    }
+}
+
+/// Major version from `java -version` output, e.g. 8 for `"1.8.0_382"`, 21 for `"21-ea"` or 17 for `"17.0.1+12"`
+pub fn parse_java_major(version_output: &str) -> Option<u32> {
+    version_output.lines()
+        .filter(|line| line.contains("version"))
+        .find_map(|line| {
+            // Only the quoted string is the version; build, update and early-access suffixes follow it
+            let version = line.split('"').nth(1)?;
+            let mut numbers = version.split(|c: char| !c.is_ascii_digit());
+            match numbers.next()?.parse().ok()? {
+                // Legacy "1.x" scheme
+                1 => numbers.next()?.parse().ok(),
+                major => Some(major),
+            }
+        })
+}
+
+/// Read `os.arch` from `java -XshowSettings:properties` output
+fn parse_java_arch(settings_output: &str) -> Option<String> {
+    settings_output.lines()
+        .find_map(|line| line.trim().strip_prefix("os.arch = "))
+        .map(|arch| normalize_arch(arch).to_string())
+}
+
 /// Java installation manager
 pub struct JavaManager {
     client: Client,
     dirs: DirectoryManager,
+    java_home: Option<PathBuf>,
+    http: HttpConfig,
 }
 
 impl JavaManager {
     pub fn new(dirs: DirectoryManager) -> Self {
-        let client = Client::builder()
-            .user_agent("MinecraftInstaller/0.1.0")
-            .build()
-            .expect("Failed to create HTTP client");
+        Self { client: HttpConfig::default().client(), dirs, java_home: None, http: HttpConfig::default() }
+    }
+
+    /// Always use the Java in `java_home` (a JDK/JRE directory) instead of finding or downloading one
+    pub fn with_override(mut self, java_home: PathBuf) -> Self {
+        self.java_home = Some(java_home);
+        self
+    }
+
+    /// Replace the default connect (10s) and total (300s) request timeouts
+    pub fn with_timeouts(self, timeouts: HttpTimeouts) -> Self {
+        let http = self.http.clone().with_timeouts(timeouts);
+        self.with_http_config(&http)
+    }
 
-        Self { client, dirs }
+    /// Replace the default timeouts, proxy settings and rate limit
+    pub fn with_http_config(mut self, http: &HttpConfig) -> Self {
+        self.client = http.client();
+        self.http = http.clone();
+        self
     }
 
-    /// Check if Java is installed and get its version
-    pub async fn check_java(&self, java_path: Option<&Path>) -> Result<Option<(PathBuf, u32)>> {
+    /// Check if Java is installed and get its version and architecture
+    pub async fn check_java(&self, java_path: Option<&Path>) -> Result<Option<JavaInstallation>> {
         let java_executable = if let Some(path) = java_path {
             path.to_path_buf()
         } else {
@@ -59,9 +128,9 @@ impl JavaManager {
             return Ok(None);
         }
 
-        // Check Java version
+        // Check Java version; the properties dump (also on stderr) carries os.arch
         let output = Command::new(&java_executable)
-            .args(["-version"])
+            .args(["-XshowSettings:properties", "-version"])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .output()
@@ -75,7 +144,11 @@ impl JavaManager {
         let version_output = String::from_utf8_lossy(&output.stderr);
         let version = self.parse_java_version(&version_output)?;
 
-        Ok(Some((java_executable, version)))
+        Ok(Some(JavaInstallation {
+            path: java_executable,
+            version,
+            arch: parse_java_arch(&version_output),
+        }))
     }
 
     /// Find Java in system PATH
@@ -143,44 +216,19 @@ impl JavaManager {
 
     /// Parse Java version from version output
     fn parse_java_version(&self, version_output: &str) -> Result<u32> {
-        // Look for version pattern like "1.8.0_XXX", "11.0.X", "17.0.X", etc.
-        for line in version_output.lines() {
-            if line.contains("version") {
-                // Extract version string between quotes
-                if let Some(start) = line.find('"') {
-                    if let Some(end) = line[start + 1..].find('"') {
-                        let version_str = &line[start + 1..start + 1 + end];
-
-                        // Parse version number
-                        if version_str.starts_with("1.") {
-                            // Old format: 1.8.0_XXX -> version 8
-                            if let Some(major) = version_str.chars().nth(2) {
-                                if let Some(version) = major.to_digit(10) {
-                                    return Ok(version);
-                                }
-                            }
-                        } else {
-                            // New format: 11.0.X, 17.0.X -> version 11, 17
-                            if let Some(dot_pos) = version_str.find('.') {
-                                if let Ok(version) = version_str[..dot_pos].parse::<u32>() {
-                                    return Ok(version);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Err(MinecraftInstallerError::JavaInstallationFailed(
-            "Could not parse Java version".to_string(),
-        ))
+        parse_java_major(version_output).ok_or_else(|| {
+            MinecraftInstallerError::JavaInstallationFailed("Could not parse Java version".to_string())
+        })
     }
 
-    /// Install Java if needed
-    pub async fn ensure_java(&self, required_version: u32) -> Result<PathBuf> {
+    /// Install Java if needed, returning its path and major version
+    pub async fn ensure_java(&self, required_version: u32) -> Result<(PathBuf, u32)> {
         info!("Checking Java installation...");
 
+        if let Some(java_home) = &self.java_home {
+            return self.check_override(java_home, required_version).await;
+        }
+
         // Check if we already have the right version installed
         let java_dir = self.dirs.java_version_dir(required_version);
         let java_executable = if cfg!(target_os = "windows") {
@@ -189,18 +237,29 @@ impl JavaManager {
             java_dir.join("bin").join("java")
         };
 
-        if let Ok(Some((path, version))) = self.check_java(Some(&java_executable)).await {
-            if version >= required_version {
-                info!("Java {} already installed at {}", version, path.display());
-                return Ok(path);
+        let target_arch = std::env::consts::ARCH;
+
+        if let Ok(Some(java)) = self.check_java(Some(&java_executable)).await {
+            if java.version >= required_version && java.matches_arch(target_arch) {
+                info!("Java {} already installed at {}", java.version, java.path.display());
+                return Ok((java.path, java.version));
             }
         }
 
-        // Check system Java
-        if let Ok(Some((path, version))) = self.check_java(None).await {
-            if version >= required_version {
-                info!("Using system Java {} at {}", version, path.display());
-                return Ok(path);
+        // Check system Java; an emulated JVM of another architecture can't load the game's natives
+        if let Ok(Some(java)) = self.check_java(None).await {
+            if java.version >= required_version {
+                if java.matches_arch(target_arch) {
+                    info!("Using system Java {} at {}", java.version, java.path.display());
+                    return Ok((java.path, java.version));
+                }
+                warn!(
+                    "Ignoring system Java {} at {}: it is {} but this machine is {}",
+                    java.version,
+                    java.path.display(),
+                    java.arch.as_deref().unwrap_or("unknown"),
+                    target_arch
+                );
             }
         }
 
@@ -209,9 +268,9 @@ impl JavaManager {
         self.install_java(required_version).await?;
 
         // Verify installation
-        if let Ok(Some((path, version))) = self.check_java(Some(&java_executable)).await {
-            info!("Java {} successfully installed at {}", version, path.display());
-            Ok(path)
+        if let Ok(Some(java)) = self.check_java(Some(&java_executable)).await {
+            info!("Java {} successfully installed at {}", java.version, java.path.display());
+            Ok((java.path, java.version))
         } else {
             Err(MinecraftInstallerError::JavaInstallationFailed(
                 "Failed to verify Java installation".to_string(),
@@ -219,6 +278,105 @@ impl JavaManager {
         }
     }
 
+    /// Use the Java in `java_home` if it runs and is new enough; never falls back to a download
+    async fn check_override(&self, java_home: &Path, required_version: u32) -> Result<(PathBuf, u32)> {
+        let java_executable = if cfg!(target_os = "windows") {
+            java_home.join("bin").join("java.exe")
+        } else {
+            java_home.join("bin").join("java")
+        };
+
+        let java = self.check_java(Some(&java_executable)).await?.ok_or_else(|| {
+            MinecraftInstallerError::JavaInstallationFailed(format!(
+                "No working Java found at {}",
+                java_executable.display()
+            ))
+        })?;
+        if java.version < required_version {
+            return Err(MinecraftInstallerError::JavaInstallationFailed(format!(
+                "Java {} at {} is too old; Java {} or newer is required",
+                java.version,
+                java.path.display(),
+                required_version
+            )));
+        }
+
+        info!("Using Java {} from {}", java.version, java_home.display());
+        Ok((java.path, java.version))
+    }
+
+    /// JREs installed under `java/java-<major>`, sorted by major version
+    pub fn list_installed(&self) -> Vec<(u32, PathBuf)> {
+        let mut installed: Vec<(u32, PathBuf)> = std::fs::read_dir(self.dirs.java_dir())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| {
+                let major = entry.file_name().to_str()?.strip_prefix("java-")?.parse().ok()?;
+                Some((major, entry.path()))
+            })
+            .collect();
+        installed.sort();
+        installed
+    }
+
+    /// Java major versions the installed Minecraft versions run on (loader versions inherit theirs)
+    pub fn referenced_versions(&self) -> Vec<u32> {
+        let mut majors: Vec<u32> = std::fs::read_dir(self.dirs.versions_dir())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let id = entry.file_name().to_str()?.to_string();
+                let json: serde_json::Value = serde_json::from_slice(&std::fs::read(self.dirs.version_json(&id)).ok()?).ok()?;
+                if json.get("inheritsFrom").is_some() {
+                    return None;
+                }
+                // Same default as the installer: versions predating javaVersion run on Java 8
+                Some(json["javaVersion"]["majorVersion"].as_u64().map_or(8, |major| major as u32))
+            })
+            .collect();
+        majors.sort_unstable();
+        majors.dedup();
+        majors
+    }
+
+    /// Delete installed JREs outside `keep`, sparing any an installed Minecraft version still runs on; returns the removed directories
+    pub async fn prune_unused(&self, keep: &[u32]) -> Result<Vec<PathBuf>> {
+        self.prune_unused_except(keep, &[]).await
+    }
+
+    /// Like [`Self::prune_unused`], also sparing JREs that contain one of `in_use` (e.g. the Java an instance is pinned to)
+    pub async fn prune_unused_except(&self, keep: &[u32], in_use: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        let referenced = self.referenced_versions();
+        let in_use: Vec<PathBuf> = in_use.iter()
+            .map(|java| std::fs::canonicalize(java).unwrap_or_else(|_| java.clone()))
+            .collect();
+        let mut removed = Vec::new();
+
+        for (major, path) in self.list_installed() {
+            if keep.contains(&major) {
+                continue;
+            }
+            if referenced.contains(&major) {
+                info!("Keeping Java {}: an installed Minecraft version still uses it", major);
+                continue;
+            }
+            let jre = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if in_use.iter().any(|java| java.starts_with(&jre)) {
+                info!("Keeping Java {}: an instance is configured to use it", major);
+                continue;
+            }
+
+            fs::remove_dir_all(&path).await?;
+            info!("Removed Java {} from {}", major, path.display());
+            removed.push(path);
+        }
+
+        Ok(removed)
+    }
+
     /// Install Java from Adoptium
     async fn install_java(&self, version: u32) -> Result<()> {
         info!("Downloading Java {} from Adoptium...", version);
@@ -230,8 +388,10 @@ impl JavaManager {
         let install_dir = self.dirs.java_version_dir(version);
         fs::create_dir_all(&install_dir).await?;
 
-        // Download Java
-        let temp_file = install_dir.join("java_installer.tmp");
+        // Download Java into the cache, it is only needed until it is extracted
+        let downloads_dir = self.dirs.downloads_dir();
+        fs::create_dir_all(&downloads_dir).await?;
+        let temp_file = downloads_dir.join(format!("java-{}-installer.tmp", version));
         self.download_java(&download_info.link, &temp_file, download_info.size).await?;
 
         // Extract Java
@@ -324,6 +484,9 @@ impl JavaManager {
 
         while let Some(chunk) = stream.next().await {
             let chunk = chunk?;
+            if let Some(limit) = self.http.rate_limit() {
+                limit.acquire(chunk.len() as u64).await;
+            }
             file.write_all(&chunk).await?;
             downloaded += chunk.len() as u64;
             progress_bar.set_position(downloaded);
@@ -334,72 +497,112 @@ impl JavaManager {
         Ok(())
     }
 
-    /// Extract Java archive
-    async fn extract_java(&self, archive_path: &Path, extract_dir: &Path) -> Result<()> {
+    /// Extract a downloaded JRE archive (zip or tar.gz) into `extract_dir`, streaming entries to disk
+    pub async fn extract_java(&self, archive_path: &Path, extract_dir: &Path) -> Result<()> {
         info!("Extracting Java...");
 
-        if archive_path.extension().and_then(|s| s.to_str()) == Some("zip") {
-            // Windows ZIP file
-            self.extract_zip(archive_path, extract_dir).await
-        } else {
-            // Unix tar.gz file
-            self.extract_tar_gz(archive_path, extract_dir).await
+        let archive_path = archive_path.to_path_buf();
+        let extract_dir = extract_dir.to_path_buf();
+        let extraction = tokio::task::spawn_blocking(move || {
+            let progress_bar = ProgressBar::new(0);
+            progress_bar.set_style(
+                ProgressStyle::default_bar()
+                    .template("{msg} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} files")
+                    .expect("Invalid progress bar template")
+                    .progress_chars("#>-"),
+            );
+            progress_bar.set_message("Extracting Java");
+
+            // The download is saved as a .tmp file, so sniff the zip magic instead of trusting the extension
+            let mut magic = [0u8; 4];
+            let is_zip = std::io::Read::read_exact(&mut std::fs::File::open(&archive_path)?, &mut magic).is_ok()
+                && magic == *b"PK\x03\x04";
+
+            std::fs::create_dir_all(&extract_dir)?;
+            if is_zip {
+                extract_zip(&archive_path, &extract_dir, &progress_bar)?;
+            } else {
+                extract_tar_gz(&archive_path, &extract_dir, &progress_bar)?;
+            }
+
+            progress_bar.finish_with_message("✓ Java extracted");
+            Ok::<(), MinecraftInstallerError>(())
+        });
+
+        match tokio::time::timeout(JAVA_EXTRACT_TIMEOUT, extraction).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => Err(MinecraftInstallerError::JavaInstallationFailed(
+                format!("Java extraction task failed: {}", e),
+            )),
+            Err(_) => Err(MinecraftInstallerError::JavaInstallationFailed(
+                format!("Java extraction timed out after {}s", JAVA_EXTRACT_TIMEOUT.as_secs()),
+            )),
         }
     }
+}
 
-    /// Extract ZIP file (Windows)
-    async fn extract_zip(&self, archive_path: &Path, extract_dir: &Path) -> Result<()> {
-        use std::io::Read;
+/// Upper bound for unpacking a JRE; a healthy extraction takes seconds
+const JAVA_EXTRACT_TIMEOUT: Duration = Duration::from_secs(600);
 
-        let file = std::fs::File::open(archive_path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
+/// Extract ZIP file (Windows)
+fn extract_zip(archive_path: &Path, extract_dir: &Path, progress_bar: &ProgressBar) -> Result<()> {
+    let file = std::fs::File::open(archive_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    progress_bar.set_length(archive.len() as u64);
 
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            let file_path = extract_dir.join(file.name());
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let Some(relative_path) = file.enclosed_name() else {
+            warn!("Skipping unsafe path in Java archive: {}", file.name());
+            continue;
+        };
+        let file_path = extract_dir.join(relative_path);
 
-            if file.is_dir() {
-                fs::create_dir_all(&file_path).await?;
-            } else {
-                if let Some(parent) = file_path.parent() {
-                    fs::create_dir_all(parent).await?;
-                }
+        if file.is_dir() {
+            std::fs::create_dir_all(&file_path)?;
+        } else {
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
 
-                let mut buffer = Vec::new();
-                file.read_to_end(&mut buffer)?;
-                fs::write(&file_path, buffer).await?;
-
-                // Set executable permissions on Unix
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    if file_path.file_name().and_then(|n| n.to_str()) == Some("java") {
-                        let metadata = fs::metadata(&file_path).await?;
-                        let mut perms = metadata.permissions();
-                        perms.set_mode(0o755);
-                        fs::set_permissions(&file_path, perms).await?;
-                    }
-                }
+            let mut output = std::fs::File::create(&file_path)?;
+            std::io::copy(&mut file, &mut output)?;
+
+            // Set executable permissions on Unix
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = match file.unix_mode() {
+                    Some(mode) => mode,
+                    None if file_path.file_name().and_then(|n| n.to_str()) == Some("java") => 0o755,
+                    None => 0o644,
+                };
+                std::fs::set_permissions(&file_path, std::fs::Permissions::from_mode(mode))?;
             }
         }
-
-        Ok(())
+        progress_bar.inc(1);
     }
 
-    /// Extract tar.gz file (Unix)
-    async fn extract_tar_gz(&self, archive_path: &Path, extract_dir: &Path) -> Result<()> {
-        // For simplicity, use system tar command
-        let output = Command::new("tar")
-            .args(["-xzf", archive_path.to_str().unwrap(), "-C", extract_dir.to_str().unwrap()])
-            .output()
-            .await?;
-
-        if !output.status.success() {
-            return Err(MinecraftInstallerError::JavaInstallationFailed(
-                format!("Failed to extract Java: {}", String::from_utf8_lossy(&output.stderr)),
-            ));
-        }
+    Ok(())
+}
 
-        Ok(())
+/// Extract tar.gz file (Unix)
+fn extract_tar_gz(archive_path: &Path, extract_dir: &Path, progress_bar: &ProgressBar) -> Result<()> {
+    let open = || -> Result<tar::Archive<flate2::read::GzDecoder<std::fs::File>>> {
+        Ok(tar::Archive::new(flate2::read::GzDecoder::new(std::fs::File::open(archive_path)?)))
+    };
+
+    // Counting needs its own pass over the stream, but keeps memory flat
+    let total = open()?.entries()?.count();
+    progress_bar.set_length(total as u64);
+
+    let mut archive = open()?;
+    archive.set_preserve_permissions(true);
+    for entry in archive.entries()? {
+        // unpack_in streams the entry and refuses paths that escape extract_dir
+        entry?.unpack_in(extract_dir)?;
+        progress_bar.inc(1);
     }
+
+    Ok(())
 }
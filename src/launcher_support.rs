@@ -5,9 +5,20 @@ use serde_json::json;
 use tracing::{info, debug, warn};
 use uuid::Uuid;
 use rusqlite::{Connection, Result as SqliteResult};
+use tokio_util::sync::CancellationToken;
+use futures::StreamExt;
+use quartz_nbt::{NbtCompound, NbtList};
+use quartz_nbt::io::{read_nbt, write_nbt, Flavor};
 
 use crate::error::{MinecraftInstallerError, Result};
 use crate::directories::DirectoryManager;
+use crate::download::{HttpConfig, HttpTimeouts};
+use crate::modrinth::ModrinthClient;
+use crate::fabric::{FabricResolver, QUILT_META_URL};
+use crate::forge::ForgeResolver;
+use crate::manifest::{InstallManifest, ManifestMod, INSTALL_MANIFEST_FILE};
+use crate::events::{Phase, ProgressReporter};
+use crate::instance::{normalize_mod_name, InstanceInfo, InstanceScanner};
 
 /// API response structure for NAHA modpack information
 #[derive(Debug, Deserialize, Serialize)]
@@ -22,6 +33,188 @@ pub struct NahaModpackInfo {
     pub download_url: String,
     pub server_ip: String,
     pub server_port: u16,
+    /// Every server the pack fronts; empty means just the one above
+    #[serde(default)]
+    pub servers: Vec<NahaServer>,
+    /// SHA-256 of the mrpack from the release asset's digest; `fingerprint` is the server's, not the file's
+    #[serde(default)]
+    pub file_hash: Option<String>,
+}
+
+impl NahaModpackInfo {
+    /// Fail with `InstallationFailed` naming the first required field that is empty
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("download_url", &self.download_url),
+            ("fingerprint", &self.fingerprint),
+            ("server_ip", &self.server_ip),
+        ] {
+            if value.trim().is_empty() {
+                return Err(MinecraftInstallerError::InstallationFailed(format!(
+                    "Modpack info for {} is missing {}", self.server_type, field
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// All servers for this pack, falling back to the single top-level server
+    pub fn all_servers(&self) -> Vec<NahaServer> {
+        if !self.servers.is_empty() {
+            return self.servers.clone();
+        }
+        vec![NahaServer {
+            server_name: self.server_name.clone(),
+            server_ip: self.server_ip.clone(),
+            server_port: self.server_port,
+            fingerprint: self.fingerprint.clone(),
+        }]
+    }
+}
+
+/// One server a NAHA modpack connects to
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct NahaServer {
+    #[serde(default = "default_naha_server_name")]
+    pub server_name: String,
+    pub server_ip: String,
+    #[serde(default = "default_naha_server_port")]
+    pub server_port: u16,
+    #[serde(default = "default_naha_fingerprint")]
+    pub fingerprint: String,
+}
+
+fn default_naha_server_name() -> String {
+    "NAHA Server".to_string()
+}
+
+fn default_naha_server_port() -> u16 {
+    25565
+}
+
+fn default_naha_fingerprint() -> String {
+    "naha-server-fingerprint".to_string()
+}
+
+impl NahaServer {
+    /// Address as the server list stores it; the port is omitted when it's the default
+    pub fn address(&self) -> String {
+        if self.server_port == 25565 {
+            self.server_ip.clone()
+        } else {
+            format!("{}:{}", self.server_ip, self.server_port)
+        }
+    }
+}
+
+/// Read the servers from NAHA API data: either a `servers` list or the single
+/// top-level server the API serves today. Always returns at least one server.
+pub fn parse_naha_servers(data: &serde_json::Value) -> Vec<NahaServer> {
+    let servers: Vec<NahaServer> = data["servers"].as_array()
+        .map(|list| list.iter().filter_map(|s| serde_json::from_value(s.clone()).ok()).collect())
+        .unwrap_or_default();
+    if !servers.is_empty() {
+        return servers;
+    }
+
+    vec![NahaServer {
+        server_name: data["server_name"].as_str().map(str::to_string).unwrap_or_else(default_naha_server_name),
+        server_ip: data["server_ip"].as_str().unwrap_or("play.naha.com").to_string(),
+        server_port: data["server_port"].as_u64().and_then(|p| u16::try_from(p).ok()).unwrap_or_else(default_naha_server_port),
+        fingerprint: data["fingerprint"].as_str().map(str::to_string).unwrap_or_else(default_naha_fingerprint),
+    }]
+}
+
+/// SHA-256 GitHub publishes for a release asset (`"digest": "sha256:<hex>"`), if any
+fn release_asset_sha256(asset: &serde_json::Value) -> Option<String> {
+    asset["digest"].as_str()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+        .filter(|hash| !hash.is_empty())
+        .map(str::to_string)
+}
+
+/// An entry in the in-game multiplayer server list (`servers.dat`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServerListEntry {
+    pub name: String,
+    pub ip: String,
+}
+
+/// Read the multiplayer server list; a missing file is an empty list
+pub fn read_server_list(servers_dat: &Path) -> Result<Vec<ServerListEntry>> {
+    if !servers_dat.exists() {
+        return Ok(Vec::new());
+    }
+    let (root, _) = read_servers_dat(servers_dat)?;
+    let entries = match root.get::<_, &NbtList>("servers") {
+        Ok(servers) => servers.iter()
+            .filter_map(|server| <&NbtCompound>::try_from(server).ok())
+            .map(|server| ServerListEntry {
+                name: server.get::<_, &str>("name").unwrap_or_default().to_string(),
+                ip: server.get::<_, &str>("ip").unwrap_or_default().to_string(),
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    Ok(entries)
+}
+
+/// `automodpack-known-hosts.json` contents trusting each server's fingerprint, keyed by the address clients connect to
+/// (the same `host[:port]` written to servers.dat, so servers sharing a host keep their own entries)
+pub(crate) fn automodpack_known_hosts(servers: &[NahaServer]) -> serde_json::Value {
+    let hosts: serde_json::Map<String, serde_json::Value> = servers.iter()
+        .map(|server| (server.address(), json!(server.fingerprint)))
+        .collect();
+    json!({ "hosts": hosts })
+}
+
+/// Append servers to the multiplayer server list, skipping addresses already on it.
+/// Existing entries are left untouched. Returns how many servers were added.
+pub fn add_servers_to_list(servers_dat: &Path, servers: &[NahaServer]) -> Result<usize> {
+    let (mut root, name) = if servers_dat.exists() {
+        read_servers_dat(servers_dat)?
+    } else {
+        (NbtCompound::new(), String::new())
+    };
+
+    if root.get::<_, &NbtList>("servers").is_err() {
+        root.insert("servers", NbtList::new());
+    }
+    let list = root.get_mut::<_, &mut NbtList>("servers").map_err(invalid_servers_dat(servers_dat))?;
+
+    let mut added = 0;
+    for server in servers {
+        let address = server.address();
+        let listed = list.iter()
+            .filter_map(|entry| <&NbtCompound>::try_from(entry).ok())
+            .any(|entry| entry.get::<_, &str>("ip").is_ok_and(|ip| ip == address));
+        if listed {
+            continue;
+        }
+        let mut entry = NbtCompound::new();
+        entry.insert("ip", address);
+        entry.insert("name", server.server_name.clone());
+        list.push(entry);
+        added += 1;
+    }
+
+    if let Some(parent) = servers_dat.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut out = Vec::new();
+    write_nbt(&mut out, Some(&name), &root, Flavor::Uncompressed).map_err(invalid_servers_dat(servers_dat))?;
+    std::fs::write(servers_dat, out)?;
+    Ok(added)
+}
+
+/// Parse an uncompressed `servers.dat`, returning its root compound and root name
+fn read_servers_dat(servers_dat: &Path) -> Result<(NbtCompound, String)> {
+    let bytes = std::fs::read(servers_dat)?;
+    read_nbt(&mut bytes.as_slice(), Flavor::Uncompressed).map_err(invalid_servers_dat(servers_dat))
+}
+
+fn invalid_servers_dat<E: std::fmt::Display>(servers_dat: &Path) -> impl Fn(E) -> MinecraftInstallerError + '_ {
+    move |e| MinecraftInstallerError::Validation(format!("Invalid NBT data in {}: {}", servers_dat.display(), e))
 }
 
 /// Supported launcher types
@@ -36,10 +229,245 @@ pub enum LauncherType {
     MultiMC,       // MultiMC (legacy)
     ATLauncher,    // ATLauncher
     Technic,       // Technic Launcher
+    GDLauncher,    // GDLauncher Next
     Other,         // Custom/Other launcher (custom path)
     Unknown,       // Unknown launcher type
 }
 
+impl LauncherType {
+    /// Every variant, in the order used for listings
+    pub const ALL: [LauncherType; 12] = [
+        LauncherType::Official,
+        LauncherType::Prism,
+        LauncherType::PrismCracked,
+        LauncherType::XMCL,
+        LauncherType::AstralRinth,
+        LauncherType::ModrinthApp,
+        LauncherType::MultiMC,
+        LauncherType::ATLauncher,
+        LauncherType::Technic,
+        LauncherType::GDLauncher,
+        LauncherType::Other,
+        LauncherType::Unknown,
+    ];
+
+    /// Canonical name, shared by both CLIs and `InstanceInfo::launcher_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LauncherType::Official => "Official",
+            LauncherType::Prism => "PrismLauncher",
+            LauncherType::PrismCracked => "PrismLauncher-Cracked",
+            LauncherType::XMCL => "XMCL",
+            LauncherType::AstralRinth => "AstralRinth",
+            LauncherType::ModrinthApp => "ModrinthApp",
+            LauncherType::MultiMC => "MultiMC",
+            LauncherType::ATLauncher => "ATLauncher",
+            LauncherType::Technic => "Technic",
+            LauncherType::GDLauncher => "GDLauncher",
+            LauncherType::Other => "Other",
+            LauncherType::Unknown => "Unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for LauncherType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LauncherType {
+    type Err = MinecraftInstallerError;
+
+    /// Case-insensitive; accepts the canonical names plus the short aliases the CLIs have always taken
+    fn from_str(s: &str) -> Result<Self> {
+        let key: String = s.chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        let launcher_type = match key.as_str() {
+            "official" | "officialminecraft" | "minecraft" => LauncherType::Official,
+            "prism" | "prismlauncher" => LauncherType::Prism,
+            "prismcracked" | "prismlaunchercracked" => LauncherType::PrismCracked,
+            "xmcl" => LauncherType::XMCL,
+            "astralrinth" | "astralrinthapp" => LauncherType::AstralRinth,
+            "modrinth" | "modrinthapp" => LauncherType::ModrinthApp,
+            "multimc" => LauncherType::MultiMC,
+            "atlauncher" => LauncherType::ATLauncher,
+            "technic" => LauncherType::Technic,
+            "gdlauncher" | "gdlaunchernext" | "gdl" => LauncherType::GDLauncher,
+            "other" => LauncherType::Other,
+            "unknown" => LauncherType::Unknown,
+            _ => {
+                return Err(MinecraftInstallerError::Validation(format!("Unknown launcher: {}", s)));
+            }
+        };
+        Ok(launcher_type)
+    }
+}
+
+/// Directory settings read from a PrismLauncher (or MultiMC) data dir's config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrismConfig {
+    pub instance_dir: PathBuf,
+}
+
+impl PrismConfig {
+    /// Load `InstanceDir`, resolving a relative value against the data dir
+    /// (which is the executable's folder in portable mode). A missing key uses Prism's default.
+    pub fn load(launcher_path: &Path) -> Self {
+        Self::load_file(launcher_path, "prismlauncher.cfg")
+    }
+
+    /// Same as [`PrismConfig::load`] for a differently named config, e.g. MultiMC's `multimc.cfg`
+    pub fn load_file(launcher_path: &Path, cfg_name: &str) -> Self {
+        let content = std::fs::read_to_string(launcher_path.join(cfg_name)).unwrap_or_default();
+        let value = |key: &str| {
+            content
+                .lines()
+                .filter_map(|line| line.trim().split_once('='))
+                .find(|(k, _)| k.trim() == key)
+                .map(|(_, v)| v.trim().trim_matches('"').to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let instance_dir = PathBuf::from(value("InstanceDir").unwrap_or_else(|| "instances".to_string()));
+
+        Self {
+            instance_dir: if instance_dir.is_absolute() { instance_dir } else { launcher_path.join(instance_dir) },
+        }
+    }
+}
+
+/// What happens when a pack override collides with a file already in the instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverridePolicy {
+    /// The pack's file replaces the existing one
+    Overwrite,
+    /// The existing file (e.g. a customized config) is kept
+    SkipExisting,
+}
+
+impl std::str::FromStr for OverridePolicy {
+    type Err = MinecraftInstallerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "overwrite" => Ok(OverridePolicy::Overwrite),
+            "skip-existing" | "skip" => Ok(OverridePolicy::SkipExisting),
+            _ => Err(MinecraftInstallerError::Validation(format!(
+                "Unknown override policy: {} (expected overwrite or skip-existing)", s
+            ))),
+        }
+    }
+}
+
+/// When pack overrides are extracted relative to the mod downloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverrideOrder {
+    /// Configs are in place before any mod is written
+    BeforeMods,
+    /// Mods are written first; overrides land last
+    AfterMods,
+}
+
+impl std::str::FromStr for OverrideOrder {
+    type Err = MinecraftInstallerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "before-mods" | "before" => Ok(OverrideOrder::BeforeMods),
+            "after-mods" | "after" => Ok(OverrideOrder::AfterMods),
+            _ => Err(MinecraftInstallerError::Validation(format!(
+                "Unknown override order: {} (expected before-mods or after-mods)", s
+            ))),
+        }
+    }
+}
+
+/// Which side of the game an mrpack is installed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackSide {
+    Client,
+    Server,
+}
+
+impl PackSide {
+    /// Archive folder holding this side's overrides, extracted after `overrides/`
+    pub fn overrides_folder(&self) -> &'static str {
+        match self {
+            PackSide::Client => "client-overrides",
+            PackSide::Server => "server-overrides",
+        }
+    }
+
+    /// Whether a pack file is installed on this side; files without `env` go everywhere
+    pub fn supports(&self, env: Option<&MrpackEnv>) -> bool {
+        env.is_none_or(|env| match self {
+            PackSide::Client => env.client != "unsupported",
+            PackSide::Server => env.server != "unsupported",
+        })
+    }
+}
+
+/// Memory, JVM arguments and Java runtime for a created instance; `None` keeps the launcher's default
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceOptions {
+    pub min_memory_mb: Option<u32>,
+    pub max_memory_mb: Option<u32>,
+    pub java_args: Option<Vec<String>>,
+    pub java_path: Option<PathBuf>,
+}
+
+impl InstanceOptions {
+    /// True when every setting is left to the launcher
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How an mrpack's `overrides/` folder is applied to an instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverrideOptions {
+    pub order: OverrideOrder,
+    pub policy: OverridePolicy,
+    pub side: PackSide,
+}
+
+impl OverrideOptions {
+    /// Fresh installs: overrides first, pack files win
+    pub fn fresh_install() -> Self {
+        Self { order: OverrideOrder::BeforeMods, policy: OverridePolicy::Overwrite, side: PackSide::Client }
+    }
+
+    /// Updates in place: overrides first, the user's existing files win
+    pub fn update() -> Self {
+        Self { order: OverrideOrder::BeforeMods, policy: OverridePolicy::SkipExisting, side: PackSide::Client }
+    }
+
+    pub fn with_order(mut self, order: OverrideOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: OverridePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Install for a dedicated server: `server-overrides/` and server-side files only
+    pub fn with_side(mut self, side: PackSide) -> Self {
+        self.side = side;
+        self
+    }
+}
+
+impl Default for OverrideOptions {
+    fn default() -> Self {
+        Self::fresh_install()
+    }
+}
+
 /// Mrpack (Modrinth modpack) format
 #[derive(Deserialize, Serialize, Debug)]
 pub struct MrpackIndex {
@@ -54,6 +482,16 @@ pub struct MrpackIndex {
     pub dependencies: std::collections::HashMap<String, String>,
 }
 
+impl MrpackIndex {
+    /// Drop repeated `path` entries, keeping the first; returns how many were removed
+    pub fn dedup_files(&mut self) -> usize {
+        let mut seen_paths = std::collections::HashSet::new();
+        let original_count = self.files.len();
+        self.files.retain(|file| seen_paths.insert(file.path.clone()));
+        original_count - self.files.len()
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct MrpackFile {
     pub path: String,
@@ -70,119 +508,981 @@ pub struct MrpackEnv {
     pub server: String,
 }
 
-/// Launcher detection and management
-pub struct LauncherManager {
-    common_launcher_paths: Vec<PathBuf>,
+/// CurseForge modpack manifest (manifest.json inside the .zip)
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CurseForgeManifest {
+    pub minecraft: CurseForgeMinecraft,
+    #[serde(rename = "manifestType")]
+    pub manifest_type: String,
+    #[serde(rename = "manifestVersion")]
+    pub manifest_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub author: String,
+    pub files: Vec<CurseForgeManifestFile>,
+    #[serde(default = "default_curseforge_overrides")]
+    pub overrides: String,
 }
 
-impl LauncherManager {
-    pub fn new() -> Self {
-        let mut common_paths = Vec::new();
+fn default_curseforge_overrides() -> String {
+    "overrides".to_string()
+}
 
-        // Windows paths
-        if cfg!(target_os = "windows") {
-            if let Some(appdata) = dirs::data_dir() {
-                common_paths.push(appdata.join(".minecraft"));
-                common_paths.push(appdata.join("PrismLauncher"));
-                common_paths.push(appdata.join("PrismLauncher-Cracked"));
-                common_paths.push(appdata.join("AstralRinthApp"));
-                common_paths.push(appdata.join("ModrinthApp"));
-            }
-            if let Some(roaming) = dirs::config_dir() {
-                common_paths.push(roaming.join(".xmcl"));
-                common_paths.push(roaming.join("ATLauncher"));
-            }
-            if let Some(home) = dirs::home_dir() {
-                common_paths.push(home.join(".xmcl"));
-            }
-        }
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CurseForgeMinecraft {
+    pub version: String,
+    #[serde(rename = "modLoaders", default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+}
 
-        // macOS paths
-        if cfg!(target_os = "macos") {
-            if let Some(home) = dirs::home_dir() {
-                common_paths.push(home.join("Library/Application Support/minecraft"));
-                common_paths.push(home.join("Library/Application Support/PrismLauncher"));
-                common_paths.push(home.join("Library/Application Support/AstralRinthApp"));
-                common_paths.push(home.join("Library/Application Support/ModrinthApp"));
-                common_paths.push(home.join(".xmcl"));
-            }
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CurseForgeModLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CurseForgeManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u64,
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    #[serde(default = "default_true")]
+    pub required: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl CurseForgeManifest {
+    /// Get the mod loader name and version from the primary loader entry (e.g. "forge-47.2.0")
+    pub fn mod_loader(&self) -> (String, Option<String>) {
+        let loader = self.minecraft.mod_loaders.iter()
+            .find(|l| l.primary)
+            .or_else(|| self.minecraft.mod_loaders.first());
+
+        match loader.and_then(|l| l.id.split_once('-')) {
+            Some((name, version)) => (name.to_lowercase(), Some(version.to_string())),
+            None => ("vanilla".to_string(), None),
         }
+    }
+}
 
-        // Linux paths
-        if cfg!(target_os = "linux") {
-            if let Some(home) = dirs::home_dir() {
-                common_paths.push(home.join(".minecraft"));
-                common_paths.push(home.join(".local/share/PrismLauncher"));
-                common_paths.push(home.join(".local/share/AstralRinthApp"));
-                common_paths.push(home.join(".local/share/ModrinthApp"));
-                common_paths.push(home.join(".xmcl"));
-            }
+/// Response wrapper for `GET /v1/mods/{modId}/files/{fileId}`
+#[derive(Deserialize, Debug)]
+pub struct CurseForgeFileResponse {
+    pub data: CurseForgeFile,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CurseForgeFile {
+    #[serde(rename = "modId")]
+    pub mod_id: u64,
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    #[serde(rename = "downloadUrl")]
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeFileHash>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CurseForgeFileHash {
+    pub value: String,
+    /// 1 = SHA1, 2 = MD5
+    pub algo: u32,
+}
+
+impl CurseForgeFile {
+    /// Get the SHA1 hash of the file, if CurseForge reported one
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes.iter()
+            .find(|h| h.algo == 1)
+            .map(|h| h.value.as_str())
+    }
+}
+
+/// Loader dependency keys an mrpack may declare, with the loader they install
+const MRPACK_LOADERS: [(&str, &str); 4] = [
+    ("fabric-loader", "fabric"),
+    ("quilt-loader", "quilt"),
+    ("forge", "forge"),
+    ("neoforge", "neoforge"),
+];
+
+/// The loader an mrpack's `dependencies` ask for: at most one known loader besides `minecraft`
+fn mrpack_loader<'a>(dependencies: impl IntoIterator<Item = &'a String>) -> std::result::Result<&'static str, String> {
+    let mut loaders = Vec::new();
+    for key in dependencies {
+        if key == "minecraft" {
+            continue;
         }
+        match MRPACK_LOADERS.iter().find(|(dependency, _)| dependency == key) {
+            Some((_, loader)) => loaders.push((key.as_str(), *loader)),
+            None => return Err(format!("unsupported dependency \"{}\"", key)),
+        }
+    }
 
-        Self {
-            common_launcher_paths: common_paths,
+    match loaders.as_slice() {
+        [] => Ok("vanilla"),
+        [(_, loader)] => Ok(loader),
+        _ => {
+            let mut keys: Vec<&str> = loaders.iter().map(|(key, _)| *key).collect();
+            keys.sort_unstable();
+            Err(format!("declares more than one loader ({})", keys.join(", ")))
         }
     }
+}
 
-    /// Detect all installed launchers
-    pub async fn detect_launchers(&self) -> Vec<(LauncherType, PathBuf)> {
-        let mut launchers = Vec::new();
+/// Refuse packs this installer can't reproduce, returning their Minecraft version and loader
+fn check_mrpack_index(index: &MrpackIndex) -> Result<(String, &'static str)> {
+    if index.format_version > 1 {
+        return Err(MinecraftInstallerError::InstallationFailed(format!(
+            "{} uses mrpack formatVersion {}; only version 1 is supported",
+            index.name, index.format_version
+        )));
+    }
+    let mod_loader = mrpack_loader(index.dependencies.keys()).map_err(|e| {
+        MinecraftInstallerError::InstallationFailed(format!("{} {}", index.name, e))
+    })?;
+    let minecraft_version = index.dependencies.get("minecraft")
+        .ok_or_else(|| MinecraftInstallerError::InstallationFailed(
+            "No Minecraft version specified in mrpack".to_string()
+        ))?;
+    Ok((minecraft_version.clone(), mod_loader))
+}
 
-        for path in &self.common_launcher_paths {
-            if path.exists() {
-                match self.detect_launcher_type(path).await {
-                    Ok(launcher_type) if launcher_type != LauncherType::Unknown => {
-                        launchers.push((launcher_type, path.clone()));
-                    }
-                    _ => {}
-                }
-            }
-        }
+/// Install manifest for an mrpack's Minecraft version and loader, with no mods recorded yet
+pub(crate) fn mrpack_install_manifest(index: &MrpackIndex) -> Result<InstallManifest> {
+    let (minecraft_version, mod_loader) = check_mrpack_index(index)?;
+    let loader_version = MRPACK_LOADERS.iter()
+        .find(|(_, loader)| *loader == mod_loader)
+        .and_then(|(dependency, _)| index.dependencies.get(*dependency));
+    Ok(InstallManifest::new(&minecraft_version, mod_loader, loader_version.map(String::as_str)))
+}
 
-        launchers
+/// File names of the jars an mrpack ships under `overrides/mods/` or `side`'s own overrides folder
+fn override_mod_filenames<'a>(archive_names: impl Iterator<Item = &'a str>, side: PackSide) -> std::collections::HashSet<String> {
+    let prefixes = ["overrides/mods/".to_string(), format!("{}/mods/", side.overrides_folder())];
+    archive_names
+        .filter(|name| name.ends_with(".jar") && prefixes.iter().any(|prefix| name.starts_with(prefix.as_str())))
+        .filter_map(|name| name.rsplit('/').next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Read an mrpack's `modrinth.index.json`, matching the entry name case-insensitively and ignoring a leading `./`
+pub fn read_mrpack_index<R: std::io::Read + std::io::Seek>(archive: &mut zip::ZipArchive<R>) -> Result<String> {
+    let name = archive.file_names()
+        .find(|name| name.trim_start_matches("./").eq_ignore_ascii_case("modrinth.index.json"))
+        .map(str::to_string)
+        .ok_or_else(|| MinecraftInstallerError::InstallationFailed("modrinth.index.json not found in mrpack".to_string()))?;
+
+    let mut index_file = archive.by_name(&name)?;
+    let mut content = String::new();
+    std::io::Read::read_to_string(&mut index_file, &mut content)?;
+    Ok(content)
+}
+
+/// Check a parsed `modrinth.index.json` against the mrpack format and list every problem found
+fn validate_mrpack_index(index: &serde_json::Value) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    match index.get("formatVersion").and_then(|v| v.as_u64()) {
+        Some(1) => {}
+        Some(v) => issues.push(format!("unsupported formatVersion {} (expected 1)", v)),
+        None => issues.push("missing or invalid formatVersion".to_string()),
     }
 
-    /// Detect launcher type from directory structure
-    pub async fn detect_launcher_type(&self, path: &Path) -> Result<LauncherType> {
-        if !path.exists() {
-            return Ok(LauncherType::Unknown);
+    match index.get("game").and_then(|v| v.as_str()) {
+        Some("minecraft") => {}
+        Some(game) => issues.push(format!("unsupported game \"{}\" (expected \"minecraft\")", game)),
+        None => issues.push("missing game".to_string()),
+    }
+
+    for field in ["versionId", "name"] {
+        match index.get(field).and_then(|v| v.as_str()) {
+            Some(value) if !value.trim().is_empty() => {}
+            _ => issues.push(format!("missing or empty {}", field)),
         }
+    }
 
-        // Check for Modrinth App first (more specific check)
-        if path.join("app-window-state.json").exists() && path.join("profiles").exists() && path.file_name().unwrap() == "ModrinthApp" {
-            return Ok(LauncherType::ModrinthApp);
+    match index.get("dependencies").and_then(|v| v.as_object()) {
+        Some(deps) => {
+            if !deps.contains_key("minecraft") {
+                issues.push("dependencies does not specify a minecraft version".to_string());
+            }
+            if let Err(e) = mrpack_loader(deps.keys()) {
+                issues.push(format!("dependencies {}", e));
+            }
         }
+        None => issues.push("missing dependencies".to_string()),
+    }
 
-        // Check for AstralRinth App
-        if path.join("app-window-state.json").exists() && path.join("profiles").exists() {
-            return Ok(LauncherType::AstralRinth);
+    let files = match index.get("files").and_then(|v| v.as_array()) {
+        Some(files) => files,
+        None => {
+            issues.push("missing files array".to_string());
+            return issues;
         }
+    };
 
-        // Check for PrismLauncher
-        if path.join("prismlauncher.cfg").exists() && path.join("instances").exists() {
-            // Check if it's the cracked version
-            if let Ok(accounts_content) = fs::read_to_string(path.join("accounts.json")).await {
-                if accounts_content.contains("Offline") {
-                    return Ok(LauncherType::PrismCracked);
+    let mut seen_paths = std::collections::HashSet::new();
+    for (i, file) in files.iter().enumerate() {
+        let path = file.get("path").and_then(|v| v.as_str()).unwrap_or("");
+        let label = if path.is_empty() { format!("files[{}]", i) } else { path.to_string() };
+
+        if path.is_empty() {
+            issues.push(format!("{}: missing or empty path", label));
+        } else {
+            if path.starts_with('/') || path.starts_with('\\') || path.contains(':')
+                || path.split(['/', '\\']).any(|part| part == "..")
+            {
+                issues.push(format!("{}: path escapes the instance directory", label));
+            }
+            if !seen_paths.insert(path) {
+                issues.push(format!("{}: duplicate path", label));
+            }
+        }
+
+        match file.get("hashes").and_then(|v| v.as_object()) {
+            Some(hashes) => {
+                for algo in ["sha1", "sha512"] {
+                    match hashes.get(algo).and_then(|v| v.as_str()) {
+                        Some(hash) if !hash.is_empty() => {}
+                        _ => issues.push(format!("{}: missing {} hash", label, algo)),
+                    }
                 }
             }
-            return Ok(LauncherType::Prism);
+            None => issues.push(format!("{}: missing hashes", label)),
         }
 
-        // Check for XMCL
-        if path.join("instances").exists() && path.join("launcher_profiles.json").exists() {
-            return Ok(LauncherType::XMCL);
+        if let Some(env) = file.get("env").filter(|v| !v.is_null()) {
+            for side in ["client", "server"] {
+                match env.get(side).and_then(|v| v.as_str()) {
+                    Some("required") | Some("optional") | Some("unsupported") => {}
+                    Some(value) => issues.push(format!("{}: invalid env.{} value \"{}\"", label, side, value)),
+                    None => issues.push(format!("{}: missing env.{}", label, side)),
+                }
+            }
         }
 
-        // Check for Official Minecraft Launcher
-        if path.join("launcher_profiles.json").exists() &&
-           (path.join("versions").exists() || path.file_name().and_then(|n| n.to_str()) == Some(".minecraft")) {
-            return Ok(LauncherType::Official);
+        match file.get("downloads").and_then(|v| v.as_array()) {
+            Some(downloads) if downloads.is_empty() => {
+                issues.push(format!("{}: no download URLs", label));
+            }
+            Some(downloads) => {
+                if downloads.iter().any(|url| url.as_str().is_none_or(|url| url.trim().is_empty())) {
+                    issues.push(format!("{}: empty download URL", label));
+                }
+            }
+            None => issues.push(format!("{}: missing downloads", label)),
         }
 
-        // Check for MultiMC
-        if path.join("multimc.cfg").exists() && path.join("instances").exists() {
-            return Ok(LauncherType::MultiMC);
+        if file.get("fileSize").and_then(|v| v.as_u64()).is_none() {
+            issues.push(format!("{}: missing or invalid fileSize", label));
+        }
+    }
+
+    issues
+}
+
+/// Default launcher instance name for a NAHA pack, e.g. "NAHA-NeoForge-0.0.18"
+///
+/// Including the pack version keeps repeated installs of different versions from landing in the same instance.
+pub fn naha_instance_name(mod_loader: &str, pack_version: Option<&str>) -> String {
+    let base = match mod_loader.to_lowercase().as_str() {
+        "neoforge" => "NAHA-NeoForge".to_string(),
+        "fabric" => "NAHA-Fabric".to_string(),
+        "forge" => "NAHA-Forge".to_string(),
+        "quilt" => "NAHA-Quilt".to_string(),
+        _ => format!("NAHA-{}", mod_loader),
+    };
+
+    match pack_version.map(str::trim) {
+        Some(version) if !version.is_empty() && version != "latest" => format!("{}-{}", base, version),
+        _ => base,
+    }
+}
+
+/// Aikar's G1GC flags, which keep memory-heavy modpacks from GC-thrashing.
+///
+/// Heap size is left to the launcher's own memory setting.
+pub const AIKAR_JVM_ARGS: &[&str] = &[
+    "-XX:+UseG1GC",
+    "-XX:+ParallelRefProcEnabled",
+    "-XX:MaxGCPauseMillis=200",
+    "-XX:+UnlockExperimentalVMOptions",
+    "-XX:+DisableExplicitGC",
+    "-XX:G1NewSizePercent=30",
+    "-XX:G1MaxNewSizePercent=40",
+    "-XX:G1HeapRegionSize=8M",
+    "-XX:G1ReservePercent=20",
+    "-XX:G1HeapWastePercent=5",
+    "-XX:G1MixedGCCountTarget=4",
+    "-XX:InitiatingHeapOccupancyPercent=15",
+    "-XX:G1MixedGCLiveThresholdPercent=90",
+    "-XX:G1RSetUpdatingPauseIntervalMillis=100",
+    "-XX:SurvivorRatio=32",
+    "-XX:+PerfDisableSharedMem",
+    "-XX:MaxTenuringThreshold=1",
+];
+
+/// Resolve a JVM argument spec: a preset name (`aikar`) or a whitespace-separated argument list
+pub fn parse_jvm_args(spec: &str) -> Vec<String> {
+    match spec.trim().to_lowercase().as_str() {
+        "aikar" => AIKAR_JVM_ARGS.iter().map(|arg| arg.to_string()).collect(),
+        _ => spec.split_whitespace().map(str::to_string).collect(),
+    }
+}
+
+/// Replace `key=value` entries in an INI section, adding the section if it is missing
+///
+/// Keys above the first header count as `[General]`, as in the headerless `instance.cfg` MultiMC writes.
+fn set_ini_values(content: &str, section: &str, values: &[(&str, &str)]) -> String {
+    let header = format!("[{}]", section);
+    let mut lines = Vec::new();
+    let mut in_section = section == "General";
+    let mut found_section = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            in_section = trimmed == header;
+            lines.push(line.to_string());
+            if in_section {
+                found_section = true;
+                lines.extend(values.iter().map(|(key, value)| format!("{}={}", key, value)));
+            }
+            continue;
+        }
+
+        let key = trimmed.split('=').next().unwrap_or_default().trim();
+        if in_section && values.iter().any(|(k, _)| *k == key) {
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+
+    if !found_section {
+        lines.insert(0, header);
+        for (i, (key, value)) in values.iter().enumerate() {
+            lines.insert(i + 1, format!("{}={}", key, value));
+        }
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+/// Turn an instance name into a directory name that is valid on Windows, macOS and Linux
+///
+/// Path separators and characters Windows reserves become `_`, control characters and emoji are
+/// dropped, trailing dots and spaces are trimmed, and device names like `CON` or `COM1` get a `_` suffix.
+pub fn sanitize_instance_dir_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .filter_map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => Some('_'),
+            c if c.is_control() => None,
+            c if !c.is_ascii() && !c.is_alphanumeric() => None,
+            c => Some(c),
+        })
+        .collect();
+    sanitized = sanitized.trim_start().trim_end_matches(['.', ' ']).to_string();
+
+    if sanitized.is_empty() || sanitized.chars().all(|c| c == '.' || c == '_') {
+        return "instance".to_string();
+    }
+
+    const RESERVED: [&str; 22] = [
+        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+        "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    ];
+    let stem = sanitized.split('.').next().unwrap_or_default();
+    if RESERVED.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        sanitized.insert(stem.len(), '_');
+    }
+    sanitized
+}
+
+/// A file name reported by a remote API, refused when it could write outside the folder it is joined onto
+fn checked_file_name(name: &str) -> Result<&str> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', ':']) {
+        return Err(MinecraftInstallerError::Validation(format!("Refusing unsafe file name \"{}\"", name)));
+    }
+    Ok(name)
+}
+
+/// Java an instance is set to launch with instead of the launcher default, as written by `apply_instance_options`
+pub fn configured_java_path(instance_path: &Path) -> Option<PathBuf> {
+    let path = if let Ok(cfg) = std::fs::read_to_string(instance_path.join("instance.cfg")) {
+        cfg.lines().find_map(|line| line.strip_prefix("JavaPath=")).map(|path| path.trim().to_string())
+    } else if let Ok(profile) = std::fs::read_to_string(instance_path.join("profile.json")) {
+        let profile: serde_json::Value = serde_json::from_str(&profile).ok()?;
+        profile["java_path"].as_str().map(str::to_string)
+    } else {
+        let instance: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(instance_path.join("instance.json")).ok()?).ok()?;
+        // ATLauncher keeps it under `launcher`, XMCL at the top level
+        instance["launcher"]["javaPath"].as_str().or(instance["java"].as_str()).map(str::to_string)
+    }?;
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// The display name stored in an instance's instance.cfg, instance.json or profile.json
+fn read_instance_name(instance_path: &Path) -> Option<String> {
+    if let Ok(cfg) = std::fs::read_to_string(instance_path.join("instance.cfg")) {
+        return cfg.lines().find_map(|line| line.strip_prefix("name=")).map(|name| name.trim().to_string());
+    }
+    ["instance.json", "profile.json"].iter().find_map(|file| {
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(instance_path.join(file)).ok()?).ok()?;
+        json["name"].as_str().map(str::to_string)
+    })
+}
+
+/// Profiles file of the Official launcher at `launcher_path`; the Microsoft Store build only writes
+/// `launcher_profiles_microsoft_store.json`
+pub fn official_profiles_path(launcher_path: &Path) -> PathBuf {
+    let store = launcher_path.join("launcher_profiles_microsoft_store.json");
+    let classic = launcher_path.join("launcher_profiles.json");
+    if store.exists() && !classic.exists() { store } else { classic }
+}
+
+/// Whether the Official profiles file holds any profile this installer created
+fn has_installer_profiles(launcher_path: &Path) -> bool {
+    std::fs::read_to_string(official_profiles_path(launcher_path)).ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|profiles| profiles["profiles"].as_object().cloned())
+        .is_some_and(|profiles| profiles.keys().any(|id| id.starts_with("minecraft-installer-")))
+}
+
+/// Directory under `parent` for an instance named `instance_name`, stored as `dir_name` once sanitized
+///
+/// An existing directory is reused when it is empty or already holds this instance, so recreating an
+/// instance updates it in place; any other instance there gets `-2`, `-3`, … appended instead.
+fn unique_instance_dir(parent: &Path, dir_name: &str, instance_name: &str) -> PathBuf {
+    let base = sanitize_instance_dir_name(dir_name);
+    let is_free = |dir: &Path| {
+        !dir.exists()
+            || read_instance_name(dir).as_deref() == Some(instance_name)
+            || std::fs::read_dir(dir).map(|mut entries| entries.next().is_none()).unwrap_or(false)
+    };
+
+    let first = parent.join(&base);
+    if is_free(&first) {
+        return first;
+    }
+    (2..)
+        .map(|n| parent.join(format!("{}-{}", base, n)))
+        .find(|dir| is_free(dir))
+        .expect("an unused suffix exists")
+}
+
+/// Directory an instance's game files (mods, config, saves, automodpack, servers.dat) live in
+///
+/// - AstralRinth / Modrinth App (`profile.json`) and XMCL / ATLauncher (`instance.json`): the instance itself
+/// - Prism / MultiMC (`mmc-pack.json` or `instance.cfg`): `.minecraft`, or `minecraft` for older MultiMC instances
+/// - anything else (Official game dirs, custom paths): the instance itself, unless it only has a
+///   `.minecraft` or `minecraft` subdirectory and no `mods` of its own
+pub fn minecraft_base_dir(instance_path: &Path) -> PathBuf {
+    let dot_minecraft = instance_path.join(".minecraft");
+    let legacy_minecraft = instance_path.join("minecraft");
+
+    if instance_path.join("profile.json").exists() || instance_path.join("instance.json").exists() {
+        instance_path.to_path_buf()
+    } else if instance_path.join("mmc-pack.json").exists() || instance_path.join("instance.cfg").exists() {
+        if !dot_minecraft.exists() && legacy_minecraft.exists() {
+            legacy_minecraft
+        } else {
+            dot_minecraft
+        }
+    } else if instance_path.join("mods").exists() || instance_path.join("launcher_profiles.json").exists() || instance_path.join("launcher_profiles_microsoft_store.json").exists() {
+        instance_path.to_path_buf()
+    } else if dot_minecraft.exists() {
+        dot_minecraft
+    } else if legacy_minecraft.exists() {
+        legacy_minecraft
+    } else {
+        instance_path.to_path_buf()
+    }
+}
+
+/// The icon a user picked for an existing instance: `icon_path` in profile.json,
+/// `iconKey` in instance.cfg, or `icon` in an XMCL instance.json
+pub fn read_instance_icon(instance_path: &Path) -> Option<String> {
+    let read_json = |name: &str| -> Option<serde_json::Value> {
+        serde_json::from_str(&std::fs::read_to_string(instance_path.join(name)).ok()?).ok()
+    };
+
+    if let Some(profile) = read_json("profile.json") {
+        return profile["icon_path"].as_str().map(str::to_string);
+    }
+    if let Ok(cfg) = std::fs::read_to_string(instance_path.join("instance.cfg")) {
+        return cfg.lines()
+            .find_map(|line| line.strip_prefix("iconKey="))
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty());
+    }
+    read_json("instance.json")?["icon"].as_str()
+        .filter(|icon| !icon.is_empty())
+        .map(str::to_string)
+}
+
+/// `icon_path` of an app.db profile row, if the row and column exist
+fn read_db_icon(conn: &Connection, profile_name: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT icon_path FROM profiles WHERE path = ?",
+        rusqlite::params![profile_name],
+        |row| row.get::<_, Option<String>>(0),
+    ).ok().flatten()
+}
+
+/// Put a preserved icon back on an app.db profile row; a schema without `icon_path` is ignored
+pub(crate) fn restore_db_icon(conn: &Connection, profile_name: &str, icon_path: Option<&str>) {
+    if let Some(icon_path) = icon_path {
+        if let Err(e) = conn.execute(
+            "UPDATE profiles SET icon_path = ? WHERE path = ?",
+            rusqlite::params![icon_path, profile_name],
+        ) {
+            debug!("Could not restore icon for {}: {}", profile_name, e);
+        }
+    }
+}
+
+/// Column names of the app.db `profiles` table, which change between app versions
+fn profile_columns(conn: &Connection) -> SqliteResult<Vec<String>> {
+    let mut statement = conn.prepare("PRAGMA table_info(profiles)")?;
+    let columns = statement
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(columns)
+}
+
+/// How a Modrinth App install expects profile metadata to be stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModrinthProfileFormat {
+    /// Older apps read everything from the app.db `profiles` row
+    Legacy,
+    /// Apps whose schema has `protocol_version` also read a `metadata` block from each profile.json
+    V2,
+}
+
+impl ModrinthProfileFormat {
+    /// Probe the `profiles` schema of an open app.db
+    pub fn detect(conn: &Connection) -> Self {
+        match profile_columns(conn) {
+            Ok(columns) if columns.iter().any(|c| c == "protocol_version") => Self::V2,
+            _ => Self::Legacy,
+        }
+    }
+}
+
+/// `INSERT OR REPLACE` an app.db profile row, skipping values for columns the schema doesn't have
+fn upsert_db_profile(conn: &Connection, row: &[(&str, &dyn rusqlite::ToSql)]) -> SqliteResult<()> {
+    let columns = profile_columns(conn)?;
+    let row: Vec<_> = row.iter().filter(|(column, _)| columns.iter().any(|c| c == column)).collect();
+    if !row.iter().any(|(column, _)| *column == "path") {
+        return Err(rusqlite::Error::InvalidColumnName("profiles.path".to_string()));
+    }
+
+    let names: Vec<String> = row.iter().map(|(column, _)| format!("\"{}\"", column)).collect();
+    let placeholders = vec!["?"; row.len()].join(", ");
+    let values: Vec<&dyn rusqlite::ToSql> = row.iter().map(|(_, value)| *value).collect();
+    conn.execute(
+        &format!("INSERT OR REPLACE INTO profiles ({}) VALUES ({})", names.join(", "), placeholders),
+        values.as_slice(),
+    )?;
+    Ok(())
+}
+
+/// Copy an app.db profile row to a new `path` and `name`, keeping its launcher overrides
+///
+/// Returns false when the source has no row to copy.
+fn clone_db_profile(conn: &Connection, source_path: &str, new_path: &str, new_name: &str) -> SqliteResult<bool> {
+    let columns = profile_columns(conn)?;
+
+    let values: Vec<String> = columns.iter().map(|column| match column.as_str() {
+        "path" => "?1".to_string(),
+        "name" => "?2".to_string(),
+        "created" | "modified" => "?3".to_string(),
+        _ => format!("\"{}\"", column),
+    }).collect();
+    let columns: Vec<String> = columns.iter().map(|column| format!("\"{}\"", column)).collect();
+
+    let inserted = conn.execute(
+        &format!(
+            "INSERT INTO profiles ({}) SELECT {} FROM profiles WHERE path = ?4",
+            columns.join(", "),
+            values.join(", ")
+        ),
+        rusqlite::params![new_path, new_name, chrono::Utc::now().timestamp_millis(), source_path],
+    )?;
+    Ok(inserted > 0)
+}
+
+/// Rough size of the libraries and assets a standalone (Other) install has to hold itself
+pub const STANDALONE_GAME_FILES_ESTIMATE: u64 = 800 * 1024 * 1024;
+
+/// Free bytes on the volume holding `path`, or its nearest existing ancestor
+pub fn available_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let target = std::fs::canonicalize(existing).ok()?;
+
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks.list().iter()
+        .filter(|disk| target.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Environment variable pointing at a portable or relocated PrismLauncher data dir
+pub const PRISM_DATA_DIR_ENV: &str = "PRISM_DATA_DIR";
+
+/// Environment variable holding the CurseForge API key
+pub const CURSEFORGE_API_KEY_ENV: &str = "CURSEFORGE_API_KEY";
+
+/// CurseForge API that CurseForge modpack files are resolved against
+pub const CURSEFORGE_API_URL: &str = "https://api.curseforge.com/v1";
+
+/// Files copied at once when copying instance folders
+const COPY_CONCURRENCY: usize = 16;
+
+/// Parallel mrpack file downloads used unless `set_download_concurrency` says otherwise
+pub const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 8;
+
+/// Mods that only run on the client, left out of server installs even when a pack's `env` says otherwise
+///
+/// Matched against the mod's normalized file name, see [`normalize_mod_name`].
+pub const CLIENT_ONLY_MODS: &[&str] = &[
+    "sodium", "sodium-extra", "reeses-sodium-options", "iris", "oculus", "embeddium", "rubidium",
+    "indium", "continuity", "entityculling", "modmenu", "zoomify", "lambdynamiclights", "betterf3",
+    "controlling", "mousetweaks",
+];
+
+/// NAHA API serving `<base>/<modpack type>/` server info
+pub const NAHA_API_URL: &str = "https://perlytiara.github.io/NAHA-MC.IO/api";
+
+/// GitHub releases of the NAHA modpacks
+pub const MODPACK_RELEASES_API_URL: &str = "https://api.github.com/repos/perlytiara/NAHA-Minecraft-Modpacks/releases";
+
+/// Extra attempts for modpack info requests that hit a connection error or a 5xx
+const MODPACK_INFO_RETRIES: u32 = 2;
+
+/// Package family prefix of the Microsoft Store (UWP) Minecraft Launcher
+const UWP_MINECRAFT_PACKAGE_PREFIX: &str = "Microsoft.4297127D64EC6_";
+
+/// Game directories of Microsoft Store launcher installs under `%LocalAppData%`
+/// (`Packages\Microsoft.4297127D64EC6_*\LocalCache\Roaming\.minecraft`)
+pub fn uwp_minecraft_dirs(local_app_data: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = std::fs::read_dir(local_app_data.join("Packages"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(UWP_MINECRAFT_PACKAGE_PREFIX))
+        .map(|entry| entry.path().join("LocalCache").join("Roaming").join(".minecraft"))
+        .filter(|path| path.is_dir())
+        .collect();
+    found.sort();
+    found
+}
+
+/// Where launchers keep their data on macOS, relative to the user's home directory
+pub fn macos_launcher_dirs(home: &Path) -> Vec<PathBuf> {
+    let support = home.join("Library").join("Application Support");
+    let mut dirs: Vec<PathBuf> = [
+        "minecraft",
+        "PrismLauncher",
+        "PrismLauncher-Cracked",
+        "AstralRinthApp",
+        "ModrinthApp",
+        "XMCL",
+        "MultiMC",
+        "ATLauncher",
+        "gdlauncher_next",
+    ]
+    .iter()
+    .map(|name| support.join(name))
+    .collect();
+    dirs.push(home.join(".xmcl"));
+    dirs
+}
+
+/// Launcher detection and management
+pub struct LauncherManager {
+    common_launcher_paths: Vec<PathBuf>,
+    jvm_args: Option<Vec<String>>,
+    /// Leave AstralRinth / Modrinth App databases alone when creating instances
+    skip_launcher_db: bool,
+    /// Leave the downloaded mrpack and its unpacked temp instance in place after an API install
+    keep_temp: bool,
+    /// Normalized mod names dropped from server installs, see [`CLIENT_ONLY_MODS`]
+    client_only_mods: Vec<String>,
+    fabric: FabricResolver,
+    quilt: FabricResolver,
+    forge: ForgeResolver,
+    progress: ProgressReporter,
+    http_config: HttpConfig,
+    http: reqwest::Client,
+    cancel: CancellationToken,
+    download_concurrency: usize,
+    naha_api_url: String,
+    modpack_releases_api_url: String,
+    curseforge_api_url: String,
+    /// Official launchers among the detected ones, found once for `find_official_profile`
+    official_launchers: tokio::sync::OnceCell<Vec<PathBuf>>,
+}
+
+impl LauncherManager {
+    pub fn new() -> Self {
+        let mut common_paths = Vec::new();
+
+        // Windows paths
+        if cfg!(target_os = "windows") {
+            if let Some(appdata) = dirs::data_dir() {
+                common_paths.push(appdata.join(".minecraft"));
+                common_paths.push(appdata.join("PrismLauncher"));
+                common_paths.push(appdata.join("PrismLauncher-Cracked"));
+                common_paths.push(appdata.join("AstralRinthApp"));
+                common_paths.push(appdata.join("ModrinthApp"));
+            }
+            if let Some(roaming) = dirs::config_dir() {
+                common_paths.push(roaming.join(".xmcl"));
+                common_paths.push(roaming.join("ATLauncher"));
+                common_paths.push(roaming.join("gdlauncher_next"));
+            }
+            if let Some(home) = dirs::home_dir() {
+                common_paths.push(home.join(".xmcl"));
+            }
+            if let Some(local) = dirs::data_local_dir() {
+                common_paths.extend(uwp_minecraft_dirs(&local));
+            }
+        }
+
+        // macOS paths
+        if cfg!(target_os = "macos") {
+            if let Some(home) = dirs::home_dir() {
+                common_paths.extend(macos_launcher_dirs(&home));
+            }
+        }
+
+        // Linux paths
+        if cfg!(target_os = "linux") {
+            if let Some(home) = dirs::home_dir() {
+                common_paths.push(home.join(".minecraft"));
+                common_paths.push(home.join(".local/share/PrismLauncher"));
+                common_paths.push(home.join(".local/share/AstralRinthApp"));
+                common_paths.push(home.join(".local/share/ModrinthApp"));
+                common_paths.push(home.join(".xmcl"));
+                common_paths.push(home.join(".config/gdlauncher_next"));
+
+                // Flatpak keeps each app's data under ~/.var/app/<app-id>
+                let flatpak = home.join(".var/app");
+                common_paths.push(flatpak.join("org.prismlauncher.PrismLauncher/data/PrismLauncher"));
+                common_paths.push(flatpak.join("com.modrinth.ModrinthApp/data/ModrinthApp"));
+                common_paths.push(flatpak.join("com.mojang.Minecraft/.minecraft"));
+
+                // Snap keeps it under ~/snap/<name>/current
+                let snap = home.join("snap");
+                common_paths.push(snap.join("prismlauncher/current/.local/share/PrismLauncher"));
+                common_paths.push(snap.join("mc-installer/current/.minecraft"));
+            }
+        }
+
+        // Portable Prism keeps its data next to the executable, which only the user knows
+        if let Some(prism_dir) = std::env::var_os(PRISM_DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            common_paths.insert(0, PathBuf::from(prism_dir));
+        }
+
+        Self {
+            common_launcher_paths: common_paths,
+            jvm_args: None,
+            skip_launcher_db: false,
+            keep_temp: false,
+            client_only_mods: CLIENT_ONLY_MODS.iter().map(|name| name.to_string()).collect(),
+            fabric: FabricResolver::new(),
+            quilt: FabricResolver::with_base_url(QUILT_META_URL),
+            forge: ForgeResolver::new(),
+            progress: ProgressReporter::default(),
+            http_config: HttpConfig::default(),
+            http: HttpConfig::default().client(),
+            cancel: CancellationToken::new(),
+            download_concurrency: DEFAULT_DOWNLOAD_CONCURRENCY,
+            naha_api_url: NAHA_API_URL.to_string(),
+            modpack_releases_api_url: MODPACK_RELEASES_API_URL.to_string(),
+            curseforge_api_url: CURSEFORGE_API_URL.to_string(),
+            official_launchers: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Also look for a launcher in `path` (e.g. a portable install on a USB drive)
+    pub fn add_search_path(&mut self, path: PathBuf) {
+        if !self.common_launcher_paths.contains(&path) {
+            self.common_launcher_paths.push(path);
+            self.official_launchers = tokio::sync::OnceCell::new();
+        }
+    }
+
+    /// Replace the default connect (10s) and total (300s) timeouts for modpack downloads
+    pub fn set_http_timeouts(&mut self, timeouts: HttpTimeouts) {
+        self.set_http_config(self.http_config.clone().with_timeouts(timeouts));
+    }
+
+    /// Timeouts and proxy for modpack, Modrinth and loader requests
+    pub fn set_http_config(&mut self, http: HttpConfig) {
+        self.http = http.client();
+        self.fabric = self.fabric.clone().with_http_config(&http);
+        self.quilt = self.quilt.clone().with_http_config(&http);
+        self.forge = self.forge.clone().with_http_config(&http);
+        self.http_config = http;
+    }
+
+    /// Create AstralRinth / Modrinth App profiles without writing to their app.db; they then have to be imported manually
+    pub fn set_skip_launcher_db(&mut self, skip: bool) {
+        self.skip_launcher_db = skip;
+    }
+
+    /// Keep the temp mrpack and instance directory of API installs instead of deleting them; they are always kept when a step fails
+    pub fn set_keep_temp(&mut self, keep: bool) {
+        self.keep_temp = keep;
+    }
+
+    /// Mods to leave out of server installs instead of [`CLIENT_ONLY_MODS`]; an empty list trusts the pack's `env` alone
+    pub fn set_client_only_mods(&mut self, mods: Vec<String>) {
+        self.client_only_mods = mods.iter().map(|name| normalize_mod_name(name)).collect();
+    }
+
+    /// Whether `file` is a mod on the client-only list
+    fn is_client_only_mod(&self, file: &MrpackFile) -> bool {
+        file.path.starts_with("mods/")
+            && Path::new(&file.path).file_name().is_some_and(|name| {
+                self.client_only_mods.contains(&normalize_mod_name(&name.to_string_lossy()))
+            })
+    }
+
+    /// JVM arguments to apply to instances created by `download_and_install_from_api`
+    pub fn set_jvm_args(&mut self, args: Vec<String>) {
+        self.jvm_args = Some(args);
+    }
+
+    /// Resolver used to pin Fabric loader versions in created instances
+    pub fn set_fabric_resolver(&mut self, fabric: FabricResolver) {
+        self.fabric = fabric;
+    }
+
+    /// Resolver used for Quilt loader versions and profiles in created instances
+    pub fn set_quilt_resolver(&mut self, quilt: FabricResolver) {
+        self.quilt = quilt;
+    }
+
+    /// Resolver used to pick the Forge build an Official profile launches
+    pub fn set_forge_resolver(&mut self, forge: ForgeResolver) {
+        self.forge = forge;
+    }
+
+    /// Where mrpack install and override progress events are sent
+    pub fn set_progress_reporter(&mut self, progress: ProgressReporter) {
+        self.progress = progress;
+    }
+
+    /// Abort mrpack mod downloads with `MinecraftInstallerError::Cancelled` once `cancel` is tripped
+    pub fn set_cancellation_token(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
+    }
+
+    /// Read server info and modpack releases from other endpoints (e.g. a mirror)
+    pub fn set_modpack_api_urls(&mut self, naha_api_url: impl Into<String>, releases_api_url: impl Into<String>) {
+        self.naha_api_url = naha_api_url.into().trim_end_matches('/').to_string();
+        self.modpack_releases_api_url = releases_api_url.into().trim_end_matches('/').to_string();
+    }
+
+    /// Resolve CurseForge modpack files against another API (e.g. a proxy or mirror)
+    pub fn set_curseforge_api_url(&mut self, api_url: impl Into<String>) {
+        self.curseforge_api_url = api_url.into().trim_end_matches('/').to_string();
+    }
+
+    /// How many mrpack files are downloaded at once (at least one)
+    pub fn set_download_concurrency(&mut self, concurrency: usize) {
+        self.download_concurrency = concurrency.max(1);
+    }
+
+    /// Instances across every detected launcher, searching this manager's paths as well as the defaults
+    pub async fn scan_all_instances(&self) -> Result<Vec<InstanceInfo>> {
+        InstanceScanner::new(self).scan_instances().await
+    }
+
+    /// Detect all installed launchers
+    pub async fn detect_launchers(&self) -> Vec<(LauncherType, PathBuf)> {
+        let mut launchers = Vec::new();
+        let mut seen = Vec::new();
+
+        for path in &self.common_launcher_paths {
+            if path.exists() {
+                // A symlinked launcher directory and its target are the same launcher
+                let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+                if seen.contains(&canonical) {
+                    debug!("Skipping {}: same launcher as {}", path.display(), canonical.display());
+                    continue;
+                }
+
+                match self.detect_launcher_type(path).await {
+                    Ok(launcher_type) if launcher_type != LauncherType::Unknown => {
+                        seen.push(canonical);
+                        launchers.push((launcher_type, path.clone()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        launchers
+    }
+
+    /// Detect launcher type from directory structure
+    pub async fn detect_launcher_type(&self, path: &Path) -> Result<LauncherType> {
+        if !path.exists() {
+            return Ok(LauncherType::Unknown);
+        }
+
+        // Check for Modrinth App first (more specific check)
+        if path.join("app-window-state.json").exists() && path.join("profiles").exists() && path.file_name().unwrap() == "ModrinthApp" {
+            return Ok(LauncherType::ModrinthApp);
+        }
+
+        // Check for AstralRinth App
+        if path.join("app-window-state.json").exists() && path.join("profiles").exists() {
+            return Ok(LauncherType::AstralRinth);
+        }
+
+        // Check for PrismLauncher
+        if path.join("prismlauncher.cfg").exists() && PrismConfig::load(path).instance_dir.exists() {
+            // Check if it's the cracked version
+            if let Ok(accounts_content) = fs::read_to_string(path.join("accounts.json")).await {
+                if accounts_content.contains("Offline") {
+                    return Ok(LauncherType::PrismCracked);
+                }
+            }
+            return Ok(LauncherType::Prism);
+        }
+
+        // Check for XMCL; Official installs also gain an instances/ folder with their first profile from this installer
+        if path.join("instances").exists() && path.join("launcher_profiles.json").exists() {
+            return Ok(if has_installer_profiles(path) { LauncherType::Official } else { LauncherType::XMCL });
+        }
+
+        // Check for Official Minecraft Launcher (the Microsoft Store build keeps its own profiles file)
+        if (path.join("launcher_profiles.json").exists() || path.join("launcher_profiles_microsoft_store.json").exists()) &&
+           (path.join("versions").exists() || path.file_name().and_then(|n| n.to_str()) == Some(".minecraft")) {
+            return Ok(LauncherType::Official);
+        }
+
+        // Check for MultiMC
+        if path.join("multimc.cfg").exists() && PrismConfig::load_file(path, "multimc.cfg").instance_dir.exists() {
+            return Ok(LauncherType::MultiMC);
         }
 
         // Check for ATLauncher
@@ -190,6 +1490,11 @@ impl LauncherManager {
             return Ok(LauncherType::ATLauncher);
         }
 
+        // Check for GDLauncher Next (game files are shared under datastore/)
+        if path.join("instances").exists() && path.join("datastore").exists() {
+            return Ok(LauncherType::GDLauncher);
+        }
+
         Ok(LauncherType::Unknown)
     }
 
@@ -201,18 +1506,62 @@ impl LauncherManager {
         minecraft_version: &str,
         mod_loader: &str,
         mod_loader_version: Option<&str>,
+    ) -> Result<PathBuf> {
+        self.create_instance_with_options(
+            launcher_path,
+            instance_name,
+            minecraft_version,
+            mod_loader,
+            mod_loader_version,
+            &InstanceOptions::default(),
+        ).await
+    }
+
+    /// Create a new instance with custom memory, JVM arguments or Java runtime
+    pub async fn create_instance_with_options(
+        &self,
+        launcher_path: &Path,
+        instance_name: &str,
+        minecraft_version: &str,
+        mod_loader: &str,
+        mod_loader_version: Option<&str>,
+        options: &InstanceOptions,
+    ) -> Result<PathBuf> {
+        let instance_path = self.create_launcher_instance(
+            launcher_path,
+            instance_name,
+            minecraft_version,
+            mod_loader,
+            mod_loader_version,
+        ).await?;
+
+        if !options.is_default() {
+            if let Err(e) = self.apply_instance_options(&instance_path, options).await {
+                warn!("Failed to apply instance options to {}: {}", instance_path.display(), e);
+            }
+        }
+        Ok(instance_path)
+    }
+
+    async fn create_launcher_instance(
+        &self,
+        launcher_path: &Path,
+        instance_name: &str,
+        minecraft_version: &str,
+        mod_loader: &str,
+        mod_loader_version: Option<&str>,
     ) -> Result<PathBuf> {
         let launcher_type = self.detect_launcher_type(launcher_path).await?;
 
         match launcher_type {
             LauncherType::Official => {
-                self.create_official_instance(launcher_path, instance_name, minecraft_version).await
+                self.create_official_instance(launcher_path, instance_name, minecraft_version, mod_loader, mod_loader_version).await
             }
             LauncherType::Prism | LauncherType::PrismCracked => {
                 self.create_prism_instance(launcher_path, instance_name, minecraft_version, mod_loader, mod_loader_version).await
             }
             LauncherType::XMCL => {
-                self.create_xmcl_instance(launcher_path, instance_name, minecraft_version, mod_loader).await
+                self.create_xmcl_instance(launcher_path, instance_name, minecraft_version, mod_loader, mod_loader_version).await
             }
             LauncherType::AstralRinth => {
                 self.create_astral_rinth_instance(launcher_path, instance_name, minecraft_version, mod_loader, mod_loader_version).await
@@ -226,9 +1575,7 @@ impl LauncherManager {
             LauncherType::Other => {
                 self.create_other_instance(launcher_path, instance_name, minecraft_version, mod_loader, mod_loader_version).await
             }
-            _ => Err(MinecraftInstallerError::InstallationFailed(
-                format!("Unsupported launcher type: {:?}", launcher_type)
-            ))
+            _ => Err(MinecraftInstallerError::UnsupportedLauncher(launcher_type))
         }
     }
 
@@ -238,8 +1585,13 @@ impl LauncherManager {
         launcher_path: &Path,
         instance_name: &str,
         minecraft_version: &str,
+        mod_loader: &str,
+        mod_loader_version: Option<&str>,
     ) -> Result<PathBuf> {
-        let profiles_path = launcher_path.join("launcher_profiles.json");
+        // Resolved first so an unavailable loader leaves nothing behind
+        let version_id = self.official_version(launcher_path, minecraft_version, mod_loader, mod_loader_version).await?;
+
+        let profiles_path = official_profiles_path(&launcher_path);
 
         // Read existing profiles or create new
         let mut profiles_json = if profiles_path.exists() {
@@ -264,16 +1616,19 @@ impl LauncherManager {
             })
         };
 
-        // Create new profile
+        // Create new profile, reusing the game directory if this instance was created before
         let profile_id = format!("minecraft-installer-{}", instance_name);
-        let instance_dir = launcher_path.join("instances").join(instance_name);
+        let instance_dir = match profiles_json["profiles"][&profile_id]["gameDir"].as_str() {
+            Some(game_dir) => PathBuf::from(game_dir),
+            None => unique_instance_dir(&launcher_path.join("instances"), instance_name, instance_name),
+        };
         fs::create_dir_all(&instance_dir).await?;
 
         let profile = json!({
             "created": chrono::Utc::now().to_rfc3339(),
             "icon": "Crafting_Table",
             "lastUsed": chrono::Utc::now().to_rfc3339(),
-            "lastVersionId": minecraft_version,
+            "lastVersionId": version_id,
             "name": instance_name,
             "type": "custom",
             "gameDir": instance_dir.to_string_lossy()
@@ -306,7 +1661,7 @@ impl LauncherManager {
         mod_loader: &str,
         mod_loader_version: Option<&str>,
     ) -> Result<PathBuf> {
-        let instance_dir = launcher_path.join("instances").join(instance_name);
+        let instance_dir = unique_instance_dir(&PrismConfig::load(launcher_path).instance_dir, instance_name, instance_name);
         fs::create_dir_all(&instance_dir).await?;
 
         // Create .minecraft directory
@@ -318,10 +1673,13 @@ impl LauncherManager {
         fs::create_dir_all(minecraft_dir.join("mods")).await?;
         fs::create_dir_all(minecraft_dir.join("config")).await?;
 
+        // Keep the icon the user picked if this instance is being recreated
+        let icon_key = read_instance_icon(&instance_dir).unwrap_or_else(|| "default".to_string());
+
         // Create instance.cfg with proper structure
         let instance_config = format!(r#"[General]
 ConfigVersion=1.2
-iconKey=default
+iconKey={}
 name={}
 AutomaticJava=true
 InstanceType=OneSix
@@ -370,7 +1728,7 @@ mods_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x1\x1\0\0
 resourcepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x1\x1\0\0\0\0\0\0\0\0\0\0\0\a\x10\0\0\0\x1\0\0\0\x4\0\0\0\x64\0\0\x2\xbc\0\0\0\a\x1\x1\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x64\xff\xff\xff\xff\0\0\0\x81\0\0\0\0\0\0\0\a\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\x1\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\x3\xe8\0\0\0\0\x64\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x1)
 shaderpacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x1\x1\0\0\0\0\0\0\0\0\0\0\0\x5\x10\0\0\0\x1\0\0\0\x4\0\0\0\x64\0\0\x1\xf4\0\0\0\x5\x1\x1\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x64\xff\xff\xff\xff\0\0\0\x81\0\0\0\0\0\0\0\x5\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\x1\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\x3\xe8\0\0\0\0\x64\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x1)
 texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x1\x1\0\0\0\0\0\0\0\0\0\0\0\x6 \0\0\0\x1\0\0\0\x5\0\0\0\x64\0\0\x2X\0\0\0\x6\x1\x1\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x64\xff\xff\xff\xff\0\0\0\x81\0\0\0\0\0\0\0\x6\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\x1\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\0\x64\0\0\0\x1\0\0\0\0\0\0\x3\xe8\0\0\0\0\x64\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x1)
-"#, instance_name, chrono::Utc::now().timestamp_millis());
+"#, icon_key, instance_name, chrono::Utc::now().timestamp_millis());
 
         fs::write(instance_dir.join("instance.cfg"), instance_config).await?;
 
@@ -402,13 +1760,16 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         // Add mod loader component if specified
         if mod_loader != "vanilla" {
             let loader_component = match mod_loader {
-                "fabric" => json!({
-                    "cachedName": "Fabric Loader",
-                    "cachedRequires": [{"uid": "net.minecraft"}],
-                    "cachedVersion": mod_loader_version.unwrap_or("stable"),
-                    "uid": "net.fabricmc.fabric-loader",
-                    "version": mod_loader_version.unwrap_or("stable")
-                }),
+                "fabric" => {
+                    let fabric_version = self.fabric.resolve(mod_loader_version, minecraft_version).await;
+                    json!({
+                        "cachedName": "Fabric Loader",
+                        "cachedRequires": [{"uid": "net.minecraft"}],
+                        "cachedVersion": fabric_version,
+                        "uid": "net.fabricmc.fabric-loader",
+                        "version": fabric_version
+                    })
+                },
                 "forge" => json!({
                     "cachedName": "Minecraft Forge",
                     "cachedRequires": [{"uid": "net.minecraft"}],
@@ -470,6 +1831,7 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         instance_name: &str,
         minecraft_version: &str,
         mod_loader: &str,
+        mod_loader_version: Option<&str>,
     ) -> Result<PathBuf> {
         let instances_path = launcher_path.join("instances.json");
 
@@ -485,7 +1847,8 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         };
 
         // Create instance directory
-        let instance_dir = launcher_path.join("instances").join(instance_name);
+        let instance_dir = unique_instance_dir(&launcher_path.join("instances"), instance_name, instance_name);
+        let icon = read_instance_icon(&instance_dir).unwrap_or_default();
         fs::create_dir_all(&instance_dir).await?;
         fs::create_dir_all(instance_dir.join("saves")).await?;
         fs::create_dir_all(instance_dir.join("resourcepacks")).await?;
@@ -493,17 +1856,23 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         fs::create_dir_all(instance_dir.join("mods")).await?;
         fs::create_dir_all(instance_dir.join("config")).await?;
 
+        let fabric_loader = if mod_loader == "fabric" {
+            Some(self.fabric.resolve(mod_loader_version, minecraft_version).await)
+        } else {
+            None
+        };
+
         // Create instance configuration
         let instance_config = json!({
             "name": instance_name,
             "maxMemory": 4096,
             "url": "",
-            "icon": "",
+            "icon": icon,
             "runtime": {
                 "minecraft": minecraft_version,
                 "forge": if mod_loader == "forge" { Some("latest") } else { None::<&str> },
                 "liteloader": None::<&str>,
-                "fabricLoader": if mod_loader == "fabric" { Some("latest") } else { None::<&str> },
+                "fabricLoader": fabric_loader,
                 "yarn": None::<&str>,
                 "optifine": None::<&str>,
                 "quiltLoader": if mod_loader == "quilt" { Some("latest") } else { None::<&str> },
@@ -544,8 +1913,13 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         mod_loader: &str,
         mod_loader_version: Option<&str>,
     ) -> Result<PathBuf> {
-        let profile_name = instance_name.to_lowercase().replace(" ", "-");
-        let profile_dir = launcher_path.join("profiles").join(&profile_name);
+        let profile_dir = unique_instance_dir(
+            &launcher_path.join("profiles"),
+            &instance_name.to_lowercase().replace(' ', "-"),
+            instance_name,
+        );
+        let profile_name = profile_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let icon_path = read_instance_icon(&profile_dir);
         fs::create_dir_all(&profile_dir).await?;
 
         // Create profile.json
@@ -554,7 +1928,7 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
             "game_version": minecraft_version,
             "loader": mod_loader,
             "loader_version": mod_loader_version,
-            "icon_path": null,
+            "icon_path": icon_path,
             "created": chrono::Utc::now().to_rfc3339(),
             "modified": chrono::Utc::now().to_rfc3339(),
             "last_played": null,
@@ -609,7 +1983,9 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         // Note: servers.dat will be copied from mrpack during file copying phase
 
         // Inject profile into AstralRinth database
-        if let Err(e) = self.inject_astralrinth_profile(launcher_path, &profile_name, instance_name, minecraft_version, mod_loader).await {
+        if self.skip_launcher_db {
+            info!("Not writing to the AstralRinth database; import {} in the launcher to use it", profile_dir.display());
+        } else if let Err(e) = self.inject_astralrinth_profile(launcher_path, &profile_name, instance_name, minecraft_version, mod_loader, icon_path.as_deref()).await {
             warn!("Failed to inject profile into AstralRinth database: {}", e);
             // Continue anyway - the profile directory structure is still created
         }
@@ -627,8 +2003,13 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         mod_loader: &str,
         mod_loader_version: Option<&str>,
     ) -> Result<PathBuf> {
-        let profile_name = instance_name.to_lowercase().replace(" ", "-");
-        let profile_dir = launcher_path.join("profiles").join(&profile_name);
+        let profile_dir = unique_instance_dir(
+            &launcher_path.join("profiles"),
+            &instance_name.to_lowercase().replace(' ', "-"),
+            instance_name,
+        );
+        let profile_name = profile_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let icon_path = read_instance_icon(&profile_dir);
         fs::create_dir_all(&profile_dir).await?;
 
         // Create profile.json
@@ -637,7 +2018,7 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
             "game_version": minecraft_version,
             "loader": mod_loader,
             "loader_version": mod_loader_version,
-            "icon_path": null,
+            "icon_path": icon_path,
             "created": chrono::Utc::now().to_rfc3339(),
             "modified": chrono::Utc::now().to_rfc3339(),
             "last_played": null,
@@ -686,208 +2067,883 @@ texturepacks_Page\Columns=@ByteArray(\0\0\0\xff\0\0\0\0\0\0\0\x1\0\0\0\0\0\0\0\x
         let options_content = "version:3955\nao:true\nbiomeBlendRadius:2\nenableVsync:true\nentityDistanceScaling:1.0\nentityShadows:true\nforceUnicodeFont:false\njapaneseGlyphVariants:false\nfov:0.0\nfovEffectScale:1.0\ndarknessEffectScale:1.0\nglintSpeed:0.5\nglintStrength:0.75\nprioritizeChunkUpdates:0\nfullscreen:false\ngamma:0.5\ngraphicsMode:1\nguiScale:0\nmaxFps:120\nmipmapLevels:4\nrenderDistance:12\nsimulationDistance:12\nuseVbo:true\n";
         fs::write(profile_dir.join("options.txt"), options_content).await?;
 
-        // Note: servers.dat will be copied from mrpack during file copying phase
+        // Note: servers.dat will be copied from mrpack during file copying phase
+
+        // Inject profile into Modrinth App database (same as AstralRinth)
+        if self.skip_launcher_db {
+            info!("Not writing to the Modrinth App database; import {} in the launcher to use it", profile_dir.display());
+        } else if let Err(e) = self.inject_modrinth_app_profile(launcher_path, &profile_name, instance_name, minecraft_version, mod_loader, icon_path.as_deref()).await {
+            warn!("Failed to inject profile into Modrinth App database: {}", e);
+            // Continue anyway - the profile directory structure is still created
+        }
+
+        info!("Created Modrinth App instance: {}", instance_name);
+        Ok(profile_dir)
+    }
+
+    /// Create instance for MultiMC
+    async fn create_mmc_instance(
+        &self,
+        launcher_path: &Path,
+        instance_name: &str,
+        minecraft_version: &str,
+        mod_loader: &str,
+    ) -> Result<PathBuf> {
+        let instance_dir = unique_instance_dir(
+            &PrismConfig::load_file(launcher_path, "multimc.cfg").instance_dir,
+            instance_name,
+            instance_name,
+        );
+        fs::create_dir_all(&instance_dir).await?;
+
+        // Create .minecraft directory
+        let minecraft_dir = instance_dir.join(".minecraft");
+        fs::create_dir_all(&minecraft_dir).await?;
+        fs::create_dir_all(minecraft_dir.join("saves")).await?;
+        fs::create_dir_all(minecraft_dir.join("resourcepacks")).await?;
+        fs::create_dir_all(minecraft_dir.join("mods")).await?;
+
+        let icon_key = read_instance_icon(&instance_dir).unwrap_or_else(|| "default".to_string());
+
+        // Create instance.cfg (similar to PrismLauncher but with MultiMC format)
+        let instance_config = format!(r#"InstanceType=OneSix
+IntendedVersion={}
+LogPrePostOutput=true
+iconKey={}
+name={}
+notes=Created by Minecraft Installer
+"#, minecraft_version, icon_key, instance_name);
+
+        fs::write(instance_dir.join("instance.cfg"), instance_config).await?;
+
+        // Create mmc-pack.json
+        let mmc_pack = json!({
+            "components": [
+                {
+                    "cachedName": "Minecraft",
+                    "cachedVersion": minecraft_version,
+                    "important": true,
+                    "uid": "net.minecraft",
+                    "version": minecraft_version
+                }
+            ],
+            "formatVersion": 1
+        });
+
+        fs::write(
+            instance_dir.join("mmc-pack.json"),
+            serde_json::to_string_pretty(&mmc_pack)?
+        ).await?;
+
+        info!("Created MultiMC instance: {}", instance_name);
+        Ok(instance_dir)
+    }
+
+    /// Inject profile into AstralRinth database
+    async fn inject_astralrinth_profile(
+        &self,
+        launcher_path: &Path,
+        profile_name: &str,
+        instance_name: &str,
+        minecraft_version: &str,
+        mod_loader: &str,
+        icon_path: Option<&str>,
+    ) -> Result<()> {
+        let db_path = launcher_path.join("app.db");
+
+        if !db_path.exists() {
+            return Err(MinecraftInstallerError::InstallationFailed(
+                "AstralRinth database not found".to_string()
+            ));
+        }
+
+        // Open database connection
+        let conn = Connection::open(&db_path)
+            .map_err(|e| MinecraftInstallerError::InstallationFailed(
+                format!("Failed to open AstralRinth database: {}", e)
+            ))?;
+
+        // INSERT OR REPLACE drops the old row, so remember its icon first
+        let icon_path = read_db_icon(&conn, profile_name).or(icon_path.map(str::to_string));
+
+        // Get current timestamp
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // Only the columns this app version's schema has are written
+        match upsert_db_profile(
+            &conn,
+            &[
+                ("path", &profile_name),
+                ("name", &instance_name),
+                ("game_version", &minecraft_version),
+                ("mod_loader", &mod_loader),
+                ("install_stage", &"installed"),
+                ("created", &now),
+                ("modified", &now),
+                ("groups", &"[]"),                       // empty JSON array
+                ("override_extra_launch_args", &"[]"),   // empty JSON array
+                ("override_custom_env_vars", &"{}"),     // empty JSON object
+            ],
+        ) {
+            Ok(()) => {
+                restore_db_icon(&conn, profile_name, icon_path.as_deref());
+                info!("Successfully injected profile into AstralRinth database");
+                Ok(())
+            }
+            Err(e) => {
+                debug!("Failed to insert profile: {}", e);
+                Err(MinecraftInstallerError::InstallationFailed(format!(
+                    "Failed to inject profile into AstralRinth database: {}", e
+                )))
+            }
+        }
+    }
+
+    /// Inject profile into Modrinth App database
+    async fn inject_modrinth_app_profile(
+        &self,
+        launcher_path: &Path,
+        profile_name: &str,
+        instance_name: &str,
+        minecraft_version: &str,
+        mod_loader: &str,
+        icon_path: Option<&str>,
+    ) -> Result<()> {
+        let db_path = launcher_path.join("app.db");
+
+        if !db_path.exists() {
+            return Err(MinecraftInstallerError::InstallationFailed(
+                "Modrinth App database not found".to_string()
+            ));
+        }
+
+        // Open database connection
+        let conn = Connection::open(&db_path)
+            .map_err(|e| MinecraftInstallerError::InstallationFailed(
+                format!("Failed to open Modrinth App database: {}", e)
+            ))?;
+
+        // INSERT OR REPLACE drops the old row, so remember its icon first
+        let icon_path = read_db_icon(&conn, profile_name).or(icon_path.map(str::to_string));
+
+        // Get current timestamp
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // Only the columns this app version's schema has are written (same as AstralRinth)
+        match upsert_db_profile(
+            &conn,
+            &[
+                ("path", &profile_name),
+                ("name", &instance_name),
+                ("game_version", &minecraft_version),
+                ("mod_loader", &mod_loader),
+                ("install_stage", &"installed"),
+                ("created", &now),
+                ("modified", &now),
+                ("groups", &"[]"),                       // empty JSON array
+                ("override_extra_launch_args", &"[]"),   // empty JSON array
+                ("override_custom_env_vars", &"{}"),     // empty JSON object
+            ],
+        ) {
+            Ok(()) => {
+                restore_db_icon(&conn, profile_name, icon_path.as_deref());
+                info!("Successfully injected profile into Modrinth App database");
+            }
+            Err(e) => {
+                debug!("Failed to insert profile: {}", e);
+            }
+        }
+
+        if ModrinthProfileFormat::detect(&conn) == ModrinthProfileFormat::V2 {
+            let profile_path = launcher_path.join("profiles").join(profile_name).join("profile.json");
+            let mut profile: serde_json::Value = match fs::read_to_string(&profile_path).await {
+                Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| json!({})),
+                Err(_) => json!({}),
+            };
+            let now = chrono::Utc::now().to_rfc3339();
+            if profile.get("uuid").and_then(|uuid| uuid.as_str()).is_none() {
+                profile["uuid"] = json!(Uuid::new_v4().to_string());
+            }
+            profile["metadata"] = json!({
+                "name": instance_name,
+                "icon": icon_path,
+                "game_version": minecraft_version,
+                "loader": mod_loader,
+                "loader_version": profile["loader_version"].clone(),
+                "groups": [],
+                "linked_data": null,
+                "date_created": profile["created"].as_str().map(str::to_string).unwrap_or_else(|| now.clone()),
+                "date_modified": now,
+                "last_played": null
+            });
+            fs::write(&profile_path, serde_json::to_string_pretty(&profile)?).await?;
+            debug!("Wrote profile.json metadata for the newer Modrinth App format");
+        }
+
+        Ok(())
+    }
+
+    /// Install mrpack (Modrinth modpack) file
+    pub async fn install_mrpack(
+        &self,
+        mrpack_path: &Path,
+        instance_dir: &Path,
+        instance_name: &str,
+    ) -> Result<(String, String)> {
+        self.install_mrpack_with_options(mrpack_path, instance_dir, instance_name, OverrideOptions::fresh_install()).await
+    }
+
+    /// Install an mrpack with explicit control over when its overrides are extracted
+    /// and whether they replace files already in the instance
+    pub async fn install_mrpack_with_options(
+        &self,
+        mrpack_path: &Path,
+        instance_dir: &Path,
+        instance_name: &str,
+        overrides: OverrideOptions,
+    ) -> Result<(String, String)> {
+        info!("Installing mrpack: {}", mrpack_path.display());
+
+        // Extract mrpack file
+        let file = std::fs::File::open(mrpack_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+
+        // Read modrinth.index.json
+        let mut index: MrpackIndex = serde_json::from_str(&read_mrpack_index(&mut archive)?)?;
+
+        // Refuse packs this installer can't reproduce before touching the instance
+        let (minecraft_version, mod_loader) = check_mrpack_index(&index)?;
+
+        let duplicates = index.dedup_files();
+        if duplicates > 0 {
+            warn!("Removed {} duplicate file entries from {}", duplicates, index.name);
+        }
+
+        info!("Installing modpack: {} v{}", index.name, index.version_id);
+        let progress = self.progress.for_instance(instance_name);
+        progress.phase_started(Phase::Mrpack);
+
+        // Create instance directory
+        fs::create_dir_all(instance_dir).await?;
+        fs::create_dir_all(instance_dir.join("mods")).await?;
+        fs::create_dir_all(instance_dir.join("config")).await?;
+        fs::create_dir_all(instance_dir.join("saves")).await?;
+        fs::create_dir_all(instance_dir.join("resourcepacks")).await?;
+
+        if overrides.order == OverrideOrder::BeforeMods {
+            self.extract_mrpack_overrides(&mut archive, instance_dir, overrides.side, overrides.policy).await?;
+        }
+
+        // A jar the overrides already ship would otherwise land twice, possibly in two versions
+        let override_mods = override_mod_filenames(archive.file_names(), overrides.side);
+        let provided_by_overrides = |file: &MrpackFile| {
+            file.path.starts_with("mods/")
+                && Path::new(&file.path).file_name().is_some_and(|name| override_mods.contains(&*name.to_string_lossy()))
+        };
+        for file in index.files.iter().filter(|file| provided_by_overrides(file)) {
+            info!("Skipping download of {}: the pack's overrides already provide it", file.path);
+        }
+
+        // Packs often mark client-only mods as needed on the server too, which would crash it on start
+        let client_only = |file: &MrpackFile| overrides.side == PackSide::Server && self.is_client_only_mod(file);
+        for file in index.files.iter().filter(|file| overrides.side.supports(file.env.as_ref()) && client_only(file)) {
+            info!("Skipping {} on the server: client-only mod", file.path);
+        }
+
+        // Download mod files a few at a time; one failure doesn't stop the rest of the batch
+        let files: Vec<&MrpackFile> = index.files.iter()
+            .filter(|file| overrides.side.supports(file.env.as_ref()) && !provided_by_overrides(file) && !client_only(file))
+            .collect();
+        let total_files = files.len() as u64;
+        info!("Downloading {} mod files...", total_files);
+
+        let mut downloads = futures::stream::iter(files)
+            .map(|file| async move { (file, self.download_mrpack_file(file, instance_dir).await) })
+            .buffer_unordered(self.download_concurrency);
+        let mut completed = 0;
+        let mut failed = Vec::new();
+        while let Some((file, result)) = downloads.next().await {
+            match result {
+                Ok(size) => {
+                    completed += 1;
+                    info!("[{}/{}] ✓ Downloaded: {}", completed, total_files, file.path);
+                    progress.progress(Phase::Mrpack, Some(file.path.clone()), size, completed, total_files);
+                }
+                Err(MinecraftInstallerError::Cancelled) => return Err(MinecraftInstallerError::Cancelled),
+                Err(e) => {
+                    warn!("{}", e);
+                    failed.push(file.path.clone());
+                }
+            }
+        }
+        drop(downloads);
+
+        if let Some(first) = failed.first() {
+            let others = match failed.len() {
+                1 => String::new(),
+                n => format!(" (and {} more)", n - 1),
+            };
+            return Err(MinecraftInstallerError::DownloadFailed(
+                format!("Failed to download file: {}{}", first, others)
+            ));
+        }
+
+        if overrides.order == OverrideOrder::AfterMods {
+            self.extract_mrpack_overrides(&mut archive, instance_dir, overrides.side, overrides.policy).await?;
+        }
+        progress.phase_completed(Phase::Mrpack);
+
+        // Create instance metadata
+        let mut manifest = mrpack_install_manifest(&index)?;
+        manifest.mods = index.files.iter()
+            .filter(|file| file.path.starts_with("mods/") && overrides.side.supports(file.env.as_ref()))
+            .filter(|file| !provided_by_overrides(file) && !client_only(file))
+            .map(|file| ManifestMod {
+                path: file.path.clone(),
+                sha1: file.hashes.get("sha1").cloned(),
+                sha512: file.hashes.get("sha512").cloned(),
+                size: file.file_size,
+            })
+            .collect();
+        manifest.write(instance_dir).await?;
+
+        info!("✓ Mrpack installation completed: {}", instance_name);
+        Ok((minecraft_version, mod_loader.to_string()))
+    }
+
+    /// Download one mrpack file into the instance, trying each of its URLs until one passes the hash check
+    async fn download_mrpack_file(&self, file: &MrpackFile, instance_dir: &Path) -> Result<u64> {
+        if self.cancel.is_cancelled() {
+            return Err(MinecraftInstallerError::Cancelled);
+        }
+
+        let file_path = instance_dir.join(&file.path);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        for download_url in &file.downloads {
+            let response = tokio::select! {
+                response = self.http.get(download_url).send() => response,
+                _ = self.cancel.cancelled() => return Err(MinecraftInstallerError::Cancelled),
+            };
+            match response {
+                Ok(response) if response.status().is_success() => {
+                    // The body is buffered and only written once complete, so a cancel leaves no partial file
+                    let bytes = tokio::select! {
+                        bytes = response.bytes() => bytes?,
+                        _ = self.cancel.cancelled() => return Err(MinecraftInstallerError::Cancelled),
+                    };
+
+                    // Verify hash if available
+                    if let Some(sha1_hash) = file.hashes.get("sha1") {
+                        use sha1_smol::{Sha1, Digest};
+                        let mut hasher = Sha1::new();
+                        hasher.update(&bytes);
+                        let calculated_hash = hex::encode(hasher.digest().bytes());
+
+                        if calculated_hash != *sha1_hash {
+                            warn!("Hash mismatch for {}: expected {}, got {}",
+                                file.path, sha1_hash, calculated_hash);
+                            continue;
+                        }
+                    }
+
+                    let size = bytes.len() as u64;
+                    fs::write(&file_path, bytes).await?;
+                    return Ok(size);
+                }
+                Ok(response) => {
+                    warn!("Failed to download {} from {}: HTTP {}",
+                        file.path, download_url, response.status());
+                }
+                Err(e) => {
+                    warn!("Failed to download {} from {}: {}",
+                        file.path, download_url, e);
+                }
+            }
+        }
+
+        Err(MinecraftInstallerError::DownloadFailed(
+            format!("Failed to download file: {}", file.path)
+        ))
+    }
+
+    /// Extract an mrpack's `overrides/` and `client-overrides/` into an existing instance,
+    /// returning how many files were written
+    pub async fn apply_mrpack_overrides(
+        &self,
+        mrpack_path: &Path,
+        instance_dir: &Path,
+        policy: OverridePolicy,
+    ) -> Result<usize> {
+        let file = std::fs::File::open(mrpack_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+        self.extract_mrpack_overrides(&mut archive, instance_dir, PackSide::Client, policy).await
+    }
+
+    /// Re-sync an instance's configs from an mrpack without downloading any of its files
+    ///
+    /// Overrides replace existing files in the instance's game directory; its mods and install
+    /// manifest are left alone. Returns the pack's Minecraft version and loader.
+    pub async fn install_mrpack_overrides_only(
+        &self,
+        mrpack_path: &Path,
+        instance_dir: &Path,
+    ) -> Result<(String, String)> {
+        info!("Applying overrides only from mrpack: {}", mrpack_path.display());
+
+        let file = std::fs::File::open(mrpack_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+        let index: MrpackIndex = serde_json::from_str(&read_mrpack_index(&mut archive)?)?;
+        let (minecraft_version, mod_loader) = check_mrpack_index(&index)?;
+
+        let written = self.apply_mrpack_overrides(mrpack_path, &minecraft_base_dir(instance_dir), OverridePolicy::Overwrite).await?;
+        info!("✓ Applied {} override files from {} v{}", written, index.name, index.version_id);
+
+        Ok((minecraft_version, mod_loader.to_string()))
+    }
+
+    /// Extract `overrides/` and then the side-specific folder, which wins over the shared one
+    async fn extract_mrpack_overrides(
+        &self,
+        archive: &mut zip::ZipArchive<std::fs::File>,
+        instance_dir: &Path,
+        side: PackSide,
+        policy: OverridePolicy,
+    ) -> Result<usize> {
+        let side_prefix = format!("{}/", side.overrides_folder());
+        if !archive.file_names().any(|name| name.starts_with(&side_prefix)) {
+            return self.extract_overrides(archive, "overrides", instance_dir, policy).await;
+        }
+
+        // Skipping existing files would let `overrides/` shadow the side folder, so write that first
+        let folders = match policy {
+            OverridePolicy::Overwrite => ["overrides", side.overrides_folder()],
+            OverridePolicy::SkipExisting => [side.overrides_folder(), "overrides"],
+        };
+        let mut written = 0;
+        for folder in folders {
+            written += self.extract_overrides(archive, folder, instance_dir, policy).await?;
+        }
+        Ok(written)
+    }
+
+    /// Extract every entry under `<folder>/` in a modpack archive into the instance directory
+    async fn extract_overrides(
+        &self,
+        archive: &mut zip::ZipArchive<std::fs::File>,
+        folder: &str,
+        instance_dir: &Path,
+        policy: OverridePolicy,
+    ) -> Result<usize> {
+        let prefix = format!("{}/", folder.trim_end_matches('/'));
+        let mut written = 0;
+        let progress = match instance_dir.file_name() {
+            Some(name) => self.progress.for_instance(name.to_string_lossy()),
+            None => self.progress.clone(),
+        };
+        progress.phase_started(Phase::Overrides);
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let file_path = file.name().to_string();
+
+            if let Some(relative_path) = file_path.strip_prefix(&prefix) {
+                let output_path = instance_dir.join(relative_path);
+
+                if file.is_dir() {
+                    fs::create_dir_all(&output_path).await?;
+                } else if policy == OverridePolicy::SkipExisting && output_path.exists() {
+                    debug!("Keeping existing {}", relative_path);
+                } else {
+                    if let Some(parent) = output_path.parent() {
+                        fs::create_dir_all(parent).await?;
+                    }
+
+                    let mut buffer = Vec::new();
+                    std::io::Read::read_to_end(&mut file, &mut buffer)?;
+                    fs::write(&output_path, buffer).await?;
+                    written += 1;
+                }
+            }
+        }
+
+        progress.log(format!("Wrote {} override files", written));
+        progress.phase_completed(Phase::Overrides);
+        Ok(written)
+    }
+
+    /// Read the pack version from an mrpack (`versionId`) or CurseForge zip (`version`)
+    pub fn modpack_version(&self, pack_path: &Path) -> Result<Option<String>> {
+        let file = std::fs::File::open(pack_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+
+        if let Ok(content) = read_mrpack_index(&mut archive) {
+            let index: serde_json::Value = serde_json::from_str(&content)?;
+            return Ok(index["versionId"].as_str().map(|v| v.to_string()));
+        }
+        if let Ok(mut manifest_file) = archive.by_name("manifest.json") {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut manifest_file, &mut content)?;
+            let manifest: serde_json::Value = serde_json::from_str(&content)?;
+            return Ok(manifest["version"].as_str().map(|v| v.to_string()));
+        }
+
+        Ok(None)
+    }
+
+    /// Validate an mrpack file's structure without installing it
+    ///
+    /// Returns the list of problems found; an empty list means the pack looks valid.
+    pub fn validate_mrpack(&self, mrpack_path: &Path) -> Result<Vec<String>> {
+        let file = std::fs::File::open(mrpack_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+
+        let index_content = match read_mrpack_index(&mut archive) {
+            Ok(content) => content,
+            Err(MinecraftInstallerError::InstallationFailed(_)) => {
+                return Ok(vec!["modrinth.index.json not found in archive".to_string()]);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let index: serde_json::Value = match serde_json::from_str(&index_content) {
+            Ok(value) => value,
+            Err(e) => return Ok(vec![format!("modrinth.index.json is not valid JSON: {}", e)]),
+        };
+
+        let mut issues = validate_mrpack_index(&index);
 
-        // Inject profile into Modrinth App database (same as AstralRinth)
-        if let Err(e) = self.inject_modrinth_app_profile(launcher_path, &profile_name, instance_name, minecraft_version, mod_loader).await {
-            warn!("Failed to inject profile into Modrinth App database: {}", e);
-            // Continue anyway - the profile directory structure is still created
+        // Anything the schema checks missed will still trip the installer's own parser
+        if issues.is_empty() {
+            if let Err(e) = serde_json::from_value::<MrpackIndex>(index) {
+                issues.push(format!("modrinth.index.json could not be parsed: {}", e));
+            }
         }
 
-        info!("Created Modrinth App instance: {}", instance_name);
-        Ok(profile_dir)
+        Ok(issues)
     }
 
-    /// Create instance for MultiMC
-    async fn create_mmc_instance(
+    /// Install a single Modrinth mod into an instance's mods folder
+    ///
+    /// `version` is a Modrinth version id, or "latest" for the newest version that supports
+    /// `loader` on `mc_version`. Returns the path the jar was written to.
+    pub async fn install_modrinth_mod(
         &self,
-        launcher_path: &Path,
-        instance_name: &str,
-        minecraft_version: &str,
-        mod_loader: &str,
+        instance_path: &Path,
+        project: &str,
+        version: &str,
+        mc_version: &str,
+        loader: &str,
     ) -> Result<PathBuf> {
-        let instance_dir = launcher_path.join("instances").join(instance_name);
-        fs::create_dir_all(&instance_dir).await?;
+        self.install_modrinth_mod_with_client(instance_path, project, version, mc_version, loader, &ModrinthClient::new().with_http_config(&self.http_config)).await
+    }
 
-        // Create .minecraft directory
-        let minecraft_dir = instance_dir.join(".minecraft");
-        fs::create_dir_all(&minecraft_dir).await?;
-        fs::create_dir_all(minecraft_dir.join("saves")).await?;
-        fs::create_dir_all(minecraft_dir.join("resourcepacks")).await?;
-        fs::create_dir_all(minecraft_dir.join("mods")).await?;
+    /// Like [`Self::install_modrinth_mod`], against a specific Modrinth API host
+    pub async fn install_modrinth_mod_with_client(
+        &self,
+        instance_path: &Path,
+        project: &str,
+        version: &str,
+        mc_version: &str,
+        loader: &str,
+        modrinth: &ModrinthClient,
+    ) -> Result<PathBuf> {
+        let loader = loader.to_lowercase();
+
+        let mod_version = if version == "latest" {
+            modrinth.project_versions(project, &loader, mc_version).await?
+                .into_iter()
+                .find(|v| v.supports(&loader, mc_version))
+                .ok_or_else(|| MinecraftInstallerError::InvalidVersion(format!(
+                    "No version of {} supports {} on Minecraft {}", project, loader, mc_version
+                )))?
+        } else {
+            let mod_version = modrinth.version(version).await?;
+            if !mod_version.loaders.iter().any(|l| l.eq_ignore_ascii_case(&loader)) {
+                return Err(MinecraftInstallerError::InvalidLoader(format!(
+                    "{} {} is for {}, not {}", project, mod_version.version_number, mod_version.loaders.join("/"), loader
+                )));
+            }
+            if !mod_version.supports(&loader, mc_version) {
+                return Err(MinecraftInstallerError::InvalidVersion(format!(
+                    "{} {} supports Minecraft {}, not {}", project, mod_version.version_number, mod_version.game_versions.join(", "), mc_version
+                )));
+            }
+            mod_version
+        };
 
-        // Create instance.cfg (similar to PrismLauncher but with MultiMC format)
-        let instance_config = format!(r#"InstanceType=OneSix
-IntendedVersion={}
-LogPrePostOutput=true
-name={}
-notes=Created by Minecraft Installer
-"#, minecraft_version, instance_name);
+        let file = mod_version.primary_file().ok_or_else(|| MinecraftInstallerError::DownloadFailed(format!(
+            "{} {} has no files", project, mod_version.version_number
+        )))?;
+        let filename = checked_file_name(&file.filename)?;
+        let bytes = modrinth.download_file(file).await?;
 
-        fs::write(instance_dir.join("instance.cfg"), instance_config).await?;
+        let mods_dir = self.find_mods_directory(instance_path);
+        fs::create_dir_all(&mods_dir).await?;
+        let target = mods_dir.join(filename);
+        fs::write(&target, bytes).await?;
 
-        // Create mmc-pack.json
-        let mmc_pack = json!({
-            "components": [
-                {
-                    "cachedName": "Minecraft",
-                    "cachedVersion": minecraft_version,
-                    "important": true,
-                    "uid": "net.minecraft",
-                    "version": minecraft_version
-                }
-            ],
-            "formatVersion": 1
-        });
+        info!("✓ Installed {} {} into {}", project, mod_version.version_number, mods_dir.display());
+        Ok(target)
+    }
 
-        fs::write(
-            instance_dir.join("mmc-pack.json"),
-            serde_json::to_string_pretty(&mmc_pack)?
-        ).await?;
+    /// Install a Modrinth resource pack into an instance's `resourcepacks` folder
+    ///
+    /// `version` is a Modrinth version id, or "latest" for the newest version listing `mc_version`.
+    /// Returns the path the pack was written to.
+    pub async fn install_modrinth_resourcepack(&self, instance_path: &Path, project: &str, version: &str, mc_version: &str) -> Result<PathBuf> {
+        self.install_modrinth_resourcepack_with_client(instance_path, project, version, mc_version, &ModrinthClient::new().with_http_config(&self.http_config)).await
+    }
 
-        info!("Created MultiMC instance: {}", instance_name);
-        Ok(instance_dir)
+    /// Like [`Self::install_modrinth_resourcepack`], against a specific Modrinth API host
+    pub async fn install_modrinth_resourcepack_with_client(
+        &self,
+        instance_path: &Path,
+        project: &str,
+        version: &str,
+        mc_version: &str,
+        modrinth: &ModrinthClient,
+    ) -> Result<PathBuf> {
+        self.install_modrinth_pack(instance_path, "resourcepacks", project, version, mc_version, modrinth).await
     }
 
-    /// Inject profile into AstralRinth database
-    async fn inject_astralrinth_profile(
+    /// Install a Modrinth shader pack into an instance's `shaderpacks` folder, like
+    /// [`Self::install_modrinth_resourcepack`]; any shader loader (Iris, OptiFine, …) is accepted
+    pub async fn install_modrinth_shaderpack(&self, instance_path: &Path, project: &str, version: &str, mc_version: &str) -> Result<PathBuf> {
+        self.install_modrinth_shaderpack_with_client(instance_path, project, version, mc_version, &ModrinthClient::new().with_http_config(&self.http_config)).await
+    }
+
+    /// Like [`Self::install_modrinth_shaderpack`], against a specific Modrinth API host
+    pub async fn install_modrinth_shaderpack_with_client(
         &self,
-        launcher_path: &Path,
-        profile_name: &str,
-        instance_name: &str,
-        minecraft_version: &str,
-        mod_loader: &str,
-    ) -> Result<()> {
-        let db_path = launcher_path.join("app.db");
+        instance_path: &Path,
+        project: &str,
+        version: &str,
+        mc_version: &str,
+        modrinth: &ModrinthClient,
+    ) -> Result<PathBuf> {
+        self.install_modrinth_pack(instance_path, "shaderpacks", project, version, mc_version, modrinth).await
+    }
 
-        if !db_path.exists() {
-            return Err(MinecraftInstallerError::InstallationFailed(
-                "AstralRinth database not found".to_string()
-            ));
-        }
+    /// Download a pack version's primary file (hash-checked) into `folder` under the instance's game directory
+    async fn install_modrinth_pack(
+        &self,
+        instance_path: &Path,
+        folder: &str,
+        project: &str,
+        version: &str,
+        mc_version: &str,
+        modrinth: &ModrinthClient,
+    ) -> Result<PathBuf> {
+        let pack_version = if version == "latest" {
+            modrinth.project_versions(project, "", mc_version).await?
+                .into_iter()
+                .find(|v| v.game_versions.iter().any(|g| g == mc_version))
+                .ok_or_else(|| MinecraftInstallerError::InvalidVersion(format!(
+                    "No version of {} supports Minecraft {}", project, mc_version
+                )))?
+        } else {
+            let pack_version = modrinth.version(version).await?;
+            if !pack_version.game_versions.iter().any(|g| g == mc_version) {
+                return Err(MinecraftInstallerError::InvalidVersion(format!(
+                    "{} {} supports Minecraft {}, not {}", project, pack_version.version_number, pack_version.game_versions.join(", "), mc_version
+                )));
+            }
+            pack_version
+        };
 
-        // Open database connection
-        let conn = Connection::open(&db_path)
-            .map_err(|e| MinecraftInstallerError::InstallationFailed(
-                format!("Failed to open AstralRinth database: {}", e)
-            ))?;
+        let file = pack_version.primary_file().ok_or_else(|| MinecraftInstallerError::DownloadFailed(format!(
+            "{} {} has no files", project, pack_version.version_number
+        )))?;
+        let filename = checked_file_name(&file.filename)?;
+        let bytes = modrinth.download_file(file).await?;
 
-        // Get current timestamp
-        let now = chrono::Utc::now().timestamp_millis();
+        let pack_dir = minecraft_base_dir(instance_path).join(folder);
+        fs::create_dir_all(&pack_dir).await?;
+        let target = pack_dir.join(filename);
+        fs::write(&target, bytes).await?;
 
-        // Try to insert into profiles table with different possible table structures
-
-        let mut success = false;
-
-        // Insert with the minimal required fields that we discovered through testing
-        match conn.execute(
-            "INSERT OR REPLACE INTO profiles (path, name, game_version, mod_loader, install_stage, created, modified, groups, override_extra_launch_args, override_custom_env_vars) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            rusqlite::params![
-                profile_name,           // path
-                instance_name,          // name
-                minecraft_version,      // game_version
-                mod_loader,             // mod_loader
-                "installed",            // install_stage
-                now,                    // created
-                now,                    // modified
-                "[]",                   // groups (empty JSON array)
-                "[]",                   // override_extra_launch_args (empty JSON array)
-                "{}"                    // override_custom_env_vars (empty JSON object)
-            ]
-        ) {
-            Ok(_) => {
-                success = true;
-                info!("Successfully injected profile into AstralRinth database");
-            }
-            Err(e) => {
-                debug!("Failed to insert profile: {}", e);
+        info!("✓ Installed {} {} into {}", project, pack_version.version_number, pack_dir.display());
+        Ok(target)
+    }
+
+    /// Export an existing instance back to a .mrpack file
+    pub async fn export_mrpack(&self, instance_path: &Path, output_path: &Path) -> Result<MrpackIndex> {
+        self.export_mrpack_with_client(instance_path, output_path, &ModrinthClient::new().with_http_config(&self.http_config)).await
+    }
+
+    /// Export an existing instance to a .mrpack file, resolving mods through the given Modrinth client
+    ///
+    /// Mods Modrinth knows by hash become download entries; everything else is bundled under `overrides/mods/`.
+    pub async fn export_mrpack_with_client(
+        &self,
+        instance_path: &Path,
+        output_path: &Path,
+        modrinth: &ModrinthClient,
+    ) -> Result<MrpackIndex> {
+        info!("Exporting instance {} to {}", instance_path.display(), output_path.display());
+
+        let instance = InstanceScanner::new(self).analyze_instance(instance_path).await?
+            .filter(|i| i.minecraft_version != "Unknown")
+            .ok_or_else(|| MinecraftInstallerError::InstallationFailed(format!(
+                "Could not detect the Minecraft version of {}",
+                instance_path.display()
+            )))?;
+
+        let mut dependencies = std::collections::HashMap::new();
+        dependencies.insert("minecraft".to_string(), instance.minecraft_version.clone());
+        let loader_key = match instance.mod_loader.to_lowercase().as_str() {
+            "fabric" => Some("fabric-loader"),
+            "quilt" => Some("quilt-loader"),
+            "forge" => Some("forge"),
+            "neoforge" => Some("neoforge"),
+            _ => None,
+        };
+        match (loader_key, &instance.mod_loader_version) {
+            (Some(key), Some(version)) => {
+                dependencies.insert(key.to_string(), version.clone());
             }
+            (Some(_), None) => warn!("{} loader version unknown, leaving it out of the mrpack", instance.mod_loader),
+            _ => {}
         }
 
-        if !success {
-            return Err(MinecraftInstallerError::InstallationFailed(
-                "Failed to inject profile into AstralRinth database - unknown table structure".to_string()
-            ));
-        }
+        let mods_dir = self.find_mods_directory(instance_path);
 
-        Ok(())
-    }
+        let mut mod_paths = Vec::new();
+        if mods_dir.exists() {
+            let mut entries = fs::read_dir(&mods_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) == Some("jar") {
+                    mod_paths.push(path);
+                }
+            }
+        }
+        mod_paths.sort();
+
+        let mut files = Vec::new();
+        let mut overrides = Vec::new();
+        for mod_path in mod_paths {
+            let filename = mod_path.file_name().unwrap().to_string_lossy().to_string();
+            let bytes = fs::read(&mod_path).await?;
+
+            let sha1_hash = {
+                let mut hasher = sha1_smol::Sha1::new();
+                hasher.update(&bytes);
+                hex::encode(hasher.digest().bytes())
+            };
+            let sha512_hash = {
+                use sha2::{Digest, Sha512};
+                hex::encode(Sha512::digest(&bytes))
+            };
 
-    /// Inject profile into Modrinth App database
-    async fn inject_modrinth_app_profile(
-        &self,
-        launcher_path: &Path,
-        profile_name: &str,
-        instance_name: &str,
-        minecraft_version: &str,
-        mod_loader: &str,
-    ) -> Result<()> {
-        let db_path = launcher_path.join("app.db");
+            let download_url = match modrinth.version_from_sha512(&sha512_hash).await {
+                Ok(Some(version)) => version.file_by_hash("sha512", &sha512_hash).map(|f| f.url.clone()),
+                Ok(None) => None,
+                Err(e) => {
+                    warn!("Failed to resolve {} on Modrinth, bundling it instead: {}", filename, e);
+                    None
+                }
+            };
 
-        if !db_path.exists() {
-            return Err(MinecraftInstallerError::InstallationFailed(
-                "Modrinth App database not found".to_string()
-            ));
+            match download_url {
+                Some(url) => {
+                    debug!("Resolved {} on Modrinth", filename);
+                    let mut hashes = std::collections::HashMap::new();
+                    hashes.insert("sha1".to_string(), sha1_hash);
+                    hashes.insert("sha512".to_string(), sha512_hash);
+                    files.push(MrpackFile {
+                        path: format!("mods/{}", filename),
+                        hashes,
+                        env: None,
+                        downloads: vec![url],
+                        file_size: bytes.len() as u64,
+                    });
+                }
+                None => {
+                    debug!("Bundling {} into overrides", filename);
+                    overrides.push((filename, bytes));
+                }
+            }
         }
 
-        // Open database connection
-        let conn = Connection::open(&db_path)
-            .map_err(|e| MinecraftInstallerError::InstallationFailed(
-                format!("Failed to open Modrinth App database: {}", e)
-            ))?;
+        let index = MrpackIndex {
+            format_version: 1,
+            game: "minecraft".to_string(),
+            version_id: "1.0.0".to_string(),
+            name: instance.name.clone(),
+            summary: None,
+            files,
+            dependencies,
+        };
 
-        // Get current timestamp
-        let now = chrono::Utc::now().timestamp_millis();
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
 
-        // Insert with the minimal required fields (same as AstralRinth)
-        match conn.execute(
-            "INSERT OR REPLACE INTO profiles (path, name, game_version, mod_loader, install_stage, created, modified, groups, override_extra_launch_args, override_custom_env_vars) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            rusqlite::params![
-                profile_name,           // path
-                instance_name,          // name
-                minecraft_version,      // game_version
-                mod_loader,             // mod_loader
-                "installed",            // install_stage
-                now,                    // created
-                now,                    // modified
-                "[]",                   // groups (empty JSON array)
-                "[]",                   // override_extra_launch_args (empty JSON array)
-                "{}"                    // override_custom_env_vars (empty JSON object)
-            ]
-        ) {
-            Ok(_) => {
-                info!("Successfully injected profile into Modrinth App database");
-            }
-            Err(e) => {
-                debug!("Failed to insert profile: {}", e);
-            }
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(output_path)?);
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("modrinth.index.json", options)?;
+        std::io::Write::write_all(&mut zip, serde_json::to_string_pretty(&index)?.as_bytes())?;
+        for (filename, bytes) in &overrides {
+            zip.start_file(format!("overrides/mods/{}", filename), options)?;
+            std::io::Write::write_all(&mut zip, bytes)?;
         }
+        zip.finish()?;
 
-        Ok(())
+        info!(
+            "✓ Exported {}: {} mods from Modrinth, {} bundled in overrides",
+            instance.name,
+            index.files.len(),
+            overrides.len()
+        );
+        Ok(index)
     }
 
-    /// Install mrpack (Modrinth modpack) file
-    pub async fn install_mrpack(
+    /// Install a CurseForge modpack (.zip with manifest.json)
+    ///
+    /// Mod files are resolved through the CurseForge API, which requires an API key
+    /// in the `CURSEFORGE_API_KEY` environment variable.
+    pub async fn install_curseforge_zip(
         &self,
-        mrpack_path: &Path,
+        zip_path: &Path,
         instance_dir: &Path,
         instance_name: &str,
     ) -> Result<(String, String)> {
-        info!("Installing mrpack: {}", mrpack_path.display());
-
-        // Extract mrpack file
-        let file = std::fs::File::open(mrpack_path)?;
+        let api_key = std::env::var(CURSEFORGE_API_KEY_ENV)
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| MinecraftInstallerError::InstallationFailed(format!(
+                "CurseForge modpacks require an API key. Set the {} environment variable \
+                 (get one at https://console.curseforge.com/)",
+                CURSEFORGE_API_KEY_ENV
+            )))?;
+
+        info!("Installing CurseForge modpack: {}", zip_path.display());
+
+        let file = std::fs::File::open(zip_path)?;
         let mut archive = zip::ZipArchive::new(file)?;
 
-        // Read modrinth.index.json
-        let index: MrpackIndex = {
-            let mut index_file = archive.by_name("modrinth.index.json")?;
-            let mut index_content = String::new();
-            std::io::Read::read_to_string(&mut index_file, &mut index_content)?;
-            serde_json::from_str(&index_content)?
+        // Read manifest.json
+        let manifest: CurseForgeManifest = {
+            let mut manifest_file = archive.by_name("manifest.json")
+                .map_err(|_| MinecraftInstallerError::InstallationFailed(
+                    "manifest.json not found in CurseForge modpack".to_string()
+                ))?;
+            let mut manifest_content = String::new();
+            std::io::Read::read_to_string(&mut manifest_file, &mut manifest_content)?;
+            serde_json::from_str(&manifest_content)?
         };
 
-        info!("Installing modpack: {} v{}", index.name, index.version_id);
+        if manifest.manifest_type != "minecraftModpack" {
+            return Err(MinecraftInstallerError::InstallationFailed(
+                format!("Unsupported CurseForge manifest type: {}", manifest.manifest_type)
+            ));
+        }
+
+        info!("Installing modpack: {} v{}", manifest.name, manifest.version);
 
         // Create instance directory
         fs::create_dir_all(instance_dir).await?;
@@ -897,112 +2953,91 @@ notes=Created by Minecraft Installer
         fs::create_dir_all(instance_dir.join("resourcepacks")).await?;
 
         // Extract overrides
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            let file_path = file.name();
-
-            if file_path.starts_with("overrides/") {
-                let relative_path = file_path.strip_prefix("overrides/").unwrap();
-                let output_path = instance_dir.join(relative_path);
-
-                if file.is_dir() {
-                    fs::create_dir_all(&output_path).await?;
-                } else {
-                    if let Some(parent) = output_path.parent() {
-                        fs::create_dir_all(parent).await?;
-                    }
-
-                    let mut buffer = Vec::new();
-                    std::io::Read::read_to_end(&mut file, &mut buffer)?;
-                    fs::write(&output_path, buffer).await?;
-                }
-            }
-        }
+        self.extract_overrides(&mut archive, &manifest.overrides, instance_dir, OverridePolicy::Overwrite).await?;
 
-        // Download mod files
-        let client = reqwest::Client::new();
-        let total_files = index.files.len();
+        // Resolve and download mod files
+        let client = self.http.clone();
+        let total_files = manifest.files.len();
         info!("Downloading {} mod files...", total_files);
 
-        for (i, file) in index.files.iter().enumerate() {
-            // Check if file should be installed on client
-            if let Some(env) = &file.env {
-                if env.client == "unsupported" {
-                    continue;
-                }
-            }
-
-            info!("[{}/{}] Downloading: {}", i + 1, total_files, file.path);
-
-            let file_path = instance_dir.join(&file.path);
-            if let Some(parent) = file_path.parent() {
-                fs::create_dir_all(parent).await?;
+        for (i, manifest_file) in manifest.files.iter().enumerate() {
+            if !manifest_file.required {
+                debug!("Skipping optional file {}/{}", manifest_file.project_id, manifest_file.file_id);
+                continue;
             }
 
-            // Try each download URL until one succeeds
-            let mut downloaded = false;
-            for download_url in &file.downloads {
-                match client.get(download_url).send().await {
-                    Ok(response) if response.status().is_success() => {
-                        let bytes = response.bytes().await?;
+            let cf_file = self.fetch_curseforge_file(&client, &api_key, manifest_file).await?;
 
-                        // Verify hash if available
-                        if let Some(sha1_hash) = file.hashes.get("sha1") {
-                            use sha1_smol::{Sha1, Digest};
-                            let mut hasher = Sha1::new();
-                            hasher.update(&bytes);
-                            let calculated_hash = hex::encode(hasher.digest().bytes());
-
-                            if calculated_hash != *sha1_hash {
-                                warn!("Hash mismatch for {}: expected {}, got {}",
-                                    file.path, sha1_hash, calculated_hash);
-                                continue;
-                            }
-                        }
+            let download_url = cf_file.download_url.as_deref().ok_or_else(|| {
+                MinecraftInstallerError::DownloadFailed(format!(
+                    "{} (project {}) does not allow third-party downloads; download it manually from CurseForge",
+                    cf_file.file_name, cf_file.mod_id
+                ))
+            })?;
 
-                        fs::write(&file_path, bytes).await?;
-                        downloaded = true;
-                        info!("✓ Downloaded: {}", file.path);
-                        break;
-                    }
-                    Ok(response) => {
-                        warn!("Failed to download {} from {}: HTTP {}",
-                            file.path, download_url, response.status());
-                    }
-                    Err(e) => {
-                        warn!("Failed to download {} from {}: {}",
-                            file.path, download_url, e);
-                    }
-                }
-            }
+            info!("[{}/{}] Downloading: {}", i + 1, total_files, cf_file.file_name);
 
-            if !downloaded {
+            let response = client.get(download_url).send().await?;
+            if !response.status().is_success() {
                 return Err(MinecraftInstallerError::DownloadFailed(
-                    format!("Failed to download file: {}", file.path)
+                    format!("HTTP {} for {}", response.status(), cf_file.file_name)
                 ));
             }
+            let bytes = response.bytes().await?;
+
+            // Verify hash if available
+            if let Some(sha1_hash) = cf_file.sha1() {
+                use sha1_smol::Sha1;
+                let mut hasher = Sha1::new();
+                hasher.update(&bytes);
+                let calculated_hash = hex::encode(hasher.digest().bytes());
+
+                if !calculated_hash.eq_ignore_ascii_case(sha1_hash) {
+                    return Err(MinecraftInstallerError::HashMismatch {
+                        path: instance_dir.join("mods").join(&cf_file.file_name),
+                        expected: sha1_hash.to_string(),
+                        actual: calculated_hash,
+                    });
+                }
+            }
+
+            fs::write(instance_dir.join("mods").join(&cf_file.file_name), bytes).await?;
+            info!("✓ Downloaded: {}", cf_file.file_name);
         }
 
-        // Create instance metadata
-        let minecraft_version = index.dependencies.get("minecraft")
-            .ok_or_else(|| MinecraftInstallerError::InstallationFailed(
-                "No Minecraft version specified in mrpack".to_string()
-            ))?;
+        let (mod_loader, _) = manifest.mod_loader();
 
-        let mod_loader = if index.dependencies.contains_key("fabric-loader") {
-            "fabric"
-        } else if index.dependencies.contains_key("forge") {
-            "forge"
-        } else if index.dependencies.contains_key("quilt-loader") {
-            "quilt"
-        } else if index.dependencies.contains_key("neoforge") {
-            "neoforge"
-        } else {
-            "vanilla"
-        };
+        info!("✓ CurseForge modpack installation completed: {}", instance_name);
+        Ok((manifest.minecraft.version.clone(), mod_loader))
+    }
 
-        info!("✓ Mrpack installation completed: {}", instance_name);
-        Ok((minecraft_version.clone(), mod_loader.to_string()))
+    /// Resolve a manifest entry to its file metadata through the CurseForge API
+    async fn fetch_curseforge_file(
+        &self,
+        client: &reqwest::Client,
+        api_key: &str,
+        manifest_file: &CurseForgeManifestFile,
+    ) -> Result<CurseForgeFile> {
+        let url = format!(
+            "{}/mods/{}/files/{}",
+            self.curseforge_api_url, manifest_file.project_id, manifest_file.file_id
+        );
+        debug!("Resolving CurseForge file: {}", url);
+
+        let response = client.get(&url)
+            .header("x-api-key", api_key)
+            .header("Accept", "application/json")
+            .send().await?;
+
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "CurseForge API request failed for project {} file {}: HTTP {}",
+                manifest_file.project_id, manifest_file.file_id, response.status()
+            )));
+        }
+
+        let file_response: CurseForgeFileResponse = response.json().await?;
+        Ok(file_response.data)
     }
 
     /// Auto-detect and install to best available launcher
@@ -1015,6 +3050,50 @@ notes=Created by Minecraft Installer
         target_launcher: Option<&str>,
         custom_path: Option<&Path>,
     ) -> Result<PathBuf> {
+        let (launcher_type, path) = self.resolve_install_target(target_launcher, custom_path).await?;
+
+        if launcher_type == LauncherType::Other {
+            info!("Installing to custom path: {}", path.display());
+            return self.create_other_instance(&path, instance_name, minecraft_version, mod_loader, mod_loader_version).await;
+        }
+
+        info!("Installing to {} launcher at: {}", launcher_type, path.display());
+        self.create_instance(&path, instance_name, minecraft_version, mod_loader, mod_loader_version).await
+    }
+
+    /// The instance `auto_install_instance` would create as `instance_name`, if a previous run already made it
+    ///
+    /// AstralRinth and Modrinth App instances only count once their `profiles` row exists too.
+    pub async fn find_existing_instance(
+        &self,
+        instance_name: &str,
+        target_launcher: Option<&str>,
+        custom_path: Option<&Path>,
+    ) -> Option<PathBuf> {
+        let (launcher_type, launcher_path) = self.resolve_install_target(target_launcher, custom_path).await.ok()?;
+
+        if launcher_type == LauncherType::Official {
+            let profiles: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(official_profiles_path(&launcher_path)).await.ok()?).ok()?;
+            let game_dir = profiles["profiles"][format!("minecraft-installer-{}", instance_name)]["gameDir"].as_str()?;
+            return Some(PathBuf::from(game_dir)).filter(|dir| dir.is_dir());
+        }
+
+        let instance_path = self.find_instance(&self.instances_dir(&launcher_type, &launcher_path)?, instance_name)?;
+        if matches!(launcher_type, LauncherType::AstralRinth | LauncherType::ModrinthApp) {
+            let profile_name = instance_path.file_name()?.to_string_lossy().to_string();
+            let conn = Connection::open(launcher_path.join("app.db")).ok()?;
+            conn.query_row("SELECT 1 FROM profiles WHERE path = ?", [&profile_name], |_| Ok(())).ok()?;
+        }
+        Some(instance_path)
+    }
+
+    /// Pick the launcher `auto_install_instance` would create an instance in
+    pub async fn resolve_install_target(
+        &self,
+        target_launcher: Option<&str>,
+        custom_path: Option<&Path>,
+    ) -> Result<(LauncherType, PathBuf)> {
         let detected_launchers = self.detect_launchers().await;
 
         if detected_launchers.is_empty() {
@@ -1025,34 +3104,17 @@ notes=Created by Minecraft Installer
 
         // If target launcher is specified, try to find it first
         if let Some(target) = target_launcher {
-            let target_type = match target.to_lowercase().as_str() {
-                "astralrinth" => LauncherType::AstralRinth,
-                "modrinth" | "modrinthapp" => LauncherType::ModrinthApp,
-                "prism" | "prismlauncher" => {
-                    // Check if PrismCracked is available, otherwise use Prism
-                    if detected_launchers.iter().any(|(t, _)| matches!(t, LauncherType::PrismCracked)) {
-                        LauncherType::PrismCracked
-                    } else {
-                        LauncherType::Prism
-                    }
-                },
-                "prismcracked" => LauncherType::PrismCracked,
-                "xmcl" => LauncherType::XMCL,
-                "official" => LauncherType::Official,
-                "multimc" => LauncherType::MultiMC,
-                "other" => LauncherType::Other,
-                _ => {
-                    return Err(MinecraftInstallerError::InstallationFailed(
-                        format!("Unknown target launcher: {}", target)
-                    ));
-                }
-            };
+            let mut target_type: LauncherType = target.parse()?;
+            // Check if PrismCracked is available, otherwise use Prism
+            if target_type == LauncherType::Prism
+                && detected_launchers.iter().any(|(t, _)| matches!(t, LauncherType::PrismCracked)) {
+                target_type = LauncherType::PrismCracked;
+            }
 
             // Handle custom path for Other launcher
             if target_type == LauncherType::Other {
                 if let Some(path) = custom_path {
-                    info!("Installing to custom path: {}", path.display());
-                    return self.create_other_instance(path, instance_name, minecraft_version, mod_loader, mod_loader_version).await;
+                    return Ok((LauncherType::Other, path.to_path_buf()));
                 } else {
                     return Err(MinecraftInstallerError::InstallationFailed(
                         "Custom path required for Other launcher type".to_string()
@@ -1062,8 +3124,7 @@ notes=Created by Minecraft Installer
 
             if let Some((_, path)) = detected_launchers.iter()
                 .find(|(launcher_type, _)| launcher_type == &target_type) {
-                info!("Installing to {:?} launcher at: {}", target_type, path.display());
-                return self.create_instance(path, instance_name, minecraft_version, mod_loader, mod_loader_version).await;
+                return Ok((target_type, path.clone()));
             } else {
                 return Err(MinecraftInstallerError::InstallationFailed(
                     format!("Target launcher '{}' not found or not compatible", target)
@@ -1085,28 +3146,82 @@ notes=Created by Minecraft Installer
         for preferred_type in &preferred_order {
             if let Some((_, path)) = detected_launchers.iter()
                 .find(|(launcher_type, _)| launcher_type == preferred_type) {
-                info!("Installing to {:?} launcher at: {}", preferred_type, path.display());
-                return self.create_instance(path, instance_name, minecraft_version, mod_loader, mod_loader_version).await;
+                return Ok((preferred_type.clone(), path.clone()));
             }
         }
 
         // Fall back to first available launcher
-        let (launcher_type, path) = &detected_launchers[0];
-        info!("Installing to {:?} launcher at: {}", launcher_type, path.display());
-        self.create_instance(path, instance_name, minecraft_version, mod_loader, mod_loader_version).await
+        Ok(detected_launchers[0].clone())
+    }
+
+    /// Estimate the bytes an mrpack adds to an instance: client-side downloads and overrides,
+    /// plus libraries and assets when the instance is standalone
+    pub fn estimate_mrpack_size(&self, mrpack_path: &Path, standalone: bool) -> Result<u64> {
+        let file = std::fs::File::open(mrpack_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+
+        let index: MrpackIndex = serde_json::from_str(&read_mrpack_index(&mut archive)?)?;
+
+        let mut total: u64 = index.files.iter()
+            .filter(|file| PackSide::Client.supports(file.env.as_ref()))
+            .map(|file| file.file_size)
+            .sum();
+
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i)?;
+            if entry.name().starts_with("overrides/") {
+                total += entry.size();
+            }
+        }
+
+        if standalone {
+            total += STANDALONE_GAME_FILES_ESTIMATE;
+        }
+        Ok(total)
+    }
+
+    /// Fail early when the volume holding `path` has less than `required` bytes free
+    pub fn check_free_space(&self, path: &Path, required: u64) -> Result<()> {
+        let Some(available) = available_space(path) else {
+            warn!("Could not determine free space for {}, continuing anyway", path.display());
+            return Ok(());
+        };
+
+        if available < required {
+            return Err(MinecraftInstallerError::FileSystem(format!(
+                "Not enough free space on {}: need {} MB, only {} MB available",
+                path.display(),
+                required / 1024 / 1024,
+                available / 1024 / 1024
+            )));
+        }
+
+        debug!("{} MB needed, {} MB free on {}", required / 1024 / 1024, available / 1024 / 1024, path.display());
+        Ok(())
+    }
+
+    /// Check the launcher `auto_install_instance` would pick has room for an mrpack
+    pub async fn check_space_for_mrpack(
+        &self,
+        mrpack_path: &Path,
+        target_launcher: Option<&str>,
+        custom_path: Option<&Path>,
+    ) -> Result<()> {
+        // Without a target there is nothing to check; auto_install_instance reports that itself
+        let Ok((launcher_type, path)) = self.resolve_install_target(target_launcher, custom_path).await else {
+            return Ok(());
+        };
+        let required = self.estimate_mrpack_size(mrpack_path, launcher_type == LauncherType::Other)?;
+        self.check_free_space(&path, required)
     }
 
     /// Download modpack info from NAHA API
     pub async fn fetch_modpack_info(&self, modpack_type: &str) -> Result<NahaModpackInfo> {
-        let client = reqwest::Client::new();
-        
         // First, fetch server info from NAHA API
-        let naha_api_url = format!("https://perlytiara.github.io/NAHA-MC.IO/api/{}/", modpack_type);
+        let naha_api_url = format!("{}/{}/", self.naha_api_url, modpack_type);
         info!("Fetching server info from NAHA API: {}", naha_api_url);
         
-        let naha_response = client.get(&naha_api_url)
-            .header("User-Agent", "Minecraft-Installer/1.0")
-            .send().await
+        let naha_response = self.get_with_retry(&naha_api_url).await
             .map_err(|e| MinecraftInstallerError::InstallationFailed(
                 format!("Failed to fetch NAHA API info: {}", e)
             ))?;
@@ -1120,20 +3235,16 @@ notes=Created by Minecraft Installer
             serde_json::json!({})
         };
 
-        // Extract server info from NAHA API
-        let server_name = naha_data["server_name"].as_str().unwrap_or("NAHA Server").to_string();
-        let fingerprint = naha_data["fingerprint"].as_str().unwrap_or("naha-server-fingerprint").to_string();
-        let server_ip = naha_data["server_ip"].as_str().unwrap_or("play.naha.com").to_string();
-        let server_port = naha_data["server_port"].as_u64().unwrap_or(25565) as u16;
+        // Extract server info from NAHA API; the first server doubles as the pack's primary one
+        let servers = parse_naha_servers(&naha_data);
+        let NahaServer { server_name, server_ip, server_port, fingerprint } = servers[0].clone();
         let last_updated = naha_data["last_updated"].as_str().unwrap_or("2025-10-08T00:00:00Z").to_string();
         
         // Now fetch the latest modpack from GitHub releases
-        let github_api_url = "https://api.github.com/repos/perlytiara/NAHA-Minecraft-Modpacks/releases/latest";
+        let github_api_url = format!("{}/latest", self.modpack_releases_api_url);
         info!("Fetching modpack from GitHub: {}", github_api_url);
 
-        let github_response = client.get(github_api_url)
-            .header("User-Agent", "Minecraft-Installer/1.0")
-            .send().await
+        let github_response = self.get_with_retry(&github_api_url).await
             .map_err(|e| MinecraftInstallerError::InstallationFailed(
                 format!("Failed to fetch GitHub release: {}", e)
             ))?;
@@ -1177,6 +3288,7 @@ notes=Created by Minecraft Installer
             .ok_or_else(|| MinecraftInstallerError::InstallationFailed(
                 "No download URL found for mrpack".to_string()
             ))?;
+        let file_hash = release_asset_sha256(asset);
 
         // Extract version from filename (e.g., "NAHA-Neoforge-1.21.1-0.2.5.mrpack" -> "0.2.5")
         let filename = asset["name"].as_str().unwrap_or("");
@@ -1196,16 +3308,41 @@ notes=Created by Minecraft Installer
             download_url: download_url.to_string(),
             server_ip,
             server_port,
+            servers,
+            file_hash,
         };
+        modpack_info.validate()?;
 
         info!("✓ Fetched modpack info: {} v{} (fingerprint: {}...)", 
             modpack_info.server_name, 
             modpack_info.version,
-            &modpack_info.fingerprint[..16]
+            modpack_info.fingerprint.chars().take(16).collect::<String>()
         );
         Ok(modpack_info)
     }
 
+    /// GET `url`, retrying connection errors and 5xx responses a couple of times before giving up
+    async fn get_with_retry(&self, url: &str) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let result = self.http.get(url).send().await;
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+            if !retryable || attempt >= MODPACK_INFO_RETRIES {
+                return result;
+            }
+
+            attempt += 1;
+            match &result {
+                Ok(response) => warn!("{} returned {}, retrying ({}/{})", url, response.status(), attempt, MODPACK_INFO_RETRIES),
+                Err(e) => warn!("Request to {} failed: {}, retrying ({}/{})", url, e, attempt, MODPACK_INFO_RETRIES),
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(250 * attempt as u64)).await;
+        }
+    }
+
     /// Fetch modpack info for a specific version/tag
     pub async fn fetch_modpack_info_version(&self, modpack_type: &str, target_version: &str) -> Result<NahaModpackInfo> {
         // Use GitHub releases API to get a specific release by tag
@@ -1215,13 +3352,10 @@ notes=Created by Minecraft Installer
             format!("Fabric-{}", target_version)
         };
         
-        let api_url = format!("https://api.github.com/repos/perlytiara/NAHA-Minecraft-Modpacks/releases/tags/{}", tag_name);
+        let api_url = format!("{}/tags/{}", self.modpack_releases_api_url, tag_name);
         info!("Fetching specific version from GitHub: {}", api_url);
 
-        let client = reqwest::Client::new();
-        let response = client.get(&api_url)
-            .header("User-Agent", "Minecraft-Installer/1.0")
-            .send().await
+        let response = self.get_with_retry(&api_url).await
             .map_err(|e| MinecraftInstallerError::InstallationFailed(
                 format!("Failed to fetch modpack info: {}", e)
             ))?;
@@ -1265,6 +3399,7 @@ notes=Created by Minecraft Installer
             .ok_or_else(|| MinecraftInstallerError::InstallationFailed(
                 "No download URL found for mrpack".to_string()
             ))?;
+        let file_hash = release_asset_sha256(asset);
 
         let filename = asset["name"].as_str().unwrap_or("");
 
@@ -1280,27 +3415,47 @@ notes=Created by Minecraft Installer
             download_url: download_url.to_string(),
             server_ip: "play.naha.com".to_string(),
             server_port: 25565,
+            servers: Vec::new(),
+            file_hash,
         };
+        modpack_info.validate()?;
 
         info!("✓ Fetched modpack info: {} v{}", modpack_info.server_name, modpack_info.version);
         Ok(modpack_info)
     }
 
     /// Download and install modpack from NAHA API
+    ///
+    /// Re-running after an interrupted install reuses the launcher instance it created unless `force` is set.
     pub async fn download_and_install_from_api(
         &self,
         modpack_type: &str,
         target_launcher: Option<&str>,
         create_instance: bool,
         custom_path: Option<&Path>,
+        instance_name: Option<&str>,
+        force: bool,
     ) -> Result<()> {
         // Fetch modpack info from API
         let modpack_info = self.fetch_modpack_info(modpack_type).await?;
+        self.install_from_modpack_info(&modpack_info, target_launcher, create_instance, custom_path, instance_name, force).await
+    }
 
+    /// Download the mrpack `modpack_info` points at and install it, creating a launcher instance if asked
+    pub async fn install_from_modpack_info(
+        &self,
+        modpack_info: &NahaModpackInfo,
+        target_launcher: Option<&str>,
+        create_instance: bool,
+        custom_path: Option<&Path>,
+        instance_name: Option<&str>,
+        force: bool,
+    ) -> Result<()> {
+        let modpack_type = modpack_info.server_type.as_str();
         info!("Downloading {} modpack from: {}", modpack_info.server_name, modpack_info.download_url);
 
         // Download the mrpack file
-        let client = reqwest::Client::new();
+        let client = self.http.clone();
         let response = client.get(&modpack_info.download_url).send().await
             .map_err(|e| MinecraftInstallerError::InstallationFailed(
                 format!("Failed to download modpack: {}", e)
@@ -1317,40 +3472,74 @@ notes=Created by Minecraft Installer
                 format!("Failed to read download data: {}", e)
             ))?;
 
-        // Create temporary file for the mrpack
-        let temp_mrpack_path = std::env::temp_dir().join(format!("naha-{}-{}.mrpack", modpack_type, modpack_info.version));
+        // Catch a truncated or tampered download before anything is installed from it
+        if let Some(expected) = &modpack_info.file_hash {
+            use sha2::{Digest, Sha256};
+            let actual = hex::encode(Sha256::digest(&bytes));
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(MinecraftInstallerError::HashMismatch {
+                    path: PathBuf::from(&modpack_info.latest_mrpack),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+            info!("✓ Verified modpack SHA-256");
+        }
+
+        // Per-run temp paths, so an interrupted earlier run can't leave stale files in this one
+        let run_id = Uuid::new_v4().simple().to_string();
+        let temp_mrpack_path = std::env::temp_dir().join(format!("naha-{}-{}-{}.mrpack", modpack_type, modpack_info.version, run_id));
         fs::write(&temp_mrpack_path, bytes).await?;
         info!("✓ Downloaded modpack to: {}", temp_mrpack_path.display());
 
         // Install the mrpack
-        let temp_instance_dir = std::env::temp_dir().join(format!("temp-{}-instance", modpack_type));
+        let temp_instance_dir = std::env::temp_dir().join(format!("temp-{}-instance-{}", modpack_type, run_id));
+
+        if create_instance {
+            if let Err(e) = self.check_space_for_mrpack(&temp_mrpack_path, target_launcher, custom_path).await {
+                let _ = fs::remove_file(&temp_mrpack_path).await;
+                return Err(e);
+            }
+        }
 
+        let mut step_failed = false;
         match self.install_mrpack(&temp_mrpack_path, &temp_instance_dir, "temp-instance").await {
             Ok((minecraft_version, mod_loader)) => {
                 info!("✓ Modpack installed successfully!");
 
                 if create_instance {
-                    // Generate proper instance name based on modpack type
-                    let instance_name = match modpack_type {
-                        "neoforge" => "NAHA-NeoForge".to_string(),
-                        "fabric" => "NAHA-Fabric".to_string(),
-                        _ => format!("NAHA-{}", modpack_type),
+                    let instance_name = instance_name
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| naha_instance_name(modpack_type, Some(&modpack_info.version)));
+
+                    let existing = if force {
+                        None
+                    } else {
+                        self.find_existing_instance(&instance_name, target_launcher, custom_path).await
+                    };
+                    let instance = match existing {
+                        Some(instance_path) => {
+                            info!("Reusing existing instance {} (pass --force to recreate it)", instance_name);
+                            Ok(instance_path)
+                        }
+                        None => self.auto_install_instance(
+                            &instance_name,
+                            &minecraft_version,
+                            &mod_loader,
+                            None,
+                            target_launcher,
+                            custom_path
+                        ).await,
                     };
 
-                    match self.auto_install_instance(
-                        &instance_name,
-                        &minecraft_version,
-                        &mod_loader,
-                        None,
-                        target_launcher,
-                        custom_path
-                    ).await {
+                    match instance {
                         Ok(instance_path) => {
                             info!("✓ Instance created at: {}", instance_path.display());
 
                             // Copy files from temp instance to launcher instance
                             if let Err(e) = self.copy_instance_files(&temp_instance_dir, &instance_path).await {
                                 warn!("Failed to copy instance files: {}", e);
+                                step_failed = true;
                             } else {
                                 info!("✓ Files copied to launcher instance");
                             }
@@ -1361,10 +3550,19 @@ notes=Created by Minecraft Installer
                             } else {
                                 info!("✓ Automodpack configured");
                             }
+
+                            if let Some(jvm_args) = &self.jvm_args {
+                                if let Err(e) = self.apply_jvm_args(&instance_path, jvm_args).await {
+                                    warn!("Failed to apply JVM arguments: {}", e);
+                                } else {
+                                    info!("✓ JVM arguments applied");
+                                }
+                            }
                         }
                         Err(e) => {
                             warn!("Failed to create launcher instance: {}", e);
                             info!("You can still launch Minecraft from your installation directory.");
+                            step_failed = true;
                         }
                     }
                 } else {
@@ -1373,12 +3571,19 @@ notes=Created by Minecraft Installer
                 }
             }
             Err(e) => {
+                warn!("Kept temporary files for inspection: {} and {}", temp_mrpack_path.display(), temp_instance_dir.display());
                 return Err(MinecraftInstallerError::InstallationFailed(
                     format!("Modpack installation failed: {}", e)
                 ));
             }
         }
 
+        if self.keep_temp || step_failed {
+            info!("Kept temporary mrpack: {}", temp_mrpack_path.display());
+            info!("Kept temporary instance: {}", temp_instance_dir.display());
+            return Ok(());
+        }
+
         // Clean up temporary files
         if let Err(e) = fs::remove_file(&temp_mrpack_path).await {
             warn!("Failed to clean up temporary mrpack file: {}", e);
@@ -1390,79 +3595,274 @@ notes=Created by Minecraft Installer
         Ok(())
     }
 
-    /// Set up automodpack configuration with server fingerprint
-    async fn setup_automodpack(&self, instance_path: &Path, modpack_info: &NahaModpackInfo) -> Result<()> {
-        // Determine the base directory for automodpack files
-        let base_dir = if instance_path.join("profile.json").exists() {
-            // AstralRinth and ModrinthApp
-            instance_path.to_path_buf()
-        } else if instance_path.join("mmc-pack.json").exists() {
-            // PrismLauncher
-            instance_path.join(".minecraft")
+    /// Set the JVM arguments a launcher passes when starting this instance.
+    ///
+    /// Writes Prism/MultiMC `JvmArgs`, XMCL `vmOptions`, ATLauncher `javaArguments`,
+    /// AstralRinth/ModrinthApp `extra_launch_args` or the Official profile's `javaArgs`.
+    pub async fn apply_jvm_args(&self, instance_path: &Path, args: &[String]) -> Result<()> {
+        let joined = args.join(" ");
+
+        if instance_path.join("profile.json").exists() {
+            let profile_path = instance_path.join("profile.json");
+            let mut profile: serde_json::Value = serde_json::from_str(&fs::read_to_string(&profile_path).await?)?;
+            profile["extra_launch_args"] = json!(args);
+            fs::write(&profile_path, serde_json::to_string_pretty(&profile)?).await?;
+
+            // The app reads its settings from app.db; profile.json alone is not enough
+            let launcher_path = instance_path.parent().and_then(Path::parent);
+            let profile_name = instance_path.file_name().and_then(|n| n.to_str());
+            if let (Some(launcher_path), Some(profile_name)) = (launcher_path, profile_name) {
+                let db_path = launcher_path.join("app.db");
+                if db_path.exists() {
+                    let conn = Connection::open(&db_path).map_err(|e| MinecraftInstallerError::InstallationFailed(
+                        format!("Failed to open launcher database: {}", e)
+                    ))?;
+                    conn.execute(
+                        "UPDATE profiles SET override_extra_launch_args = ? WHERE path = ?",
+                        rusqlite::params![serde_json::to_string(args)?, profile_name],
+                    ).map_err(|e| MinecraftInstallerError::InstallationFailed(
+                        format!("Failed to update launcher database: {}", e)
+                    ))?;
+                }
+            }
+        } else if instance_path.join("instance.cfg").exists() {
+            let cfg_path = instance_path.join("instance.cfg");
+            let content = fs::read_to_string(&cfg_path).await?;
+            let content = set_ini_values(&content, "General", &[("OverrideJavaArgs", "true"), ("JvmArgs", &joined)]);
+            fs::write(&cfg_path, content).await?;
         } else if instance_path.join("instance.json").exists() {
-            // XMCL
-            instance_path.to_path_buf()
+            let instance_json = instance_path.join("instance.json");
+            let mut instance: serde_json::Value = serde_json::from_str(&fs::read_to_string(&instance_json).await?)?;
+            if instance["launcher"].is_object() {
+                // ATLauncher
+                instance["launcher"]["javaArguments"] = json!(joined);
+            } else {
+                instance["vmOptions"] = json!(args);
+            }
+            fs::write(&instance_json, serde_json::to_string_pretty(&instance)?).await?;
+        } else if let Some((launcher_path, profile_id)) = self.find_official_profile(instance_path).await {
+            let profiles_path = official_profiles_path(&launcher_path);
+            let mut profiles: serde_json::Value = serde_json::from_str(&fs::read_to_string(&profiles_path).await?)?;
+            profiles["profiles"][&profile_id]["javaArgs"] = json!(joined);
+            fs::write(&profiles_path, serde_json::to_string_pretty(&profiles)?).await?;
         } else {
-            // Default
-            instance_path.join(".minecraft")
-        };
+            return Err(MinecraftInstallerError::Validation(format!(
+                "Don't know how to set JVM arguments for {}",
+                instance_path.display()
+            )));
+        }
+
+        info!("Applied {} JVM argument(s) to {}", args.len(), instance_path.display());
+        Ok(())
+    }
+
+    /// Write memory limits, JVM arguments and a Java path into an existing instance
+    ///
+    /// Prism/MultiMC get `[General]` override keys, XMCL `minMemory`/`maxMemory`/`java`, ATLauncher its
+    /// `launcher` settings, AstralRinth/ModrinthApp `memory`/`java_path` (plus their app.db overrides) and
+    /// Official profiles `-Xms`/`-Xmx` in `javaArgs` and `javaDir`.
+    pub async fn apply_instance_options(&self, instance_path: &Path, options: &InstanceOptions) -> Result<()> {
+        if let Some(args) = &options.java_args {
+            self.apply_jvm_args(instance_path, args).await?;
+        }
+
+        let java_path = options.java_path.as_ref().map(|path| path.to_string_lossy().to_string());
+        let (min, max) = (options.min_memory_mb, options.max_memory_mb);
+        if min.is_none() && max.is_none() && java_path.is_none() {
+            return Ok(());
+        }
+
+        if instance_path.join("profile.json").exists() {
+            let profile_path = instance_path.join("profile.json");
+            let mut profile: serde_json::Value = serde_json::from_str(&fs::read_to_string(&profile_path).await?)?;
+            if let Some(max) = max {
+                profile["memory"] = json!({ "maximum": max });
+            }
+            if min.is_some() {
+                debug!("{} has no minimum memory setting", instance_path.display());
+            }
+            if let Some(java_path) = &java_path {
+                profile["java_path"] = json!(java_path);
+            }
+            fs::write(&profile_path, serde_json::to_string_pretty(&profile)?).await?;
+
+            let launcher_path = instance_path.parent().and_then(Path::parent);
+            let profile_name = instance_path.file_name().and_then(|n| n.to_str());
+            if let (Some(launcher_path), Some(profile_name)) = (launcher_path, profile_name) {
+                let db_path = launcher_path.join("app.db");
+                if db_path.exists() {
+                    let conn = Connection::open(&db_path).map_err(|e| MinecraftInstallerError::InstallationFailed(
+                        format!("Failed to open launcher database: {}", e)
+                    ))?;
+                    // Older app.db schemas lack these columns; profile.json still carries the values
+                    if let Some(max) = max {
+                        if let Err(e) = conn.execute(
+                            "UPDATE profiles SET override_mc_memory_max = ? WHERE path = ?",
+                            rusqlite::params![max, profile_name],
+                        ) {
+                            debug!("Could not set memory for {}: {}", profile_name, e);
+                        }
+                    }
+                    if let Some(java_path) = &java_path {
+                        if let Err(e) = conn.execute(
+                            "UPDATE profiles SET override_java_path = ? WHERE path = ?",
+                            rusqlite::params![java_path, profile_name],
+                        ) {
+                            debug!("Could not set Java path for {}: {}", profile_name, e);
+                        }
+                    }
+                }
+            }
+        } else if instance_path.join("instance.cfg").exists() {
+            let min = min.map(|mb| mb.to_string());
+            let max = max.map(|mb| mb.to_string());
+            let mut values = Vec::new();
+            if min.is_some() || max.is_some() {
+                values.push(("OverrideMemory", "true"));
+            }
+            if let Some(min) = &min {
+                values.push(("MinMemAlloc", min.as_str()));
+            }
+            if let Some(max) = &max {
+                values.push(("MaxMemAlloc", max.as_str()));
+            }
+            if let Some(java_path) = &java_path {
+                values.push(("OverrideJavaLocation", "true"));
+                values.push(("JavaPath", java_path.as_str()));
+            }
+
+            let cfg_path = instance_path.join("instance.cfg");
+            let content = fs::read_to_string(&cfg_path).await?;
+            fs::write(&cfg_path, set_ini_values(&content, "General", &values)).await?;
+        } else if instance_path.join("instance.json").exists() {
+            let instance_json = instance_path.join("instance.json");
+            let mut instance: serde_json::Value = serde_json::from_str(&fs::read_to_string(&instance_json).await?)?;
+            if instance["launcher"].is_object() {
+                // ATLauncher
+                let launcher = &mut instance["launcher"];
+                if let Some(min) = min {
+                    launcher["initialMemory"] = json!(min);
+                }
+                if let Some(max) = max {
+                    launcher["maximumMemory"] = json!(max);
+                }
+                if let Some(java_path) = &java_path {
+                    launcher["javaPath"] = json!(java_path);
+                }
+            } else {
+                if let Some(min) = min {
+                    instance["minMemory"] = json!(min);
+                }
+                if let Some(max) = max {
+                    instance["maxMemory"] = json!(max);
+                }
+                if min.is_some() || max.is_some() {
+                    instance["assignMemory"] = json!(true);
+                }
+                if let Some(java_path) = &java_path {
+                    instance["java"] = json!(java_path);
+                }
+            }
+            fs::write(&instance_json, serde_json::to_string_pretty(&instance)?).await?;
+        } else if let Some((launcher_path, profile_id)) = self.find_official_profile(instance_path).await {
+            let profiles_path = official_profiles_path(&launcher_path);
+            let mut profiles: serde_json::Value = serde_json::from_str(&fs::read_to_string(&profiles_path).await?)?;
+            let profile = &mut profiles["profiles"][&profile_id];
+
+            // Memory goes in front of any other arguments, replacing earlier limits
+            let mut args: Vec<String> = min.map(|mb| format!("-Xms{}M", mb)).into_iter()
+                .chain(max.map(|mb| format!("-Xmx{}M", mb)))
+                .collect();
+            args.extend(
+                profile["javaArgs"].as_str().unwrap_or_default()
+                    .split_whitespace()
+                    .filter(|arg| {
+                        let replaced = (min.is_some() && arg.starts_with("-Xms")) || (max.is_some() && arg.starts_with("-Xmx"));
+                        !replaced
+                    })
+                    .map(str::to_string),
+            );
+            profile["javaArgs"] = json!(args.join(" "));
+            if let Some(java_path) = &java_path {
+                profile["javaDir"] = json!(java_path);
+            }
+            fs::write(&profiles_path, serde_json::to_string_pretty(&profiles)?).await?;
+        } else {
+            return Err(MinecraftInstallerError::Validation(format!(
+                "Don't know how to set instance options for {}",
+                instance_path.display()
+            )));
+        }
+
+        info!("Applied instance options to {}", instance_path.display());
+        Ok(())
+    }
+
+    /// Set up automodpack known hosts and the multiplayer server list for every pack server
+    async fn setup_automodpack(&self, instance_path: &Path, modpack_info: &NahaModpackInfo) -> Result<()> {
+        let base_dir = minecraft_base_dir(instance_path);
 
         let automodpack_dir = base_dir.join("automodpack");
         let automodpack_private_dir = automodpack_dir.join(".private");
 
         fs::create_dir_all(&automodpack_private_dir).await?;
 
-        // Create automodpack-known-hosts.json with server fingerprint (matching real format)
-        // Use server hostname (e.g., "play.naha.com") as the key, not the IP
-        let server_host = if modpack_info.server_ip.contains(".") && !modpack_info.server_ip.chars().all(|c| c.is_numeric() || c == '.') {
-            modpack_info.server_ip.clone()
-        } else {
-            "play.naha.com".to_string()
-        };
-        
-        let known_hosts = json!({
-            "hosts": {
-                server_host: modpack_info.fingerprint.clone()
-            }
-        });
-
+        // Create automodpack-known-hosts.json with server fingerprints (matching real format)
+        let servers = modpack_info.all_servers();
         fs::write(
             automodpack_private_dir.join("automodpack-known-hosts.json"),
-            serde_json::to_string_pretty(&known_hosts)?
+            serde_json::to_string_pretty(&automodpack_known_hosts(&servers))?
         ).await?;
 
         // Note: automodpack-client.json and automodpack-server.json are created automatically by automodpack
 
-        info!("✓ Automodpack configured for server {}:{}", modpack_info.server_ip, modpack_info.server_port);
+        let added = add_servers_to_list(&base_dir.join("servers.dat"), &servers)?;
+        debug!("Added {} server(s) to servers.dat", added);
+
+        for server in &servers {
+            info!("✓ Automodpack configured for server {}:{}", server.server_ip, server.server_port);
+        }
         Ok(())
     }
 
+    /// Write `automodpack/automodpack-server.json` for the server hosting `modpack_info`'s pack
+    ///
+    /// Clients are synced every pack file the server needs that isn't marked unsupported on the client.
+    /// Returns the path of the written config.
+    pub async fn generate_automodpack_server_config(
+        &self,
+        server_dir: &Path,
+        modpack_info: &NahaModpackInfo,
+        mrpack_index: &MrpackIndex,
+    ) -> Result<PathBuf> {
+        let synced_files: Vec<String> = mrpack_index.files.iter()
+            .filter(|file| file.env.as_ref().is_none_or(|env| env.server == "required"))
+            .filter(|file| PackSide::Client.supports(file.env.as_ref()))
+            .map(|file| format!("/{}", file.path.trim_start_matches('/')))
+            .collect();
+
+        let config = json!({
+            "DO_NOT_CHANGE_IT": 2,
+            "modpackName": modpack_info.server_name,
+            "modpackHost": true,
+            "generateModpackOnStart": true,
+            "syncedFiles": synced_files,
+            "hostIp": modpack_info.server_ip,
+            "hostPort": modpack_info.server_port,
+        });
+
+        let automodpack_dir = server_dir.join("automodpack");
+        fs::create_dir_all(&automodpack_dir).await?;
+        let config_path = automodpack_dir.join("automodpack-server.json");
+        fs::write(&config_path, serde_json::to_string_pretty(&config)?).await?;
+
+        info!("✓ Automodpack server config written with {} synced file(s)", synced_files.len());
+        Ok(config_path)
+    }
+
     /// Copy files from temporary instance to launcher instance (moved from main.rs)
     pub async fn copy_instance_files(&self, temp_dir: &Path, target_dir: &Path) -> Result<()> {
-        // Detect launcher type based on directory structure
-        let is_astralrinth_or_modrinth = target_dir.join("profile.json").exists();
-        let is_prism = target_dir.join("mmc-pack.json").exists();
-        let is_xmcl = target_dir.join("instance.json").exists();
-        let is_other_launcher = target_dir.join("launcher_profiles.json").exists() &&
-                               !is_astralrinth_or_modrinth && !is_prism && !is_xmcl;
-
-        // Determine the base directory for copying files
-        let base_dir = if is_astralrinth_or_modrinth {
-            // AstralRinth and ModrinthApp expect files directly in the profile directory
-            target_dir.to_path_buf()
-        } else if is_prism {
-            // PrismLauncher expects files in .minecraft subdirectory
-            target_dir.join(".minecraft")
-        } else if is_xmcl {
-            // XMCL expects files directly in the instance directory
-            target_dir.to_path_buf()
-        } else if is_other_launcher {
-            // Other/Custom launcher expects files directly in the target directory
-            target_dir.to_path_buf()
-        } else {
-            // Default to .minecraft for other launchers
-            target_dir.join(".minecraft")
-        };
+        let base_dir = minecraft_base_dir(target_dir);
 
         // Copy mods directory
         let temp_mods = temp_dir.join("mods");
@@ -1529,29 +3929,473 @@ notes=Created by Minecraft Installer
             info!("✓ Copied servers.dat file");
         }
 
+        let temp_manifest = temp_dir.join(INSTALL_MANIFEST_FILE);
+        if temp_manifest.exists() {
+            fs::copy(&temp_manifest, base_dir.join(INSTALL_MANIFEST_FILE)).await?;
+        }
+
         Ok(())
     }
 
-    /// Recursively copy directory contents
-    fn copy_dir_recursive<'a>(&'a self, src: &'a Path, dst: &'a Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + 'a>> {
-        Box::pin(async move {
-            let mut entries = fs::read_dir(src).await?;
+    /// Locate an instance's mods folder under its `minecraft_base_dir`
+    pub fn find_mods_directory(&self, instance_path: &Path) -> PathBuf {
+        minecraft_base_dir(instance_path).join("mods")
+    }
+
+    /// Enable or disable a mod by renaming `<file>.jar` to/from `<file>.jar.disabled`
+    ///
+    /// `filename` may be given with or without the `.disabled` suffix. Returns the mod's new path;
+    /// a mod already in the requested state is left alone.
+    pub async fn set_mod_enabled(&self, instance_path: &Path, filename: &str, enabled: bool) -> Result<PathBuf> {
+        let mods_dir = self.find_mods_directory(instance_path);
+        let jar_name = filename.strip_suffix(".disabled").unwrap_or(filename);
+        let enabled_path = mods_dir.join(jar_name);
+        let disabled_path = mods_dir.join(format!("{}.disabled", jar_name));
+
+        let (from, to) = if enabled {
+            (disabled_path, enabled_path)
+        } else {
+            (enabled_path, disabled_path)
+        };
 
-            while let Some(entry) = entries.next_entry().await? {
-                let entry_path = entry.path();
-                let file_name = entry_path.file_name().unwrap();
-                let dst_path = dst.join(file_name);
+        if to.exists() {
+            debug!("{} is already {}", jar_name, if enabled { "enabled" } else { "disabled" });
+            return Ok(to);
+        }
+        if !from.exists() {
+            return Err(MinecraftInstallerError::FileSystem(format!(
+                "Mod {} not found in {}", jar_name, mods_dir.display()
+            )));
+        }
+
+        fs::rename(&from, &to).await?;
+        info!("{} {}", if enabled { "Enabled" } else { "Disabled" }, jar_name);
+        Ok(to)
+    }
+
+    /// Find the Official launcher profile whose `gameDir` is `game_dir`, returning the launcher path
+    /// and profile id. Checks the nearest ancestor with a profiles file, then every detected Official
+    /// launcher; those are detected once per manager.
+    pub async fn find_official_profile(&self, game_dir: &Path) -> Option<(PathBuf, String)> {
+        let target = std::fs::canonicalize(game_dir).unwrap_or_else(|_| game_dir.to_path_buf());
+
+        let nearest = game_dir.ancestors().skip(1).find(|dir| official_profiles_path(dir).exists());
+        let detected = self.official_launchers
+            .get_or_init(|| async {
+                self.detect_launchers().await
+                    .into_iter()
+                    .filter(|(launcher_type, _)| *launcher_type == LauncherType::Official)
+                    .map(|(_, path)| path)
+                    .collect()
+            })
+            .await;
+
+        for launcher_path in nearest.into_iter().chain(detected.iter().map(PathBuf::as_path)) {
+            let Ok(content) = fs::read_to_string(official_profiles_path(launcher_path)).await else {
+                continue;
+            };
+            let Ok(profiles_json) = serde_json::from_str::<serde_json::Value>(&content) else {
+                continue;
+            };
+            let Some(profiles) = profiles_json["profiles"].as_object() else {
+                continue;
+            };
+
+            for (profile_id, profile) in profiles {
+                let Some(profile_dir) = profile["gameDir"].as_str().map(PathBuf::from) else {
+                    continue;
+                };
+                if std::fs::canonicalize(&profile_dir).unwrap_or(profile_dir) == target {
+                    return Some((launcher_path.to_path_buf(), profile_id.clone()));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Directory a launcher keeps its instances in, or `None` for launchers without one
+    pub fn instances_dir(&self, launcher_type: &LauncherType, launcher_path: &Path) -> Option<PathBuf> {
+        match launcher_type {
+            LauncherType::Prism | LauncherType::PrismCracked => Some(PrismConfig::load(launcher_path).instance_dir),
+            LauncherType::MultiMC => Some(PrismConfig::load_file(launcher_path, "multimc.cfg").instance_dir),
+            LauncherType::XMCL | LauncherType::ATLauncher | LauncherType::GDLauncher => Some(launcher_path.join("instances")),
+            LauncherType::AstralRinth | LauncherType::ModrinthApp => Some(launcher_path.join("profiles")),
+            _ => None,
+        }
+    }
+
+    /// Java paths the detected launchers' instances and Official profiles are configured to launch with
+    pub async fn configured_java_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        for (launcher_type, launcher_path) in self.detect_launchers().await {
+            if launcher_type == LauncherType::Official {
+                let Ok(content) = fs::read_to_string(official_profiles_path(&launcher_path)).await else { continue };
+                let profiles: serde_json::Value = serde_json::from_str(&content).unwrap_or_default();
+                paths.extend(
+                    profiles["profiles"].as_object().into_iter().flatten()
+                        .filter_map(|(_, profile)| profile["javaDir"].as_str())
+                        .filter(|dir| !dir.is_empty())
+                        .map(PathBuf::from),
+                );
+            } else if let Some(instances_dir) = self.instances_dir(&launcher_type, &launcher_path) {
+                let Ok(entries) = std::fs::read_dir(instances_dir) else { continue };
+                paths.extend(entries.flatten().filter_map(|entry| configured_java_path(&entry.path())));
+            }
+        }
+        paths
+    }
+
+    /// Find an instance by its display name, or failing that by its directory name
+    pub fn find_instance(&self, instances_dir: &Path, name: &str) -> Option<PathBuf> {
+        let by_name = std::fs::read_dir(instances_dir).ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.is_dir() && read_instance_name(path).as_deref() == Some(name));
+        by_name.or_else(|| Some(instances_dir.join(name)).filter(|path| path.is_dir()))
+    }
+
+    /// Duplicate an instance under a new name, without downloading anything again
+    ///
+    /// The copy gets its own sanitized directory and its name is rewritten in `instance.cfg`,
+    /// `instance.json` or `profile.json`; AstralRinth and Modrinth App also get a new `profiles` row.
+    pub async fn clone_instance(&self, launcher_path: &Path, source_name: &str, new_name: &str) -> Result<PathBuf> {
+        let launcher_type = self.detect_launcher_type(launcher_path).await?;
+        let instances_dir = self.instances_dir(&launcher_type, launcher_path)
+            .ok_or_else(|| MinecraftInstallerError::UnsupportedLauncher(launcher_type.clone()))?;
+        let source = self.find_instance(&instances_dir, source_name).ok_or_else(|| {
+            MinecraftInstallerError::InstallationFailed(format!("No instance named {} in {}", source_name, instances_dir.display()))
+        })?;
+
+        let db_backed = matches!(launcher_type, LauncherType::AstralRinth | LauncherType::ModrinthApp);
+        let dir_name = if db_backed { new_name.to_lowercase().replace(' ', "-") } else { new_name.to_string() };
+        let target = unique_instance_dir(&instances_dir, &dir_name, new_name);
+        if target.exists() && read_instance_name(&target).as_deref() == Some(new_name) {
+            return Err(MinecraftInstallerError::InstallationFailed(format!(
+                "An instance named {} already exists at {}", new_name, target.display()
+            )));
+        }
+
+        fs::create_dir_all(&target).await?;
+        self.copy_dir_recursive(&source, &target).await?;
+        let target_dir_name = target.file_name().unwrap_or_default().to_string_lossy().to_string();
+        self.rename_instance_metadata(&target, new_name, &target_dir_name, true).await?;
+
+        if db_backed {
+            let source_dir_name = source.file_name().unwrap_or_default().to_string_lossy().to_string();
+            self.clone_app_db_profile(&launcher_type, launcher_path, &target, &source_dir_name, &target_dir_name, new_name).await;
+        }
+
+        info!("Cloned {} into {}", source_name, target.display());
+        Ok(target)
+    }
+
+    /// Rename an instance in place: its sanitized directory, the name in its `instance.cfg`,
+    /// `instance.json` or `profile.json`, the AstralRinth/Modrinth App `profiles` row, or the
+    /// Official launcher's `launcher_profiles.json` entry. Returns the instance's new path.
+    pub async fn rename_instance(&self, launcher_path: &Path, old_name: &str, new_name: &str) -> Result<PathBuf> {
+        let launcher_type = self.detect_launcher_type(launcher_path).await?;
+        if launcher_type == LauncherType::Official {
+            return self.rename_official_instance(launcher_path, old_name, new_name).await;
+        }
+
+        let instances_dir = self.instances_dir(&launcher_type, launcher_path)
+            .ok_or_else(|| MinecraftInstallerError::UnsupportedLauncher(launcher_type.clone()))?;
+        let source = self.find_instance(&instances_dir, old_name).ok_or_else(|| {
+            MinecraftInstallerError::InstallationFailed(format!("No instance named {} in {}", old_name, instances_dir.display()))
+        })?;
+
+        let db_backed = matches!(launcher_type, LauncherType::AstralRinth | LauncherType::ModrinthApp);
+        let dir_name = if db_backed { new_name.to_lowercase().replace(' ', "-") } else { new_name.to_string() };
+        // A name that sanitizes to the current folder only changes the metadata
+        let target = match instances_dir.join(sanitize_instance_dir_name(&dir_name)) {
+            same if same == source => same,
+            _ => unique_instance_dir(&instances_dir, &dir_name, new_name),
+        };
+        if target != source && target.exists() && read_instance_name(&target).as_deref() == Some(new_name) {
+            return Err(MinecraftInstallerError::InstallationFailed(format!(
+                "An instance named {} already exists at {}", new_name, target.display()
+            )));
+        }
+
+        if target != source {
+            if target.exists() {
+                // unique_instance_dir only hands back existing folders when they are empty
+                fs::remove_dir(&target).await?;
+            }
+            fs::rename(&source, &target).await?;
+        }
+        let source_dir_name = source.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let target_dir_name = target.file_name().unwrap_or_default().to_string_lossy().to_string();
+        self.rename_instance_metadata(&target, new_name, &target_dir_name, false).await?;
+
+        let db_path = launcher_path.join("app.db");
+        if db_backed && db_path.exists() {
+            let updated = Connection::open(&db_path).and_then(|conn| conn.execute(
+                "UPDATE profiles SET path = ?1, name = ?2 WHERE path = ?3",
+                rusqlite::params![target_dir_name, new_name, source_dir_name],
+            ));
+            if let Err(e) = updated {
+                warn!("Failed to rename {} in the {} database: {}", old_name, launcher_type, e);
+            }
+        }
+
+        info!("Renamed {} to {}", old_name, new_name);
+        Ok(target)
+    }
+
+    /// Rename an Official launcher profile created by this installer, moving its game directory along
+    async fn rename_official_instance(&self, launcher_path: &Path, old_name: &str, new_name: &str) -> Result<PathBuf> {
+        let profiles_path = official_profiles_path(&launcher_path);
+        let mut profiles_json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&profiles_path).await?)?;
+        let profiles = profiles_json["profiles"].as_object_mut().ok_or_else(|| {
+            MinecraftInstallerError::InstallationFailed(format!("{} has no profiles", profiles_path.display()))
+        })?;
+
+        let old_id = format!("minecraft-installer-{}", old_name);
+        let new_id = format!("minecraft-installer-{}", new_name);
+        if profiles.contains_key(&new_id) {
+            return Err(MinecraftInstallerError::InstallationFailed(format!(
+                "An instance named {} already exists in {}", new_name, profiles_path.display()
+            )));
+        }
+        let mut profile = profiles.remove(&old_id).ok_or_else(|| {
+            MinecraftInstallerError::InstallationFailed(format!("No instance named {} in {}", old_name, profiles_path.display()))
+        })?;
+
+        let game_dir = match profile["gameDir"].as_str().map(PathBuf::from) {
+            Some(source) if source.is_dir() => {
+                let target = unique_instance_dir(&launcher_path.join("instances"), new_name, new_name);
+                if target.exists() {
+                    fs::remove_dir(&target).await?;
+                }
+                fs::rename(&source, &target).await?;
+                target
+            }
+            Some(source) => source,
+            None => launcher_path.to_path_buf(),
+        };
+        profile["name"] = new_name.into();
+        if profile.get("gameDir").is_some() {
+            profile["gameDir"] = game_dir.to_string_lossy().into();
+        }
+        profiles.insert(new_id, profile);
+
+        fs::write(&profiles_path, serde_json::to_string_pretty(&profiles_json)?).await?;
+        info!("Renamed {} to {}", old_name, new_name);
+        Ok(game_dir)
+    }
+
+    /// Recreate an instance in another launcher, carrying over its version, loader and game files
+    ///
+    /// The target instance is created with the regular creators, then mods, config, saves,
+    /// resourcepacks and shaderpacks are copied over. Returns the new instance's path.
+    pub async fn migrate_instance(
+        &self,
+        source_launcher_path: &Path,
+        source_name: &str,
+        target_launcher_path: &Path,
+    ) -> Result<PathBuf> {
+        let source_type = self.detect_launcher_type(source_launcher_path).await?;
+        let instances_dir = self.instances_dir(&source_type, source_launcher_path)
+            .ok_or_else(|| MinecraftInstallerError::UnsupportedLauncher(source_type.clone()))?;
+        let source = self.find_instance(&instances_dir, source_name).ok_or_else(|| {
+            MinecraftInstallerError::InstallationFailed(format!("No instance named {} in {}", source_name, instances_dir.display()))
+        })?;
+
+        let instance = InstanceScanner::new(self).analyze_instance(&source).await?
+            .filter(|i| i.minecraft_version != "Unknown")
+            .ok_or_else(|| MinecraftInstallerError::InstallationFailed(format!(
+                "Could not detect the Minecraft version of {}",
+                source.display()
+            )))?;
+        let instance_name = read_instance_name(&source).unwrap_or_else(|| source_name.to_string());
+        let mod_loader = match instance.mod_loader.to_lowercase().as_str() {
+            "unknown" => "vanilla".to_string(),
+            loader => loader.to_string(),
+        };
+
+        let target_type = self.detect_launcher_type(target_launcher_path).await?;
+        info!("Migrating {} from {} to {}", instance_name, source_type, target_type);
+
+        let target = self.create_instance(
+            target_launcher_path,
+            &instance_name,
+            &instance.minecraft_version,
+            &mod_loader,
+            instance.mod_loader_version.as_deref(),
+        ).await?;
+        self.copy_instance_files(&minecraft_base_dir(&source), &target).await?;
+
+        info!("✓ Migrated {} to {}", instance_name, target.display());
+        Ok(target)
+    }
+
+    /// Point an instance's metadata at its new name (and, for profile.json, its new folder); copies pass
+    /// `fresh_id` so Modrinth App doesn't see two profiles with one uuid
+    async fn rename_instance_metadata(&self, instance_path: &Path, new_name: &str, dir_name: &str, fresh_id: bool) -> Result<()> {
+        let cfg_path = instance_path.join("instance.cfg");
+        if cfg_path.exists() {
+            let cfg = fs::read_to_string(&cfg_path).await?;
+            fs::write(&cfg_path, set_ini_values(&cfg, "General", &[("name", new_name)])).await?;
+        }
+
+        for file in ["instance.json", "profile.json"] {
+            let path = instance_path.join(file);
+            let Ok(content) = fs::read_to_string(&path).await else {
+                continue;
+            };
+            let mut json: serde_json::Value = serde_json::from_str(&content)?;
+            if json.get("name").is_some() {
+                json["name"] = new_name.into();
+            }
+            if json["launcher"].get("name").is_some() {
+                json["launcher"]["name"] = new_name.into();
+            }
+            if file == "profile.json" && json.get("path").is_some() {
+                json["path"] = dir_name.into();
+            }
+            // Newer Modrinth App profiles repeat the name under metadata and need an id of their own
+            if json["metadata"].get("name").is_some() {
+                json["metadata"]["name"] = new_name.into();
+            }
+            if fresh_id && file == "profile.json" && json.get("uuid").is_some() {
+                json["uuid"] = Uuid::new_v4().to_string().into();
+            }
+            fs::write(&path, serde_json::to_string_pretty(&json)?).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Insert the app.db row for a profile folder the launcher doesn't list, rebuilt from its profile.json
+    ///
+    /// For profiles left behind by a failed database injection (locked app.db, schema change).
+    /// Returns false when the profile already has a row.
+    pub async fn repair_profile_db(&self, launcher_path: &Path, profile_name: &str) -> Result<bool> {
+        let launcher_type = self.detect_launcher_type(launcher_path).await?;
+        if !matches!(launcher_type, LauncherType::AstralRinth | LauncherType::ModrinthApp) {
+            return Err(MinecraftInstallerError::UnsupportedLauncher(launcher_type));
+        }
+
+        let profile_path = launcher_path.join("profiles").join(profile_name).join("profile.json");
+        let profile: serde_json::Value = match fs::read_to_string(&profile_path).await {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(e) => {
+                return Err(MinecraftInstallerError::InstallationFailed(format!(
+                    "Cannot repair {}: {}: {}", profile_name, profile_path.display(), e
+                )));
+            }
+        };
 
-                if entry_path.is_dir() {
-                    fs::create_dir_all(&dst_path).await?;
-                    self.copy_dir_recursive(&entry_path, &dst_path).await?;
+        let conn = Connection::open(launcher_path.join("app.db")).map_err(|e| {
+            MinecraftInstallerError::InstallationFailed(format!("Failed to open {} database: {}", launcher_type, e))
+        })?;
+        if conn.query_row("SELECT 1 FROM profiles WHERE path = ?", [profile_name], |_| Ok(())).is_ok() {
+            debug!("{} already has a database row", profile_name);
+            return Ok(false);
+        }
+        drop(conn);
+
+        let name = profile["name"].as_str().unwrap_or(profile_name);
+        let minecraft_version = profile["game_version"].as_str().unwrap_or_default();
+        let mod_loader = profile["loader"].as_str().unwrap_or("vanilla");
+        let icon_path = profile["icon_path"].as_str();
+        if launcher_type == LauncherType::ModrinthApp {
+            self.inject_modrinth_app_profile(launcher_path, profile_name, name, minecraft_version, mod_loader, icon_path).await?;
+        } else {
+            self.inject_astralrinth_profile(launcher_path, profile_name, name, minecraft_version, mod_loader, icon_path).await?;
+        }
+
+        info!("Restored the database row for {}", profile_name);
+        Ok(true)
+    }
+
+    /// Run `repair_profile_db` on every folder under `profiles/` with a profile.json, returning the repaired ones
+    pub async fn repair_profile_dbs(&self, launcher_path: &Path) -> Result<Vec<String>> {
+        let mut profile_names = Vec::new();
+        let mut entries = fs::read_dir(launcher_path.join("profiles")).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.path().join("profile.json").is_file() {
+                profile_names.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        profile_names.sort();
+
+        let mut repaired = Vec::new();
+        for profile_name in profile_names {
+            if self.repair_profile_db(launcher_path, &profile_name).await? {
+                repaired.push(profile_name);
+            }
+        }
+        Ok(repaired)
+    }
+
+    /// Register a cloned profile in app.db, copying the source row or building one from profile.json
+    async fn clone_app_db_profile(
+        &self,
+        launcher_type: &LauncherType,
+        launcher_path: &Path,
+        profile_dir: &Path,
+        source_dir_name: &str,
+        profile_name: &str,
+        new_name: &str,
+    ) {
+        let db_path = launcher_path.join("app.db");
+        if !db_path.exists() {
+            warn!("{} database not found; the clone will appear after the launcher rescans its profiles", launcher_type);
+            return;
+        }
+
+        let copied = Connection::open(&db_path)
+            .and_then(|conn| clone_db_profile(&conn, source_dir_name, profile_name, new_name));
+        match copied {
+            Ok(true) => return,
+            Ok(false) => debug!("No database row for {}, registering the clone from profile.json", source_dir_name),
+            Err(e) => debug!("Could not copy the database row for {}: {}", source_dir_name, e),
+        }
+
+        let profile: serde_json::Value = std::fs::read_to_string(profile_dir.join("profile.json")).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let minecraft_version = profile["game_version"].as_str().unwrap_or_default();
+        let mod_loader = profile["loader"].as_str().unwrap_or("vanilla");
+        let icon_path = profile["icon_path"].as_str();
+        let injected = if *launcher_type == LauncherType::ModrinthApp {
+            self.inject_modrinth_app_profile(launcher_path, profile_name, new_name, minecraft_version, mod_loader, icon_path).await
+        } else {
+            self.inject_astralrinth_profile(launcher_path, profile_name, new_name, minecraft_version, mod_loader, icon_path).await
+        };
+        if let Err(e) = injected {
+            warn!("Failed to register the clone in the {} database: {}", launcher_type, e);
+        }
+    }
+
+    /// Recursively copy directory contents: the directory tree is created first, then files are copied a few at a time
+    async fn copy_dir_recursive(&self, src: &Path, dst: &Path) -> Result<()> {
+        let mut files = Vec::new();
+        let mut pending = vec![(src.to_path_buf(), dst.to_path_buf())];
+        while let Some((src_dir, dst_dir)) = pending.pop() {
+            fs::create_dir_all(&dst_dir).await?;
+            let mut entries = fs::read_dir(&src_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let dst_path = dst_dir.join(entry.file_name());
+                if entry.path().is_dir() {
+                    pending.push((entry.path(), dst_path));
                 } else {
-                    fs::copy(&entry_path, &dst_path).await?;
+                    files.push((entry.path(), dst_path));
                 }
             }
+        }
 
-            Ok(())
-        })
+        // The first failure stops the copy; copies still in flight are dropped with the stream
+        let mut copies = futures::stream::iter(files)
+            .map(|(from, to)| async move { fs::copy(&from, &to).await })
+            .buffer_unordered(COPY_CONCURRENCY);
+        while let Some(copied) = copies.next().await {
+            copied?;
+        }
+        Ok(())
     }
 
     /// Create instance for Other/Custom launcher (custom path)
@@ -1623,7 +4467,71 @@ notes=Created by Minecraft Installer
         ).await?;
 
         // Create version-specific files based on mod loader
+        let (version_id, version_json) = self.loader_version_json(minecraft_version, mod_loader, mod_loader_version).await;
+        Self::write_version_json(&minecraft_dir, &version_id, &version_json).await?;
+
+        info!("Created Other/Custom launcher instance: {} at {}", instance_name, minecraft_dir.display());
+        Ok(minecraft_dir)
+    }
+
+    /// The version id an Official profile launches for `mod_loader`
+    ///
+    /// The launcher resolves vanilla versions itself. Fabric and Quilt get the profile JSON their meta API
+    /// serves written to `versions/`; it inherits `downloads` and `assetIndex` from the vanilla version.
+    /// Forge and NeoForge have to be installed by their installer first, which patches the client.
+    async fn official_version(
+        &self,
+        launcher_path: &Path,
+        minecraft_version: &str,
+        mod_loader: &str,
+        mod_loader_version: Option<&str>,
+    ) -> Result<String> {
+        let installed = |version_id: String, loader: &str| {
+            if launcher_path.join("versions").join(&version_id).join(format!("{}.json", version_id)).is_file() {
+                Ok(version_id)
+            } else {
+                Err(MinecraftInstallerError::InvalidLoader(format!(
+                    "{} is not installed in {}; run the {} installer for it first", version_id, launcher_path.display(), loader
+                )))
+            }
+        };
+
         match mod_loader {
+            "vanilla" | "" => Ok(minecraft_version.to_string()),
+            "fabric" | "quilt" => {
+                let resolver = if mod_loader == "quilt" { &self.quilt } else { &self.fabric };
+                let loader_version = resolver.resolve(mod_loader_version, minecraft_version).await;
+                let profile = resolver.profile_json(&loader_version, minecraft_version).await?;
+                let version_id = profile["id"].as_str().map(str::to_string).ok_or_else(|| MinecraftInstallerError::InvalidLoader(format!(
+                    "{} profile for {} {} has no id", mod_loader, minecraft_version, loader_version
+                )))?;
+                Self::write_version_json(launcher_path, &version_id, &profile).await?;
+                Ok(version_id)
+            }
+            "forge" => {
+                let forge_version = self.forge.resolve(mod_loader_version, minecraft_version).await?;
+                installed(format!("{}-forge-{}", minecraft_version, forge_version), "Forge")
+            }
+            "neoforge" => match mod_loader_version.filter(|v| !matches!(*v, "" | "stable" | "latest" | "recommended")) {
+                Some(neoforge_version) => installed(format!("neoforge-{}", neoforge_version), "NeoForge"),
+                None => Err(MinecraftInstallerError::InvalidLoader(
+                    "NeoForge needs a concrete --loader-version for the Official launcher".to_string()
+                )),
+            },
+            other => Err(MinecraftInstallerError::InvalidLoader(format!(
+                "{} cannot be set up for the Official launcher", other
+            ))),
+        }
+    }
+
+    /// The version id and JSON a launcher needs for `mod_loader`, inheriting from the vanilla version for loaders
+    async fn loader_version_json(
+        &self,
+        minecraft_version: &str,
+        mod_loader: &str,
+        mod_loader_version: Option<&str>,
+    ) -> (String, serde_json::Value) {
+        let (version_id, version_json) = match mod_loader {
             "neoforge" => {
                 // Create NeoForge version file
                 let neoforge_version = mod_loader_version.unwrap_or("21.1.209");
@@ -1683,16 +4591,11 @@ notes=Created by Minecraft Installer
                     }
                 });
 
-                let versions_dir = minecraft_dir.join("versions").join(&version_id);
-                fs::create_dir_all(&versions_dir).await?;
-                fs::write(
-                    versions_dir.join(format!("{}.json", version_id)),
-                    serde_json::to_string_pretty(&version_json)?
-                ).await?;
+                (version_id, version_json)
             }
             "fabric" => {
                 // Create Fabric version file
-                let fabric_version = mod_loader_version.unwrap_or("0.15.11");
+                let fabric_version = self.fabric.resolve(mod_loader_version, minecraft_version).await;
                 let version_id = format!("{}-fabric-{}", minecraft_version, fabric_version);
 
                 let version_json = json!({
@@ -1723,7 +4626,7 @@ notes=Created by Minecraft Installer
                     },
                     "libraries": [
                         {
-                            "name": "net.fabricmc:fabric-loader:0.15.11",
+                            "name": format!("net.fabricmc:fabric-loader:{}", fabric_version),
                             "url": "https://maven.fabricmc.net/"
                         }
                     ],
@@ -1749,12 +4652,7 @@ notes=Created by Minecraft Installer
                     }
                 });
 
-                let versions_dir = minecraft_dir.join("versions").join(&version_id);
-                fs::create_dir_all(&versions_dir).await?;
-                fs::write(
-                    versions_dir.join(format!("{}.json", version_id)),
-                    serde_json::to_string_pretty(&version_json)?
-                ).await?;
+                (version_id, version_json)
             }
             _ => {
                 // For vanilla or other loaders, just create a basic version file
@@ -1805,16 +4703,20 @@ notes=Created by Minecraft Installer
                     }
                 });
 
-                let versions_dir = minecraft_dir.join("versions").join(minecraft_version);
-                fs::create_dir_all(&versions_dir).await?;
-                fs::write(
-                    versions_dir.join(format!("{}.json", minecraft_version)),
-                    serde_json::to_string_pretty(&version_json)?
-                ).await?;
+                (minecraft_version.to_string(), version_json)
             }
-        }
+        };
+        (version_id, version_json)
+    }
 
-        info!("Created Other/Custom launcher instance: {} at {}", instance_name, minecraft_dir.display());
-        Ok(minecraft_dir)
+    /// Write `versions/<id>/<id>.json` under `root`
+    async fn write_version_json(root: &Path, version_id: &str, version_json: &serde_json::Value) -> Result<()> {
+        let versions_dir = root.join("versions").join(version_id);
+        fs::create_dir_all(&versions_dir).await?;
+        fs::write(
+            versions_dir.join(format!("{}.json", version_id)),
+            serde_json::to_string_pretty(version_json)?
+        ).await?;
+        Ok(())
     }
 }
@@ -1,15 +1,27 @@
 pub mod installer;
+pub mod instance;
+pub mod config;
 pub mod error;
 pub mod directories;
 pub mod download;
+pub mod events;
 pub mod java;
 pub mod launcher_support;
+pub mod loader;
+pub mod lock;
+pub mod logging;
+pub mod manifest;
+pub mod modrinth;
+pub mod fabric;
+pub mod forge;
+pub mod neoforge;
 pub mod updater;
 
 pub use error::{MinecraftInstallerError, Result};
 pub use installer::MinecraftInstaller;
 pub use launcher_support::{LauncherManager, LauncherType};
 pub use directories::DirectoryManager;
+pub use tokio_util::sync::CancellationToken;
 
 
 
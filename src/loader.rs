@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+/// A loader version as shown by `list_loader_versions`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LoaderListing {
+    pub version: String,
+    /// The version installed for "stable"; at most one per list
+    pub recommended: bool,
+}
@@ -0,0 +1,81 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::error::{MinecraftInstallerError, Result};
+
+/// An OS lock on a lockfile holding this process's PID, removed again when dropped
+///
+/// Keeps two installer processes from writing the same install dir at once. The OS drops the lock
+/// when its holder exits, so a lockfile left behind by a crash is simply taken over.
+#[derive(Debug)]
+pub struct InstallLock {
+    path: PathBuf,
+    file: File,
+}
+
+impl InstallLock {
+    /// Lock `path` and write our PID into it, failing if another live process holds it
+    pub fn acquire(path: &Path) -> Result<Self> {
+        loop {
+            let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+            match file.try_lock() {
+                Ok(()) => {}
+                Err(TryLockError::WouldBlock) => {
+                    let mut pid = String::new();
+                    let _ = file.read_to_string(&mut pid);
+                    return Err(MinecraftInstallerError::InstallationFailed(format!(
+                        "Another install is in progress (PID {} holds {}); wait for it to finish",
+                        pid.trim(),
+                        path.display()
+                    )));
+                }
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+
+            // The previous holder may have removed the file between our open and lock
+            if !same_file(&file, path) {
+                continue;
+            }
+
+            file.set_len(0)?;
+            file.rewind()?;
+            write!(file, "{}", std::process::id())?;
+            return Ok(Self { path: path.to_path_buf(), file });
+        }
+    }
+
+    /// Where the lock lives
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        // Removed before unlocking; a process that opened it meanwhile sees it is gone and starts over
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            debug!("Failed to remove install lock {}: {}", self.path.display(), e);
+        }
+        let _ = self.file.unlock();
+    }
+}
+
+/// Whether `path` still names the file behind `file`
+#[cfg(unix)]
+fn same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (file.metadata(), std::fs::metadata(path)) {
+        (Ok(held), Ok(current)) => held.dev() == current.dev() && held.ino() == current.ino(),
+        _ => false,
+    }
+}
+
+/// Whether `path` still names a file; elsewhere a removed lockfile can't be told from its replacement
+#[cfg(not(unix))]
+fn same_file(_file: &File, path: &Path) -> bool {
+    path.exists()
+}
@@ -0,0 +1,37 @@
+use tracing::Subscriber;
+use tracing_subscriber::EnvFilter;
+
+use crate::error::{MinecraftInstallerError, Result};
+
+/// How the CLIs write log lines, independent of the `--format` of their command output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Plain lines for a terminal
+    #[default]
+    Human,
+    /// One JSON object per event, with its level, target and fields
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = MinecraftInstallerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "human" | "text" => Ok(LogFormat::Human),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(MinecraftInstallerError::Validation(format!(
+                "Unknown log format: {} (expected human or json)", s
+            ))),
+        }
+    }
+}
+
+/// Build the subscriber a CLI installs as its global default
+pub fn subscriber(format: LogFormat, filter: EnvFilter) -> Box<dyn Subscriber + Send + Sync> {
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match format {
+        LogFormat::Human => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(builder.json().finish()),
+    }
+}
@@ -1,26 +1,34 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 use tracing::{error, info, warn};
 use tokio::fs;
-
-mod installer;
-mod error;
-mod directories;
-mod download;
-mod java;
-mod launcher_support;
-
-use crate::error::Result;
-use crate::installer::MinecraftInstaller;
-use crate::launcher_support::LauncherManager;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use minecraft_installer::config::Config;
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::download::{DownloadOptions, HttpConfig, HttpTimeouts};
+use minecraft_installer::error::Result;
+use minecraft_installer::installer::MinecraftInstaller;
+use minecraft_installer::launcher_support::{naha_instance_name, parse_jvm_args, LauncherManager, LauncherType};
+use minecraft_installer::logging::{self, LogFormat};
+use minecraft_installer::updater::MinecraftUpdater;
 
 #[derive(Parser)]
 #[command(name = "minecraft-installer")]
 #[command(about = "A standalone Minecraft installer")]
-#[command(version = "0.1.0")]
+#[command(version = "0.1.0", disable_version_flag = true)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    /// Print version (`--version` names the Minecraft version to install)
+    #[arg(short = 'V', action = clap::ArgAction::Version)]
+    print_version: (),
+
     /// Minecraft version to install (e.g., "1.20.1", "1.19.4")
-    #[arg(short, long, required_unless_present_any = ["list_versions", "mrpack", "list_launchers", "download_neoforge", "download_fabric"])]
+    #[arg(long, required_unless_present_any = ["list_versions", "mrpack", "list_launchers", "download_neoforge", "download_fabric", "check_update", "prune_java", "scan_instances", "repair_db"])]
     version: Option<String>,
 
     /// Installation directory (defaults to system's games directory)
@@ -35,38 +43,131 @@ struct Args {
     #[arg(long, default_value = "stable")]
     loader_version: String,
 
-    /// Force reinstall even if already installed
+    /// Force reinstall even if already installed (with --create-instance, recreate an existing instance)
     #[arg(short, long)]
     force: bool,
 
+    /// Skip downloading assets (client JAR, version JSON and libraries only), e.g. for a dedicated server
+    #[arg(long)]
+    no_assets: bool,
+
+    /// Download the dedicated server JAR (versions/<version>/server.jar) with eula.txt and server.properties stubs instead of the client
+    #[arg(long, requires = "version")]
+    server: bool,
+
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
 
+    /// Log line format (human, json); json emits one parseable event per line
+    #[arg(long, value_name = "FORMAT", default_value = "human")]
+    log_format: LogFormat,
+
     /// List available Minecraft versions
     #[arg(long)]
     list_versions: bool,
 
+    /// List the versions of a loader (fabric, quilt, forge, neoforge) available for --version, marking the recommended one
+    #[arg(long, value_name = "LOADER", requires = "version")]
+    list_loaders: Option<String>,
+
+    /// Delete JREs in <install dir>/java that no installed Minecraft version needs
+    #[arg(long)]
+    prune_java: bool,
+
+    /// Check whether a newer installer binary has been released (does not download it)
+    #[arg(long)]
+    check_update: bool,
+
     /// Filter versions by type (release, snapshot, alpha, beta)
     #[arg(long)]
     version_type: Option<String>,
 
-    /// Install mrpack file (Modrinth modpack)
+    /// Output format for --list-versions and --list-loaders (table, json)
+    #[arg(long, default_value = "table")]
+    format: String,
+
+    /// Number of versions shown by --list-versions and --list-loaders (0 = all)
+    #[arg(long, default_value_t = 20)]
+    limit: usize,
+
+    /// Install mrpack file (Modrinth modpack) or CurseForge modpack .zip
     #[arg(long)]
     mrpack: Option<PathBuf>,
 
-    /// Target launcher for instance creation (auto-detect if not specified)
+    /// Write the files installing --version would download (URLs, sizes, sha1s) as JSON, without downloading
+    #[arg(long, value_name = "FILE", requires = "version")]
+    dump_plan: Option<PathBuf>,
+
+    /// Install from a local cache of files named by SHA1 instead of downloading
+    #[arg(long, value_name = "DIR")]
+    offline_cache: Option<PathBuf>,
+
+    /// Use the Java in this JDK/JRE directory instead of downloading one; fails if it is older than the version needs
+    #[arg(long, value_name = "DIR")]
+    java_home: Option<PathBuf>,
+
+    /// Store asset objects once in a per-user store and hardlink them into each install dir
+    #[arg(long)]
+    shared_assets: bool,
+
+    /// Asset store for --shared-assets instead of the user cache dir (implies --shared-assets)
+    #[arg(long, value_name = "DIR")]
+    shared_assets_dir: Option<PathBuf>,
+
+    /// Hash asset objects that are already present instead of only checking their size
+    #[arg(long)]
+    rehash_assets: bool,
+
+    /// Give up on a download that hasn't finished after this many seconds (default 300)
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// Cap Mojang and Adoptium downloads at this many bytes per second in total (0 = unlimited)
+    #[arg(long, value_name = "BYTES_PER_SEC", default_value_t = 0)]
+    max_rate: u64,
+
+    /// Proxy for all downloads (Mojang, Adoptium, Modrinth, GitHub), e.g. http://proxy:8080 or socks5://127.0.0.1:1080.
+    /// Without it, HTTPS_PROXY / ALL_PROXY from the environment are used
+    #[arg(long, value_name = "URL")]
+    proxy: Option<String>,
+
+    /// Extra directory to search for launchers (repeatable)
+    #[arg(long = "launcher-path", value_name = "DIR")]
+    launcher_paths: Vec<PathBuf>,
+
+    /// Target launcher for instance creation, e.g. PrismLauncher, XMCL, ModrinthApp (auto-detect if not specified)
     #[arg(long)]
-    target_launcher: Option<String>,
+    target_launcher: Option<LauncherType>,
+
+    /// Number of mrpack files downloaded in parallel (default 8)
+    #[arg(long, value_name = "N")]
+    download_concurrency: Option<usize>,
 
     /// Create instance in detected launchers
     #[arg(long)]
     create_instance: bool,
 
+    /// With --create-instance, don't write to AstralRinth / Modrinth App databases; import the profile folder manually
+    #[arg(long)]
+    no_db: bool,
+
+    /// Keep the temporary mrpack and instance directories and print their paths (they are always kept on failure)
+    #[arg(long)]
+    keep_temp: bool,
+
             /// List detected launchers
             #[arg(long)]
             list_launchers: bool,
 
+    /// Print the instances of every detected launcher as JSON
+    #[arg(long)]
+    scan_instances: bool,
+
+    /// Re-register AstralRinth / Modrinth App profiles whose folder exists but that the launcher doesn't list
+    #[arg(long)]
+    repair_db: bool,
+
             /// Download and install NeoForge modpack from NAHA API
             #[arg(long)]
             download_neoforge: bool,
@@ -78,6 +179,64 @@ struct Args {
     /// Custom installation path for Other launcher type
     #[arg(long)]
     custom_path: Option<PathBuf>,
+
+    /// JVM arguments for the created instance: "aikar" for Aikar's GC flags, or a quoted argument list
+    #[arg(long, value_name = "ARGS", allow_hyphen_values = true)]
+    jvm_args: Option<String>,
+
+    /// Name for the created launcher instance (defaults to NAHA-<Loader>-<pack version>)
+    #[arg(long)]
+    instance_name: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Check an mrpack file for structural problems without installing it
+    ValidateMrpack {
+        /// Path to the .mrpack file
+        file: PathBuf,
+    },
+    /// Check an installed version's client JAR, libraries and assets against its version JSON
+    Verify {
+        /// Installed Minecraft version to check
+        version: String,
+        /// Re-download only the missing or corrupt files
+        #[arg(long)]
+        repair: bool,
+    },
+}
+
+/// Options given on the command line, the top layer over the environment and config file
+fn cli_config(args: &Args) -> Config {
+    Config {
+        install_dir: args.install_dir.clone(),
+        target_launcher: args.target_launcher.as_ref().map(|t| t.as_str().to_string()),
+        java_home: args.java_home.clone(),
+        download_concurrency: args.download_concurrency,
+        proxy: args.proxy.clone(),
+        timeout: args.timeout,
+    }
+}
+
+/// Merge `minecraft-installer.toml`, `MINECRAFT_INSTALLER_*` variables and the CLI, in increasing precedence
+fn resolve_config(args: &Args) -> Result<Config> {
+    let file = match Config::default_path() {
+        Some(path) => Config::load(&path)?,
+        None => Config::default(),
+    };
+    Ok(file.merge(Config::from_env()?).merge(cli_config(args)))
+}
+
+/// Timeouts and proxy from `--timeout` and `--proxy` or their configured defaults
+fn http_config(config: &Config, max_rate: u64) -> Result<HttpConfig> {
+    let mut http = HttpConfig::default();
+    if let Some(seconds) = config.timeout {
+        http = http.with_timeouts(HttpTimeouts { total: std::time::Duration::from_secs(seconds), ..Default::default() });
+    }
+    if let Some(proxy) = &config.proxy {
+        http = http.with_proxy(proxy)?;
+    }
+    Ok(http.with_max_rate(max_rate))
 }
 
 #[tokio::main]
@@ -85,28 +244,163 @@ async fn main() -> Result<()> {
     let args = Args::parse();
 
     // Initialize logging
-    let log_level = if args.verbose { "debug" } else { "info" };
-    tracing_subscriber::fmt()
-        .with_env_filter(format!("minecraft_installer={}", log_level))
-        .init();
+    // --scan-instances writes JSON to stdout, so keep progress lines out of it
+    let log_level = match (args.verbose, args.scan_instances) {
+        (true, _) => "debug",
+        (false, true) => "error",
+        (false, false) => "info",
+    };
+    logging::subscriber(args.log_format, EnvFilter::new(format!("minecraft_installer={}", log_level))).init();
 
     info!("Minecraft Installer v0.1.0");
+
+    // Validating a pack and checking for updates only use the CLI, so a broken config file can't get in their way
+    if let Some(Commands::ValidateMrpack { file }) = &args.command {
+        let issues = match LauncherManager::new().validate_mrpack(file) {
+            Ok(issues) => issues,
+            Err(e) => {
+                error!("✗ Failed to read {}: {}", file.display(), e);
+                std::process::exit(1);
+            }
+        };
+
+        if issues.is_empty() {
+            println!("✓ {} is a valid mrpack", file.display());
+            return Ok(());
+        }
+
+        println!("✗ Found {} problem(s) in {}:", issues.len(), file.display());
+        for issue in &issues {
+            println!("  - {}", issue);
+        }
+        std::process::exit(1);
+    }
+
+    if args.check_update {
+        match MinecraftUpdater::new().with_http_config(http_config(&cli_config(&args), args.max_rate)?).check_binary_update().await {
+            Ok(Some(update)) => {
+                println!("⬆️  minecraft-installer {} is available (running {})", update.latest_version, update.current_version);
+                println!("   {}", update.release_url);
+            }
+            Ok(None) => println!("✓ minecraft-installer {} is up to date", env!("CARGO_PKG_VERSION")),
+            Err(e) => {
+                error!("✗ Failed to check for updates: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    let config = match resolve_config(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("✗ {}", e);
+            std::process::exit(1);
+        }
+    };
+    let target_launcher = match config.target_launcher.as_deref().map(str::parse::<LauncherType>).transpose() {
+        Ok(target_launcher) => target_launcher,
+        Err(e) => {
+            error!("✗ Invalid target launcher: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     if let Some(ref version) = args.version {
         info!("Installing Minecraft {} with {} loader", version, args.loader);
     }
 
+    let http = http_config(&config, args.max_rate)?;
+
     // Determine installation directory
-    let install_dir = args.install_dir.unwrap_or_else(|| {
-        dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("MinecraftInstaller")
-    });
+    let mut directories = match config.install_dir.clone() {
+        Some(install_dir) => DirectoryManager::new(install_dir),
+        None => {
+            let data_dir = dirs::data_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("MinecraftInstaller");
+            DirectoryManager::new(data_dir)
+        }
+    };
+    // Following XDG, regenerable downloads of the default install go to $XDG_CACHE_HOME rather than $XDG_DATA_HOME
+    if cfg!(target_os = "linux") && config.install_dir.is_none() {
+        if let Some(cache_dir) = dirs::cache_dir() {
+            directories = directories.with_cache_dir(cache_dir.join("MinecraftInstaller"));
+        }
+    }
 
-    info!("Installation directory: {}", install_dir.display());
+    info!("Installation directory: {}", directories.base_dir.display());
 
     // Create installer instance
-    let installer = MinecraftInstaller::new(install_dir).await?;
-    let launcher_manager = LauncherManager::new();
+    let mut installer = MinecraftInstaller::with_directories(directories).await?;
+    if let Some(cache_dir) = args.offline_cache.clone() {
+        installer = installer.with_offline_cache(cache_dir);
+    }
+    if args.shared_assets || args.shared_assets_dir.is_some() {
+        // One store per user, so every install dir links the same objects
+        let store = args.shared_assets_dir.clone()
+            .or_else(|| dirs::cache_dir().map(|cache| cache.join("MinecraftInstaller").join("shared-assets")));
+        installer = match store {
+            Some(store) => installer.with_shared_assets_at(store),
+            None => installer.with_shared_assets(),
+        };
+    }
+    if args.rehash_assets {
+        installer = installer.with_asset_rehash();
+    }
+    if let Some(java_home) = config.java_home.clone() {
+        installer = installer.with_java_home(java_home);
+    }
+    let mut launcher_manager = LauncherManager::new();
+    installer = installer.with_http_config(&http);
+    launcher_manager.set_http_config(http);
+    if let Some(concurrency) = config.download_concurrency {
+        launcher_manager.set_download_concurrency(concurrency);
+    }
+    for path in &args.launcher_paths {
+        launcher_manager.add_search_path(path.clone());
+    }
+    launcher_manager.set_skip_launcher_db(args.no_db);
+    launcher_manager.set_keep_temp(args.keep_temp);
+    let jvm_args = args.jvm_args.as_deref().map(parse_jvm_args);
+    if let Some(jvm_args) = &jvm_args {
+        launcher_manager.set_jvm_args(jvm_args.clone());
+    }
+
+    if let Some(Commands::Verify { version, repair }) = &args.command {
+        let mut report = match installer.verify_installation(version).await {
+            Ok(report) => report,
+            Err(e) => {
+                error!("✗ Verification failed: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        if !report.is_intact() && *repair {
+            println!("🔧 Repairing {} file(s) for {}...", report.missing.len() + report.corrupt.len(), version);
+            report = match installer.repair_installation(version, &report).await {
+                Ok(report) => report,
+                Err(e) => {
+                    error!("✗ Repair failed: {}", e);
+                    std::process::exit(1);
+                }
+            };
+        }
+
+        if report.is_intact() {
+            println!("✓ All {} files for {} are intact", report.checked, version);
+            return Ok(());
+        }
+
+        println!("✗ Checked {} files for {}:", report.checked, version);
+        for file in &report.missing {
+            println!("  missing: {}", file.path);
+        }
+        for file in &report.corrupt {
+            println!("  corrupt: {}", file.path);
+        }
+        std::process::exit(1);
+    }
 
     // Handle list launchers command
     if args.list_launchers {
@@ -115,28 +409,60 @@ async fn main() -> Result<()> {
                 println!("\n🚀 Detected Launchers");
                 println!("════════════════════");
                 for (launcher_type, path) in launcher_manager.detect_launchers().await {
-                    println!("{:15} {}", format!("{:?}", launcher_type), path.display());
+                    println!("{:22} {}", launcher_type, path.display());
                 }
             }
             true => {
                 println!("No compatible launchers detected.");
-                println!("Supported launchers:");
-                println!("  - Official Minecraft Launcher");
-                println!("  - PrismLauncher");
-                println!("  - PrismLauncher-Cracked");
-                println!("  - XMCL (X Minecraft Launcher)");
-                println!("  - AstralRinth App");
-                println!("  - Modrinth App");
-                println!("  - MultiMC");
-                println!("  - Other (Custom Path)");
+                println!("Supported launchers (names accepted by --target-launcher):");
+                for launcher_type in LauncherType::ALL.iter().filter(|t| **t != LauncherType::Unknown) {
+                    println!("  - {}", launcher_type);
+                }
             }
         }
         return Ok(());
     }
 
+    if args.scan_instances {
+        match launcher_manager.scan_all_instances().await {
+            Ok(instances) => println!("{}", serde_json::to_string_pretty(&instances)?),
+            Err(e) => {
+                error!("✗ Failed to scan instances: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    if args.repair_db {
+        let mut failed = false;
+        for (launcher_type, launcher_path) in launcher_manager.detect_launchers().await {
+            if !matches!(launcher_type, LauncherType::AstralRinth | LauncherType::ModrinthApp) {
+                continue;
+            }
+            match launcher_manager.repair_profile_dbs(&launcher_path).await {
+                Ok(repaired) if repaired.is_empty() => println!("✓ {}: every profile is registered", launcher_type),
+                Ok(repaired) => {
+                    println!("🔧 {}: restored {} profile(s):", launcher_type, repaired.len());
+                    for profile_name in &repaired {
+                        println!("  - {}", profile_name);
+                    }
+                }
+                Err(e) => {
+                    error!("✗ Failed to repair {} profiles at {}: {}", launcher_type, launcher_path.display(), e);
+                    failed = true;
+                }
+            }
+        }
+        if failed {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Handle list versions command
     if args.list_versions {
-        match installer.list_versions(args.version_type.as_deref()).await {
+        match installer.list_versions(args.version_type.as_deref(), args.limit, &args.format).await {
             Ok(_) => {}
             Err(e) => {
                 error!("✗ Failed to list versions: {}", e);
@@ -146,14 +472,42 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let (Some(loader), Some(version)) = (&args.list_loaders, &args.version) {
+        if let Err(e) = installer.list_loader_versions(loader, version, args.limit, &args.format).await {
+            error!("✗ Failed to list {} versions: {}", loader, e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args.prune_java {
+        let in_use = launcher_manager.configured_java_paths().await;
+        match installer.prune_java(&in_use).await {
+            Ok(removed) if removed.is_empty() => println!("✓ No unused Java installations to remove"),
+            Ok(removed) => {
+                println!("🧹 Removed {} unused Java installation(s):", removed.len());
+                for path in &removed {
+                    println!("  - {}", path.display());
+                }
+            }
+            Err(e) => {
+                error!("✗ Failed to prune Java installations: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
     // Handle API download commands
     if args.download_neoforge {
         info!("Downloading NeoForge modpack from NAHA API...");
         match launcher_manager.download_and_install_from_api(
             "neoforge",
-            args.target_launcher.as_deref(),
+            target_launcher.as_ref().map(LauncherType::as_str),
             args.create_instance,
             args.custom_path.as_deref(),
+            args.instance_name.as_deref(),
+            args.force,
         ).await {
             Ok(_) => {
                 info!("✓ NeoForge modpack downloaded and installed successfully!");
@@ -170,9 +524,11 @@ async fn main() -> Result<()> {
         info!("Downloading Fabric modpack from NAHA API...");
         match launcher_manager.download_and_install_from_api(
             "fabric",
-            args.target_launcher.as_deref(),
+            target_launcher.as_ref().map(LauncherType::as_str),
             args.create_instance,
             args.custom_path.as_deref(),
+            args.instance_name.as_deref(),
+            args.force,
         ).await {
             Ok(_) => {
                 info!("✓ Fabric modpack downloaded and installed successfully!");
@@ -192,27 +548,41 @@ async fn main() -> Result<()> {
         // Create temporary instance directory
         let temp_instance_dir = installer.get_install_dir().join("temp-mrpack-instance");
 
-        match launcher_manager.install_mrpack(&mrpack_path, &temp_instance_dir, "temp-instance").await {
+        let is_curseforge = mrpack_path.extension().and_then(|e| e.to_str()) == Some("zip");
+
+        // CurseForge manifests carry no file sizes, so only mrpacks can be checked up front
+        if args.create_instance && !is_curseforge {
+            if let Err(e) = launcher_manager.check_space_for_mrpack(
+                &mrpack_path,
+                target_launcher.as_ref().map(LauncherType::as_str),
+                args.custom_path.as_deref(),
+            ).await {
+                error!("✗ {}", e);
+                std::process::exit(1);
+            }
+        }
+        let install_result = if is_curseforge {
+            launcher_manager.install_curseforge_zip(&mrpack_path, &temp_instance_dir, "temp-instance").await
+        } else {
+            launcher_manager.install_mrpack(&mrpack_path, &temp_instance_dir, "temp-instance").await
+        };
+
+        match install_result {
             Ok((minecraft_version, mod_loader)) => {
                 info!("✓ Mrpack installed successfully!");
 
                 // If create_instance is specified, also create launcher instances
                 if args.create_instance {
-                    // Generate proper instance name based on mod loader
-                    let instance_name = match mod_loader.as_str() {
-                        "neoforge" => "NAHA-NeoForge".to_string(),
-                        "fabric" => "NAHA-Fabric".to_string(),
-                        "forge" => "NAHA-Forge".to_string(),
-                        "quilt" => "NAHA-Quilt".to_string(),
-                        _ => format!("NAHA-{}", mod_loader),
+                    let instance_name = match &args.instance_name {
+                        Some(name) => name.clone(),
+                        None => {
+                            let pack_version = launcher_manager.modpack_version(&mrpack_path).ok().flatten();
+                            naha_instance_name(&mod_loader, pack_version.as_deref())
+                        }
                     };
 
                     // Handle custom path for Other launcher
-                    let target_launcher = if args.target_launcher.as_deref() == Some("other") && args.custom_path.is_some() {
-                        Some("other")
-                    } else {
-                        args.target_launcher.as_deref()
-                    };
+                    let target_launcher = target_launcher.as_ref().map(LauncherType::as_str);
 
                     match launcher_manager.auto_install_instance(
                         &instance_name,
@@ -228,11 +598,20 @@ async fn main() -> Result<()> {
                             // Copy files from temp instance to launcher instance
                             if let Err(e) = launcher_manager.copy_instance_files(&temp_instance_dir, &instance_path).await {
                                 warn!("Failed to copy files to launcher instance: {}", e);
+                                info!("Kept temporary directory: {}", temp_instance_dir.display());
                             } else {
                                 info!("✓ Files copied to launcher instance");
 
+                                if let Some(jvm_args) = &jvm_args {
+                                    if let Err(e) = launcher_manager.apply_jvm_args(&instance_path, jvm_args).await {
+                                        warn!("Failed to apply JVM arguments: {}", e);
+                                    }
+                                }
+
                                 // Clean up temporary directory
-                                if let Err(e) = tokio::fs::remove_dir_all(&temp_instance_dir).await {
+                                if args.keep_temp {
+                                    info!("Kept temporary directory: {}", temp_instance_dir.display());
+                                } else if let Err(e) = tokio::fs::remove_dir_all(&temp_instance_dir).await {
                                     warn!("Failed to clean up temporary directory: {}", e);
                                 } else {
                                     info!("✓ Temporary directory cleaned up");
@@ -241,12 +620,53 @@ async fn main() -> Result<()> {
                         }
                         Err(e) => {
                             warn!("Failed to create launcher instance: {}", e);
+                            info!("Kept temporary directory: {}", temp_instance_dir.display());
                         }
                     }
+                } else if args.keep_temp {
+                    info!("Kept temporary directory: {}", temp_instance_dir.display());
                 }
             }
             Err(e) => {
                 error!("✗ Mrpack installation failed: {}", e);
+                if temp_instance_dir.exists() {
+                    info!("Kept temporary directory for inspection: {}", temp_instance_dir.display());
+                }
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    if let (Some(plan_path), Some(version)) = (&args.dump_plan, &args.version) {
+        let plan = match installer.download_plan(version).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                error!("✗ Failed to resolve download plan: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let total_size: u64 = plan.files.iter().map(|f| f.size).sum();
+        fs::write(plan_path, serde_json::to_string_pretty(&plan)?).await?;
+        println!(
+            "✓ Wrote {} files ({} MB) for {} to {}",
+            plan.files.len(),
+            total_size / 1024 / 1024,
+            version,
+            plan_path.display()
+        );
+        return Ok(());
+    }
+
+    if let (true, Some(version)) = (args.server, &args.version) {
+        match installer.install_server(version).await {
+            Ok(server_jar) => {
+                info!("✓ Minecraft {} server ready: {}", version, server_jar.display());
+                info!("Set eula=true in eula.txt to accept the Minecraft EULA before starting it.");
+            }
+            Err(e) => {
+                error!("✗ Server installation failed: {}", e);
                 std::process::exit(1);
             }
         }
@@ -255,11 +675,13 @@ async fn main() -> Result<()> {
 
     // Install Minecraft
     if let Some(version) = args.version {
-        match installer.install_minecraft(
+        let download_options = DownloadOptions { download_assets: !args.no_assets, ..Default::default() };
+        match installer.install_minecraft_with_options(
             &version,
             &args.loader,
             &args.loader_version,
             args.force,
+            download_options,
         ).await {
             Ok(_) => {
                 info!("✓ Minecraft {} successfully installed!", version);
@@ -272,7 +694,7 @@ async fn main() -> Result<()> {
                         &version,
                         &args.loader,
                         Some(&args.loader_version),
-                        args.target_launcher.as_deref(),
+                        target_launcher.as_ref().map(LauncherType::as_str),
                         args.custom_path.as_deref()
                     ).await {
                         Ok(instance_path) => {
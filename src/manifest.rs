@@ -0,0 +1,59 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::debug;
+
+use crate::error::Result;
+
+/// File written into an instance's game directory after an install
+pub const INSTALL_MANIFEST_FILE: &str = "install-manifest.json";
+
+/// What an install put into an instance, so tooling can verify or diff it without opening every jar
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InstallManifest {
+    pub minecraft_version: String,
+    pub loader: String,
+    pub loader_version: Option<String>,
+    pub mods: Vec<ManifestMod>,
+    /// RFC 3339 time the install finished
+    pub installed_at: String,
+}
+
+/// A mod file from the install, with the hashes it was verified against
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ManifestMod {
+    /// Path relative to the game directory, e.g. "mods/sodium-fabric-0.6.0+mc1.21.1.jar"
+    pub path: String,
+    pub sha1: Option<String>,
+    pub sha512: Option<String>,
+    pub size: u64,
+}
+
+impl InstallManifest {
+    /// A manifest stamped with the current time and no mods yet
+    pub fn new(minecraft_version: &str, loader: &str, loader_version: Option<&str>) -> Self {
+        Self {
+            minecraft_version: minecraft_version.to_string(),
+            loader: loader.to_string(),
+            loader_version: loader_version.map(str::to_string),
+            mods: Vec::new(),
+            installed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Write `install-manifest.json` into `game_dir`, returning its path
+    pub async fn write(&self, game_dir: &Path) -> Result<PathBuf> {
+        let path = game_dir.join(INSTALL_MANIFEST_FILE);
+        fs::write(&path, serde_json::to_string_pretty(self)?).await?;
+        Ok(path)
+    }
+
+    /// Read the manifest in `game_dir`; a missing or unreadable one is `None`
+    pub async fn read(game_dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(game_dir.join(INSTALL_MANIFEST_FILE)).await.ok()?;
+        serde_json::from_str(&content)
+            .inspect_err(|e| debug!("Ignoring invalid {} in {}: {}", INSTALL_MANIFEST_FILE, game_dir.display(), e))
+            .ok()
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::download::HttpConfig;
+use crate::error::{MinecraftInstallerError, Result};
+
+/// Default Modrinth API base URL
+pub const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+
+/// A Modrinth project version
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ModrinthVersion {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub version_number: String,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    pub files: Vec<ModrinthFile>,
+}
+
+/// A downloadable file attached to a Modrinth version
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ModrinthFile {
+    pub hashes: HashMap<String, String>,
+    pub url: String,
+    pub filename: String,
+    #[serde(default)]
+    pub primary: bool,
+    pub size: u64,
+}
+
+impl ModrinthVersion {
+    /// Find the file in this version with the given hash
+    pub fn file_by_hash(&self, algorithm: &str, hash: &str) -> Option<&ModrinthFile> {
+        self.files.iter()
+            .find(|f| f.hashes.get(algorithm).is_some_and(|h| h.eq_ignore_ascii_case(hash)))
+    }
+
+    /// The file flagged primary, or the first one if none is
+    pub fn primary_file(&self) -> Option<&ModrinthFile> {
+        self.files.iter().find(|f| f.primary).or_else(|| self.files.first())
+    }
+
+    /// Whether this version lists both the loader and the Minecraft version
+    pub fn supports(&self, loader: &str, game_version: &str) -> bool {
+        self.loaders.iter().any(|l| l.eq_ignore_ascii_case(loader))
+            && self.game_versions.iter().any(|v| v == game_version)
+    }
+}
+
+/// Minimal client for the Modrinth v2 API
+#[derive(Clone)]
+pub struct ModrinthClient {
+    client: Client,
+    base_url: String,
+}
+
+impl ModrinthClient {
+    pub fn new() -> Self {
+        Self::with_base_url(MODRINTH_API_URL)
+    }
+
+    /// Create a client against a different API host (e.g. a mirror or a test server)
+    pub fn with_base_url(base_url: &str) -> Self {
+        let client = Client::builder()
+            .user_agent("MinecraftInstaller/0.1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Apply timeouts and proxy settings to API requests
+    pub fn with_http_config(mut self, http: &HttpConfig) -> Self {
+        self.client = http.client();
+        self
+    }
+
+    /// Look up the version that contains a file with the given sha512 hash
+    ///
+    /// Returns `None` if Modrinth doesn't know the file.
+    pub async fn version_from_sha512(&self, sha512: &str) -> Result<Option<ModrinthVersion>> {
+        let url = format!("{}/version_file/{}?algorithm=sha512", self.base_url, sha512);
+        debug!("Resolving file hash on Modrinth: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Modrinth API request failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        Ok(Some(response.json().await?))
+    }
+
+    /// List a project's versions for a loader and Minecraft version, newest first; an empty `loader` matches any
+    pub async fn project_versions(&self, project: &str, loader: &str, game_version: &str) -> Result<Vec<ModrinthVersion>> {
+        let url = format!("{}/project/{}/version", self.base_url, project);
+        debug!("Listing Modrinth versions: {}", url);
+
+        let mut query = vec![("game_versions", format!("[\"{}\"]", game_version))];
+        if !loader.is_empty() {
+            query.push(("loaders", format!("[\"{}\"]", loader)));
+        }
+        let response = self.client.get(&url).query(&query).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(MinecraftInstallerError::Validation(format!("Modrinth project not found: {}", project)));
+        }
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Modrinth API request failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Fetch a single version by its id
+    pub async fn version(&self, version_id: &str) -> Result<ModrinthVersion> {
+        let url = format!("{}/version/{}", self.base_url, version_id);
+        debug!("Fetching Modrinth version: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(MinecraftInstallerError::InvalidVersion(format!("Modrinth version not found: {}", version_id)));
+        }
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Modrinth API request failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Look up many files at once by hash; hashes Modrinth doesn't know are absent from the map
+    pub async fn versions_from_hashes(&self, hashes: &[String], algorithm: &str) -> Result<HashMap<String, ModrinthVersion>> {
+        let url = format!("{}/version_files", self.base_url);
+        self.post_hashes(&url, serde_json::json!({ "hashes": hashes, "algorithm": algorithm })).await
+    }
+
+    /// Newest version of each file's project, limited to the given loaders and game versions
+    /// (empty means no filter), keyed by the file hash
+    pub async fn latest_versions_from_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: &str,
+        loaders: &[String],
+        game_versions: &[String],
+    ) -> Result<HashMap<String, ModrinthVersion>> {
+        let url = format!("{}/version_files/update", self.base_url);
+        let mut body = serde_json::json!({ "hashes": hashes, "algorithm": algorithm });
+        if !loaders.is_empty() {
+            body["loaders"] = serde_json::json!(loaders);
+        }
+        if !game_versions.is_empty() {
+            body["game_versions"] = serde_json::json!(game_versions);
+        }
+        self.post_hashes(&url, body).await
+    }
+
+    async fn post_hashes(&self, url: &str, body: serde_json::Value) -> Result<HashMap<String, ModrinthVersion>> {
+        if body["hashes"].as_array().is_some_and(|h| h.is_empty()) {
+            return Ok(HashMap::new());
+        }
+        debug!("Bulk hash lookup on Modrinth: {}", url);
+
+        let response = self.client.post(url).json(&body).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "Modrinth API request failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Download a version file, checking it against its sha512 when Modrinth provides one
+    pub async fn download_file(&self, file: &ModrinthFile) -> Result<Vec<u8>> {
+        debug!("Downloading {} from {}", file.filename, file.url);
+
+        let response = self.client.get(&file.url).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::DownloadFailed(format!(
+                "HTTP {} for {}", response.status(), file.filename
+            )));
+        }
+        let bytes = response.bytes().await?.to_vec();
+
+        if let Some(expected) = file.hashes.get("sha512") {
+            use sha2::{Digest, Sha512};
+            let actual = hex::encode(Sha512::digest(&bytes));
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(MinecraftInstallerError::HashMismatch {
+                    path: file.filename.clone().into(),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl Default for ModrinthClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
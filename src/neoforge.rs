@@ -0,0 +1,88 @@
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::download::HttpConfig;
+use crate::error::{MinecraftInstallerError, Result};
+use crate::loader::LoaderListing;
+
+/// Default list of NeoForge releases on the NeoForged maven
+pub const NEOFORGE_VERSIONS_URL: &str = "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
+
+#[derive(Deserialize)]
+struct MavenVersions {
+    versions: Vec<String>,
+}
+
+/// Looks up the NeoForge builds available for a Minecraft version
+#[derive(Clone)]
+pub struct NeoForgeResolver {
+    client: Client,
+    versions_url: String,
+}
+
+impl NeoForgeResolver {
+    pub fn new() -> Self {
+        Self::with_url(NEOFORGE_VERSIONS_URL)
+    }
+
+    /// Create a resolver against a different versions endpoint (e.g. a mirror or a test server)
+    pub fn with_url(versions_url: &str) -> Self {
+        let client = HttpConfig::default().client();
+
+        Self {
+            client,
+            versions_url: versions_url.to_string(),
+        }
+    }
+
+    /// Route requests through the configured proxy and timeouts
+    pub fn with_http_config(mut self, http: &HttpConfig) -> Self {
+        self.client = http.client();
+        self
+    }
+
+    /// Every NeoForge version for a Minecraft version, newest first; the newest non-beta build is recommended
+    pub async fn list_versions(&self, mc_version: &str) -> Result<Vec<LoaderListing>> {
+        let prefix = version_prefix(mc_version).ok_or_else(|| MinecraftInstallerError::InvalidLoader(format!(
+            "NeoForge is not available for Minecraft {}", mc_version
+        )))?;
+
+        debug!("Listing NeoForge versions: {}", self.versions_url);
+        let response = self.client.get(&self.versions_url).send().await?;
+        if !response.status().is_success() {
+            return Err(MinecraftInstallerError::Network(format!(
+                "NeoForge maven returned {} for {}", response.status(), self.versions_url
+            )));
+        }
+
+        // The maven lists releases oldest first
+        let maven: MavenVersions = response.json().await?;
+        let versions: Vec<String> = maven.versions.into_iter().rev().filter(|v| v.starts_with(&prefix)).collect();
+        if versions.is_empty() {
+            return Err(MinecraftInstallerError::InvalidLoader(format!(
+                "NeoForge is not available for Minecraft {}", mc_version
+            )));
+        }
+
+        let recommended = versions.iter().position(|v| !v.contains("-beta")).unwrap_or(0);
+        Ok(versions.into_iter()
+            .enumerate()
+            .map(|(i, version)| LoaderListing { version, recommended: i == recommended })
+            .collect())
+    }
+}
+
+impl Default for NeoForgeResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// NeoForge numbers its builds after the Minecraft version without the leading "1.": 1.21.1 is 21.1.x, 1.21 is 21.0.x
+fn version_prefix(mc_version: &str) -> Option<String> {
+    let mut parts = mc_version.strip_prefix("1.")?.split('.');
+    let minor: u32 = parts.next()?.parse().ok()?;
+    let patch: u32 = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    Some(format!("{}.{}.", minor, patch))
+}
@@ -1,47 +1,32 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tokio::fs;
-use tracing::{info, debug};
+use tracing::{info, debug, warn};
+use crate::download::HttpConfig;
 use crate::error::{MinecraftInstallerError, Result};
-use crate::launcher_support::{LauncherManager, LauncherType, MrpackIndex, MrpackFile, NahaModpackInfo};
-
-/// Instance information for display in Electron app
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct InstanceInfo {
-    pub name: String,
-    pub launcher_type: String,
-    pub launcher_path: String,
-    pub instance_path: String,
-    pub minecraft_version: String,
-    pub mod_loader: String,
-    pub mod_loader_version: Option<String>,
-    pub mod_count: usize,
-    pub mods: Vec<ModInfo>,
-    pub has_automodpack: bool,
-    pub server_info: Option<ServerInfo>,
-    pub last_updated: Option<String>,
-}
-
-/// Mod information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModInfo {
-    pub name: String,
-    pub filename: String,
-    pub version: Option<String>,
-    pub mod_id: Option<String>,
-    pub is_user_mod: bool, // true if added by user, false if from modpack
-    pub file_size: u64,
-    pub last_modified: String,
-}
-
-/// Server information from automodpack
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ServerInfo {
-    pub server_ip: String,
-    pub server_port: u16,
-    pub fingerprint: String,
-    pub server_name: String,
+use crate::modrinth::ModrinthClient;
+use crate::events::{Phase, PhaseTotal, ProgressEvent, ProgressReporter};
+use tokio::sync::mpsc::UnboundedReceiver;
+use crate::instance::{read_jar_mod_id, read_jar_mod_version, relative_mod_path, InstanceScanner};
+use crate::manifest::{InstallManifest, ManifestMod};
+
+pub use crate::instance::{normalize_mod_name, InstanceInfo, ModInfo, ServerInfo};
+use crate::launcher_support::{add_servers_to_list, automodpack_known_hosts, minecraft_base_dir, read_instance_icon, restore_db_icon, LauncherManager, LauncherType, MrpackIndex, MrpackFile, NahaModpackInfo, MODPACK_RELEASES_API_URL, mrpack_install_manifest, OverrideOptions, OverrideOrder, read_mrpack_index};
+
+/// Whether `path` exists and matches the mrpack file's sha512, or its sha1 when no sha512 is listed
+async fn matches_mrpack_hashes(path: &Path, mrpack_file: &MrpackFile) -> bool {
+    let Ok(bytes) = fs::read(path).await else {
+        return false;
+    };
+    if let Some(expected) = mrpack_file.hashes.get("sha512") {
+        use sha2::{Digest, Sha512};
+        return hex::encode(Sha512::digest(&bytes)).eq_ignore_ascii_case(expected);
+    }
+    match mrpack_file.hashes.get("sha1") {
+        Some(expected) => sha1_smol::Sha1::from(&bytes).digest().to_string().eq_ignore_ascii_case(expected),
+        None => false,
+    }
 }
 
 /// Update result for a specific instance
@@ -54,67 +39,494 @@ pub struct UpdateResult {
     pub preserved_mods: Vec<String>,
     pub errors: Vec<String>,
     pub message: String,
+    /// True when this result is a plan and nothing on disk was changed
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Installed mods a `.naha-pins.json` pin kept at their current version
+    #[serde(default)]
+    pub pinned_mods: Vec<String>,
+    /// Pack mods whose file was already on disk with the pack's hash, so nothing was downloaded
+    #[serde(default)]
+    pub already_current: Vec<String>,
+}
+
+/// What applying a planned update would do to the mods folder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateSummary {
+    pub added: Vec<String>,
+    /// New filenames of mods that get a different version
+    pub updated: Vec<String>,
+    /// Installed jars deleted to make way for their updated versions
+    pub removed: Vec<String>,
+    pub preserved: Vec<String>,
+}
+
+impl UpdateResult {
+    /// Split the plan's `old → new` entries into the files added, updated and removed
+    pub fn summary(&self) -> UpdateSummary {
+        let mut summary = UpdateSummary {
+            added: self.new_mods.clone(),
+            preserved: self.preserved_mods.clone(),
+            ..Default::default()
+        };
+        for entry in &self.updated_mods {
+            match entry.split_once(" → ") {
+                Some((old, new)) => {
+                    summary.removed.push(old.to_string());
+                    summary.updated.push(new.to_string());
+                }
+                None => summary.updated.push(entry.clone()),
+            }
+        }
+        summary
+    }
+}
+
+/// Asks whether a planned update should go ahead; a trait so callers and tests can answer without a terminal
+pub trait ConfirmPrompt {
+    fn confirm(&mut self, question: &str) -> bool;
+}
+
+/// Prompts on stdout and reads a y/N answer from stdin; anything but "y" or "yes" declines
+pub struct StdinPrompt;
+
+impl ConfirmPrompt for StdinPrompt {
+    fn confirm(&mut self, question: &str) -> bool {
+        use std::io::Write;
+        print!("{} [y/N]: ", question);
+        let _ = std::io::stdout().flush();
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer).is_err() {
+            return false;
+        }
+        matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+    }
+}
+
+/// Print what a planned update will change and ask before applying it; `assume_yes` skips the question
+pub fn confirm_update(plan: &UpdateResult, prompt: &mut dyn ConfirmPrompt, assume_yes: bool) -> bool {
+    let summary = plan.summary();
+    println!("📋 Planned changes for {}:", plan.instance_name);
+    println!("   ➕ {} to add", summary.added.len());
+    println!("   🔄 {} to update", summary.updated.len());
+    println!("   🗑️  {} to remove", summary.removed.len());
+    for filename in &summary.removed {
+        println!("      • {}", filename);
+    }
+    println!("   🔒 {} user mods kept", summary.preserved.len());
+
+    assume_yes || prompt.confirm("Apply these changes?")
+}
+
+/// File in an instance that pins or excludes mods from pack updates
+pub const MOD_PINS_FILE: &str = ".naha-pins.json";
+
+/// How a pack update treats one mod, keyed by normalized mod name in `.naha-pins.json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModPin {
+    /// The installed version is never replaced
+    Pinned,
+    /// The mod is never added by an update
+    Excluded,
+}
+
+/// An installed mod Modrinth recognised, with the newest compatible version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModUpdate {
+    /// Path relative to the mods directory
+    pub filename: String,
+    pub project_id: String,
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+}
+
+/// How two instances' mod sets differ, matching mods by their normalized names
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct InstanceDiff {
+    /// Filenames of the mods only the first instance has
+    pub only_in_a: Vec<String>,
+    /// Filenames of the mods only the second instance has
+    pub only_in_b: Vec<String>,
+    pub version_differs: Vec<ModVersionDiff>,
+}
+
+/// A mod both instances have, in different versions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModVersionDiff {
+    /// Normalized name, see [`normalize_mod_name`]
+    pub name: String,
+    pub filename_a: String,
+    pub version_a: Option<String>,
+    pub filename_b: String,
+    pub version_b: Option<String>,
+}
+
+/// GitHub releases API for the repository that publishes the installer and updater binaries
+pub const BINARY_RELEASES_API_URL: &str = "https://api.github.com/repos/perlytiara/NAHA-MC-Helper/releases";
+
+/// A published binary release newer than the running one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinaryUpdate {
+    pub current_version: String,
+    pub latest_version: String,
+    pub tag: String,
+    pub release_url: String,
+    pub published_at: Option<String>,
 }
 
 /// Main updater for Minecraft instances
 pub struct MinecraftUpdater {
     launcher_manager: LauncherManager,
+    recursive_mods: bool,
+    releases_api_url: String,
+    modpack_releases_api_url: String,
+    overrides: OverrideOptions,
+    modrinth: ModrinthClient,
+    progress: ProgressReporter,
+    http: reqwest::Client,
+}
+
+/// Releases fetched when looking for the previous modpack version; it is almost always on the first page
+const PREVIOUS_RELEASES_PER_PAGE: u32 = 10;
+
+/// Releases fetched when checking for a newer binary (GitHub's default page size)
+const BINARY_RELEASES_PER_PAGE: u32 = 30;
+
+/// Largest releases page read from the GitHub API
+const MAX_RELEASES_PAGE_BYTES: usize = 2 * 1024 * 1024;
+
+/// A pack mod the updater will download, and the installed jar it replaces (path and file name)
+struct PlannedModDownload<'a> {
+    file: &'a MrpackFile,
+    filename: String,
+    target: PathBuf,
+    replaces: Option<(PathBuf, String)>,
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// One page of a repository's releases, newest first, refusing responses over `max_bytes`
+async fn fetch_github_releases<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api_url: &str,
+    per_page: u32,
+    max_bytes: usize,
+) -> Result<Vec<T>> {
+    let mut response = client.get(api_url)
+        .query(&[("per_page", per_page)])
+        .send().await?;
+
+    if !response.status().is_success() {
+        return Err(MinecraftInstallerError::Network(format!(
+            "GitHub releases request failed with status: {}",
+            response.status()
+        )));
+    }
+    if response.content_length().is_some_and(|len| len > max_bytes as u64) {
+        return Err(MinecraftInstallerError::Network(format!(
+            "GitHub releases page is larger than {} bytes", max_bytes
+        )));
+    }
+
+    // Compressed API responses are usually chunked without a length, so count while reading
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max_bytes {
+            return Err(MinecraftInstallerError::Network(format!(
+                "GitHub releases page is larger than {} bytes", max_bytes
+            )));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Parse a release tag like `v1.2.3` or `1.2.3-beta` into `[major, minor, patch]`
+fn parse_release_version(tag: &str) -> Option<[u64; 3]> {
+    let version = tag.trim_start_matches(['v', 'V']).split(['-', '+']).next()?;
+    let mut parts = [0u64; 3];
+    for (i, part) in version.split('.').enumerate() {
+        *parts.get_mut(i)? = part.parse().ok()?;
+    }
+    Some(parts)
+}
+
+/// Loader family of a detected mod loader name, `None` when it isn't a known modded loader
+fn loader_family(mod_loader: &str) -> Option<&'static str> {
+    let loader = mod_loader.to_lowercase();
+    if loader.contains("neoforge") {
+        Some("neoforge")
+    } else if loader.contains("forge") {
+        Some("forge")
+    } else if loader.contains("fabric") {
+        Some("fabric")
+    } else if loader.contains("quilt") {
+        Some("quilt")
+    } else {
+        None
+    }
+}
+
+/// Whether a NAHA pack of `modpack_type` ("neoforge" or "fabric") can update an instance running `mod_loader`
+///
+/// Legacy Forge is its own loader: NeoForge mods don't load on it, so Forge instances never match.
+pub fn pack_supports_loader(modpack_type: &str, mod_loader: &str) -> bool {
+    loader_family(mod_loader).is_some_and(|family| family == modpack_type)
+}
+
+/// NAHA modpack type ("neoforge" or "fabric") for an instance running `mod_loader`
+///
+/// Fails for loaders no NAHA pack targets (vanilla, Forge, Quilt, unknown), where the type has to be given.
+pub fn infer_modpack_type(mod_loader: &str) -> Result<&'static str> {
+    match loader_family(mod_loader) {
+        Some(family @ ("neoforge" | "fabric")) => Ok(family),
+        _ => Err(MinecraftInstallerError::InvalidLoader(format!(
+            "Can't tell which modpack fits a {} instance; pass --modpack-type", mod_loader
+        ))),
+    }
 }
 
 impl MinecraftUpdater {
     pub fn new() -> Self {
         Self {
             launcher_manager: LauncherManager::new(),
+            recursive_mods: false,
+            releases_api_url: BINARY_RELEASES_API_URL.to_string(),
+            modpack_releases_api_url: MODPACK_RELEASES_API_URL.to_string(),
+            overrides: OverrideOptions::update(),
+            modrinth: ModrinthClient::new(),
+            progress: ProgressReporter::default(),
+            http: HttpConfig::default().client(),
         }
     }
 
+    /// Timeouts and proxy for GitHub, Modrinth and modpack downloads
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http.client();
+        self.modrinth = self.modrinth.with_http_config(&http);
+        self.launcher_manager.set_http_config(http);
+        self
+    }
+
+    /// Stream typed progress events for later updates, including mrpack installs and overrides
+    pub fn subscribe_progress(&mut self) -> UnboundedReceiver<ProgressEvent> {
+        let (progress, receiver) = ProgressReporter::channel();
+        self.launcher_manager.set_progress_reporter(progress.clone());
+        self.progress = progress;
+        receiver
+    }
+
+    /// Modrinth API used to identify installed mods (e.g. a mirror or a test server)
+    pub fn with_modrinth_client(mut self, modrinth: ModrinthClient) -> Self {
+        self.modrinth = modrinth;
+        self
+    }
+
+    /// How a pack's `overrides/` are applied during updates (default: before mods, keep existing files)
+    pub fn with_override_options(mut self, overrides: OverrideOptions) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Point the binary self-update check at a different releases API (e.g. a fork or mirror)
+    pub fn with_releases_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.releases_api_url = api_url.into();
+        self
+    }
+
+    /// Look up earlier modpack releases somewhere else (e.g. a mirror)
+    pub fn with_modpack_releases_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.modpack_releases_api_url = api_url.into();
+        self
+    }
+
+    /// Check whether a newer installer/updater binary has been released.
+    ///
+    /// Only reports the release; nothing is downloaded. Releases whose tag has no
+    /// version number (such as the rolling `latest` build) are ignored; the release
+    /// workflow publishes a `v<crate version>` release next to `latest` for this check.
+    pub async fn check_binary_update(&self) -> Result<Option<BinaryUpdate>> {
+        let current_version = env!("CARGO_PKG_VERSION");
+        let current = parse_release_version(current_version).unwrap_or_default();
+
+        let releases: Vec<serde_json::Value> =
+            fetch_github_releases(&self.http, &self.releases_api_url, BINARY_RELEASES_PER_PAGE, MAX_RELEASES_PAGE_BYTES).await?;
+        let newest = releases.iter()
+            .filter(|release| !release["draft"].as_bool().unwrap_or(false) && !release["prerelease"].as_bool().unwrap_or(false))
+            .filter_map(|release| {
+                let tag = release["tag_name"].as_str()?;
+                Some((parse_release_version(tag)?, tag, release))
+            })
+            .max_by_key(|(version, _, _)| *version);
+
+        match newest {
+            Some((version, tag, release)) if version > current => {
+                debug!("Found newer binary release {} (running {})", tag, current_version);
+                Ok(Some(BinaryUpdate {
+                    current_version: current_version.to_string(),
+                    latest_version: format!("{}.{}.{}", version[0], version[1], version[2]),
+                    tag: tag.to_string(),
+                    release_url: release["html_url"].as_str().unwrap_or_default().to_string(),
+                    published_at: release["published_at"].as_str().map(str::to_string),
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Extra directories to check for launchers, on top of the default locations
+    pub fn with_launcher_search_paths(mut self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        for path in paths {
+            self.launcher_manager.add_search_path(path);
+        }
+        self
+    }
+
+    /// Also scan and update jars in subfolders of the mods directory (e.g. `mods/optional/`)
+    pub fn with_recursive_mods(mut self, recursive: bool) -> Self {
+        self.recursive_mods = recursive;
+        self
+    }
+
     /// Scan all launchers and return instance information
     pub async fn scan_instances(&self) -> Result<Vec<InstanceInfo>> {
-        info!("🔍 Scanning for Minecraft instances...");
-
-        let mut instances = Vec::new();
-        let detected_launchers = self.launcher_manager.detect_launchers().await;
-
-        for (launcher_type, launcher_path) in detected_launchers {
-            match launcher_type {
-                LauncherType::AstralRinth => {
-                    info!("📱 Scanning AstralRinth database at: {}", launcher_path.display());
-                    let profiles = self.scan_astralrinth_profiles(&launcher_path).await?;
-                    info!("✅ Found {} AstralRinth instances", profiles.len());
-                    instances.extend(profiles);
-                }
-                LauncherType::ModrinthApp => {
-                    info!("📱 Scanning ModrinthApp database at: {}", launcher_path.display());
-                    let profiles = self.scan_astralrinth_profiles(&launcher_path).await?;
-                    info!("✅ Found {} ModrinthApp instances", profiles.len());
-                    instances.extend(profiles);
-                }
-                LauncherType::XMCL => {
-                    info!("📁 Scanning XMCL folders at: {}", launcher_path.display());
-                    let xmcl_instances = self.scan_xmcl_instances(&launcher_path).await?;
-                    info!("✅ Found {} XMCL instances", xmcl_instances.len());
-                    instances.extend(xmcl_instances);
-                }
-                LauncherType::Prism | LauncherType::PrismCracked => {
-                    info!("📁 Scanning PrismLauncher folders at: {}", launcher_path.display());
-                    let prism_instances = self.scan_prism_instances(&launcher_path).await?;
-                    info!("✅ Found {} PrismLauncher instances", prism_instances.len());
-                    instances.extend(prism_instances);
-                }
-                LauncherType::Official => {
-                    info!("📁 Scanning Official Minecraft profiles at: {}", launcher_path.display());
-                    let official_instances = self.scan_official_instances(&launcher_path).await?;
-                    info!("✅ Found {} Official Minecraft instances", official_instances.len());
-                    instances.extend(official_instances);
-                }
-                _ => {
-                    debug!("⏭️  Skipping unsupported launcher type: {:?}", launcher_type);
+        self.scanner().scan_instances().await
+    }
+
+    /// Scan the instances of a single launcher
+    pub async fn scan_launcher(&self, launcher_type: &LauncherType, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
+        self.scanner().scan_launcher(launcher_type, launcher_path).await
+    }
+
+    /// Analyze a single instance directory, detecting its launcher layout from the files present
+    pub async fn analyze_instance(&self, instance_path: &Path) -> Result<Option<InstanceInfo>> {
+        self.scanner().analyze_instance(instance_path).await
+    }
+
+    fn scanner(&self) -> InstanceScanner<'_> {
+        InstanceScanner::new(&self.launcher_manager).with_recursive_mods(self.recursive_mods)
+    }
+
+    /// Modpack type for the instance at `instance_path`, inferred from its detected mod loader
+    pub async fn detect_modpack_type(&self, instance_path: &Path) -> Result<&'static str> {
+        let instance = self.analyze_instance(instance_path).await?.ok_or_else(|| {
+            MinecraftInstallerError::Validation(format!("{} is not a recognised instance", instance_path.display()))
+        })?;
+        infer_modpack_type(&instance.mod_loader)
+    }
+
+    /// Every mod jar in an instance's mods directory, sorted by filename
+    pub async fn list_instance_mods(&self, instance_path: &Path) -> Result<Vec<ModInfo>> {
+        let mods_dir = self.find_mods_directory(instance_path).await?;
+        if !mods_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut mods = self.scanner().analyze_mods_directory(&mods_dir).await?;
+        mods.sort_by(|a, b| a.filename.cmp(&b.filename));
+        Ok(mods)
+    }
+
+    /// Compare the mods of two instances, e.g. a broken one against a known-good one
+    ///
+    /// Versions come from each jar's metadata, falling back to the filename; two jars without
+    /// a version differ when their filenames do.
+    pub async fn diff_instances(&self, instance_a: &Path, instance_b: &Path) -> Result<InstanceDiff> {
+        let mut mods_a = self.mod_versions_by_name(instance_a).await?;
+        let mods_b = self.mod_versions_by_name(instance_b).await?;
+
+        let mut diff = InstanceDiff::default();
+        for (name, (filename_b, version_b)) in mods_b {
+            match mods_a.remove(&name) {
+                None => diff.only_in_b.push(filename_b),
+                Some((filename_a, version_a)) => {
+                    let differs = match (&version_a, &version_b) {
+                        (None, None) => filename_a != filename_b,
+                        _ => version_a != version_b,
+                    };
+                    if differs {
+                        diff.version_differs.push(ModVersionDiff { name, filename_a, version_a, filename_b, version_b });
+                    }
                 }
             }
         }
+        diff.only_in_a = mods_a.into_values().map(|(filename, _)| filename).collect();
+        diff.only_in_a.sort();
+        diff.only_in_b.sort();
+        Ok(diff)
+    }
+
+    /// An instance's mods keyed by normalized name, with their filename and best-known version
+    async fn mod_versions_by_name(&self, instance_path: &Path) -> Result<BTreeMap<String, (String, Option<String>)>> {
+        let mods_dir = self.find_mods_directory(instance_path).await?;
+        Ok(self.list_instance_mods(instance_path).await?
+            .into_iter()
+            .map(|mod_info| {
+                let version = read_jar_mod_version(&mods_dir.join(&mod_info.filename)).or(mod_info.version);
+                (normalize_mod_name(&mod_info.filename), (mod_info.filename, version))
+            })
+            .collect())
+    }
+
+    /// Check an instance's mods against Modrinth without changing anything
+    ///
+    /// Jars are identified by SHA1 through the bulk `version_files` endpoints; ones Modrinth
+    /// doesn't know (custom or CurseForge-only builds) are left out of the result.
+    pub async fn check_mod_updates(&self, instance_path: &Path) -> Result<Vec<ModUpdate>> {
+        let mods_dir = self.find_mods_directory(instance_path).await?;
+        if !mods_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut jars = Vec::new();
+        for path in self.scanner().collect_mod_jars(&mods_dir).await? {
+            let hash = sha1_smol::Sha1::from(fs::read(&path).await?).digest().to_string();
+            jars.push((relative_mod_path(&mods_dir, &path), hash));
+        }
+        let hashes: Vec<String> = jars.iter().map(|(_, hash)| hash.clone()).collect();
+
+        // Only suggest versions that run on this instance's loader and game version
+        let (loaders, game_versions) = match self.analyze_instance(instance_path).await.ok().flatten() {
+            Some(info) => {
+                let loader = info.mod_loader.to_lowercase();
+                let loaders = if matches!(loader.as_str(), "" | "vanilla" | "unknown") { vec![] } else { vec![loader] };
+                let game_versions = if info.minecraft_version == "Unknown" { vec![] } else { vec![info.minecraft_version] };
+                (loaders, game_versions)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
 
-        info!("🎯 Total instances found: {}", instances.len());
-        Ok(instances)
+        let current = self.modrinth.versions_from_hashes(&hashes, "sha1").await?;
+        let latest = self.modrinth.latest_versions_from_hashes(&hashes, "sha1", &loaders, &game_versions).await?;
+
+        let mut updates: Vec<ModUpdate> = jars.into_iter()
+            .filter_map(|(filename, hash)| {
+                let current = current.get(&hash)?;
+                let latest = latest.get(&hash).unwrap_or(current);
+                Some(ModUpdate {
+                    filename,
+                    project_id: current.project_id.clone(),
+                    current_version: current.version_number.clone(),
+                    latest_version: latest.version_number.clone(),
+                    update_available: latest.id != current.id,
+                })
+            })
+            .collect();
+        updates.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+        Ok(updates)
     }
 
     /// Update mods for a specific instance
@@ -132,6 +544,26 @@ impl MinecraftUpdater {
         instance_path: &Path,
         modpack_type: &str, // "neoforge" or "fabric"
         version: Option<&str>, // Optional specific version (e.g., "0.0.18")
+    ) -> Result<UpdateResult> {
+        self.run_instance_update(instance_path, modpack_type, version, false).await
+    }
+
+    /// Compute what an update would change without touching the instance
+    pub async fn plan_instance_update(
+        &self,
+        instance_path: &Path,
+        modpack_type: &str,
+        version: Option<&str>,
+    ) -> Result<UpdateResult> {
+        self.run_instance_update(instance_path, modpack_type, version, true).await
+    }
+
+    async fn run_instance_update(
+        &self,
+        instance_path: &Path,
+        modpack_type: &str,
+        version: Option<&str>,
+        dry_run: bool,
     ) -> Result<UpdateResult> {
         println!("🔄 Starting update process for: {}", instance_path.display());
         println!("📦 Modpack type: {}", modpack_type);
@@ -141,6 +573,16 @@ impl MinecraftUpdater {
         
         info!("Updating mods for instance: {}", instance_path.display());
 
+        // Refuse before downloading anything if the pack is built for a different loader
+        if let Some(instance) = self.analyze_instance(instance_path).await? {
+            if loader_family(&instance.mod_loader).is_some() && !pack_supports_loader(modpack_type, &instance.mod_loader) {
+                return Err(MinecraftInstallerError::InvalidLoader(format!(
+                    "{} is a {} instance; refusing to update it with the {} modpack",
+                    instance.name, instance.mod_loader, modpack_type
+                )));
+            }
+        }
+
         // Get modpack info from GitHub
         println!("🌐 Fetching modpack info from GitHub...");
         let modpack_info = if let Some(target_version) = version {
@@ -152,16 +594,42 @@ impl MinecraftUpdater {
         println!("📥 Download URL: {}", modpack_info.download_url);
 
         // Download and extract the latest mrpack
+        // A dry run must leave the instance untouched, so its download goes to the system temp dir
         println!("📁 Creating temporary directory...");
-        let temp_dir = instance_path.join("temp_update");
+        let temp_dir = if dry_run {
+            std::env::temp_dir().join(format!("minecraft_updater_plan_{}", uuid::Uuid::new_v4()))
+        } else {
+            instance_path.join("temp_update")
+        };
         fs::create_dir_all(&temp_dir).await?;
 
         println!("⬇️  Downloading latest mrpack...");
         let mrpack_path = self.download_latest_mrpack(&modpack_info, &temp_dir).await?;
         println!("✅ Downloaded: {}", mrpack_path.display());
-        
+
+        let update_result = self.update_instance_from_mrpack(instance_path, &mrpack_path, &modpack_info, dry_run).await;
+
+        // Clean up temp directory
+        println!("🧹 Cleaning up temporary files...");
+        let _ = fs::remove_dir_all(&temp_dir).await;
+
+        let update_result = update_result?;
+        println!("✅ Update completed successfully!");
+        Ok(update_result)
+    }
+
+    /// Update an instance's mods from an already downloaded mrpack
+    ///
+    /// With `dry_run` set, the analysis runs in full but no files or launcher databases are written.
+    pub async fn update_instance_from_mrpack(
+        &self,
+        instance_path: &Path,
+        mrpack_path: &Path,
+        modpack_info: &NahaModpackInfo,
+        dry_run: bool,
+    ) -> Result<UpdateResult> {
         println!("📦 Extracting mrpack contents...");
-        let mrpack_index_json = self.extract_mrpack_index(&mrpack_path).await?;
+        let mrpack_index_json = self.extract_mrpack_index(mrpack_path).await?;
         
         // Parse the mrpack index
         println!("🔍 Parsing mrpack index...");
@@ -172,31 +640,10 @@ impl MinecraftUpdater {
                 e
             })?;
         
-        // Debug: Check for duplicate paths in the mrpack index
-        let mut path_counts = std::collections::HashMap::new();
-        for file in &mrpack_index.files {
-            *path_counts.entry(&file.path).or_insert(0) += 1;
-        }
-        
-        let duplicates: Vec<_> = path_counts.iter()
-            .filter(|(_, &count)| count > 1)
-            .collect();
-        
-        if !duplicates.is_empty() {
-            println!("⚠️  Found {} duplicate paths in mrpack index, deduplicating...", duplicates.len());
-            
-            // Deduplicate files by path
-            let mut seen_paths = std::collections::HashSet::new();
-            let original_count = mrpack_index.files.len();
-            mrpack_index.files.retain(|file| {
-                if seen_paths.contains(&file.path) {
-                    false
-                } else {
-                    seen_paths.insert(file.path.clone());
-                    true
-                }
-            });
-            println!("✅ Removed {} duplicate entries from mrpack", original_count - mrpack_index.files.len());
+        // Malformed packs can list the same path more than once
+        let duplicates = mrpack_index.dedup_files();
+        if duplicates > 0 {
+            println!("✅ Removed {} duplicate entries from mrpack", duplicates);
         }
         
         println!("✅ Parsed mrpack with {} unique files", mrpack_index.files.len());
@@ -209,82 +656,93 @@ impl MinecraftUpdater {
         let existing_mods = self.analyze_existing_mods_simple(instance_path).await?;
         println!("📊 Found {} existing mods", existing_mods.len());
 
+        if !dry_run && self.overrides.order == OverrideOrder::BeforeMods {
+            self.apply_overrides(instance_path, mrpack_path).await?;
+        }
+
         // Update mods intelligently
         println!("🔄 Updating mods intelligently...");
-        let update_result = self.update_mods_intelligently(
+        let progress = match instance_path.file_name() {
+            Some(name) => self.progress.for_instance(name.to_string_lossy()),
+            None => self.progress.clone(),
+        };
+        progress.phase_started(Phase::Update);
+        let result = self.update_mods_intelligently(
             instance_path,
             &mrpack_index,
             &existing_mods,
-            &modpack_info,
+            modpack_info,
+            dry_run,
+            &progress,
         ).await?;
 
-        // Clean up temp directory
-        println!("🧹 Cleaning up temporary files...");
-        let _ = fs::remove_dir_all(&temp_dir).await;
+        if !dry_run && self.overrides.order == OverrideOrder::AfterMods {
+            self.apply_overrides(instance_path, mrpack_path).await?;
+        }
 
-        println!("✅ Update completed successfully!");
-        Ok(update_result)
+        if !dry_run {
+            // Packs this installer couldn't install itself still update; they just don't get a manifest
+            if let Err(e) = self.write_install_manifest(instance_path, &mrpack_index).await {
+                warn!("Could not rewrite the install manifest: {}", e);
+            }
+        }
+
+        progress.log(result.message.clone());
+        progress.phase_completed(Phase::Update);
+        Ok(result)
+    }
+
+    /// Extract the pack's overrides into the instance's game directory according to the configured policy
+    async fn apply_overrides(&self, instance_path: &Path, mrpack_path: &Path) -> Result<()> {
+        println!("📂 Applying pack overrides ({:?})...", self.overrides.policy);
+        let written = self.launcher_manager
+            .apply_mrpack_overrides(mrpack_path, &minecraft_base_dir(instance_path), self.overrides.policy)
+            .await?;
+        println!("✅ Wrote {} override files", written);
+        Ok(())
     }
 
     /// Extract mrpack index from downloaded mrpack file
     async fn extract_mrpack_index(&self, mrpack_path: &Path) -> Result<String> {
-        use zip::ZipArchive;
-        use std::io::Read;
-        
         let file = std::fs::File::open(mrpack_path)?;
-        let mut archive = ZipArchive::new(file)?;
-        
-        // Look for modrinth.index.json in the mrpack
-        let mut index_file = archive.by_name("modrinth.index.json")
-            .map_err(|_| MinecraftInstallerError::InstallationFailed(
-                "modrinth.index.json not found in mrpack".to_string()
-            ))?;
-        
-        let mut contents = String::new();
-        index_file.read_to_string(&mut contents)?;
-        
-        Ok(contents)
+        let mut archive = zip::ZipArchive::new(file)?;
+        read_mrpack_index(&mut archive)
     }
 
-    /// Get previous version mrpack for comparison
-    async fn get_previous_version_mrpack(&self, modpack_info: &NahaModpackInfo) -> Result<Option<PathBuf>> {
+    /// Download the mrpack of the release before the latest one, for comparing mod lists
+    ///
+    /// Only the first page of releases is read; `None` when it has no earlier pack of this type.
+    pub async fn get_previous_version_mrpack(&self, modpack_info: &NahaModpackInfo) -> Result<Option<PathBuf>> {
         println!("🔍 Looking for previous version for comparison...");
         
-        // Use GitHub API to get all releases
-        let api_url = "https://api.github.com/repos/perlytiara/NAHA-Minecraft-Modpacks/releases";
-        let client = reqwest::Client::new();
-        let response = client.get(api_url)
-            .header("User-Agent", "Minecraft-Installer/1.0")
-            .send().await?;
-
-        if !response.status().is_success() {
-            println!("⚠️  Could not fetch previous releases, skipping comparison");
-            return Ok(None);
-        }
-
-        let releases: Vec<serde_json::Value> = response.json().await?;
+        let releases: Vec<GithubRelease> = match fetch_github_releases(
+            &self.http, &self.modpack_releases_api_url, PREVIOUS_RELEASES_PER_PAGE, MAX_RELEASES_PAGE_BYTES,
+        ).await {
+            Ok(releases) => releases,
+            Err(MinecraftInstallerError::Network(e)) => {
+                debug!("{}", e);
+                println!("⚠️  Could not fetch previous releases, skipping comparison");
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
         
         // Find the previous release (not the latest)
         for release in releases.iter().skip(1) { // Skip latest release
-            let empty_vec = vec![];
-            let assets = release["assets"].as_array().unwrap_or(&empty_vec);
+            let assets = &release.assets;
             let mrpack_asset = if modpack_info.server_type == "neoforge" {
                 assets.iter().find(|asset| {
-                    let name = asset["name"].as_str().unwrap_or("");
-                    (name.contains("NeoForge") || name.contains("Neoforge")) && name.ends_with(".mrpack")
+                    (asset.name.contains("NeoForge") || asset.name.contains("Neoforge")) && asset.name.ends_with(".mrpack")
                 })
             } else if modpack_info.server_type == "fabric" {
-                assets.iter().find(|asset| {
-                    let name = asset["name"].as_str().unwrap_or("");
-                    name.contains("Fabric") && name.ends_with(".mrpack")
-                })
+                assets.iter().find(|asset| asset.name.contains("Fabric") && asset.name.ends_with(".mrpack"))
             } else {
                 None
             };
 
             if let Some(asset) = mrpack_asset {
-                let download_url = asset["browser_download_url"].as_str().unwrap_or("");
-                let filename = asset["name"].as_str().unwrap_or("previous.mrpack");
+                let download_url = &asset.browser_download_url;
+                let filename = &asset.name;
                 
                 println!("📥 Downloading previous version: {}", filename);
                 
@@ -293,7 +751,12 @@ impl MinecraftUpdater {
                 fs::create_dir_all(&temp_dir).await?;
                 let mrpack_path = temp_dir.join(filename);
                 
-                let response = client.get(download_url).send().await?;
+                let response = self.http.get(download_url).send().await?;
+                if !response.status().is_success() {
+                    return Err(MinecraftInstallerError::DownloadFailed(format!(
+                        "HTTP {} for {}", response.status(), download_url
+                    )));
+                }
                 let content = response.bytes().await?;
                 fs::write(&mrpack_path, content).await?;
                 
@@ -309,17 +772,12 @@ impl MinecraftUpdater {
     /// Extract mods from a mrpack file
     async fn extract_mods_from_mrpack(&self, mrpack_path: &Path) -> Result<Vec<ModInfo>> {
         use zip::ZipArchive;
-        use std::io::Read;
         
         let file = std::fs::File::open(mrpack_path)?;
         let mut archive = ZipArchive::new(file)?;
         let mut mods = Vec::new();
         
-        // Extract modrinth.index.json
-        let mut index_file = archive.by_name("modrinth.index.json")?;
-        let mut contents = String::new();
-        index_file.read_to_string(&mut contents)?;
-        
+        let contents = read_mrpack_index(&mut archive)?;
         let index_data: serde_json::Value = serde_json::from_str(&contents)?;
         if let Some(files) = index_data["files"].as_array() {
             for file in files {
@@ -338,6 +796,7 @@ impl MinecraftUpdater {
                             file_size: 0,
                             last_modified: "unknown".to_string(),
                             mod_id: None,
+                            sha1: None,
                         };
                         mods.push(mod_info);
                     }
@@ -352,7 +811,7 @@ impl MinecraftUpdater {
     fn create_mod_map(&self, mods: &[ModInfo]) -> HashMap<String, ModInfo> {
         let mut map = HashMap::new();
         for mod_info in mods {
-            let normalized_name = self.normalize_mod_name(&mod_info.name);
+            let normalized_name = normalize_mod_name(&mod_info.name);
             map.insert(normalized_name, mod_info.clone());
         }
         map
@@ -362,7 +821,7 @@ impl MinecraftUpdater {
     fn create_mod_map_from_hashmap(&self, mods: &HashMap<String, ModInfo>) -> HashMap<String, ModInfo> {
         let mut map = HashMap::new();
         for (_, mod_info) in mods {
-            let normalized_name = self.normalize_mod_name(&mod_info.name);
+            let normalized_name = normalize_mod_name(&mod_info.name);
             map.insert(normalized_name, mod_info.clone());
         }
         map
@@ -377,7 +836,7 @@ impl MinecraftUpdater {
                     .unwrap()
                     .to_string_lossy()
                     .to_string();
-                let normalized_name = self.normalize_mod_name(&filename);
+                let normalized_name = normalize_mod_name(&filename);
                 let mod_info = ModInfo {
                     name: filename.clone(),
                     filename: filename,
@@ -386,6 +845,7 @@ impl MinecraftUpdater {
                     file_size: 0,
                     last_modified: "unknown".to_string(),
                     mod_id: None,
+                    sha1: None,
                 };
                 map.insert(normalized_name, mod_info);
             }
@@ -393,59 +853,39 @@ impl MinecraftUpdater {
         map
     }
 
-    /// Normalize mod name for comparison
-    fn normalize_mod_name(&self, name: &str) -> String {
-        let name = name.to_lowercase().replace(".jar", "").replace(".disabled", "");
-        
-        // Handle complex mod names with $ separators first
-        let name = if name.contains('$') {
-            name.split('$').next().unwrap_or(&name).to_string()
-        } else {
-            name
+    /// Read an instance's `.naha-pins.json`, keyed by normalized mod name; no file means no pins
+    pub async fn load_mod_pins(&self, instance_path: &Path) -> Result<HashMap<String, ModPin>> {
+        let pins_path = instance_path.join(MOD_PINS_FILE);
+        let content = match fs::read_to_string(&pins_path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(e.into()),
         };
-        
-        // Extract just the base mod name (first part before version patterns)
-        let parts: Vec<&str> = name.split('-').collect();
-        
-        if parts.is_empty() {
-            return name;
-        }
-        
-        // Special handling for multi-word mod names
-        // "sodium-extra" should stay as "sodium-extra", not just "sodium"
-        if parts.len() >= 2 {
-            let second_part = parts[1];
-            // If second part is NOT a version number (doesn't start with digit), include it
-            if !second_part.chars().next().unwrap_or('0').is_ascii_digit() {
-                return format!("{}-{}", parts[0], parts[1]);
-            }
-        }
-        
-        // Just return the first part - the actual mod name
-        // "badoptimizations-2.3.0-1.21.1" -> "badoptimizations"
-        // "bocchud-0.4.0+mc1.21.1" -> "bocchud"
-        // "chat_heads-0.14.0-neoforge-1.21" -> "chat_heads"
-        // "sodium-extra-0.6.0" -> "sodium-extra"
-        parts[0].to_string()
+
+        let pins: HashMap<String, ModPin> = serde_json::from_str(&content).map_err(|e| {
+            MinecraftInstallerError::Validation(format!("Invalid {}: {}", pins_path.display(), e))
+        })?;
+        Ok(pins.into_iter().map(|(name, pin)| (normalize_mod_name(&name), pin)).collect())
     }
 
     /// Clean up duplicate mods
     async fn cleanup_duplicate_mods(&self, mods_dir: &Path) -> Result<()> {
         let mut entries = fs::read_dir(mods_dir).await?;
-        let mut mod_groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        let mut mod_groups: HashMap<(String, Option<String>), Vec<PathBuf>> = HashMap::new();
 
-        // Group mods by normalized name
+        // Group mods by normalized name and declared mod id, so different mods sharing a name prefix stay apart
         while let Some(entry) = entries.next_entry().await? {
             let path = entry.path();
             if path.extension().map_or(false, |ext| ext == "jar") {
                 let filename = path.file_name().unwrap().to_string_lossy();
-                let normalized = self.normalize_mod_name(&filename);
-                mod_groups.entry(normalized).or_insert_with(Vec::new).push(path);
+                let normalized = normalize_mod_name(&filename);
+                let mod_id = read_jar_mod_id(&path);
+                mod_groups.entry((normalized, mod_id)).or_insert_with(Vec::new).push(path);
             }
         }
 
         // Remove duplicates, keeping the newest version
-        for (normalized_name, mut paths) in mod_groups {
+        for ((normalized_name, _), mut paths) in mod_groups {
             if paths.len() > 1 {
                 println!("🔍 Found {} duplicates for mod: {}", paths.len(), normalized_name);
                 
@@ -470,541 +910,6 @@ impl MinecraftUpdater {
         Ok(())
     }
 
-    /// Scan AstralRinth/ModrinthApp profiles
-    async fn scan_astralrinth_profiles(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
-        let mut instances = Vec::new();
-        let profiles_dir = launcher_path.join("profiles");
-
-        if !profiles_dir.exists() {
-            return Ok(instances);
-        }
-
-        let mut entries = fs::read_dir(&profiles_dir).await?;
-        while let Some(entry) = entries.next_entry().await? {
-            let profile_path = entry.path();
-            if profile_path.is_dir() {
-                if let Some(instance_info) = self.analyze_astralrinth_profile(&profile_path, launcher_path).await? {
-                    instances.push(instance_info);
-                }
-            }
-        }
-
-        Ok(instances)
-    }
-
-    /// Analyze a single AstralRinth profile
-    async fn analyze_astralrinth_profile(
-        &self,
-        profile_path: &Path,
-        launcher_path: &Path,
-    ) -> Result<Option<InstanceInfo>> {
-        // AstralRinth stores profile info in the database, not in profile.json files
-        // For now, we'll extract basic info from the folder structure
-        let profile_name = profile_path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("Unknown")
-            .to_string();
-        
-        // Try to determine mod loader from folder name
-        let (mod_loader, mod_loader_version) = if profile_name.to_lowercase().contains("neoforge") {
-            ("NeoForge".to_string(), None)
-        } else if profile_name.to_lowercase().contains("fabric") {
-            ("Fabric".to_string(), None)
-        } else if profile_name.to_lowercase().contains("forge") {
-            ("Forge".to_string(), None)
-        } else {
-            ("Unknown".to_string(), None)
-        };
-        
-        // Try to extract Minecraft version from folder name
-        let minecraft_version = if let Some(version_match) = profile_name.split('-').find(|part| {
-            part.starts_with("1.") && part.len() >= 3
-        }) {
-            version_match.to_string()
-        } else {
-            "Unknown".to_string()
-        };
-
-        // Analyze mods
-        let mods_dir = profile_path.join("mods");
-        let (mods, mod_count) = if mods_dir.exists() {
-            let mods = self.analyze_mods_directory(&mods_dir).await?;
-            (mods.clone(), mods.len())
-        } else {
-            (Vec::new(), 0)
-        };
-
-        // Check for automodpack
-        let has_automodpack = profile_path.join("automodpack-known-hosts.json").exists();
-        let server_info = if has_automodpack {
-            self.extract_server_info(profile_path).await.ok()
-        } else {
-            None
-        };
-
-        // Determine launcher type based on the launcher path
-        let launcher_type = if launcher_path.to_string_lossy().contains("ModrinthApp") {
-            "ModrinthApp"
-        } else {
-            "AstralRinth"
-        };
-
-        Ok(Some(InstanceInfo {
-            name: profile_name,
-            launcher_type: launcher_type.to_string(),
-            launcher_path: launcher_path.to_string_lossy().to_string(),
-            instance_path: profile_path.to_string_lossy().to_string(),
-            minecraft_version,
-            mod_loader,
-            mod_loader_version,
-            mod_count,
-            mods,
-            has_automodpack,
-            server_info,
-            last_updated: None, // TODO: Extract from profile metadata
-        }))
-    }
-
-    /// Scan XMCL instances
-    async fn scan_xmcl_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
-        let mut instances = Vec::new();
-        let instances_dir = launcher_path.join("instances");
-
-        if !instances_dir.exists() {
-            return Ok(instances);
-        }
-
-        let mut entries = fs::read_dir(&instances_dir).await?;
-        while let Some(entry) = entries.next_entry().await? {
-            let instance_path = entry.path();
-            if instance_path.is_dir() {
-                if let Some(instance_info) = self.analyze_xmcl_instance(&instance_path, launcher_path).await? {
-                    instances.push(instance_info);
-                }
-            }
-        }
-
-        Ok(instances)
-    }
-
-    /// Analyze a single XMCL instance
-    async fn analyze_xmcl_instance(
-        &self,
-        instance_path: &Path,
-        launcher_path: &Path,
-    ) -> Result<Option<InstanceInfo>> {
-        let instance_json_path = instance_path.join("instance.json");
-        if !instance_json_path.exists() {
-            return Ok(None);
-        }
-
-        let instance_content = fs::read_to_string(&instance_json_path).await?;
-        let instance_data: serde_json::Value = serde_json::from_str(&instance_content)?;
-
-        let name = instance_data["name"].as_str().unwrap_or("Unknown").to_string();
-        let minecraft_version = instance_data["runtime"]["minecraft"].as_str().unwrap_or("Unknown").to_string();
-        
-        // Determine mod loader from XMCL runtime structure
-        let (mod_loader, mod_loader_version) = if let Some(neo_forged) = instance_data["runtime"]["neoForged"].as_str() {
-            if !neo_forged.is_empty() {
-                ("NeoForge".to_string(), Some(neo_forged.to_string()))
-            } else if let Some(fabric_loader) = instance_data["runtime"]["fabricLoader"].as_str() {
-                if !fabric_loader.is_empty() {
-                    ("Fabric".to_string(), Some(fabric_loader.to_string()))
-                } else if let Some(forge) = instance_data["runtime"]["forge"].as_str() {
-                    if !forge.is_empty() {
-                        ("Forge".to_string(), Some(forge.to_string()))
-                    } else {
-                        ("Unknown".to_string(), None)
-                    }
-                } else {
-                    ("Unknown".to_string(), None)
-                }
-            } else if let Some(forge) = instance_data["runtime"]["forge"].as_str() {
-                if !forge.is_empty() {
-                    ("Forge".to_string(), Some(forge.to_string()))
-                } else {
-                    ("Unknown".to_string(), None)
-                }
-            } else {
-                ("Unknown".to_string(), None)
-            }
-        } else if let Some(fabric_loader) = instance_data["runtime"]["fabricLoader"].as_str() {
-            if !fabric_loader.is_empty() {
-                ("Fabric".to_string(), Some(fabric_loader.to_string()))
-            } else {
-                ("Unknown".to_string(), None)
-            }
-        } else if let Some(forge) = instance_data["runtime"]["forge"].as_str() {
-            if !forge.is_empty() {
-                ("Forge".to_string(), Some(forge.to_string()))
-            } else {
-                ("Unknown".to_string(), None)
-            }
-        } else {
-            ("Unknown".to_string(), None)
-        };
-
-        // Analyze mods
-        let mods_dir = instance_path.join("mods");
-        let (mods, mod_count) = if mods_dir.exists() {
-            let mods = self.analyze_mods_directory(&mods_dir).await?;
-            (mods.clone(), mods.len())
-        } else {
-            (Vec::new(), 0)
-        };
-
-        // Check for automodpack
-        let has_automodpack = instance_path.join("automodpack-known-hosts.json").exists();
-        let server_info = if has_automodpack {
-            self.extract_server_info(instance_path).await.ok()
-        } else {
-            None
-        };
-
-        Ok(Some(InstanceInfo {
-            name,
-            launcher_type: "XMCL".to_string(),
-            launcher_path: launcher_path.to_string_lossy().to_string(),
-            instance_path: instance_path.to_string_lossy().to_string(),
-            minecraft_version,
-            mod_loader,
-            mod_loader_version,
-            mod_count,
-            mods,
-            has_automodpack,
-            server_info,
-            last_updated: None,
-        }))
-    }
-
-    /// Scan PrismLauncher instances
-    async fn scan_prism_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
-        let mut instances = Vec::new();
-        let instances_dir = launcher_path.join("instances");
-
-        if !instances_dir.exists() {
-            return Ok(instances);
-        }
-
-        let mut entries = fs::read_dir(&instances_dir).await?;
-        while let Some(entry) = entries.next_entry().await? {
-            let instance_path = entry.path();
-            if instance_path.is_dir() {
-                if let Some(instance_info) = self.analyze_prism_instance(&instance_path, launcher_path).await? {
-                    instances.push(instance_info);
-                }
-            }
-        }
-
-        Ok(instances)
-    }
-
-    /// Analyze a single PrismLauncher instance
-    async fn analyze_prism_instance(
-        &self,
-        instance_path: &Path,
-        launcher_path: &Path,
-    ) -> Result<Option<InstanceInfo>> {
-        let instance_cfg_path = instance_path.join("instance.cfg");
-        let mmc_pack_path = instance_path.join("mmc-pack.json");
-
-        if !instance_cfg_path.exists() || !mmc_pack_path.exists() {
-            return Ok(None);
-        }
-
-        // Read instance name from instance.cfg
-        let instance_cfg_content = fs::read_to_string(&instance_cfg_path).await?;
-        let name = instance_cfg_content
-            .lines()
-            .find(|line| line.starts_with("name="))
-            .and_then(|line| line.split('=').nth(1))
-            .unwrap_or("Unknown")
-            .to_string();
-
-        // Read pack info from mmc-pack.json
-        let mmc_pack_content = fs::read_to_string(&mmc_pack_path).await?;
-        let mmc_pack_data: serde_json::Value = serde_json::from_str(&mmc_pack_content)?;
-
-        let minecraft_version = mmc_pack_data["components"]
-            .as_array()
-            .and_then(|components| {
-                components.iter().find(|c| c["cachedName"].as_str() == Some("Minecraft"))
-            })
-            .and_then(|mc| mc["version"].as_str())
-            .unwrap_or("Unknown")
-            .to_string();
-
-        // Determine mod loader from components
-        let (mod_loader, mod_loader_version) = if let Some(components) = mmc_pack_data["components"].as_array() {
-            if let Some(fabric_loader) = components.iter().find(|c| {
-                let name = c["cachedName"].as_str().unwrap_or("");
-                name.contains("Fabric") || name.contains("fabric")
-            }) {
-                let version = fabric_loader["cachedVersion"].as_str().unwrap_or("Unknown");
-                ("Fabric".to_string(), Some(version.to_string()))
-            } else if let Some(forge_loader) = components.iter().find(|c| {
-                let name = c["cachedName"].as_str().unwrap_or("");
-                name.contains("Forge") || name.contains("forge")
-            }) {
-                let version = forge_loader["cachedVersion"].as_str().unwrap_or("Unknown");
-                ("Forge".to_string(), Some(version.to_string()))
-            } else if let Some(neoforge_loader) = components.iter().find(|c| {
-                let name = c["cachedName"].as_str().unwrap_or("");
-                name.contains("NeoForge") || name.contains("neoforge")
-            }) {
-                let version = neoforge_loader["cachedVersion"].as_str().unwrap_or("Unknown");
-                ("NeoForge".to_string(), Some(version.to_string()))
-            } else {
-                ("Unknown".to_string(), None)
-            }
-        } else {
-            ("Unknown".to_string(), None)
-        };
-
-        // Analyze mods
-        let mods_dir = instance_path.join("minecraft").join("mods");
-        let (mods, mod_count) = if mods_dir.exists() {
-            let mods = self.analyze_mods_directory(&mods_dir).await?;
-            (mods.clone(), mods.len())
-        } else {
-            (Vec::new(), 0)
-        };
-
-        // Check for automodpack
-        let minecraft_dir = instance_path.join(".minecraft");
-        let has_automodpack = minecraft_dir.join("automodpack-known-hosts.json").exists();
-        let server_info = if has_automodpack {
-            self.extract_server_info(&minecraft_dir).await.ok()
-        } else {
-            None
-        };
-
-        Ok(Some(InstanceInfo {
-            name,
-            launcher_type: "PrismLauncher".to_string(),
-            launcher_path: launcher_path.to_string_lossy().to_string(),
-            instance_path: instance_path.to_string_lossy().to_string(),
-            minecraft_version,
-            mod_loader,
-            mod_loader_version: None,
-            mod_count,
-            mods,
-            has_automodpack,
-            server_info,
-            last_updated: None,
-        }))
-    }
-
-    /// Scan Official Minecraft Launcher instances
-    async fn scan_official_instances(&self, launcher_path: &Path) -> Result<Vec<InstanceInfo>> {
-        let mut instances = Vec::new();
-        let profiles_path = launcher_path.join("launcher_profiles.json");
-
-        if !profiles_path.exists() {
-            return Ok(instances);
-        }
-
-        let profiles_content = fs::read_to_string(&profiles_path).await?;
-        let profiles_data: serde_json::Value = serde_json::from_str(&profiles_content)?;
-
-        if let Some(profiles) = profiles_data["profiles"].as_object() {
-            for (profile_id, profile) in profiles {
-                if let Some(instance_info) = self.analyze_official_profile(profile_id, profile, launcher_path).await? {
-                    instances.push(instance_info);
-                }
-            }
-        }
-
-        Ok(instances)
-    }
-
-    /// Analyze a single Official Minecraft profile
-    async fn analyze_official_profile(
-        &self,
-        _profile_id: &str,
-        profile: &serde_json::Value,
-        launcher_path: &Path,
-    ) -> Result<Option<InstanceInfo>> {
-        let name = profile["name"].as_str().unwrap_or("Unknown").to_string();
-        let minecraft_version = profile["lastVersionId"].as_str().unwrap_or("Unknown").to_string();
-
-        // Official launcher doesn't have mod loaders by default
-        let mod_loader = "Vanilla".to_string();
-
-        // Check if this is a modded profile by looking for mods directory
-        let game_dir = profile["gameDir"].as_str()
-            .map(|s| PathBuf::from(s))
-            .unwrap_or_else(|| launcher_path.to_path_buf());
-
-        let mods_dir = game_dir.join("mods");
-        let (mods, mod_count) = if mods_dir.exists() {
-            let mods = self.analyze_mods_directory(&mods_dir).await?;
-            (mods.clone(), mods.len())
-        } else {
-            (Vec::new(), 0)
-        };
-
-        // Check for automodpack
-        let has_automodpack = game_dir.join("automodpack-known-hosts.json").exists();
-        let server_info = if has_automodpack {
-            self.extract_server_info(&game_dir).await.ok()
-        } else {
-            None
-        };
-
-        Ok(Some(InstanceInfo {
-            name,
-            launcher_type: "Official".to_string(),
-            launcher_path: launcher_path.to_string_lossy().to_string(),
-            instance_path: game_dir.to_string_lossy().to_string(),
-            minecraft_version,
-            mod_loader,
-            mod_loader_version: None,
-            mod_count,
-            mods,
-            has_automodpack,
-            server_info,
-            last_updated: None,
-        }))
-    }
-
-    /// Analyze mods in a directory
-    async fn analyze_mods_directory(&self, mods_dir: &Path) -> Result<Vec<ModInfo>> {
-        let mut mods = Vec::new();
-        let mut entries = fs::read_dir(mods_dir).await?;
-
-        while let Some(entry) = entries.next_entry().await? {
-            let mod_path = entry.path();
-            if mod_path.extension().and_then(|s| s.to_str()) == Some("jar") {
-                if let Some(mod_info) = self.analyze_mod_file(&mod_path).await? {
-                    mods.push(mod_info);
-                }
-            }
-        }
-
-        Ok(mods)
-    }
-
-    /// Analyze a single mod file
-    async fn analyze_mod_file(&self, mod_path: &Path) -> Result<Option<ModInfo>> {
-        let filename = mod_path.file_name().unwrap().to_string_lossy().to_string();
-        let metadata = fs::metadata(mod_path).await?;
-        let file_size = metadata.len();
-        let last_modified = metadata.modified()?.duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
-        // Try to extract mod information from JAR
-        let (name, version, mod_id) = self.extract_mod_metadata(mod_path).await?;
-
-        // Determine if this is a user mod (not from a known modpack)
-        let is_user_mod = self.is_user_mod(&filename, &name);
-
-        Ok(Some(ModInfo {
-            name,
-            filename,
-            version,
-            mod_id,
-            is_user_mod,
-            file_size,
-            last_modified: chrono::DateTime::from_timestamp(last_modified as i64, 0)
-                .unwrap_or_default()
-                .to_rfc3339(),
-        }))
-    }
-
-    /// Extract mod metadata from JAR file
-    async fn extract_mod_metadata(&self, mod_path: &Path) -> Result<(String, Option<String>, Option<String>)> {
-        // This is a simplified version - in a real implementation, you'd parse the JAR's mods.toml or fabric.mod.json
-        let filename = mod_path.file_name().unwrap().to_string_lossy();
-
-        // Extract name from filename (remove version numbers)
-        let name = filename
-            .replace(".jar", "")
-            .split('-')
-            .next()
-            .unwrap_or(&filename)
-            .to_string();
-
-        // Try to extract version from filename
-        let version = self.extract_version_from_filename(&filename);
-
-        Ok((name, version, None))
-    }
-
-    /// Extract version from filename
-    fn extract_version_from_filename(&self, filename: &str) -> Option<String> {
-        // Look for version patterns like -1.0.0, -1.0, etc.
-        let parts: Vec<&str> = filename.split('-').collect();
-        if parts.len() > 1 {
-            let last_part = parts.last().unwrap().replace(".jar", "");
-            if last_part.chars().any(|c| c.is_ascii_digit()) {
-                return Some(last_part);
-            }
-        }
-        None
-    }
-
-    /// Determine if a mod is user-added
-    fn is_user_mod(&self, filename: &str, name: &str) -> bool {
-        // Known modpack mods that should be updated
-        let known_modpack_mods = [
-            "sodium", "iris", "lithium", "phosphor", "fabric-api", "neoforge",
-            "jei", "jade", "wthit", "modmenu", "cloth-config", "auto-config",
-        ];
-
-        let lowercase_name = name.to_lowercase();
-        let lowercase_filename = filename.to_lowercase();
-
-        // Check if it's a known modpack mod
-        for known_mod in &known_modpack_mods {
-            if lowercase_name.contains(known_mod) || lowercase_filename.contains(known_mod) {
-                return false; // This is a modpack mod, not a user mod
-            }
-        }
-
-        // If it doesn't match known modpack patterns, assume it's user-added
-        true
-    }
-
-    /// Extract server information from automodpack files
-    async fn extract_server_info(&self, instance_path: &Path) -> Result<ServerInfo> {
-        let known_hosts_path = instance_path.join("automodpack-known-hosts.json");
-        let servers_dat_path = instance_path.join("servers.dat");
-
-        let mut server_ip = "Unknown".to_string();
-        let server_port = 25565;
-        let mut fingerprint = "Unknown".to_string();
-
-        // Read from automodpack-known-hosts.json
-        if known_hosts_path.exists() {
-            let content = fs::read_to_string(&known_hosts_path).await?;
-            if let Ok(hosts_data) = serde_json::from_str::<serde_json::Value>(&content) {
-                if let Some(hosts) = hosts_data["hosts"].as_object() {
-                    for (ip, fp) in hosts {
-                        server_ip = ip.clone();
-                        fingerprint = fp.as_str().unwrap_or("Unknown").to_string();
-                        break; // Take the first server
-                    }
-                }
-            }
-        }
-
-        // Try to extract port from servers.dat if available
-        if servers_dat_path.exists() {
-            // This would require parsing NBT format - simplified for now
-            debug!("servers.dat found but NBT parsing not implemented");
-        }
-
-        Ok(ServerInfo {
-            server_ip,
-            server_port,
-            fingerprint,
-            server_name: "NAHA Server".to_string(),
-        })
-    }
-
     /// Download latest mrpack from API
     async fn download_latest_mrpack(&self, modpack_info: &NahaModpackInfo, temp_dir: &Path) -> Result<PathBuf> {
         info!("Downloading latest mrpack: {}", modpack_info.latest_mrpack);
@@ -1012,8 +917,7 @@ impl MinecraftUpdater {
         let mrpack_path = temp_dir.join("latest.mrpack");
 
         // Download the mrpack file
-        let client = reqwest::Client::new();
-        let response = client.get(&modpack_info.download_url).send().await?;
+        let response = self.http.get(&modpack_info.download_url).send().await?;
 
         if !response.status().is_success() {
             return Err(MinecraftInstallerError::DownloadFailed(
@@ -1028,56 +932,87 @@ impl MinecraftUpdater {
     }
 
     /// Analyze existing mods in an instance
+    ///
+    /// Jars whose install manifest entry still matches their size on disk take its hash and install time;
+    /// the rest are stat'ed.
     async fn analyze_existing_mods_simple(&self, instance_path: &Path) -> Result<HashMap<String, ModInfo>> {
         let mut existing_mods = HashMap::new();
 
         // Find mods directory based on launcher type
         let mods_dir = self.find_mods_directory(instance_path).await?;
+        let manifest = InstallManifest::read(&minecraft_base_dir(instance_path)).await;
+        let installed: HashMap<&str, &ManifestMod> = manifest.iter()
+            .flat_map(|manifest| &manifest.mods)
+            .filter_map(|m| Some((m.path.strip_prefix("mods/")?, m)))
+            .collect();
 
         if mods_dir.exists() {
-            let mut entries = fs::read_dir(&mods_dir).await?;
-            while let Some(entry) = entries.next_entry().await? {
-                let path = entry.path();
-                if path.extension().and_then(|s| s.to_str()) == Some("jar") {
-                    let filename = path.file_name().unwrap().to_string_lossy().to_string();
-                    let normalized_name = self.normalize_mod_name(&filename);
-                    let metadata = fs::metadata(&path).await?;
-                    
-                    let mod_info = ModInfo {
-                        name: normalized_name.clone(),
-                        filename: filename.clone(),
-                        version: None,
-                        is_user_mod: false, // We'll determine this later based on mrpack
-                        file_size: metadata.len(),
-                        last_modified: format!("{:?}", metadata.modified().ok()),
-                        mod_id: None,
-                    };
-                    
-                    existing_mods.insert(normalized_name, mod_info);
-                }
+            for path in self.scanner().collect_mod_jars(&mods_dir).await? {
+                let filename = path.file_name().unwrap().to_string_lossy().to_string();
+                let normalized_name = normalize_mod_name(&filename);
+                // Relative to the mods dir so subfolder mods are updated in place
+                let relative = relative_mod_path(&mods_dir, &path);
+                let metadata = fs::metadata(&path).await?;
+                // A jar replaced by hand since the install no longer matches its entry
+                let recorded = installed.get(relative.as_str()).filter(|m| m.size == metadata.len());
+                let (last_modified, sha1) = match (recorded, &manifest) {
+                    (Some(recorded), Some(manifest)) => (manifest.installed_at.clone(), recorded.sha1.clone()),
+                    _ => (format!("{:?}", metadata.modified().ok()), None),
+                };
+
+                let mod_info = ModInfo {
+                    name: normalized_name.clone(),
+                    filename: relative,
+                    version: None,
+                    is_user_mod: false, // We'll determine this later based on mrpack
+                    file_size: metadata.len(),
+                    last_modified,
+                    mod_id: None,
+                    sha1,
+                };
+                
+                existing_mods.insert(normalized_name, mod_info);
             }
         }
 
         Ok(existing_mods)
     }
 
-    /// Find the mods directory for an instance
-    async fn find_mods_directory(&self, instance_path: &Path) -> Result<PathBuf> {
-        // Try different possible locations
-        let possible_paths = [
-            instance_path.join("mods"),
-            instance_path.join(".minecraft").join("mods"),
-            instance_path.join("minecraft").join("mods"),
-        ];
-
-        for path in &possible_paths {
-            if path.exists() {
-                return Ok(path.clone());
+    /// Rewrite the install manifest after an update so it lists the pack mods now on disk
+    async fn write_install_manifest(&self, instance_path: &Path, mrpack_index: &MrpackIndex) -> Result<()> {
+        let mut manifest = mrpack_install_manifest(mrpack_index)?;
+        let pack_mods: HashMap<&str, &MrpackFile> = mrpack_index.files.iter()
+            .filter(|file| file.path.starts_with("mods/"))
+            .filter_map(|file| Some((Path::new(&file.path).file_name()?.to_str()?, file)))
+            .collect();
+
+        let mods_dir = self.find_mods_directory(instance_path).await?;
+        if mods_dir.exists() {
+            for path in self.scanner().collect_mod_jars(&mods_dir).await? {
+                let Some(file) = path.file_name().and_then(|name| name.to_str()).and_then(|name| pack_mods.get(name)) else {
+                    continue;
+                };
+                let size = fs::metadata(&path).await?.len();
+                // Pinned or hand-replaced jars keep the pack's file name but not its content
+                if size != file.file_size {
+                    continue;
+                }
+                manifest.mods.push(ManifestMod {
+                    path: format!("mods/{}", relative_mod_path(&mods_dir, &path)),
+                    sha1: file.hashes.get("sha1").cloned(),
+                    sha512: file.hashes.get("sha512").cloned(),
+                    size,
+                });
             }
         }
 
-        // Default to instance_path/mods
-        Ok(instance_path.join("mods"))
+        manifest.write(&minecraft_base_dir(instance_path)).await?;
+        Ok(())
+    }
+
+    /// Find the mods directory for an instance
+    async fn find_mods_directory(&self, instance_path: &Path) -> Result<PathBuf> {
+        Ok(self.launcher_manager.find_mods_directory(instance_path))
     }
 
     /// Update mods intelligently
@@ -1087,14 +1022,21 @@ impl MinecraftUpdater {
         mrpack_index: &MrpackIndex,
         existing_mods: &HashMap<String, ModInfo>,
         modpack_info: &NahaModpackInfo,
+        dry_run: bool,
+        progress: &ProgressReporter,
     ) -> Result<UpdateResult> {
         let mut updated_mods = Vec::new();
         let mut new_mods = Vec::new();
         let mut preserved_mods = Vec::new();
+        let mut pinned_mods = Vec::new();
+        let mut already_current = Vec::new();
         let mut errors = Vec::new();
 
+        let pins = self.load_mod_pins(instance_path).await?;
         let mods_dir = self.find_mods_directory(instance_path).await?;
-        fs::create_dir_all(&mods_dir).await?;
+        if !dry_run {
+            fs::create_dir_all(&mods_dir).await?;
+        }
 
         // Build a set of modpack mod names from the mrpack
         let mut modpack_mod_names = std::collections::HashSet::new();
@@ -1104,12 +1046,13 @@ impl MinecraftUpdater {
                     .unwrap()
                     .to_string_lossy()
                     .to_string();
-                let normalized = self.normalize_mod_name(&filename);
+                let normalized = normalize_mod_name(&filename);
                 modpack_mod_names.insert(normalized);
             }
         }
 
-        // Process each file in the mrpack
+        // Decide what each pack mod needs, then download in a second pass so progress totals are known up front
+        let mut downloads: Vec<PlannedModDownload> = Vec::new();
         for mrpack_file in &mrpack_index.files {
             if !mrpack_file.path.starts_with("mods/") {
                 continue; // Skip non-mod files
@@ -1120,47 +1063,107 @@ impl MinecraftUpdater {
                 .to_string_lossy()
                 .to_string();
 
-            let mod_name = self.normalize_mod_name(&mod_filename);
-            let target_path = mods_dir.join(&mod_filename);
+            let mod_name = normalize_mod_name(&mod_filename);
+            let pin = pins.get(&mod_name).copied();
 
             // Check if this mod already exists
             if let Some(existing_mod) = existing_mods.get(&mod_name) {
                 // Check if the filename is exactly the same (already up to date)
-                if existing_mod.filename == mod_filename {
+                let existing_path = mods_dir.join(&existing_mod.filename);
+                // The manifest's hash also catches a jar the pack re-published under the same name
+                let changed = match (&existing_mod.sha1, mrpack_file.hashes.get("sha1")) {
+                    (Some(installed), Some(pack)) => !installed.eq_ignore_ascii_case(pack),
+                    _ => false,
+                };
+                if !changed && existing_path.file_name().and_then(|n| n.to_str()) == Some(mod_filename.as_str()) {
                     // Same file, no update needed - skip it completely
                     continue;
                 }
 
-                // This is a modpack mod with a different version, update it
-                // Remove the old version first
-                let old_path = mods_dir.join(&existing_mod.filename);
-                if old_path.exists() {
-                    let _ = fs::remove_file(&old_path).await;
+                if pin.is_some() {
+                    println!("📌 Pinned: keeping {} (pack has {})", existing_mod.filename, mod_filename);
+                    pinned_mods.push(existing_mod.filename.clone());
+                    continue;
                 }
-                
-                // Download the new version
-                match self.download_mod_file(&mrpack_file, &target_path).await {
-                    Ok(_) => {
-                        println!("🔄 Updated: {} → {}", existing_mod.filename, mod_filename);
-                        updated_mods.push(format!("{} → {}", existing_mod.filename, mod_filename));
-                        info!("Updated mod: {}", mod_filename);
-                    }
-                    Err(e) => {
-                        errors.push(format!("Failed to update {}: {}", mod_filename, e));
-                    }
+
+                if dry_run {
+                    println!("🔄 Would update: {} → {}", existing_mod.filename, mod_filename);
+                    updated_mods.push(format!("{} → {}", existing_mod.filename, mod_filename));
+                    continue;
                 }
+
+                // This is a modpack mod with a different version, update it in whichever folder it lives in
+                let target_path = existing_path.parent()
+                    .map(|parent| parent.join(&mod_filename))
+                    .unwrap_or_else(|| mods_dir.join(&mod_filename));
+                downloads.push(PlannedModDownload {
+                    file: mrpack_file,
+                    filename: mod_filename,
+                    target: target_path,
+                    replaces: Some((existing_path, existing_mod.filename.clone())),
+                });
+            } else if pin == Some(ModPin::Excluded) {
+                println!("🚫 Excluded: {}", mod_filename);
+            } else if dry_run {
+                println!("➕ Would add: {}", mod_filename);
+                new_mods.push(mod_filename.clone());
             } else {
                 // New mod, download it
-                match self.download_mod_file(&mrpack_file, &target_path).await {
-                    Ok(_) => {
+                let target_path = mods_dir.join(&mod_filename);
+                downloads.push(PlannedModDownload { file: mrpack_file, filename: mod_filename, target: target_path, replaces: None });
+            }
+        }
+
+        let total = downloads.len() as u64;
+        if !dry_run {
+            progress.planned(vec![PhaseTotal {
+                phase: Phase::Update,
+                bytes: downloads.iter().map(|download| download.file.file_size).sum(),
+            }]);
+        }
+        for (index, PlannedModDownload { file: mrpack_file, filename: mod_filename, target: target_path, replaces }) in downloads.into_iter().enumerate() {
+            let current = index as u64 + 1;
+            match replaces {
+                Some((existing_path, existing_filename)) => {
+                    // Remove the old version first
+                    if existing_path.exists() {
+                        let _ = fs::remove_file(&existing_path).await;
+                    }
+
+                    // Download the new version
+                    match self.download_mod_file(mrpack_file, &target_path).await {
+                        Ok(false) => {
+                            println!("✓ Already current: {} (removed {})", mod_filename, existing_filename);
+                            progress.progress(Phase::Update, Some(mod_filename.clone()), mrpack_file.file_size, current, total);
+                            already_current.push(mod_filename.clone());
+                        }
+                        Ok(true) => {
+                            println!("🔄 Updated: {} → {}", existing_filename, mod_filename);
+                            progress.progress(Phase::Update, Some(mod_filename.clone()), mrpack_file.file_size, current, total);
+                            updated_mods.push(format!("{} → {}", existing_filename, mod_filename));
+                            info!("Updated mod: {}", mod_filename);
+                        }
+                        Err(e) => {
+                            errors.push(format!("Failed to update {}: {}", mod_filename, e));
+                        }
+                    }
+                }
+                None => match self.download_mod_file(mrpack_file, &target_path).await {
+                    Ok(false) => {
+                        println!("✓ Already current: {}", mod_filename);
+                        progress.progress(Phase::Update, Some(mod_filename.clone()), mrpack_file.file_size, current, total);
+                        already_current.push(mod_filename.clone());
+                    }
+                    Ok(true) => {
                         println!("➕ Added: {}", mod_filename);
+                        progress.progress(Phase::Update, Some(mod_filename.clone()), mrpack_file.file_size, current, total);
                         new_mods.push(mod_filename.clone());
                         info!("Added new mod: {}", mod_filename);
                     }
                     Err(e) => {
                         errors.push(format!("Failed to add {}: {}", mod_filename, e));
                     }
-                }
+                },
             }
         }
         
@@ -1171,6 +1174,24 @@ impl MinecraftUpdater {
             }
         }
 
+        if dry_run {
+            let message = format!("Would update {} mods, add {} new mods, preserve {} user mods",
+                   updated_mods.len(), new_mods.len(), preserved_mods.len());
+
+            return Ok(UpdateResult {
+                instance_name: instance_path.file_name().unwrap().to_string_lossy().to_string(),
+                success: true,
+                updated_mods,
+                new_mods,
+                preserved_mods,
+                errors,
+                message,
+                dry_run: true,
+                pinned_mods,
+                already_current,
+            });
+        }
+
         // Clean up duplicate mods
         println!("🧹 Cleaning up duplicate mods...");
         if let Err(e) = self.cleanup_duplicate_mods(&mods_dir).await {
@@ -1205,53 +1226,12 @@ impl MinecraftUpdater {
             preserved_mods,
             errors,
             message,
+            dry_run: false,
+            pinned_mods,
+            already_current,
         })
     }
 
-    /// Extract mod name from filename
-    fn extract_mod_name_from_filename(&self, filename: &str) -> String {
-        // Remove .jar extension and .disabled
-        let name = filename.replace(".jar", "").replace(".disabled", "");
-        
-        // Handle complex mod names with $ separators
-        if name.contains('$') {
-            // For names like "reinforced-barrels-2.6.1+1.21.1$reinforced-core-4.0.2+1.21.1$cloth-config-fabric-15.0.130-fabric"
-            // Extract just the first part before the first $
-            return name.split('$').next().unwrap_or(&name).to_string();
-        }
-        
-        // Handle special cases where mod names have different patterns
-        let name_lower = name.to_lowercase();
-        
-        // Handle kotlinforforge (special case)
-        if name_lower.starts_with("kotlinforforge") {
-            return "kotlinforforge".to_string();
-        }
-        
-        // Handle yet_another_config_lib (special case)
-        if name_lower.starts_with("yet_another_config_lib") {
-            return "yet_another_config_lib".to_string();
-        }
-        
-        // Handle mods with version patterns like "modname-version" or "modname+version"
-        // Try to extract just the mod name part
-        let parts: Vec<&str> = name.split('-').collect();
-        if parts.len() >= 2 {
-            // Check if the second part looks like a version (contains numbers)
-            let second_part = parts[1];
-            if second_part.chars().any(|c| c.is_ascii_digit()) {
-                // This looks like "modname-version", so just return the mod name
-                return parts[0].to_string();
-            } else {
-                // This might be "modname-loader", so return both
-                return format!("{}-{}", parts[0], parts[1]);
-            }
-        }
-        
-        // Fallback to just the first part
-        parts[0].to_string()
-    }
-
     /// Determine if a mod should be updated
     fn should_update_mod(&self, existing_mod: &ModInfo, _mrpack_file: &MrpackFile) -> bool {
         // For now, always update modpack mods
@@ -1259,16 +1239,20 @@ impl MinecraftUpdater {
         !existing_mod.is_user_mod
     }
 
-    /// Download a mod file from the mrpack
-    async fn download_mod_file(&self, mrpack_file: &MrpackFile, target_path: &Path) -> Result<()> {
+    /// Download a mod file from the mrpack; returns false when `target_path` already has the file's hash
+    async fn download_mod_file(&self, mrpack_file: &MrpackFile, target_path: &Path) -> Result<bool> {
+        if matches_mrpack_hashes(target_path, mrpack_file).await {
+            debug!("{} already matches the pack's hash, skipping download", target_path.display());
+            return Ok(false);
+        }
+
         if mrpack_file.downloads.is_empty() {
             return Err(MinecraftInstallerError::DownloadFailed(
                 "No download URLs available for mod".to_string()
             ));
         }
 
-        let client = reqwest::Client::new();
-        let response = client.get(&mrpack_file.downloads[0]).send().await?;
+        let response = self.http.get(&mrpack_file.downloads[0]).send().await?;
 
         if !response.status().is_success() {
             return Err(MinecraftInstallerError::DownloadFailed(
@@ -1279,7 +1263,7 @@ impl MinecraftUpdater {
         let content = response.bytes().await?;
         fs::write(target_path, content).await?;
 
-        Ok(())
+        Ok(true)
     }
 
     /// Update launcher database for AstralRinth/ModrinthApp
@@ -1338,7 +1322,10 @@ impl MinecraftUpdater {
                             "{}"
                         ]
                     ) {
-                        Ok(_) => println!("✅ Created new database entry for profile: {}", profile_name),
+                        Ok(_) => {
+                            restore_db_icon(&conn, &profile_name, read_instance_icon(instance_path).as_deref());
+                            println!("✅ Created new database entry for profile: {}", profile_name);
+                        }
                         Err(e) => println!("⚠️  Could not create database entry: {}", e),
                     }
                 }
@@ -1353,20 +1340,18 @@ impl MinecraftUpdater {
 
     /// Update automodpack configuration
     async fn update_automodpack_config(&self, instance_path: &Path, modpack_info: &NahaModpackInfo) -> Result<()> {
+        let base_dir = minecraft_base_dir(instance_path);
+
         // Update automodpack-known-hosts.json
-        let known_hosts_path = instance_path.join("automodpack-known-hosts.json");
-          let hosts_data = serde_json::json!({
-            "hosts": {
-                modpack_info.server_ip.clone(): modpack_info.fingerprint.clone()
-            }
-        });
-        fs::write(&known_hosts_path, serde_json::to_string_pretty(&hosts_data)?).await?;
+        let known_hosts_path = base_dir.join("automodpack-known-hosts.json");
+        let servers = modpack_info.all_servers();
+        fs::write(&known_hosts_path, serde_json::to_string_pretty(&automodpack_known_hosts(&servers))?).await?;
 
-        // Update servers.dat if it exists
-        let servers_dat_path = instance_path.join("servers.dat");
+        // Add any pack servers missing from the multiplayer list
+        let servers_dat_path = base_dir.join("servers.dat");
         if servers_dat_path.exists() {
-            // This would require NBT parsing/writing - simplified for now
-            debug!("servers.dat update not implemented (requires NBT library)");
+            let added = add_servers_to_list(&servers_dat_path, &servers)?;
+            debug!("Added {} server(s) to servers.dat", added);
         }
 
         Ok(())
@@ -0,0 +1,119 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, mrpack_bytes, temp_dir, MockServer, Route};
+use minecraft_installer::launcher_support::{LauncherManager, NahaModpackInfo};
+
+fn pack_with_config() -> Vec<u8> {
+    mrpack_bytes(&fabric_index(json!([])), &[("overrides/config/naha.toml", b"pack = true")])
+}
+
+fn api_modpack_info(server: &MockServer) -> NahaModpackInfo {
+    NahaModpackInfo { download_url: server.url("/NAHA-Fabric-1.21.1-0.2.0.mrpack"), ..modpack_info() }
+}
+
+fn prism_launcher() -> (PathBuf, LauncherManager) {
+    let root = temp_dir("api-install-rerun").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    let mut manager = LauncherManager::new();
+    manager.add_search_path(root.clone());
+    (root, manager)
+}
+
+fn instances(root: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(root.join("instances"))
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+async fn install(manager: &LauncherManager, info: &NahaModpackInfo, force: bool) {
+    manager
+        .install_from_modpack_info(info, Some("PrismLauncher"), true, None, Some("NAHA Fabric"), force)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rerun_reuses_the_instance_it_created() {
+    let server = MockServer::start(vec![Route::new("/NAHA-Fabric-1.21.1-0.2.0.mrpack", 200, pack_with_config())]).await;
+    let (root, manager) = prism_launcher();
+    let info = api_modpack_info(&server);
+
+    install(&manager, &info, false).await;
+    let created = instances(&root);
+    assert_eq!(created.len(), 1);
+    let instance = &created[0];
+
+    // A setting changed in the launcher after the first run must survive the second
+    let cfg_path = instance.join("instance.cfg");
+    let cfg = std::fs::read_to_string(&cfg_path).unwrap();
+    std::fs::write(&cfg_path, format!("{}notes=Edited in Prism\n", cfg)).unwrap();
+    std::fs::remove_file(instance.join(".minecraft/config/naha.toml")).unwrap();
+
+    install(&manager, &info, false).await;
+
+    assert_eq!(instances(&root), created);
+    assert!(std::fs::read_to_string(&cfg_path).unwrap().contains("notes=Edited in Prism"));
+    assert_eq!(std::fs::read(instance.join(".minecraft/config/naha.toml")).unwrap(), b"pack = true");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn force_recreates_the_instance_in_place() {
+    let server = MockServer::start(vec![Route::new("/NAHA-Fabric-1.21.1-0.2.0.mrpack", 200, pack_with_config())]).await;
+    let (root, manager) = prism_launcher();
+    let info = api_modpack_info(&server);
+
+    install(&manager, &info, false).await;
+    let created = instances(&root);
+    let cfg_path = created[0].join("instance.cfg");
+    let cfg = std::fs::read_to_string(&cfg_path).unwrap();
+    std::fs::write(&cfg_path, format!("{}notes=Edited in Prism\n", cfg)).unwrap();
+
+    install(&manager, &info, true).await;
+
+    assert_eq!(instances(&root), created);
+    assert!(!std::fs::read_to_string(&cfg_path).unwrap().contains("notes=Edited in Prism"));
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn keep_temp_leaves_the_unpacked_pack_behind() {
+    let server = MockServer::start(vec![Route::new("/NAHA-Fabric-1.21.1-0.2.0.mrpack", 200, pack_with_config())]).await;
+    let (root, mut manager) = prism_launcher();
+    manager.set_keep_temp(true);
+    // A pack type unique to this run, so the kept paths can be told apart from other tests'
+    let mut info = api_modpack_info(&server);
+    info.server_type = format!("keep-temp-{}", std::process::id());
+
+    install(&manager, &info, false).await;
+
+    let prefix = format!("temp-{}-instance-", info.server_type);
+    let kept: Vec<PathBuf> = std::fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.file_name().unwrap().to_string_lossy().starts_with(&prefix))
+        .collect();
+    assert_eq!(kept.len(), 1, "{:?}", kept);
+    assert_eq!(std::fs::read_to_string(kept[0].join("config/naha.toml")).unwrap(), "pack = true");
+    assert_eq!(instances(&root).len(), 1);
+
+    let mrpack_prefix = format!("naha-{}-", info.server_type);
+    for entry in std::fs::read_dir(std::env::temp_dir()).unwrap().flatten() {
+        if entry.file_name().to_string_lossy().starts_with(&mrpack_prefix) {
+            std::fs::remove_file(entry.path()).unwrap();
+        }
+    }
+    std::fs::remove_dir_all(&kept[0]).unwrap();
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::{LauncherManager, ModrinthProfileFormat};
+
+/// Schema written by the app versions this installer was first built against
+const CURRENT_SCHEMA: &str = "CREATE TABLE profiles (
+    path TEXT PRIMARY KEY, name TEXT NOT NULL, icon_path TEXT, game_version TEXT NOT NULL,
+    mod_loader TEXT NOT NULL, install_stage TEXT NOT NULL, created INTEGER NOT NULL, modified INTEGER NOT NULL,
+    groups TEXT NOT NULL, override_extra_launch_args TEXT NOT NULL, override_custom_env_vars TEXT NOT NULL
+);";
+
+/// A later schema: the override columns were dropped and new defaulted ones added; `protocol_version` marks
+/// apps that also read a `metadata` block from profile.json
+const NEWER_SCHEMA: &str = "CREATE TABLE profiles (
+    path TEXT PRIMARY KEY, name TEXT NOT NULL, icon_path TEXT, game_version TEXT NOT NULL,
+    mod_loader TEXT NOT NULL, mod_loader_version TEXT, install_stage TEXT NOT NULL,
+    created INTEGER NOT NULL, modified INTEGER NOT NULL, last_played INTEGER,
+    protocol_version INTEGER NOT NULL DEFAULT 0
+);";
+
+fn astralrinth_launcher(schema: &str) -> PathBuf {
+    let root = temp_dir("app-db-schema").join("AstralRinthApp");
+    std::fs::create_dir_all(root.join("profiles")).unwrap();
+    std::fs::write(root.join("app-window-state.json"), "{}").unwrap();
+    rusqlite::Connection::open(root.join("app.db")).unwrap().execute_batch(schema).unwrap();
+    root
+}
+
+fn modrinth_app(schema: &str) -> PathBuf {
+    let root = temp_dir("app-db-schema").join("ModrinthApp");
+    std::fs::create_dir_all(root.join("profiles")).unwrap();
+    std::fs::write(root.join("app-window-state.json"), "{}").unwrap();
+    rusqlite::Connection::open(root.join("app.db")).unwrap().execute_batch(schema).unwrap();
+    root
+}
+
+fn profile_json(instance: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(instance.join("profile.json")).unwrap()).unwrap()
+}
+
+fn profile_row(root: &Path) -> (String, String, String, String) {
+    rusqlite::Connection::open(root.join("app.db"))
+        .unwrap()
+        .query_row(
+            "SELECT name, game_version, mod_loader, install_stage FROM profiles WHERE path = 'naha-fabric'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap()
+}
+
+#[tokio::test]
+async fn injection_works_against_both_schemas() {
+    for schema in [CURRENT_SCHEMA, NEWER_SCHEMA] {
+        let root = astralrinth_launcher(schema);
+
+        LauncherManager::new()
+            .create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            profile_row(&root),
+            ("NAHA-Fabric".to_string(), "1.21.1".to_string(), "fabric".to_string(), "installed".to_string())
+        );
+
+        std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn reinjection_replaces_the_row_on_the_newer_schema() {
+    let root = astralrinth_launcher(NEWER_SCHEMA);
+    let manager = LauncherManager::new();
+
+    manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None).await.unwrap();
+    manager.create_instance(&root, "NAHA-Fabric", "1.21.2", "fabric", None).await.unwrap();
+
+    let conn = rusqlite::Connection::open(root.join("app.db")).unwrap();
+    let rows: i64 = conn.query_row("SELECT COUNT(*) FROM profiles", [], |row| row.get(0)).unwrap();
+    assert_eq!(rows, 1);
+    assert_eq!(profile_row(&root).1, "1.21.2");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn skipping_the_database_still_creates_the_profile() {
+    let root = astralrinth_launcher(CURRENT_SCHEMA);
+    let db_before = std::fs::read(root.join("app.db")).unwrap();
+
+    let mut manager = LauncherManager::new();
+    manager.set_skip_launcher_db(true);
+    let profile = manager
+        .create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None)
+        .await
+        .unwrap();
+
+    assert_eq!(profile, root.join("profiles").join("naha-fabric"));
+    let profile_json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(profile.join("profile.json")).unwrap()).unwrap();
+    assert_eq!(profile_json["name"], "NAHA-Fabric");
+    assert_eq!(profile_json["game_version"], "1.21.1");
+    for dir in ["mods", "config", "saves", "resourcepacks"] {
+        assert!(profile.join(dir).is_dir(), "{} missing", dir);
+    }
+    assert!(profile.join("options.txt").is_file());
+    assert_eq!(std::fs::read(root.join("app.db")).unwrap(), db_before);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn legacy_modrinth_app_gets_only_the_database_row() {
+    let root = modrinth_app(CURRENT_SCHEMA);
+    let conn = rusqlite::Connection::open(root.join("app.db")).unwrap();
+    assert_eq!(ModrinthProfileFormat::detect(&conn), ModrinthProfileFormat::Legacy);
+
+    let instance = LauncherManager::new()
+        .create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5"))
+        .await
+        .unwrap();
+
+    assert_eq!(profile_row(&root).0, "NAHA-Fabric");
+    let profile = profile_json(&instance);
+    assert!(profile.get("metadata").is_none(), "{}", profile);
+    assert!(profile.get("uuid").is_none(), "{}", profile);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn newer_modrinth_app_gets_the_row_and_profile_metadata() {
+    let root = modrinth_app(NEWER_SCHEMA);
+    let conn = rusqlite::Connection::open(root.join("app.db")).unwrap();
+    assert_eq!(ModrinthProfileFormat::detect(&conn), ModrinthProfileFormat::V2);
+
+    let instance = LauncherManager::new()
+        .create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5"))
+        .await
+        .unwrap();
+
+    assert_eq!(profile_row(&root).0, "NAHA-Fabric");
+    let profile = profile_json(&instance);
+    assert!(profile["uuid"].as_str().is_some_and(|uuid| !uuid.is_empty()), "{}", profile);
+    let metadata = &profile["metadata"];
+    assert_eq!(metadata["name"], "NAHA-Fabric");
+    assert_eq!(metadata["game_version"], "1.21.1");
+    assert_eq!(metadata["loader"], "fabric");
+    assert_eq!(metadata["loader_version"], "0.16.5");
+    assert_eq!(metadata["groups"], serde_json::json!([]));
+    // The top-level fields stay for tools that read them
+    assert_eq!(profile["game_version"], "1.21.1");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use serde_json::json;
+use sha1_smol::Sha1;
+
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::download::DownloadManager;
+
+fn sha1_hex(data: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    hex::encode(hasher.digest().bytes())
+}
+
+async fn temp_dirs() -> DirectoryManager {
+    let base_dir: PathBuf = std::env::temp_dir().join(format!("asset-verify-{}", uuid::Uuid::new_v4()));
+    let dirs = DirectoryManager::new(base_dir);
+    dirs.init().await.unwrap();
+    dirs
+}
+
+#[tokio::test]
+async fn flags_missing_and_corrupt_assets() {
+    let dirs = temp_dirs().await;
+
+    let good = b"good asset".to_vec();
+    let bad = b"expected contents".to_vec();
+    let absent = b"never written".to_vec();
+    let (good_hash, bad_hash, absent_hash) = (sha1_hex(&good), sha1_hex(&bad), sha1_hex(&absent));
+
+    let index = json!({
+        "objects": {
+            "minecraft/sounds/good.ogg": { "hash": good_hash, "size": good.len() },
+            "minecraft/lang/bad.json": { "hash": bad_hash, "size": bad.len() },
+            "minecraft/textures/absent.png": { "hash": absent_hash, "size": absent.len() }
+        }
+    });
+    std::fs::write(dirs.assets_index_dir().join("17.json"), index.to_string()).unwrap();
+
+    for (hash, data) in [(&good_hash, &good[..]), (&bad_hash, &b"tampered contents"[..])] {
+        let path = dirs.asset_object_path(hash);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, data).unwrap();
+    }
+
+    let verification = DownloadManager::new(dirs.clone()).verify_assets("17").await.unwrap();
+
+    assert_eq!(verification.checked, 3);
+    assert_eq!(verification.missing, vec!["minecraft/textures/absent.png".to_string()]);
+    assert_eq!(verification.corrupt, vec!["minecraft/lang/bad.json".to_string()]);
+    assert!(!verification.is_intact());
+
+    std::fs::remove_dir_all(&dirs.base_dir).unwrap();
+}
+
+#[tokio::test]
+async fn intact_assets_pass() {
+    let dirs = temp_dirs().await;
+
+    let data = b"sound".to_vec();
+    let hash = sha1_hex(&data);
+    let index = json!({ "objects": { "minecraft/sounds/a.ogg": { "hash": hash, "size": data.len() } } });
+    std::fs::write(dirs.assets_index_dir().join("17.json"), index.to_string()).unwrap();
+
+    let path = dirs.asset_object_path(&hash);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, &data).unwrap();
+
+    let verification = DownloadManager::new(dirs.clone()).verify_assets("17").await.unwrap();
+    assert!(verification.is_intact());
+    assert_eq!(verification.checked, 1);
+
+    std::fs::remove_dir_all(&dirs.base_dir).unwrap();
+}
+
+#[tokio::test]
+async fn missing_index_is_an_error() {
+    let dirs = temp_dirs().await;
+
+    assert!(DownloadManager::new(dirs.clone()).verify_assets("17").await.is_err());
+
+    std::fs::remove_dir_all(&dirs.base_dir).unwrap();
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use std::path::Path;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::LauncherType;
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn create_app_db(launcher_path: &Path) {
+    let conn = rusqlite::Connection::open(launcher_path.join("app.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE profiles (
+            path TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            game_version TEXT NOT NULL,
+            mod_loader TEXT NOT NULL,
+            mod_loader_version TEXT,
+            install_stage TEXT NOT NULL,
+            created INTEGER NOT NULL,
+            modified INTEGER NOT NULL
+        );
+        INSERT INTO profiles VALUES
+            ('my-cool-pack', 'My Cool Pack', '1.21.1', 'neoforge', '21.1.65', 'installed', 1728345600000, 1728345600000);",
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn profile_details_come_from_app_db() {
+    let root = temp_dir("astralrinth-scan").join("AstralRinthApp");
+    let profile = root.join("profiles").join("my-cool-pack");
+    std::fs::create_dir_all(profile.join("mods")).unwrap();
+    std::fs::write(profile.join("mods").join("jei-19.0.0.jar"), b"jei").unwrap();
+    create_app_db(&root);
+
+    let instances = MinecraftUpdater::new()
+        .scan_launcher(&LauncherType::AstralRinth, &root)
+        .await
+        .unwrap();
+
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].name, "My Cool Pack");
+    assert_eq!(instances[0].minecraft_version, "1.21.1");
+    assert_eq!(instances[0].mod_loader, "NeoForge");
+    assert_eq!(instances[0].mod_loader_version.as_deref(), Some("21.1.65"));
+    assert_eq!(instances[0].mod_count, 1);
+    assert_eq!(instances[0].last_updated.as_deref(), Some("2024-10-08T00:00:00+00:00"));
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn missing_row_falls_back_to_folder_name() {
+    let root = temp_dir("astralrinth-scan").join("AstralRinthApp");
+    std::fs::create_dir_all(root.join("profiles").join("NAHA-Fabric-1.21.1")).unwrap();
+    create_app_db(&root);
+
+    let instances = MinecraftUpdater::new()
+        .scan_launcher(&LauncherType::AstralRinth, &root)
+        .await
+        .unwrap();
+
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].name, "NAHA-Fabric-1.21.1");
+    assert_eq!(instances[0].minecraft_version, "1.21.1");
+    assert_eq!(instances[0].mod_loader, "Fabric");
+    assert_eq!(instances[0].mod_loader_version, None);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use serde_json::json;
+
+use common::{MockServer, Route};
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn release(tag: &str) -> serde_json::Value {
+    json!({
+        "tag_name": tag,
+        "html_url": format!("https://github.com/perlytiara/NAHA-MC-Helper/releases/tag/{}", tag),
+        "published_at": "2025-01-01T00:00:00Z",
+        "draft": false,
+        "prerelease": false
+    })
+}
+
+#[tokio::test]
+async fn reports_newest_versioned_release() {
+    let mut prerelease = release("v100.0.0-rc1");
+    prerelease["prerelease"] = json!(true);
+    let releases = json!([release("latest"), prerelease, release("v99.1.0"), release("v99.0.3"), release("v0.0.1")]);
+    let server = MockServer::start(vec![Route::new("/releases", 200, releases.to_string())]).await;
+
+    let update = MinecraftUpdater::new()
+        .with_releases_api_url(server.url("/releases"))
+        .check_binary_update()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(update.latest_version, "99.1.0");
+    assert_eq!(update.tag, "v99.1.0");
+    assert_eq!(update.current_version, env!("CARGO_PKG_VERSION"));
+    assert!(update.release_url.ends_with("/v99.1.0"));
+}
+
+#[tokio::test]
+async fn no_update_when_only_older_or_unversioned_releases() {
+    let releases = json!([release("latest"), release("v0.0.1")]);
+    let server = MockServer::start(vec![Route::new("/releases", 200, releases.to_string())]).await;
+
+    let update = MinecraftUpdater::new()
+        .with_releases_api_url(server.url("/releases"))
+        .check_binary_update()
+        .await
+        .unwrap();
+
+    assert!(update.is_none());
+}
+
+#[tokio::test]
+async fn failed_request_is_an_error() {
+    let server = MockServer::start(vec![Route::new("/releases", 403, "rate limited")]).await;
+
+    let result = MinecraftUpdater::new()
+        .with_releases_api_url(server.url("/releases"))
+        .check_binary_update()
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+use common::{fabric_index, sha1_hex, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::events::{ProgressEvent, ProgressReporter};
+use minecraft_installer::launcher_support::LauncherManager;
+use minecraft_installer::CancellationToken;
+
+const MODS: [(&str, &[u8]); 3] = [
+    ("sodium.jar", b"sodium"),
+    ("lithium.jar", b"lithium"),
+    ("iris.jar", b"iris"),
+];
+
+fn write_pack(path: &Path, server: &MockServer) {
+    let files: Vec<_> = MODS
+        .iter()
+        .map(|(name, body)| {
+            json!({
+                "path": format!("mods/{}", name),
+                "hashes": { "sha1": sha1_hex(body) },
+                "downloads": [server.url(&format!("/{}", name))],
+                "fileSize": body.len()
+            })
+        })
+        .collect();
+    write_mrpack(path, &fabric_index(json!(files)), &[]);
+}
+
+#[tokio::test]
+async fn cancelling_after_the_first_mod_stops_the_mrpack_install() {
+    let root = temp_dir("cancellation");
+    let server = MockServer::start(vec![
+        Route::new("/sodium.jar", 200, MODS[0].1.to_vec()),
+        // Stalls long enough that only a cancel can end the install quickly
+        Route::new("/lithium.jar", 200, MODS[1].1.to_vec()).delayed(Duration::from_secs(10)),
+        Route::new("/iris.jar", 200, MODS[2].1.to_vec()),
+    ])
+    .await;
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack, &server);
+
+    let cancel = CancellationToken::new();
+    let (reporter, mut events) = ProgressReporter::channel();
+    let mut manager = LauncherManager::new();
+    manager.set_progress_reporter(reporter);
+    manager.set_cancellation_token(cancel.clone());
+    // One at a time, so iris is only requested after lithium
+    manager.set_download_concurrency(1);
+
+    let trip = tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            if matches!(event, ProgressEvent::Progress { current: 1, .. }) {
+                cancel.cancel();
+                break;
+            }
+        }
+    });
+
+    let instance = root.join("NAHA-Fabric");
+    let started = Instant::now();
+    let error = manager.install_mrpack(&mrpack, &instance, "NAHA-Fabric").await.unwrap_err();
+    trip.await.unwrap();
+
+    assert!(matches!(error, MinecraftInstallerError::Cancelled), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+    assert_eq!(std::fs::read(instance.join("mods/sodium.jar")).unwrap(), b"sodium");
+    assert!(!instance.join("mods/lithium.jar").exists());
+    assert!(!instance.join("mods/iris.jar").exists());
+    assert!(!server.requests().iter().any(|r| r == "/iris.jar"), "{:?}", server.requests());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn cancelled_install_downloads_nothing() {
+    use common::{install_fake_java, seed_offline_cache};
+    use minecraft_installer::installer::MinecraftInstaller;
+
+    let root = temp_dir("cancellation");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let error = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache)
+        .with_cancellation(cancel)
+        .install_minecraft("1.20.1", "vanilla", "stable", false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::Cancelled), "{}", error);
+    assert!(!install_dir.join("minecraft/versions/1.20.1/1.20.1.jar").exists());
+    assert!(!install_dir.join("minecraft/libraries").read_dir().is_ok_and(|mut d| d.next().is_some()));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use std::path::Path;
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::LauncherManager;
+
+fn read_cfg_name(instance: &Path) -> Vec<String> {
+    std::fs::read_to_string(instance.join("instance.cfg"))
+        .unwrap()
+        .lines()
+        .filter(|line| line.starts_with("name="))
+        .map(str::to_string)
+        .collect()
+}
+
+#[tokio::test]
+async fn prism_clone_gets_its_own_folder_and_name() {
+    let root = temp_dir("clone-instance").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let manager = LauncherManager::new();
+    let source = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5")).await.unwrap();
+    let mods = source.join(".minecraft").join("mods");
+    std::fs::create_dir_all(&mods).unwrap();
+    std::fs::write(mods.join("sodium.jar"), b"sodium").unwrap();
+
+    let clone = manager.clone_instance(&root, "NAHA-Fabric", "NAHA: Risky Mods?").await.unwrap();
+
+    assert_eq!(clone, root.join("instances").join("NAHA_ Risky Mods_"));
+    assert_eq!(read_cfg_name(&clone), vec!["name=NAHA: Risky Mods?".to_string()]);
+    assert_eq!(read_cfg_name(&source), vec!["name=NAHA-Fabric".to_string()]);
+    assert_eq!(std::fs::read(clone.join(".minecraft/mods/sodium.jar")).unwrap(), b"sodium");
+    assert!(clone.join("mmc-pack.json").exists());
+
+    let error = manager.clone_instance(&root, "NAHA-Fabric", "NAHA: Risky Mods?").await.unwrap_err();
+    assert!(error.to_string().contains("already exists"), "{}", error);
+    let error = manager.clone_instance(&root, "Missing", "Copy").await.unwrap_err();
+    assert!(error.to_string().contains("No instance named Missing"), "{}", error);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn astralrinth_clone_copies_the_profiles_row() {
+    let root = temp_dir("clone-instance").join("AstralRinthApp");
+    let source = root.join("profiles").join("my-pack");
+    std::fs::create_dir_all(source.join("mods")).unwrap();
+    std::fs::write(root.join("app-window-state.json"), "{}").unwrap();
+    std::fs::write(
+        source.join("profile.json"),
+        json!({ "name": "My Pack", "path": "my-pack", "game_version": "1.21.1", "loader": "neoforge" }).to_string(),
+    )
+    .unwrap();
+    let conn = rusqlite::Connection::open(root.join("app.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE profiles (
+            path TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            game_version TEXT NOT NULL,
+            mod_loader TEXT NOT NULL,
+            install_stage TEXT NOT NULL,
+            created INTEGER NOT NULL,
+            modified INTEGER NOT NULL,
+            override_mc_memory_max INTEGER
+        );
+        INSERT INTO profiles VALUES ('my-pack', 'My Pack', '1.21.1', 'neoforge', 'installed', 1, 1, 6144);",
+    )
+    .unwrap();
+
+    let clone = LauncherManager::new().clone_instance(&root, "My Pack", "My Pack Copy").await.unwrap();
+
+    assert_eq!(clone, root.join("profiles").join("my-pack-copy"));
+    let profile: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(clone.join("profile.json")).unwrap()).unwrap();
+    assert_eq!(profile["name"], "My Pack Copy");
+    assert_eq!(profile["path"], "my-pack-copy");
+
+    let (name, version, memory, created): (String, String, i64, i64) = conn
+        .query_row(
+            "SELECT name, game_version, override_mc_memory_max, created FROM profiles WHERE path = 'my-pack-copy'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!((name.as_str(), version.as_str(), memory), ("My Pack Copy", "1.21.1", 6144));
+    assert!(created > 1);
+    let rows: i64 = conn.query_row("SELECT COUNT(*) FROM profiles", [], |row| row.get(0)).unwrap();
+    assert_eq!(rows, 2);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn astralrinth_profile_without_a_row_is_repaired() {
+    let root = temp_dir("clone-instance").join("AstralRinthApp");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("app-window-state.json"), "{}").unwrap();
+    for (folder, name) in [("listed", "Listed"), ("naha-fabric", "NAHA Fabric")] {
+        let profile = root.join("profiles").join(folder);
+        std::fs::create_dir_all(profile.join("mods")).unwrap();
+        std::fs::write(
+            profile.join("profile.json"),
+            json!({ "name": name, "path": folder, "game_version": "1.21.1", "loader": "fabric" }).to_string(),
+        )
+        .unwrap();
+    }
+    let conn = rusqlite::Connection::open(root.join("app.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE profiles (
+            path TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            game_version TEXT NOT NULL,
+            mod_loader TEXT NOT NULL,
+            install_stage TEXT NOT NULL,
+            created INTEGER NOT NULL,
+            modified INTEGER NOT NULL
+        );
+        INSERT INTO profiles VALUES ('listed', 'Listed', '1.21.1', 'fabric', 'installed', 1, 1);",
+    )
+    .unwrap();
+
+    let manager = LauncherManager::new();
+    assert!(!manager.repair_profile_db(&root, "listed").await.unwrap());
+    assert_eq!(manager.repair_profile_dbs(&root).await.unwrap(), vec!["naha-fabric".to_string()]);
+
+    let (name, version, loader): (String, String, String) = conn
+        .query_row(
+            "SELECT name, game_version, mod_loader FROM profiles WHERE path = 'naha-fabric'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!((name.as_str(), version.as_str(), loader.as_str()), ("NAHA Fabric", "1.21.1", "fabric"));
+    assert!(manager.repair_profile_dbs(&root).await.unwrap().is_empty());
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
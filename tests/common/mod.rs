@@ -0,0 +1,351 @@
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A canned response for a request path (query string ignored)
+#[derive(Clone)]
+pub struct Route {
+    pub path: String,
+    pub status: u16,
+    pub body: Vec<u8>,
+    pub delay: Duration,
+    /// Answer the first `n` hits with this status instead
+    pub failures: Option<(usize, u16)>,
+    /// Send the body with `Transfer-Encoding: chunked` and no `Content-Length`
+    pub chunked: bool,
+    hits: Arc<AtomicUsize>,
+}
+
+impl Route {
+    pub fn new(path: &str, status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self { path: path.to_string(), status, body: body.into(), delay: Duration::ZERO, failures: None, chunked: false, hits: Arc::default() }
+    }
+
+    /// Hold the response back for `delay`, like a stalled server
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Answer the first `times` requests with `status`, like a flaky server
+    pub fn failing_first(mut self, times: usize, status: u16) -> Self {
+        self.failures = Some((times, status));
+        self
+    }
+
+    /// Stream the body in chunks without announcing its length, like a compressed API response
+    pub fn chunked(mut self) -> Self {
+        self.chunked = true;
+        self
+    }
+
+    /// Status and body for the next hit on this route
+    fn respond(&self) -> (u16, Vec<u8>) {
+        let hit = self.hits.fetch_add(1, Ordering::SeqCst);
+        match self.failures {
+            Some((times, status)) if hit < times => (status, b"unavailable".to_vec()),
+            _ => (self.status, self.body.clone()),
+        }
+    }
+}
+
+/// Minimal HTTP/1.1 server for tests; unknown paths return 404
+pub struct MockServer {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+    peak_in_flight: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    pub async fn start(routes: Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let routes = Arc::new(routes);
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let seen = requests.clone();
+        let peak = peak_in_flight.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                let routes = routes.clone();
+                let seen = seen.clone();
+                let in_flight = in_flight.clone();
+                let peak = peak.clone();
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&buffer);
+                    let target = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    let path = target.split('?').next().unwrap_or("/").to_string();
+                    seen.lock().unwrap().push(target);
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+
+                    let (status, body, delay, chunked) = routes.iter()
+                        .find(|r| r.path == path)
+                        .map(|r| {
+                            let (status, body) = r.respond();
+                            (status, body, r.delay, r.chunked)
+                        })
+                        .unwrap_or((404, b"not found".to_vec(), Duration::ZERO, false));
+                    tokio::time::sleep(delay).await;
+
+                    if chunked {
+                        let header = format!(
+                            "HTTP/1.1 {} MOCK\r\nTransfer-Encoding: chunked\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n",
+                            status
+                        );
+                        let _ = socket.write_all(header.as_bytes()).await;
+                        for chunk in body.chunks(64 * 1024) {
+                            let _ = socket.write_all(format!("{:x}\r\n", chunk.len()).as_bytes()).await;
+                            let _ = socket.write_all(chunk).await;
+                            let _ = socket.write_all(b"\r\n").await;
+                        }
+                        let _ = socket.write_all(b"0\r\n\r\n").await;
+                    } else {
+                        let header = format!(
+                            "HTTP/1.1 {} MOCK\r\nContent-Length: {}\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n",
+                            status,
+                            body.len()
+                        );
+                        let _ = socket.write_all(header.as_bytes()).await;
+                        let _ = socket.write_all(&body).await;
+                    }
+                    let _ = socket.shutdown().await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        Self { base_url, requests, peak_in_flight }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// The most requests that were being answered at the same time
+    pub fn peak_in_flight(&self) -> usize {
+        self.peak_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Request targets (path + query) received so far
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Fabric meta answering 1.21.x loader lookups with the recorded 1.21.1 loader list,
+/// and profile lookups for loaders 0.16.5 and 0.16.9 (the stable one)
+pub async fn fabric_meta() -> MockServer {
+    let loaders = std::fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fabric/loader-1.21.1.json")).unwrap();
+    let mut routes = Vec::new();
+    for mc in ["1.21.1", "1.21.2"] {
+        routes.push(Route::new(&format!("/v2/versions/loader/{}", mc), 200, loaders.clone()));
+        for loader in ["0.16.5", "0.16.9"] {
+            let profile = fabric_profile(mc, loader).to_string();
+            routes.push(Route::new(&format!("/v2/versions/loader/{}/{}/profile/json", mc, loader), 200, profile));
+        }
+    }
+    MockServer::start(routes).await
+}
+
+/// The launcher profile Fabric meta serves for `loader` on `mc`, trimmed to the fields launchers read
+pub fn fabric_profile(mc: &str, loader: &str) -> serde_json::Value {
+    let library = |name: String| serde_json::json!({ "name": name, "url": "https://maven.fabricmc.net/" });
+    serde_json::json!({
+        "id": format!("fabric-loader-{}-{}", loader, mc),
+        "inheritsFrom": mc,
+        "releaseTime": "2024-11-14T12:00:00+0000",
+        "time": "2024-11-14T12:00:00+0000",
+        "type": "release",
+        "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+        "arguments": { "game": [], "jvm": ["-DFabricMcEmu= net.minecraft.client.main.Main "] },
+        "libraries": [
+            library("org.ow2.asm:asm:9.7.1".to_string()),
+            library("org.ow2.asm:asm-analysis:9.7.1".to_string()),
+            library("org.ow2.asm:asm-commons:9.7.1".to_string()),
+            library("org.ow2.asm:asm-tree:9.7.1".to_string()),
+            library("org.ow2.asm:asm-util:9.7.1".to_string()),
+            library("net.fabricmc:sponge-mixin:0.15.4+mixin.0.8.7".to_string()),
+            library(format!("net.fabricmc:intermediary:{}", mc)),
+            library(format!("net.fabricmc:fabric-loader:{}", loader)),
+        ]
+    })
+}
+
+/// A launcher manager that resolves Fabric loaders against `meta` instead of the real API
+pub fn launcher_manager(meta: &MockServer) -> minecraft_installer::launcher_support::LauncherManager {
+    let mut manager = minecraft_installer::launcher_support::LauncherManager::new();
+    manager.set_fabric_resolver(minecraft_installer::fabric::FabricResolver::with_base_url(&meta.url("/v2")));
+    manager
+}
+
+/// The NAHA Fabric 0.2.0 pack as the NAHA API lists it; override fields with `..modpack_info()`
+pub fn modpack_info() -> minecraft_installer::launcher_support::NahaModpackInfo {
+    minecraft_installer::launcher_support::NahaModpackInfo {
+        server_name: "NAHA Server".to_string(),
+        server_type: "fabric".to_string(),
+        latest_mrpack: "NAHA-Fabric-1.21.1-0.2.0.mrpack".to_string(),
+        fingerprint: "fingerprint".to_string(),
+        version: "0.2.0".to_string(),
+        last_updated: "2025-10-08T00:00:00Z".to_string(),
+        description: "NAHA fabric Modpack v0.2.0".to_string(),
+        download_url: "http://127.0.0.1:9/pack.mrpack".to_string(),
+        server_ip: "play.naha.com".to_string(),
+        server_port: 25565,
+        servers: Vec::new(),
+        file_hash: None,
+    }
+}
+
+/// `modrinth.index.json` of the NAHA Fabric 0.2.0 pack with `files`
+pub fn fabric_index(files: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": "0.2.0",
+        "name": "NAHA Fabric",
+        "files": files,
+        "dependencies": { "minecraft": "1.21.1", "fabric-loader": "0.16.5" }
+    })
+}
+
+/// An mrpack holding `index` followed by `extra_entries` (overrides and the like)
+pub fn mrpack_bytes(index: &serde_json::Value, extra_entries: &[(&str, &[u8])]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("modrinth.index.json", options).unwrap();
+    zip.write_all(index.to_string().as_bytes()).unwrap();
+    for (name, body) in extra_entries {
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(body).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+pub fn write_mrpack(path: &Path, index: &serde_json::Value, extra_entries: &[(&str, &[u8])]) {
+    std::fs::write(path, mrpack_bytes(index, extra_entries)).unwrap();
+}
+
+/// Create a fresh, uniquely named directory under the system temp dir
+pub fn temp_dir(prefix: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+pub fn sha1_hex(data: &[u8]) -> String {
+    let mut hasher = sha1_smol::Sha1::new();
+    hasher.update(data);
+    hex::encode(hasher.digest().bytes())
+}
+
+pub fn sha512_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha512};
+    hex::encode(Sha512::digest(data))
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(data))
+}
+
+/// Store `data` in the cache under its SHA1 and return the hash
+fn cache_file(cache: &Path, data: &[u8]) -> String {
+    let sha1 = sha1_hex(data);
+    std::fs::write(cache.join(&sha1), data).unwrap();
+    sha1
+}
+
+/// Pre-seed a cache for vanilla "1.20.1"; returns the hashes of the client, library and asset
+pub fn seed_offline_cache(cache: &Path) -> (String, String, String) {
+    std::fs::create_dir_all(cache).unwrap();
+
+    let client = cache_file(cache, b"client jar");
+    let library = cache_file(cache, b"library jar");
+    let asset = cache_file(cache, b"asset object");
+    let asset_index = serde_json::json!({ "objects": { "minecraft/lang/en_us.json": { "hash": asset, "size": 12 } } }).to_string();
+    let asset_index_sha1 = cache_file(cache, asset_index.as_bytes());
+
+    let version_json = serde_json::json!({
+        "id": "1.20.1",
+        "type": "release",
+        "mainClass": "net.minecraft.client.main.Main",
+        "assets": "5",
+        "assetIndex": {
+            "id": "5",
+            "sha1": asset_index_sha1,
+            "size": asset_index.len(),
+            "totalSize": 12,
+            "url": "https://piston-meta.mojang.com/v1/packages/5.json"
+        },
+        "downloads": {
+            "client": { "sha1": client, "size": 10, "url": "https://piston-data.mojang.com/client.jar" }
+        },
+        "libraries": [{
+            "name": "com.example:lib:1.0",
+            "downloads": {
+                "artifact": {
+                    "path": "com/example/lib/1.0/lib-1.0.jar",
+                    "sha1": library,
+                    "size": 11,
+                    "url": "https://libraries.minecraft.net/com/example/lib/1.0/lib-1.0.jar"
+                }
+            }
+        }]
+    })
+    .to_string();
+    let version_sha1 = cache_file(cache, version_json.as_bytes());
+
+    let manifest = serde_json::json!({
+        "latest": { "release": "1.20.1", "snapshot": "1.20.1" },
+        "versions": [{
+            "id": "1.20.1",
+            "type": "release",
+            "url": "https://piston-meta.mojang.com/v1/packages/1.20.1.json",
+            "time": "2023-06-12T13:25:51+00:00",
+            "releaseTime": "2023-06-12T13:25:51+00:00",
+            "sha1": version_sha1
+        }]
+    });
+    std::fs::write(cache.join("version_manifest.json"), manifest.to_string()).unwrap();
+
+    (client, library, asset)
+}
+
+/// Install a stand-in Java 17 so the installer doesn't fetch one from Adoptium
+#[cfg(unix)]
+pub fn install_fake_java(install_dir: &Path) {
+    fake_java_home(&install_dir.join("java").join("java-8"), "17.0.8");
+}
+
+/// A `<home>/bin/java` script that reports `version`, like `java -version` does
+#[cfg(unix)]
+pub fn fake_java_home(home: &Path, version: &str) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin = home.join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let java = bin.join("java");
+    std::fs::write(&java, format!("#!/bin/sh\necho 'openjdk version \"{}\" 2023-07-18' >&2\n", version)).unwrap();
+    std::fs::set_permissions(&java, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
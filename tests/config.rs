@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use minecraft_installer::config::Config;
+use minecraft_installer::error::MinecraftInstallerError;
+
+const SAMPLE: &str = r#"
+install-dir = "/games/minecraft"
+target-launcher = "prism"
+java-home = "/usr/lib/jvm/java-21"
+download-concurrency = 4
+timeout = 600
+"#;
+
+#[test]
+fn sample_config_deserializes() {
+    let config = Config::from_toml(SAMPLE).unwrap();
+    assert_eq!(config.install_dir, Some(PathBuf::from("/games/minecraft")));
+    assert_eq!(config.target_launcher.as_deref(), Some("prism"));
+    assert_eq!(config.java_home, Some(PathBuf::from("/usr/lib/jvm/java-21")));
+    assert_eq!(config.download_concurrency, Some(4));
+    assert_eq!(config.proxy, None);
+    assert_eq!(config.timeout, Some(600));
+
+    let error = Config::from_toml("install_dir = \"/typo\"").unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::Validation(_)), "{}", error);
+}
+
+#[test]
+fn cli_beats_env_beats_file() {
+    let file = Config::from_toml(SAMPLE).unwrap();
+    let env = Config::from_vars([
+        ("MINECRAFT_INSTALLER_DOWNLOAD_CONCURRENCY", "16"),
+        ("MINECRAFT_INSTALLER_TARGET_LAUNCHER", "xmcl"),
+        ("MINECRAFT_INSTALLER_PROXY", ""),
+        ("HOME", "/home/player"),
+    ])
+    .unwrap();
+    let cli = Config { target_launcher: Some("ModrinthApp".to_string()), ..Default::default() };
+
+    let config = file.merge(env).merge(cli);
+    assert_eq!(config.target_launcher.as_deref(), Some("ModrinthApp"));
+    assert_eq!(config.download_concurrency, Some(16));
+    assert_eq!(config.install_dir, Some(PathBuf::from("/games/minecraft")));
+    assert_eq!(config.timeout, Some(600));
+    assert_eq!(config.proxy, None);
+}
+
+#[test]
+fn missing_file_and_bad_env_values() {
+    let config = Config::load(&std::env::temp_dir().join("no-such-minecraft-installer.toml")).unwrap();
+    assert_eq!(config, Config::default());
+
+    let error = Config::from_vars([("MINECRAFT_INSTALLER_TIMEOUT", "soon")]).unwrap_err();
+    assert!(error.to_string().contains("MINECRAFT_INSTALLER_TIMEOUT must be a number"), "{}", error);
+}
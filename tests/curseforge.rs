@@ -0,0 +1,132 @@
+mod common;
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use common::{sha1_hex, temp_dir, MockServer, Route};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::{
+    CurseForgeFileResponse, CurseForgeManifest, LauncherManager, CURSEFORGE_API_KEY_ENV,
+};
+
+/// Tests that set or clear the API key variable must not run at the same time
+static API_KEY_ENV: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("curseforge")
+        .join(name);
+    std::fs::read_to_string(path).expect("missing fixture")
+}
+
+#[test]
+fn parses_recorded_manifest() {
+    let manifest: CurseForgeManifest = serde_json::from_str(&fixture("manifest.json")).unwrap();
+
+    assert_eq!(manifest.manifest_type, "minecraftModpack");
+    assert_eq!(manifest.minecraft.version, "1.20.1");
+    assert_eq!(manifest.overrides, "overrides");
+    assert_eq!(manifest.files.len(), 2);
+    assert!(manifest.files[0].required);
+    assert!(!manifest.files[1].required);
+    assert_eq!(
+        manifest.mod_loader(),
+        ("forge".to_string(), Some("47.2.0".to_string()))
+    );
+}
+
+#[test]
+fn manifest_without_loader_is_vanilla() {
+    let manifest: CurseForgeManifest = serde_json::from_str(
+        r#"{"minecraft":{"version":"1.20.1"},"manifestType":"minecraftModpack","manifestVersion":1,"name":"Vanilla+","files":[]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(manifest.mod_loader(), ("vanilla".to_string(), None));
+    assert_eq!(manifest.overrides, "overrides");
+}
+
+#[test]
+fn parses_recorded_file_responses() {
+    let jei: CurseForgeFileResponse =
+        serde_json::from_str(&fixture("file-238222-4593548.json")).unwrap();
+    assert_eq!(jei.data.mod_id, 238222);
+    assert_eq!(jei.data.file_name, "jei-1.20.1-forge-15.2.0.27.jar");
+    assert_eq!(jei.data.sha1(), Some("bb0d5be6ab2ec4a7d5d6ae6e9a8cb0d7f5f3b7a1"));
+    assert!(jei.data.download_url.is_some());
+
+    let journeymap: CurseForgeFileResponse =
+        serde_json::from_str(&fixture("file-32274-4596872.json")).unwrap();
+    assert!(journeymap.data.download_url.is_none());
+}
+
+#[tokio::test]
+async fn install_requires_api_key() {
+    let _env = API_KEY_ENV.lock().await;
+    std::env::remove_var(CURSEFORGE_API_KEY_ENV);
+
+    let temp_dir = std::env::temp_dir().join(format!("cf-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let zip_path = temp_dir.join("pack.zip");
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+    zip.start_file("manifest.json", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(fixture("manifest.json").as_bytes()).unwrap();
+    zip.finish().unwrap();
+
+    let manager = LauncherManager::new();
+    let result = manager
+        .install_curseforge_zip(&zip_path, &temp_dir.join("instance"), "NAHA Forge")
+        .await;
+
+    match result {
+        Err(MinecraftInstallerError::InstallationFailed(msg)) => {
+            assert!(msg.contains(CURSEFORGE_API_KEY_ENV), "unexpected message: {}", msg);
+        }
+        other => panic!("expected missing API key error, got {:?}", other),
+    }
+    assert!(!temp_dir.join("instance").exists());
+
+    std::fs::remove_dir_all(&temp_dir).unwrap();
+}
+
+#[tokio::test]
+async fn install_resolves_files_through_the_api() {
+    let jar = b"jei jar";
+    let files = MockServer::start(vec![Route::new("/jei.jar", 200, jar.to_vec())]).await;
+    let mut jei: serde_json::Value = serde_json::from_str(&fixture("file-238222-4593548.json")).unwrap();
+    jei["data"]["downloadUrl"] = files.url("/jei.jar").into();
+    jei["data"]["hashes"][0]["value"] = sha1_hex(jar).into();
+    let api = MockServer::start(vec![Route::new("/v1/mods/238222/files/4593548", 200, jei.to_string())]).await;
+
+    let root = temp_dir("cf-api");
+    let zip_path = root.join("pack.zip");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("manifest.json", options).unwrap();
+    zip.write_all(fixture("manifest.json").as_bytes()).unwrap();
+    zip.start_file("overrides/config/jei-client.toml", options).unwrap();
+    zip.write_all(b"pack config").unwrap();
+    zip.finish().unwrap();
+
+    let mut manager = LauncherManager::new();
+    manager.set_curseforge_api_url(api.url("/v1/"));
+    let instance = root.join("instance");
+    let result = {
+        let _env = API_KEY_ENV.lock().await;
+        std::env::set_var(CURSEFORGE_API_KEY_ENV, "test-key");
+        let result = manager.install_curseforge_zip(&zip_path, &instance, "NAHA Forge").await;
+        std::env::remove_var(CURSEFORGE_API_KEY_ENV);
+        result
+    };
+
+    assert_eq!(result.unwrap(), ("1.20.1".to_string(), "forge".to_string()));
+    assert_eq!(std::fs::read(instance.join("mods/jei-1.20.1-forge-15.2.0.27.jar")).unwrap(), jar);
+    assert_eq!(std::fs::read(instance.join("config/jei-client.toml")).unwrap(), b"pack config");
+    // The optional JourneyMap entry is never looked up
+    assert_eq!(api.requests(), ["/v1/mods/238222/files/4593548"]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::temp_dir;
+use minecraft_installer::directories::{DirectoryLayout, DirectoryManager};
+
+#[test]
+fn default_layout_keeps_the_existing_paths() {
+    let dirs = DirectoryManager::new(PathBuf::from("/games"));
+
+    assert_eq!(dirs.version_jar("1.20.1"), PathBuf::from("/games/minecraft/versions/1.20.1/1.20.1.jar"));
+    assert_eq!(dirs.java_dir(), PathBuf::from("/games/java"));
+    assert_eq!(dirs.instances_dir(), PathBuf::from("/games/instances"));
+    assert_eq!(dirs.shared_assets_dir(), PathBuf::from("/games/shared-assets"));
+}
+
+#[test]
+fn cache_dir_holds_only_regenerable_files() {
+    let dirs = DirectoryManager::new(PathBuf::from("/home/me/.local/share/MinecraftInstaller"))
+        .with_cache_dir(PathBuf::from("/home/me/.cache/MinecraftInstaller"));
+
+    assert_eq!(dirs.shared_assets_dir(), PathBuf::from("/home/me/.cache/MinecraftInstaller/shared-assets"));
+    assert_eq!(dirs.downloads_dir(), PathBuf::from("/home/me/.cache/MinecraftInstaller/downloads"));
+    // Installed data stays in the data dir
+    assert_eq!(dirs.java_version_dir(17), PathBuf::from("/home/me/.local/share/MinecraftInstaller/java/java-17"));
+    assert_eq!(dirs.assets_objects_dir(), PathBuf::from("/home/me/.local/share/MinecraftInstaller/minecraft/assets/objects"));
+    assert_eq!(DirectoryManager::new(PathBuf::from("/games")).downloads_dir(), PathBuf::from("/games/downloads"));
+}
+
+#[test]
+fn custom_minecraft_dir_flows_into_every_game_path() {
+    let layout = DirectoryLayout { minecraft: ".minecraft".to_string(), java: "runtimes".to_string(), ..Default::default() };
+    let dirs = DirectoryManager::new(PathBuf::from("/games")).with_layout(layout);
+
+    assert_eq!(dirs.version_jar("1.20.1"), PathBuf::from("/games/.minecraft/versions/1.20.1/1.20.1.jar"));
+    assert_eq!(dirs.libraries_dir(), PathBuf::from("/games/.minecraft/libraries"));
+    assert_eq!(dirs.asset_object_path("abcdef"), PathBuf::from("/games/.minecraft/assets/objects/ab/abcdef"));
+    assert_eq!(dirs.launcher_profiles(), PathBuf::from("/games/.minecraft/launcher_profiles.json"));
+    assert_eq!(dirs.java_version_dir(17), PathBuf::from("/games/runtimes/java-17"));
+}
+
+#[test]
+fn empty_minecraft_dir_installs_into_the_base_dir() {
+    let layout = DirectoryLayout { minecraft: String::new(), ..Default::default() };
+    let dirs = DirectoryManager::new(PathBuf::from("/home/me/.minecraft")).with_layout(layout);
+
+    assert_eq!(dirs.version_json("1.20.1"), PathBuf::from("/home/me/.minecraft/versions/1.20.1/1.20.1.json"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn installer_writes_into_the_custom_layout() {
+    use common::{install_fake_java, seed_offline_cache};
+    use minecraft_installer::installer::MinecraftInstaller;
+
+    let root = temp_dir("directory-layout");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let layout = DirectoryLayout { minecraft: ".minecraft".to_string(), ..Default::default() };
+    let dirs = DirectoryManager::new(install_dir.clone()).with_layout(layout);
+    MinecraftInstaller::with_directories(dirs.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache)
+        .install_minecraft("1.20.1", "vanilla", "stable", false)
+        .await
+        .unwrap();
+
+    assert!(dirs.version_jar("1.20.1").is_file());
+    assert!(install_dir.join(".minecraft/versions/1.20.1/1.20.1.jar").is_file());
+    assert!(!install_dir.join("minecraft").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, temp_dir, write_mrpack};
+use minecraft_installer::updater::MinecraftUpdater;
+
+/// Write a Fabric mod jar declaring `mod_id`, modified `age_secs` seconds ago
+fn fabric_jar(path: &Path, mod_id: &str, age_secs: u64) {
+    let file = std::fs::File::create(path).unwrap();
+    let mut zip = zip::ZipWriter::new(file);
+    zip.start_file("fabric.mod.json", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(json!({ "schemaVersion": 1, "id": mod_id, "version": "1.0.0" }).to_string().as_bytes()).unwrap();
+    let file = zip.finish().unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(age_secs)).unwrap();
+}
+
+#[tokio::test]
+async fn mods_sharing_a_name_prefix_both_survive() {
+    let root = temp_dir("duplicate-mods");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    let mods_dir = instance.join("mods");
+    std::fs::create_dir_all(&mods_dir).unwrap();
+    // Both normalize to "create" but declare different mod ids
+    fabric_jar(&mods_dir.join("create-1.21.1-6.0.6.jar"), "create", 60);
+    fabric_jar(&mods_dir.join("create-1.21.1-stuff-additions-2.0.jar"), "create_sa", 0);
+    let mrpack = root.join("pack.mrpack");
+    write_mrpack(&mrpack, &fabric_index(json!([])), &[]);
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert!(mods_dir.join("create-1.21.1-6.0.6.jar").exists());
+    assert!(mods_dir.join("create-1.21.1-stuff-additions-2.0.jar").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn older_copy_of_the_same_mod_is_removed() {
+    let root = temp_dir("duplicate-mods");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    let mods_dir = instance.join("mods");
+    std::fs::create_dir_all(&mods_dir).unwrap();
+    fabric_jar(&mods_dir.join("create-1.21.1-6.0.4.jar"), "create", 60);
+    fabric_jar(&mods_dir.join("create-1.21.1-6.0.6.jar"), "create", 0);
+    let mrpack = root.join("pack.mrpack");
+    write_mrpack(&mrpack, &fabric_index(json!([])), &[]);
+
+    MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+
+    assert!(!mods_dir.join("create-1.21.1-6.0.4.jar").exists());
+    assert!(mods_dir.join("create-1.21.1-6.0.6.jar").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn listed_mods_carry_the_declared_mod_id() {
+    let root = temp_dir("duplicate-mods");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    let mods_dir = instance.join("mods");
+    std::fs::create_dir_all(&mods_dir).unwrap();
+    fabric_jar(&mods_dir.join("create-1.21.1-stuff-additions-2.0.jar"), "create_sa", 0);
+
+    let mods = MinecraftUpdater::new().list_instance_mods(&instance).await.unwrap();
+    assert_eq!(mods[0].mod_id.as_deref(), Some("create_sa"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::LauncherType;
+
+#[test]
+fn every_variant_has_a_stable_code() {
+    let http = reqwest::Client::new().get("not a url").build().unwrap_err();
+    let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let cases = [
+        (MinecraftInstallerError::Io(std::io::Error::other("disk")), "IO_ERROR"),
+        (MinecraftInstallerError::from(http), "HTTP_ERROR"),
+        (MinecraftInstallerError::Json(json), "JSON_ERROR"),
+        (MinecraftInstallerError::Zip(zip::result::ZipError::FileNotFound), "ZIP_ERROR"),
+        (MinecraftInstallerError::InvalidVersion("2.0".into()), "INVALID_VERSION"),
+        (MinecraftInstallerError::InvalidLoader("rift".into()), "INVALID_LOADER"),
+        (MinecraftInstallerError::DownloadFailed("x".into()), "DOWNLOAD_FAILED"),
+        (MinecraftInstallerError::InstallationFailed("x".into()), "INSTALLATION_FAILED"),
+        (MinecraftInstallerError::JavaInstallationFailed("x".into()), "JAVA_INSTALLATION_FAILED"),
+        (MinecraftInstallerError::FileSystem("x".into()), "FILE_SYSTEM_ERROR"),
+        (MinecraftInstallerError::Network("x".into()), "NETWORK_ERROR"),
+        (MinecraftInstallerError::Validation("x".into()), "VALIDATION_ERROR"),
+        (MinecraftInstallerError::Cancelled, "CANCELLED"),
+        (
+            MinecraftInstallerError::HashMismatch {
+                path: PathBuf::from("mods/sodium.jar"),
+                expected: "aaaa".into(),
+                actual: "bbbb".into(),
+            },
+            "HASH_MISMATCH",
+        ),
+        (MinecraftInstallerError::UnsupportedLauncher(LauncherType::Technic), "UNSUPPORTED_LAUNCHER"),
+    ];
+
+    for (error, code) in cases {
+        assert_eq!(error.error_code(), code, "{}", error);
+    }
+}
+
+#[test]
+fn structured_variants_keep_readable_messages() {
+    let mismatch = MinecraftInstallerError::HashMismatch {
+        path: PathBuf::from("mods/sodium.jar"),
+        expected: "aaaa".into(),
+        actual: "bbbb".into(),
+    };
+    assert_eq!(mismatch.to_string(), "Hash mismatch for mods/sodium.jar: expected aaaa, got bbbb");
+    assert_eq!(
+        MinecraftInstallerError::UnsupportedLauncher(LauncherType::Technic).to_string(),
+        "Unsupported launcher: Technic"
+    );
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{fabric_meta, temp_dir};
+use minecraft_installer::fabric::{FabricResolver, FALLBACK_FABRIC_LOADER};
+use minecraft_installer::installer::{format_loader_versions, MinecraftInstaller};
+use minecraft_installer::launcher_support::LauncherManager;
+
+#[tokio::test]
+async fn stable_and_latest_map_to_concrete_versions() {
+    let server = fabric_meta().await;
+    let resolver = FabricResolver::with_base_url(&server.url("/v2"));
+
+    assert_eq!(resolver.resolve(Some("stable"), "1.21.1").await, "0.16.9");
+    assert_eq!(resolver.resolve(None, "1.21.1").await, "0.16.9");
+    assert_eq!(resolver.resolve(Some("latest"), "1.21.1").await, "0.16.10-beta.1");
+    assert_eq!(resolver.resolve(Some("0.15.3"), "1.21.1").await, "0.15.3");
+}
+
+#[tokio::test]
+async fn unreachable_meta_falls_back() {
+    let resolver = FabricResolver::with_base_url("http://127.0.0.1:9");
+    assert_eq!(resolver.resolve(Some("stable"), "1.21.1").await, FALLBACK_FABRIC_LOADER);
+}
+
+#[tokio::test]
+async fn prism_instance_embeds_resolved_loader() {
+    let server = fabric_meta().await;
+    let root = temp_dir("fabric-resolver").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let mut manager = LauncherManager::new();
+    manager.set_fabric_resolver(FabricResolver::with_base_url(&server.url("/v2")));
+    let instance = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("stable")).await.unwrap();
+
+    let pack: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(instance.join("mmc-pack.json")).unwrap()).unwrap();
+    let loader = pack["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["uid"] == "net.fabricmc.fabric-loader")
+        .unwrap();
+    assert_eq!(loader["version"], "0.16.9");
+    assert_eq!(loader["cachedVersion"], "0.16.9");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn loader_list_marks_the_stable_version() {
+    let server = fabric_meta().await;
+    let root = temp_dir("fabric-resolver");
+    let installer = MinecraftInstaller::new(root.clone())
+        .await
+        .unwrap()
+        .with_fabric_resolver(FabricResolver::with_base_url(&server.url("/v2")));
+
+    let versions = installer.loader_versions("fabric", "1.21.1").await.unwrap();
+    let recommended: Vec<_> = versions.iter().filter(|v| v.recommended).map(|v| v.version.as_str()).collect();
+    assert_eq!(recommended, ["0.16.9"]);
+    assert_eq!(versions[0].version, "0.16.10-beta.1");
+
+    let table = format_loader_versions("fabric", "1.21.1", &versions[..3]);
+    let lines: Vec<_> = table.lines().map(str::trim_end).filter(|l| l.starts_with("0.")).collect();
+    assert_eq!(lines, ["0.16.10-beta.1", "0.16.9               ⭐ recommended", "0.16.7"]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,22 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::{LauncherManager, LauncherType};
+
+// Only test in this binary: it points HOME at a mock home directory
+#[tokio::test]
+async fn detects_flatpak_prism_launcher() {
+    let home = temp_dir("flatpak-home");
+    let prism = home.join(".var/app/org.prismlauncher.PrismLauncher/data/PrismLauncher");
+    std::fs::create_dir_all(prism.join("instances")).unwrap();
+    std::fs::write(prism.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    std::env::set_var("HOME", &home);
+    let launchers = LauncherManager::new().detect_launchers().await;
+
+    assert_eq!(launchers, vec![(LauncherType::Prism, prism)]);
+
+    std::fs::remove_dir_all(&home).unwrap();
+}
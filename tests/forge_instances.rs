@@ -0,0 +1,83 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::updater::{pack_supports_loader, MinecraftUpdater};
+
+fn prism_instance(root: &Path, folder: &str, loader_name: &str, uid: &str) -> PathBuf {
+    let instance = root.join("instances").join(folder);
+    std::fs::create_dir_all(instance.join("minecraft").join("mods")).unwrap();
+    std::fs::write(instance.join("instance.cfg"), format!("InstanceType=OneSix\nname={}\n", folder)).unwrap();
+    std::fs::write(
+        instance.join("mmc-pack.json"),
+        json!({
+            "formatVersion": 1,
+            "components": [
+                { "cachedName": "Minecraft", "uid": "net.minecraft", "version": "1.21.1" },
+                { "cachedName": loader_name, "uid": uid, "cachedVersion": "21.1.65" }
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    instance
+}
+
+#[test]
+fn forge_never_matches_the_neoforge_pack() {
+    assert!(pack_supports_loader("neoforge", "NeoForge"));
+    assert!(!pack_supports_loader("neoforge", "Forge"));
+    assert!(pack_supports_loader("fabric", "Fabric"));
+    assert!(!pack_supports_loader("fabric", "NeoForge"));
+    assert!(!pack_supports_loader("neoforge", "Unknown"));
+}
+
+#[tokio::test]
+async fn neoforge_and_forge_components_are_told_apart() {
+    let root = temp_dir("forge-instances").join("PrismLauncher");
+    let neoforge = prism_instance(&root, "NAHA-NeoForge", "NeoForge", "net.neoforged");
+    let forge = prism_instance(&root, "Legacy-Forge", "Forge", "net.minecraftforge");
+    let updater = MinecraftUpdater::new();
+
+    assert_eq!(updater.analyze_instance(&neoforge).await.unwrap().unwrap().mod_loader, "NeoForge");
+    assert_eq!(updater.analyze_instance(&forge).await.unwrap().unwrap().mod_loader, "Forge");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn neoforge_component_reports_its_version() {
+    let root = temp_dir("forge-instances").join("PrismLauncher");
+    // The display name alone would match both Forge checks; only the uid is authoritative
+    let neoforge = prism_instance(&root, "NAHA-NeoForge", "NeoForge (Forge fork)", "net.neoforged");
+
+    let info = MinecraftUpdater::new().analyze_instance(&neoforge).await.unwrap().unwrap();
+    assert_eq!(info.mod_loader, "NeoForge");
+    assert_eq!(info.mod_loader_version.as_deref(), Some("21.1.65"));
+    assert_eq!(info.minecraft_version, "1.21.1");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn forge_instance_is_not_updated_with_the_neoforge_pack() {
+    let root = temp_dir("forge-instances").join("PrismLauncher");
+    let forge = prism_instance(&root, "Legacy-Forge", "Forge", "net.minecraftforge");
+    std::fs::write(forge.join("minecraft").join("mods").join("jei-forge.jar"), b"jei").unwrap();
+
+    let error = MinecraftUpdater::new()
+        .update_instance_mods(&forge, "neoforge")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::InvalidLoader(_)), "{}", error);
+    assert!(error.to_string().contains("Forge instance"), "{}", error);
+    assert!(!forge.join("temp_update").exists());
+    assert_eq!(std::fs::read(forge.join("minecraft").join("mods").join("jei-forge.jar")).unwrap(), b"jei");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
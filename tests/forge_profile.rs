@@ -0,0 +1,186 @@
+mod common;
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use common::{sha1_hex, temp_dir, MockServer, Route};
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::forge::{ForgeInstallProfile, ForgeResolver};
+use minecraft_installer::installer::MinecraftInstaller;
+
+/// A Forge fixture; the profiles are synthetic, with made-up hashes (see `tests/fixtures/forge/README.md`)
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("forge")
+        .join(name);
+    std::fs::read_to_string(path).expect("missing fixture")
+}
+
+fn write_installer(path: &Path) {
+    std::fs::write(path, installer_jar(&fixture("install_profile-1.20.1-47.2.0.json"), &fixture("version-1.20.1-47.2.0.json"))).unwrap();
+}
+
+fn installer_jar(install_profile: &str, version_json: &str) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    zip.start_file("install_profile.json", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(install_profile.as_bytes()).unwrap();
+    zip.start_file("version.json", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(version_json.as_bytes()).unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+/// Point every library with a URL at `libraries`, hashed to match the body `library_routes` serves for it
+fn rehost_libraries(document: &str, libraries: &MockServer) -> String {
+    let mut json: serde_json::Value = serde_json::from_str(document).unwrap();
+    for library in json["libraries"].as_array_mut().unwrap() {
+        let artifact = &mut library["downloads"]["artifact"];
+        if artifact["url"].as_str().is_some_and(|url| !url.is_empty()) {
+            let path = artifact["path"].as_str().unwrap().to_string();
+            artifact["url"] = libraries.url(&format!("/{}", path)).into();
+            artifact["sha1"] = sha1_hex(path.as_bytes()).into();
+        }
+    }
+    json.to_string()
+}
+
+fn library_routes() -> Vec<Route> {
+    let profile = ForgeInstallProfile::from_json(
+        &fixture("install_profile-1.20.1-47.2.0.json"),
+        &fixture("version-1.20.1-47.2.0.json"),
+    ).unwrap();
+    profile.libraries().iter().map(|a| Route::new(&format!("/{}", a.path), 200, a.path.clone())).collect()
+}
+
+/// A `java` that records its arguments in the working directory, then exits with `status`
+#[cfg(unix)]
+fn recording_java(dir: &Path, status: i32) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::create_dir_all(dir).unwrap();
+    let java = dir.join("java");
+    std::fs::write(&java, format!("#!/bin/sh\necho \"$@\" > java-args.txt\necho 'Processor failed' >&2\nexit {}\n", status)).unwrap();
+    std::fs::set_permissions(&java, std::fs::Permissions::from_mode(0o755)).unwrap();
+    java
+}
+
+/// Serve the Forge maven (installer plus libraries) and return an installer that uses it
+#[cfg(unix)]
+async fn forge_installer(install_dir: &Path) -> (MinecraftInstaller, MockServer, MockServer) {
+    let libraries = MockServer::start(library_routes()).await;
+    let jar = installer_jar(
+        &rehost_libraries(&fixture("install_profile-1.20.1-47.2.0.json"), &libraries),
+        &rehost_libraries(&fixture("version-1.20.1-47.2.0.json"), &libraries),
+    );
+    let maven = MockServer::start(vec![Route::new(
+        "/maven/net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-installer.jar", 200, jar,
+    )]).await;
+
+    let installer = MinecraftInstaller::new(install_dir.to_path_buf())
+        .await
+        .unwrap()
+        .with_forge_resolver(ForgeResolver::with_urls(&maven.url("/maven"), &maven.url("/promotions_slim.json")));
+    (installer, maven, libraries)
+}
+
+#[test]
+fn installer_profile_resolves_libraries() {
+    let root = temp_dir("forge-profile");
+    let jar = root.join("forge-1.20.1-47.2.0-installer.jar");
+    write_installer(&jar);
+
+    let profile = ForgeInstallProfile::from_installer(&jar).unwrap();
+    assert_eq!(profile.version, "1.20.1-forge-47.2.0");
+    assert_eq!(profile.minecraft, "1.20.1");
+    assert!(profile.needs_processors());
+    assert_eq!(profile.version_json["inheritsFrom"], "1.20.1");
+
+    // jopt-simple is shared by both lists and the patched client has no URL to fetch
+    let paths: Vec<_> = profile.libraries().iter().map(|a| a.path.as_str()).collect();
+    assert_eq!(paths, [
+        "net/minecraftforge/installertools/1.3.0/installertools-1.3.0.jar",
+        "net/minecraftforge/binarypatcher/1.1.1/binarypatcher-1.1.1.jar",
+        "net/sf/jopt-simple/jopt-simple/5.0.4/jopt-simple-5.0.4.jar",
+        "cpw/mods/securejarhandler/2.1.10/securejarhandler-2.1.10.jar",
+        "net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-universal.jar",
+    ]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn legacy_installer_is_rejected() {
+    let legacy = r#"{"install": {"profileName": "Forge", "minecraft": "1.12.2"}, "versionInfo": {"id": "1.12.2-forge"}}"#;
+    let error = ForgeInstallProfile::from_json(legacy, "{}").unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::InvalidLoader(_)), "{}", error);
+}
+
+#[tokio::test]
+async fn promotions_map_to_concrete_versions() {
+    let server = MockServer::start(vec![Route::new("/promotions_slim.json", 200, fixture("promotions_slim.json"))]).await;
+    let resolver = ForgeResolver::with_urls(&server.url("/maven"), &server.url("/promotions_slim.json"));
+
+    assert_eq!(resolver.resolve(Some("stable"), "1.20.1").await.unwrap(), "47.2.0");
+    assert_eq!(resolver.resolve(Some("latest"), "1.20.1").await.unwrap(), "47.2.20");
+    // No recommended build yet, so the latest one is used
+    assert_eq!(resolver.resolve(None, "1.20.4").await.unwrap(), "49.0.30");
+    assert_eq!(resolver.resolve(Some("1.20.1-47.1.0"), "1.20.1").await.unwrap(), "47.1.0");
+    assert_eq!(
+        resolver.installer_url("1.20.1", "47.2.0"),
+        server.url("/maven/net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-installer.jar")
+    );
+
+    let error = resolver.resolve(Some("stable"), "23w13a_or_b").await.unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::InvalidLoader(_)), "{}", error);
+    assert!(error.to_string().contains("Forge is not available for Minecraft 23w13a_or_b"), "{}", error);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn install_forge_downloads_libraries_and_runs_the_installer() {
+    let root = temp_dir("forge-install");
+    let (installer, _maven, libraries) = forge_installer(&root.join("install")).await;
+    let java = recording_java(&root.join("java"), 0);
+
+    let (version_id, forge_version) = installer.install_forge("1.20.1", "47.2.0", &java).await.unwrap();
+    assert_eq!(version_id, "1.20.1-forge-47.2.0");
+    assert_eq!(forge_version, "47.2.0");
+
+    let dirs = DirectoryManager::new(root.join("install"));
+    for route in library_routes() {
+        let library = dirs.libraries_dir().join(route.path.trim_start_matches('/'));
+        assert_eq!(std::fs::read(&library).unwrap(), route.body, "{}", library.display());
+    }
+    assert_eq!(libraries.requests().len(), 5);
+
+    let version_json: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(dirs.version_json("1.20.1-forge-47.2.0")).unwrap(),
+    ).unwrap();
+    assert_eq!(version_json["inheritsFrom"], "1.20.1");
+    assert!(dirs.launcher_profiles().exists());
+
+    // The processors ran headlessly against the game directory, then the installer was cleaned up
+    let version_dir = dirs.version_dir("1.20.1");
+    let installer_jar = version_dir.join("forge-1.20.1-47.2.0-installer.jar");
+    let args = std::fs::read_to_string(version_dir.join("java-args.txt")).unwrap();
+    assert_eq!(args.trim(), format!("-jar {} --installClient {}", installer_jar.display(), dirs.minecraft_dir().display()));
+    assert!(!installer_jar.exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn install_forge_reports_a_failed_installer_run() {
+    let root = temp_dir("forge-install-failed");
+    let (installer, _maven, _libraries) = forge_installer(&root.join("install")).await;
+    let java = recording_java(&root.join("java"), 1);
+
+    let error = installer.install_forge("1.20.1", "47.2.0", &java).await.unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::InstallationFailed(_)), "{}", error);
+    assert!(error.to_string().contains("Processor failed"), "{}", error);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
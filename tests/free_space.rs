@@ -0,0 +1,49 @@
+mod common;
+
+use serde_json::json;
+
+use common::{temp_dir, write_mrpack};
+use minecraft_installer::launcher_support::{available_space, LauncherManager, STANDALONE_GAME_FILES_ESTIMATE};
+
+fn space_test_index() -> serde_json::Value {
+    json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": "1.0.0",
+        "name": "Space Test",
+        "dependencies": { "minecraft": "1.21.1", "fabric-loader": "0.16.5" },
+        "files": [
+            { "path": "mods/a.jar", "hashes": {}, "downloads": [], "fileSize": 1000 },
+            { "path": "mods/b.jar", "hashes": {}, "env": { "client": "required", "server": "required" }, "downloads": [], "fileSize": 2000 },
+            { "path": "mods/server-only.jar", "hashes": {}, "env": { "client": "unsupported", "server": "required" }, "downloads": [], "fileSize": 4000 }
+        ]
+    })
+}
+
+#[test]
+fn estimate_counts_client_files_and_overrides() {
+    let root = temp_dir("free-space");
+    let mrpack = root.join("pack.mrpack");
+    write_mrpack(&mrpack, &space_test_index(), &[("overrides/config/a.toml", &[b'x'; 300])]);
+
+    let manager = LauncherManager::new();
+    assert_eq!(manager.estimate_mrpack_size(&mrpack, false).unwrap(), 3300);
+    assert_eq!(manager.estimate_mrpack_size(&mrpack, true).unwrap(), 3300 + STANDALONE_GAME_FILES_ESTIMATE);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn insufficient_space_is_an_error() {
+    let root = temp_dir("free-space");
+    let target = root.join("not-created-yet");
+    let manager = LauncherManager::new();
+
+    assert!(manager.check_free_space(&target, 1).is_ok());
+    if available_space(&target).is_some() {
+        let message = manager.check_free_space(&target, u64::MAX).unwrap_err().to_string();
+        assert!(message.contains("Not enough free space"), "{}", message);
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+use common::{sha1_hex, temp_dir, MockServer, Route};
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::download::{DownloadManager, HttpConfig, VersionDetails};
+use minecraft_installer::error::MinecraftInstallerError;
+
+fn version_with_client(url: &str, sha1: &str) -> VersionDetails {
+    serde_json::from_value(json!({
+        "id": "1.20.1",
+        "type": "release",
+        "mainClass": "net.minecraft.client.main.Main",
+        "assets": "5",
+        "assetIndex": { "id": "5", "sha1": "0", "size": 0, "totalSize": 0, "url": url },
+        "downloads": { "client": { "sha1": sha1, "size": 10, "url": url } },
+        "libraries": []
+    }))
+    .unwrap()
+}
+
+#[test]
+fn proxy_urls_are_validated() {
+    for url in ["http://proxy.example:8080", "https://proxy.example", "socks5://127.0.0.1:1080", "socks5h://127.0.0.1:1080"] {
+        let http = HttpConfig::default().with_proxy(url).unwrap();
+        assert!(http.has_proxy(), "{}", url);
+        http.client();
+    }
+
+    let error = HttpConfig::default().with_proxy("not a proxy").unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::Validation(_)), "{}", error);
+}
+
+#[tokio::test]
+async fn downloads_go_through_the_configured_proxy() {
+    let root = temp_dir("http-proxy");
+    // A forward proxy receives the absolute URL as the request target
+    let target = "http://piston-data.mojang.invalid/client.jar";
+    let proxy = MockServer::start(vec![Route::new(target, 200, b"client jar".to_vec())]).await;
+
+    let http = HttpConfig::default().with_proxy(&proxy.base_url).unwrap();
+    let manager = DownloadManager::new(DirectoryManager::new(root.clone())).with_http_config(&http);
+    manager
+        .download_client(&version_with_client(target, &sha1_hex(b"client jar")))
+        .await
+        .unwrap();
+
+    assert_eq!(proxy.requests(), vec![target.to_string()]);
+    assert_eq!(std::fs::read(root.join("minecraft/versions/1.20.1/1.20.1.jar")).unwrap(), b"client jar");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn max_rate_slows_a_download_to_the_cap() {
+    let root = temp_dir("http-max-rate");
+    let payload = vec![7u8; 30_000];
+    let server = MockServer::start(vec![Route::new("/client.jar", 200, payload.clone())]).await;
+
+    let http = HttpConfig::default().with_max_rate(10_000);
+    assert_eq!(http.rate_limit().unwrap().bytes_per_sec(), 10_000);
+    let manager = DownloadManager::new(DirectoryManager::new(root.clone())).with_http_config(&http);
+
+    let started = Instant::now();
+    manager
+        .download_client(&version_with_client(&server.url("/client.jar"), &sha1_hex(&payload)))
+        .await
+        .unwrap();
+
+    // The first 10 kB go through on the full bucket, so the other 20 kB take two seconds (not three)
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(1900), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(2700), "{:?}", elapsed);
+    assert_eq!(std::fs::read(root.join("minecraft/versions/1.20.1/1.20.1.jar")).unwrap(), payload);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn zero_max_rate_is_unlimited() {
+    assert!(HttpConfig::default().with_max_rate(0).rate_limit().is_none());
+    assert!(HttpConfig::default().rate_limit().is_none());
+}
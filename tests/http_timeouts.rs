@@ -0,0 +1,79 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+use common::{temp_dir, MockServer, Route};
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::download::{DownloadManager, HttpTimeouts, VersionDetails};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::fabric::FabricResolver;
+use minecraft_installer::installer::MinecraftInstaller;
+
+fn version_with_client(url: &str) -> VersionDetails {
+    serde_json::from_value(json!({
+        "id": "1.20.1",
+        "type": "release",
+        "mainClass": "net.minecraft.client.main.Main",
+        "assets": "5",
+        "assetIndex": { "id": "5", "sha1": "0", "size": 0, "totalSize": 0, "url": url },
+        "downloads": { "client": { "sha1": "0", "size": 10, "url": url } },
+        "libraries": []
+    }))
+    .unwrap()
+}
+
+#[test]
+fn defaults_are_ten_seconds_to_connect_and_five_minutes_in_total() {
+    let timeouts = HttpTimeouts::default();
+    assert_eq!(timeouts.connect, Duration::from_secs(10));
+    assert_eq!(timeouts.total, Duration::from_secs(300));
+}
+
+#[tokio::test]
+async fn stalled_download_fails_with_a_network_error() {
+    let root = temp_dir("http-timeouts");
+    let server = MockServer::start(vec![
+        Route::new("/client.jar", 200, b"client jar".to_vec()).delayed(Duration::from_secs(30)),
+    ])
+    .await;
+
+    let timeouts = HttpTimeouts { total: Duration::from_millis(300), ..Default::default() };
+    let manager = DownloadManager::new(DirectoryManager::new(root.clone())).with_timeouts(timeouts);
+
+    let started = Instant::now();
+    let error = manager
+        .download_client(&version_with_client(&server.url("/client.jar")))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::Network(_)), "{}", error);
+    assert!(error.to_string().contains("timed out"), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn installer_timeouts_also_apply_to_loader_lookups() {
+    let root = temp_dir("http-timeouts");
+    let meta = MockServer::start(vec![
+        Route::new("/v2/versions/loader/1.21.1", 200, b"[]".to_vec()).delayed(Duration::from_secs(30)),
+    ])
+    .await;
+
+    let installer = MinecraftInstaller::new(root.clone())
+        .await
+        .unwrap()
+        .with_fabric_resolver(FabricResolver::with_base_url(&meta.url("/v2")))
+        .with_timeouts(HttpTimeouts { total: Duration::from_millis(300), ..Default::default() });
+
+    let started = Instant::now();
+    let error = installer.loader_versions("fabric", "1.21.1").await.unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::Network(_)), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use std::path::Path;
+
+use common::{fabric_meta, launcher_manager, temp_dir};
+use minecraft_installer::launcher_support::{read_instance_icon};
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn prism_recreation_keeps_icon_key() {
+    let root = temp_dir("icon-preservation").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let instance = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None).await.unwrap();
+    assert_eq!(read_instance_icon(&instance).as_deref(), Some("default"));
+
+    let cfg = std::fs::read_to_string(instance.join("instance.cfg")).unwrap();
+    std::fs::write(instance.join("instance.cfg"), cfg.replace("iconKey=default", "iconKey=creeper")).unwrap();
+    manager.create_instance(&root, "NAHA-Fabric", "1.21.2", "fabric", None).await.unwrap();
+
+    let cfg = std::fs::read_to_string(instance.join("instance.cfg")).unwrap();
+    assert!(cfg.contains("iconKey=creeper\n"), "{}", cfg);
+    assert!(!cfg.contains("iconKey=default"), "{}", cfg);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn astralrinth_recreation_keeps_profile_and_database_icon() {
+    let root = temp_dir("icon-preservation").join("AstralRinthApp");
+    std::fs::create_dir_all(root.join("profiles")).unwrap();
+    std::fs::write(root.join("app-window-state.json"), "{}").unwrap();
+    rusqlite::Connection::open(root.join("app.db"))
+        .unwrap()
+        .execute_batch(
+            "CREATE TABLE profiles (
+                path TEXT PRIMARY KEY, name TEXT, icon_path TEXT, game_version TEXT, mod_loader TEXT, install_stage TEXT,
+                created INTEGER, modified INTEGER, groups TEXT, override_extra_launch_args TEXT, override_custom_env_vars TEXT
+            );",
+        )
+        .unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let instance = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None).await.unwrap();
+    let db_icon = || -> Option<String> {
+        rusqlite::Connection::open(root.join("app.db"))
+            .unwrap()
+            .query_row("SELECT icon_path FROM profiles WHERE path = 'naha-fabric'", [], |row| row.get(0))
+            .unwrap()
+    };
+    assert_eq!(db_icon(), None);
+
+    let icon = "/home/user/.local/share/AstralRinthApp/caches/icons/naha.png";
+    let mut profile = read_json(&instance.join("profile.json"));
+    profile["icon_path"] = icon.into();
+    std::fs::write(instance.join("profile.json"), profile.to_string()).unwrap();
+    rusqlite::Connection::open(root.join("app.db"))
+        .unwrap()
+        .execute("UPDATE profiles SET icon_path = ?", [icon])
+        .unwrap();
+
+    manager.create_instance(&root, "NAHA-Fabric", "1.21.2", "fabric", None).await.unwrap();
+
+    assert_eq!(read_json(&instance.join("profile.json"))["icon_path"], icon);
+    assert_eq!(db_icon().as_deref(), Some(icon));
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn xmcl_recreation_keeps_icon() {
+    let root = temp_dir("icon-preservation").join("xmcl");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("launcher_profiles.json"), "{}").unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let instance = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None).await.unwrap();
+    assert_eq!(read_instance_icon(&instance), None);
+
+    let mut config = read_json(&instance.join("instance.json"));
+    config["icon"] = "image://icons/naha.png".into();
+    std::fs::write(instance.join("instance.json"), config.to_string()).unwrap();
+
+    manager.create_instance(&root, "NAHA-Fabric", "1.21.2", "fabric", None).await.unwrap();
+
+    assert_eq!(read_json(&instance.join("instance.json"))["icon"], "image://icons/naha.png");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
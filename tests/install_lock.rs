@@ -0,0 +1,73 @@
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::installer::MinecraftInstaller;
+use minecraft_installer::lock::InstallLock;
+
+#[tokio::test]
+async fn second_install_fails_while_the_lock_is_held() {
+    let root = temp_dir("install-lock");
+    let dirs = DirectoryManager::new(root.clone());
+    let installer = MinecraftInstaller::with_directories(dirs.clone()).await.unwrap();
+
+    let lock = InstallLock::acquire(&dirs.install_lock()).unwrap();
+    assert_eq!(std::fs::read_to_string(lock.path()).unwrap(), std::process::id().to_string());
+
+    // Fails before resolving anything, so no network or cache is needed
+    let error = installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::InstallationFailed(_)), "{}", error);
+    assert!(error.to_string().contains("Another install is in progress"), "{}", error);
+
+    drop(lock);
+    assert!(!dirs.install_lock().exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn lock_left_by_a_dead_process_is_reclaimed() {
+    let root = temp_dir("install-lock");
+    let path = root.join(".installer.lock");
+    // Above any real pid_max, so no process can have it
+    std::fs::write(&path, "999999999").unwrap();
+
+    let lock = InstallLock::acquire(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+    drop(lock);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn only_one_holder_at_a_time() {
+    let root = temp_dir("install-lock");
+    let path = root.join(".installer.lock");
+
+    let first = InstallLock::acquire(&path).unwrap();
+    let error = InstallLock::acquire(&path).unwrap_err();
+    assert!(error.to_string().contains("Another install is in progress"), "{}", error);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+
+    drop(first);
+    let second = InstallLock::acquire(&path).unwrap();
+    drop(second);
+    assert!(!path.exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn unlocked_lockfile_naming_a_live_process_is_taken_over() {
+    let root = temp_dir("install-lock");
+    let path = root.join(".installer.lock");
+    // A recycled PID: some live process, but not the one that wrote the lock
+    std::fs::write(&path, "1").unwrap();
+
+    let lock = InstallLock::acquire(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+    drop(lock);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
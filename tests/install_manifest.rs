@@ -0,0 +1,135 @@
+mod common;
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, sha1_hex, sha512_hex, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::launcher_support::LauncherManager;
+use minecraft_installer::manifest::{InstallManifest, ManifestMod, INSTALL_MANIFEST_FILE};
+use minecraft_installer::updater::MinecraftUpdater;
+
+const SODIUM: &[u8] = b"sodium 0.6.0 fabric jar";
+
+#[tokio::test]
+async fn mrpack_install_records_mods_and_hashes() {
+    let server = MockServer::start(vec![Route::new("/sodium.jar", 200, SODIUM.to_vec())]).await;
+    let root = temp_dir("install-manifest");
+    let mrpack = root.join("pack.mrpack");
+    write_mrpack(&mrpack, &fabric_index(json!([{
+        "path": "mods/sodium-fabric-0.6.0+mc1.21.1.jar",
+        "hashes": { "sha1": sha1_hex(SODIUM), "sha512": sha512_hex(SODIUM) },
+        "downloads": [server.url("/sodium.jar")],
+        "fileSize": SODIUM.len()
+    }])), &[]);
+
+    let instance = root.join("instance");
+    LauncherManager::new().install_mrpack(&mrpack, &instance, "NAHA Fabric").await.unwrap();
+
+    assert!(instance.join(INSTALL_MANIFEST_FILE).is_file());
+    let manifest = InstallManifest::read(&instance).await.unwrap();
+    assert_eq!(manifest.minecraft_version, "1.21.1");
+    assert_eq!(manifest.loader, "fabric");
+    assert_eq!(manifest.loader_version.as_deref(), Some("0.16.5"));
+    assert!(chrono::DateTime::parse_from_rfc3339(&manifest.installed_at).is_ok(), "{}", manifest.installed_at);
+
+    assert_eq!(manifest.mods.len(), 1);
+    let sodium = &manifest.mods[0];
+    assert_eq!(sodium.path, "mods/sodium-fabric-0.6.0+mc1.21.1.jar");
+    assert_eq!(sodium.sha1.as_deref(), Some(sha1_hex(SODIUM).as_str()));
+    assert_eq!(sodium.sha512.as_deref(), Some(sha512_hex(SODIUM).as_str()));
+    assert_eq!(sodium.size, SODIUM.len() as u64);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn missing_or_invalid_manifest_reads_as_none() {
+    let root = temp_dir("install-manifest");
+    assert!(InstallManifest::read(&root).await.is_none());
+
+    std::fs::write(root.join(INSTALL_MANIFEST_FILE), "{\"minecraft_version\": 1}").unwrap();
+    assert!(InstallManifest::read(&root).await.is_none());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// A pack listing `mods/sodium.jar` with `content`, served by `server`
+fn write_sodium_pack(path: &std::path::Path, server: &MockServer, content: &[u8]) {
+    write_mrpack(path, &fabric_index(json!([{
+        "path": "mods/sodium.jar",
+        "hashes": { "sha1": sha1_hex(content), "sha512": sha512_hex(content) },
+        "downloads": [server.url("/sodium.jar")],
+        "fileSize": content.len()
+    }])), &[]);
+}
+
+#[tokio::test]
+async fn updater_trusts_the_manifest_hash_and_rewrites_it() {
+    const NEW_SODIUM: &[u8] = b"sodium 0.6.1 fabric jar";
+    let server = MockServer::start(vec![Route::new("/sodium.jar", 200, NEW_SODIUM.to_vec())]).await;
+    let root = temp_dir("install-manifest");
+    let mrpack = root.join("pack.mrpack");
+    write_sodium_pack(&mrpack, &server, NEW_SODIUM);
+
+    // The pack re-published sodium.jar under the same name; only the manifest's hash tells them apart
+    let instance = root.join("instance");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    std::fs::write(instance.join("mods/sodium.jar"), SODIUM).unwrap();
+    let mut manifest = InstallManifest::new("1.21.1", "fabric", Some("0.16.5"));
+    manifest.mods.push(ManifestMod {
+        path: "mods/sodium.jar".to_string(),
+        sha1: Some(sha1_hex(SODIUM)),
+        sha512: Some(sha512_hex(SODIUM)),
+        size: SODIUM.len() as u64,
+    });
+    manifest.write(&instance).await.unwrap();
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(result.updated_mods, ["sodium.jar → sodium.jar"]);
+    assert_eq!(server.requests(), ["/sodium.jar"]);
+    assert_eq!(std::fs::read(instance.join("mods/sodium.jar")).unwrap(), NEW_SODIUM);
+
+    let rewritten = InstallManifest::read(&instance).await.unwrap();
+    assert_eq!(rewritten.mods.len(), 1);
+    assert_eq!(rewritten.mods[0].sha1.as_deref(), Some(sha1_hex(NEW_SODIUM).as_str()));
+    assert_eq!(rewritten.mods[0].size, NEW_SODIUM.len() as u64);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn updater_ignores_manifest_entries_that_no_longer_match_the_jar() {
+    const NEW_SODIUM: &[u8] = b"sodium 0.6.1 fabric jar";
+    let server = MockServer::start(vec![Route::new("/sodium.jar", 200, NEW_SODIUM.to_vec())]).await;
+    let root = temp_dir("install-manifest");
+    let mrpack = root.join("pack.mrpack");
+    write_sodium_pack(&mrpack, &server, NEW_SODIUM);
+
+    // Replaced by hand since the install, so the recorded hash is stale
+    let instance = root.join("instance");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    std::fs::write(instance.join("mods/sodium.jar"), b"hand-built sodium").unwrap();
+    let mut manifest = InstallManifest::new("1.21.1", "fabric", Some("0.16.5"));
+    manifest.mods.push(ManifestMod {
+        path: "mods/sodium.jar".to_string(),
+        sha1: Some(sha1_hex(SODIUM)),
+        sha512: None,
+        size: SODIUM.len() as u64,
+    });
+    manifest.write(&instance).await.unwrap();
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+
+    assert!(result.updated_mods.is_empty());
+    assert!(server.requests().is_empty());
+    assert_eq!(std::fs::read(instance.join("mods/sodium.jar")).unwrap(), b"hand-built sodium");
+    assert!(InstallManifest::read(&instance).await.unwrap().mods.is_empty());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
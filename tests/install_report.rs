@@ -0,0 +1,62 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::path::Path;
+
+use common::{install_fake_java, seed_offline_cache, temp_dir};
+use minecraft_installer::installer::MinecraftInstaller;
+
+fn count_files(dir: &Path) -> (usize, u64) {
+    let mut count = 0;
+    let mut bytes = 0;
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            let (c, b) = count_files(&path);
+            count += c;
+            bytes += b;
+        } else {
+            count += 1;
+            bytes += std::fs::metadata(&path).unwrap().len();
+        }
+    }
+    (count, bytes)
+}
+
+#[tokio::test]
+async fn report_matches_files_on_disk() {
+    let root = temp_dir("install-report");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache);
+    let report = installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap();
+
+    let minecraft = install_dir.join("minecraft");
+    let (library_files, library_bytes) = count_files(&minecraft.join("libraries"));
+    let (_, version_bytes) = count_files(&minecraft.join("versions"));
+    let (_, asset_bytes) = count_files(&minecraft.join("assets"));
+    let (asset_objects, _) = count_files(&minecraft.join("assets/objects"));
+
+    assert_eq!(report.version, "1.20.1");
+    assert_eq!(report.loader, "vanilla");
+    assert_eq!(report.loader_version, "stable");
+    assert_eq!(report.java_version, Some(17));
+    assert_eq!(report.client_jar_path, minecraft.join("versions/1.20.1/1.20.1.jar"));
+    assert_eq!(report.library_count, library_files);
+    assert_eq!(report.asset_count, asset_objects);
+    assert_eq!(report.total_bytes, library_bytes + version_bytes + asset_bytes);
+
+    // A second run finds the version installed and reports the same files without a Java check
+    let again = installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap();
+    assert_eq!(again.java_version, None);
+    assert_eq!((again.library_count, again.asset_count, again.total_bytes), (1, 1, report.total_bytes));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,71 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use common::{install_fake_java, seed_offline_cache, temp_dir};
+use minecraft_installer::installer::MinecraftInstaller;
+
+async fn offline_install(root: &Path) -> (MinecraftInstaller, PathBuf) {
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache);
+    installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap();
+    (installer, install_dir.join("minecraft"))
+}
+
+#[tokio::test]
+async fn fresh_install_is_intact() {
+    let root = temp_dir("installation-verify");
+    let (installer, _) = offline_install(&root).await;
+
+    let report = installer.verify_installation("1.20.1").await.unwrap();
+    assert!(report.is_intact());
+    assert_eq!(report.checked, 4);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn flags_exactly_the_corrupt_library_and_repairs_it() {
+    let root = temp_dir("installation-verify");
+    let (installer, minecraft) = offline_install(&root).await;
+    let library = minecraft.join("libraries/com/example/lib/1.0/lib-1.0.jar");
+    std::fs::write(&library, b"library jaR").unwrap();
+
+    let report = installer.verify_installation("1.20.1").await.unwrap();
+    assert!(report.missing.is_empty());
+    let corrupt: Vec<_> = report.corrupt.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(corrupt, vec!["libraries/com/example/lib/1.0/lib-1.0.jar"]);
+
+    let repaired = installer.repair_installation("1.20.1", &report).await.unwrap();
+    assert!(repaired.is_intact());
+    assert_eq!(std::fs::read(&library).unwrap(), b"library jar");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn repair_restores_assets_behind_a_deleted_index() {
+    let root = temp_dir("installation-verify");
+    let (installer, minecraft) = offline_install(&root).await;
+    std::fs::remove_file(minecraft.join("assets/indexes/5.json")).unwrap();
+    std::fs::remove_dir_all(minecraft.join("assets/objects")).unwrap();
+
+    let report = installer.verify_installation("1.20.1").await.unwrap();
+    let missing: Vec<_> = report.missing.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(missing, vec!["assets/indexes/5.json"]);
+
+    let repaired = installer.repair_installation("1.20.1", &report).await.unwrap();
+    assert!(repaired.is_intact());
+    assert_eq!(repaired.checked, 4);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
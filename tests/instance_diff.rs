@@ -0,0 +1,50 @@
+mod common;
+
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::updater::{MinecraftUpdater, ModVersionDiff};
+
+fn write_fabric_mod(mods_dir: &Path, filename: &str, id: &str, version: &str) {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(mods_dir.join(filename)).unwrap());
+    zip.start_file("fabric.mod.json", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(json!({ "schemaVersion": 1, "id": id, "version": version }).to_string().as_bytes()).unwrap();
+    zip.finish().unwrap();
+}
+
+fn instance(root: &Path, name: &str) -> std::path::PathBuf {
+    let instance = root.join("instances").join(name);
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    instance
+}
+
+#[tokio::test]
+async fn diff_sorts_mods_into_three_buckets() {
+    let root = temp_dir("instance-diff");
+    let broken = instance(&root, "Broken");
+    let good = instance(&root, "Good");
+
+    write_fabric_mod(&broken.join("mods"), "sodium-fabric-0.5.3.jar", "sodium", "0.5.3");
+    write_fabric_mod(&broken.join("mods"), "lithium-fabric-0.11.2.jar", "lithium", "0.11.2");
+    write_fabric_mod(&broken.join("mods"), "modmenu-11.0.1.jar", "modmenu", "11.0.1");
+    write_fabric_mod(&good.join("mods"), "sodium-fabric-0.6.0+mc1.21.1.jar", "sodium", "0.6.0+mc1.21.1");
+    write_fabric_mod(&good.join("mods"), "iris-fabric-1.8.0.jar", "iris", "1.8.0");
+    write_fabric_mod(&good.join("mods"), "modmenu-11.0.1.jar", "modmenu", "11.0.1");
+
+    let diff = MinecraftUpdater::new().diff_instances(&broken, &good).await.unwrap();
+
+    assert_eq!(diff.only_in_a, ["lithium-fabric-0.11.2.jar"]);
+    assert_eq!(diff.only_in_b, ["iris-fabric-1.8.0.jar"]);
+    assert_eq!(diff.version_differs, [ModVersionDiff {
+        name: "sodium".to_string(),
+        filename_a: "sodium-fabric-0.5.3.jar".to_string(),
+        version_a: Some("0.5.3".to_string()),
+        filename_b: "sodium-fabric-0.6.0+mc1.21.1.jar".to_string(),
+        version_b: Some("0.6.0+mc1.21.1".to_string()),
+    }]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::io::Write;
+
+use serde_json::json;
+
+use common::{fabric_meta, launcher_manager, temp_dir, write_mrpack};
+use minecraft_installer::launcher_support::{naha_instance_name, sanitize_instance_dir_name, LauncherManager};
+
+#[test]
+fn default_names_include_pack_version() {
+    assert_eq!(naha_instance_name("neoforge", Some("0.0.18")), "NAHA-NeoForge-0.0.18");
+    assert_eq!(naha_instance_name("fabric", Some("0.2.5")), "NAHA-Fabric-0.2.5");
+    assert_eq!(naha_instance_name("quilt", Some("1.0")), "NAHA-Quilt-1.0");
+}
+
+#[test]
+fn unknown_version_falls_back_to_loader_name() {
+    assert_eq!(naha_instance_name("neoforge", None), "NAHA-NeoForge");
+    assert_eq!(naha_instance_name("fabric", Some("")), "NAHA-Fabric");
+    assert_eq!(naha_instance_name("fabric", Some("latest")), "NAHA-Fabric");
+    assert_eq!(naha_instance_name("vanilla", None), "NAHA-vanilla");
+}
+
+#[test]
+fn reads_pack_version_from_archives() {
+    let dir = temp_dir("instance-naming");
+    let manager = LauncherManager::new();
+
+    let mrpack = dir.join("pack.mrpack");
+    write_mrpack(&mrpack, &json!({"formatVersion":1,"game":"minecraft","versionId":"0.0.18","name":"NAHA","files":[],"dependencies":{}}), &[]);
+    assert_eq!(manager.modpack_version(&mrpack).unwrap().as_deref(), Some("0.0.18"));
+
+    let cf_zip = dir.join("pack.zip");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&cf_zip).unwrap());
+    zip.start_file("manifest.json", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(br#"{"manifestType":"minecraftModpack","version":"2.1.0"}"#).unwrap();
+    zip.finish().unwrap();
+    assert_eq!(manager.modpack_version(&cf_zip).unwrap().as_deref(), Some("2.1.0"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn directory_names_are_sanitized() {
+    assert_eq!(sanitize_instance_dir_name("NAHA/Fabric"), "NAHA_Fabric");
+    assert_eq!(sanitize_instance_dir_name("NAHA: Survival 🎮"), "NAHA_ Survival");
+    assert_eq!(sanitize_instance_dir_name("Modpack v1.0..."), "Modpack v1.0");
+    assert_eq!(sanitize_instance_dir_name("Café Créatif"), "Café Créatif");
+    assert_eq!(sanitize_instance_dir_name("con"), "con_");
+    assert_eq!(sanitize_instance_dir_name("COM1.txt"), "COM1_.txt");
+    assert_eq!(sanitize_instance_dir_name(" ... "), "instance");
+}
+
+#[tokio::test]
+async fn slashes_and_collisions_get_their_own_directory() {
+    let root = temp_dir("instance-naming").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+
+    let slashed = manager.create_instance(&root, "NAHA/Fabric", "1.21.1", "fabric", Some("0.16.5")).await.unwrap();
+    assert_eq!(slashed, root.join("instances").join("NAHA_Fabric"));
+    let cfg = std::fs::read_to_string(slashed.join("instance.cfg")).unwrap();
+    assert!(cfg.contains("name=NAHA/Fabric\n"), "{}", cfg);
+
+    let colliding = manager.create_instance(&root, "NAHA:Fabric", "1.21.1", "fabric", Some("0.16.5")).await.unwrap();
+    assert_eq!(colliding, root.join("instances").join("NAHA_Fabric-2"));
+
+    // Recreating the first instance updates it in place
+    let recreated = manager.create_instance(&root, "NAHA/Fabric", "1.21.2", "fabric", Some("0.16.5")).await.unwrap();
+    assert_eq!(recreated, slashed);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use common::{fabric_meta, launcher_manager, temp_dir};
+use minecraft_installer::launcher_support::InstanceOptions;
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn options() -> InstanceOptions {
+    InstanceOptions {
+        min_memory_mb: Some(2048),
+        max_memory_mb: Some(6144),
+        java_args: Some(vec!["-XX:+UseZGC".to_string()]),
+        java_path: Some(PathBuf::from("/opt/java/21/bin/java")),
+    }
+}
+
+#[tokio::test]
+async fn prism_instance_cfg_overrides_memory_and_java() {
+    let root = temp_dir("instance-options").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let meta = fabric_meta().await;
+    let instance = launcher_manager(&meta)
+        .create_instance_with_options(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5"), &options())
+        .await
+        .unwrap();
+
+    let cfg = std::fs::read_to_string(instance.join("instance.cfg")).unwrap();
+    for line in [
+        "OverrideMemory=true",
+        "MinMemAlloc=2048",
+        "MaxMemAlloc=6144",
+        "OverrideJavaLocation=true",
+        "JavaPath=/opt/java/21/bin/java",
+        "OverrideJavaArgs=true",
+        "JvmArgs=-XX:+UseZGC",
+    ] {
+        assert_eq!(cfg.lines().filter(|l| *l == line).count(), 1, "{} in\n{}", line, cfg);
+    }
+    assert!(!cfg.contains("OverrideMemory=false"), "{}", cfg);
+    assert!(!cfg.contains("JavaPath=\n"), "{}", cfg);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn xmcl_instance_json_gets_memory_and_vm_options() {
+    let root = temp_dir("instance-options").join(".xmcl");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("launcher_profiles.json"), "{}").unwrap();
+
+    let meta = fabric_meta().await;
+    let instance = launcher_manager(&meta)
+        .create_instance_with_options(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5"), &options())
+        .await
+        .unwrap();
+
+    let json = read_json(&instance.join("instance.json"));
+    assert_eq!(json["minMemory"], 2048);
+    assert_eq!(json["maxMemory"], 6144);
+    assert_eq!(json["assignMemory"], true);
+    assert_eq!(json["java"], "/opt/java/21/bin/java");
+    assert_eq!(json["vmOptions"], json!(["-XX:+UseZGC"]));
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn default_options_keep_launcher_defaults() {
+    let root = temp_dir("instance-options").join(".xmcl");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("launcher_profiles.json"), "{}").unwrap();
+
+    let meta = fabric_meta().await;
+    let instance = launcher_manager(&meta)
+        .create_instance_with_options(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5"), &InstanceOptions::default())
+        .await
+        .unwrap();
+
+    let json = read_json(&instance.join("instance.json"));
+    assert_eq!(json["maxMemory"], 4096);
+    assert_eq!(json["java"], "");
+    assert!(json.get("vmOptions").is_none());
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
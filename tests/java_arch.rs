@@ -0,0 +1,54 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use common::temp_dir;
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::java::{normalize_arch, JavaInstallation, JavaManager};
+
+/// A `java` that prints a properties dump and version banner like a real JVM
+fn fake_java(dir: &Path, os_arch: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::create_dir_all(dir).unwrap();
+    let java = dir.join("java");
+    std::fs::write(
+        &java,
+        format!(
+            "#!/bin/sh\ncat >&2 <<'EOF'\nProperty settings:\n    java.home = /opt/jdk\n    os.arch = {}\n    os.name = Linux\n\nopenjdk version \"21.0.4\" 2024-07-16\nEOF\n",
+            os_arch
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&java, std::fs::Permissions::from_mode(0o755)).unwrap();
+    java
+}
+
+#[tokio::test]
+async fn check_java_reports_architecture() {
+    let root = temp_dir("java-arch");
+    let manager = JavaManager::new(DirectoryManager::new(root.clone()));
+
+    let java = fake_java(&root.join("x64"), "amd64");
+    let found = manager.check_java(Some(&java)).await.unwrap().unwrap();
+    assert_eq!(found, JavaInstallation { path: java, version: 21, arch: Some("x86_64".to_string()) });
+    assert!(found.matches_arch("x86_64"));
+    assert!(!found.matches_arch("aarch64"));
+
+    let java = fake_java(&root.join("arm"), "aarch64");
+    let found = manager.check_java(Some(&java)).await.unwrap().unwrap();
+    assert!(found.matches_arch("aarch64"));
+    assert!(!found.matches_arch("x86_64"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn unreported_architecture_is_accepted() {
+    let java = JavaInstallation { path: PathBuf::from("java"), version: 17, arch: None };
+    assert!(java.matches_arch("aarch64"));
+    assert_eq!(normalize_arch("arm64"), "aarch64");
+    assert_eq!(normalize_arch("i386"), "x86");
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::io::Write;
+
+use common::temp_dir;
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::java::JavaManager;
+
+const RELEASE: &[u8] = b"JAVA_VERSION=\"21.0.4\"\n";
+
+#[tokio::test]
+async fn extracts_tar_gz_with_permissions() {
+    let root = temp_dir("java-extract");
+    let archive_path = root.join("java_installer.tmp");
+
+    let encoder = flate2::write::GzEncoder::new(std::fs::File::create(&archive_path).unwrap(), flate2::Compression::fast());
+    let mut builder = tar::Builder::new(encoder);
+    for (path, mode, data) in [("jdk-21/bin/java", 0o755, b"#!/bin/sh\n".as_slice()), ("jdk-21/release", 0o644, RELEASE)] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(mode);
+        header.set_cksum();
+        builder.append_data(&mut header, path, data).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let extract_dir = root.join("java-21");
+    let manager = JavaManager::new(DirectoryManager::new(root.clone()));
+    manager.extract_java(&archive_path, &extract_dir).await.unwrap();
+
+    assert_eq!(std::fs::read(extract_dir.join("jdk-21/release")).unwrap(), RELEASE);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(extract_dir.join("jdk-21/bin/java")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o111, 0o111);
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn extracts_zip_regardless_of_extension() {
+    let root = temp_dir("java-extract");
+    let archive_path = root.join("java_installer.tmp");
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive_path).unwrap());
+    zip.start_file("jdk-21/release", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(RELEASE).unwrap();
+    zip.start_file("../escaped.txt", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(b"nope").unwrap();
+    zip.finish().unwrap();
+
+    let extract_dir = root.join("java-21");
+    let manager = JavaManager::new(DirectoryManager::new(root.clone()));
+    manager.extract_java(&archive_path, &extract_dir).await.unwrap();
+
+    assert_eq!(std::fs::read(extract_dir.join("jdk-21/release")).unwrap(), RELEASE);
+    assert!(!root.join("escaped.txt").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
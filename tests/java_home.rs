@@ -0,0 +1,53 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{fake_java_home, seed_offline_cache, temp_dir};
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::installer::MinecraftInstaller;
+use minecraft_installer::java::JavaManager;
+
+#[tokio::test]
+async fn java_home_is_used_without_installing_a_jre() {
+    let root = temp_dir("java-home");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let java_home = root.join("jdk-21");
+    seed_offline_cache(&cache);
+    fake_java_home(&java_home, "21.0.2");
+
+    let report = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache)
+        .with_java_home(java_home)
+        .install_minecraft("1.20.1", "vanilla", "stable", false)
+        .await
+        .unwrap();
+
+    assert_eq!(report.java_version, Some(21));
+    let java_dirs = std::fs::read_dir(install_dir.join("java")).map(|dir| dir.count()).unwrap_or(0);
+    assert_eq!(java_dirs, 0, "a JRE was installed");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn java_home_older_than_required_is_rejected() {
+    let root = temp_dir("java-home");
+    let java_home = root.join("jdk-8");
+    fake_java_home(&java_home, "1.8.0_392");
+
+    let error = JavaManager::new(DirectoryManager::new(root.join("install")))
+        .with_override(java_home)
+        .ensure_java(17)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::JavaInstallationFailed(_)), "{}", error);
+    assert!(error.to_string().contains("Java 8"), "{}", error);
+    assert!(error.to_string().contains("too old"), "{}", error);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
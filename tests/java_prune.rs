@@ -0,0 +1,93 @@
+mod common;
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::java::JavaManager;
+use minecraft_installer::launcher_support::{InstanceOptions, LauncherManager};
+
+fn mock_jre(dirs: &DirectoryManager, major: u32) {
+    let bin = dirs.java_version_dir(major).join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    std::fs::write(bin.join("java"), "#!/bin/sh\n").unwrap();
+}
+
+fn installed_version(dirs: &DirectoryManager, id: &str, json: serde_json::Value) {
+    std::fs::create_dir_all(dirs.version_dir(id)).unwrap();
+    std::fs::write(dirs.version_json(id), json.to_string()).unwrap();
+}
+
+fn majors(java: &JavaManager) -> Vec<u32> {
+    java.list_installed().into_iter().map(|(major, _)| major).collect()
+}
+
+#[tokio::test]
+async fn prunes_every_jre_outside_the_keep_set() {
+    let root = temp_dir("java-prune");
+    let dirs = DirectoryManager::new(root.clone());
+    for major in [8, 17, 21] {
+        mock_jre(&dirs, major);
+    }
+    std::fs::create_dir_all(dirs.java_dir().join("not-a-jre")).unwrap();
+    let java = JavaManager::new(dirs.clone());
+    assert_eq!(majors(&java), vec![8, 17, 21]);
+
+    let removed = java.prune_unused(&[21]).await.unwrap();
+
+    assert_eq!(removed, vec![dirs.java_version_dir(8), dirs.java_version_dir(17)]);
+    assert_eq!(majors(&java), vec![21]);
+    assert!(dirs.java_dir().join("not-a-jre").is_dir());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn jre_an_installed_version_needs_is_kept() {
+    let root = temp_dir("java-prune");
+    let dirs = DirectoryManager::new(root.clone());
+    for major in [8, 17, 21] {
+        mock_jre(&dirs, major);
+    }
+    installed_version(&dirs, "1.20.1", json!({ "id": "1.20.1", "javaVersion": { "majorVersion": 17 } }));
+    installed_version(&dirs, "fabric-loader-0.16.5-1.20.1", json!({ "id": "fabric-loader-0.16.5-1.20.1", "inheritsFrom": "1.20.1" }));
+    let java = JavaManager::new(dirs.clone());
+    assert_eq!(java.referenced_versions(), vec![17]);
+
+    let removed = java.prune_unused(&[]).await.unwrap();
+
+    assert_eq!(removed.len(), 2);
+    assert_eq!(majors(&java), vec![17]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn jre_an_instance_is_pinned_to_is_kept() {
+    let root = temp_dir("java-prune");
+    let dirs = DirectoryManager::new(root.join("installer"));
+    for major in [17, 21] {
+        mock_jre(&dirs, major);
+    }
+    let prism = root.join("PrismLauncher");
+    let instance = prism.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(prism.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    std::fs::write(instance.join("instance.cfg"), "[General]\nname=NAHA-Fabric\n").unwrap();
+
+    let mut manager = LauncherManager::new();
+    manager.add_search_path(prism.clone());
+    let options = InstanceOptions {
+        java_path: Some(dirs.java_version_dir(17).join("bin").join("java")),
+        ..Default::default()
+    };
+    manager.apply_instance_options(&instance, &options).await.unwrap();
+
+    let java = JavaManager::new(dirs.clone());
+    let removed = java.prune_unused_except(&[], &manager.configured_java_paths().await).await.unwrap();
+
+    assert_eq!(removed, vec![dirs.java_version_dir(21)]);
+    assert_eq!(majors(&java), vec![17]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
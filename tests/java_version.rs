@@ -0,0 +1,29 @@
+use minecraft_installer::java::parse_java_major;
+
+#[test]
+fn version_banners_parse_to_their_major_version() {
+    let cases = [
+        ("java version \"1.8.0_382\"\nJava(TM) SE Runtime Environment (build 1.8.0_382-b05)", 8),
+        ("openjdk version \"11.0.2\" 2019-01-15\nOpenJDK Runtime Environment 18.9 (build 11.0.2+9)", 11),
+        ("openjdk version \"17.0.1+12\" 2021-10-19", 17),
+        ("openjdk version \"21-ea\" 2023-09-19\nOpenJDK Runtime Environment (build 21-ea+35-2513)", 21),
+        ("openjdk version \"21\" 2023-09-19", 21),
+        (
+            "openjdk version \"17.0.8.1\" 2023-08-24 LTS\nOpenJDK Runtime Environment Zulu17.44+53-CA (build 17.0.8.1+1-LTS)\nOpenJDK 64-Bit Server VM Zulu17.44+53-CA (build 17.0.8.1+1-LTS, mixed mode, sharing)",
+            17,
+        ),
+        ("openjdk version \"1.8.0_392\"\nOpenJDK Runtime Environment (Zulu 8.74.0.17-CA-linux64) (build 1.8.0_392-b08)", 8),
+        ("Property settings:\n    java.version = 21.0.4\n\nopenjdk version \"21.0.4\" 2024-07-16", 21),
+    ];
+
+    for (output, major) in cases {
+        assert_eq!(parse_java_major(output), Some(major), "{}", output);
+    }
+}
+
+#[test]
+fn output_without_a_version_string_is_rejected() {
+    for output in ["", "Error: could not create the Java Virtual Machine.", "openjdk version \"ea\""] {
+        assert_eq!(parse_java_major(output), None, "{}", output);
+    }
+}
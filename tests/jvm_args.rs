@@ -0,0 +1,94 @@
+mod common;
+
+use std::path::Path;
+
+use serde_json::json;
+
+use common::{fabric_meta, launcher_manager, temp_dir};
+use minecraft_installer::launcher_support::{parse_jvm_args, LauncherManager, AIKAR_JVM_ARGS};
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn presets_and_custom_lists_parse() {
+    assert_eq!(parse_jvm_args("Aikar"), AIKAR_JVM_ARGS.iter().map(|a| a.to_string()).collect::<Vec<_>>());
+    assert_eq!(parse_jvm_args(" -Xss2M  -XX:+UseZGC "), vec!["-Xss2M", "-XX:+UseZGC"]);
+}
+
+#[tokio::test]
+async fn prism_instance_gets_jvm_args_override() {
+    let root = temp_dir("jvm-args").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let instance = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None).await.unwrap();
+    manager.apply_jvm_args(&instance, &parse_jvm_args("aikar")).await.unwrap();
+
+    let cfg = std::fs::read_to_string(instance.join("instance.cfg")).unwrap();
+    assert_eq!(cfg.lines().filter(|l| l.starts_with("OverrideJavaArgs=")).collect::<Vec<_>>(), vec!["OverrideJavaArgs=true"]);
+    assert!(cfg.contains(&format!("JvmArgs={}\n", AIKAR_JVM_ARGS.join(" "))));
+    assert!(cfg.contains("name=NAHA-Fabric\n"));
+    assert!(cfg.contains("[UI]\n"));
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn xmcl_instance_gets_vm_options() {
+    let root = temp_dir("jvm-args").join(".xmcl");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("launcher_profiles.json"), "{}").unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let instance = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None).await.unwrap();
+    manager.apply_jvm_args(&instance, &parse_jvm_args("-Xss2M -XX:+UseZGC")).await.unwrap();
+
+    assert_eq!(read_json(&instance.join("instance.json"))["vmOptions"], json!(["-Xss2M", "-XX:+UseZGC"]));
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn astralrinth_profile_and_database_get_extra_launch_args() {
+    let root = temp_dir("jvm-args").join("AstralRinthApp");
+    std::fs::create_dir_all(root.join("profiles")).unwrap();
+    std::fs::write(root.join("app-window-state.json"), "{}").unwrap();
+    rusqlite::Connection::open(root.join("app.db"))
+        .unwrap()
+        .execute_batch(
+            "CREATE TABLE profiles (
+                path TEXT PRIMARY KEY, name TEXT, game_version TEXT, mod_loader TEXT, install_stage TEXT,
+                created INTEGER, modified INTEGER, groups TEXT, override_extra_launch_args TEXT, override_custom_env_vars TEXT
+            );",
+        )
+        .unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let instance = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", None).await.unwrap();
+    manager.apply_jvm_args(&instance, &parse_jvm_args("aikar")).await.unwrap();
+
+    assert_eq!(read_json(&instance.join("profile.json"))["extra_launch_args"], json!(AIKAR_JVM_ARGS));
+    let stored: String = rusqlite::Connection::open(root.join("app.db"))
+        .unwrap()
+        .query_row("SELECT override_extra_launch_args FROM profiles WHERE path = 'naha-fabric'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&stored).unwrap(), json!(AIKAR_JVM_ARGS));
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn unknown_instance_layout_is_rejected() {
+    let root = temp_dir("jvm-args");
+
+    let result = LauncherManager::new().apply_jvm_args(&root, &parse_jvm_args("aikar")).await;
+    assert!(result.is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::LauncherType;
+use minecraft_installer::updater::MinecraftUpdater;
+
+#[tokio::test]
+async fn xmcl_uses_last_played_date() {
+    let root = temp_dir("last-updated");
+    let instance = root.join(".xmcl").join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(
+        instance.join("instance.json"),
+        json!({
+            "name": "NAHA-Fabric",
+            "lastPlayedDate": 1728388800000i64,
+            "runtime": { "minecraft": "1.21.1", "fabricLoader": "0.16.5", "forge": "", "neoForged": "" }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let info = MinecraftUpdater::new().analyze_instance(&instance).await.unwrap().unwrap();
+    assert_eq!(info.last_updated.as_deref(), Some("2024-10-08T12:00:00+00:00"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn official_uses_profile_last_used() {
+    let root = temp_dir("last-updated").join(".minecraft");
+    std::fs::create_dir_all(root.join("versions")).unwrap();
+    std::fs::write(
+        root.join("launcher_profiles.json"),
+        json!({
+            "profiles": {
+                "abc123": {
+                    "name": "Fabric 1.21.1",
+                    "type": "custom",
+                    "lastVersionId": "fabric-loader-0.16.5-1.21.1",
+                    "lastUsed": "2024-10-08T14:30:00.000Z"
+                },
+                "def456": {
+                    "name": "Never Played",
+                    "type": "custom",
+                    "lastVersionId": "1.21.1"
+                }
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let instances = MinecraftUpdater::new().scan_launcher(&LauncherType::Official, &root).await.unwrap();
+    let last_updated = |name: &str| {
+        instances.iter().find(|i| i.name == name).unwrap().last_updated.clone()
+    };
+    assert_eq!(last_updated("Fabric 1.21.1").as_deref(), Some("2024-10-08T14:30:00+00:00"));
+    assert_eq!(last_updated("Never Played"), None);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn prism_uses_last_launch_time() {
+    let root = temp_dir("last-updated");
+    let instance = root.join("instances").join("NAHA-NeoForge");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(instance.join("instance.cfg"), "name=NAHA-NeoForge\nlastLaunchTime=1728388800000\n").unwrap();
+    std::fs::write(instance.join("mmc-pack.json"), json!({ "components": [] }).to_string()).unwrap();
+
+    let info = MinecraftUpdater::new().analyze_instance(&instance).await.unwrap().unwrap();
+    assert_eq!(info.last_updated.as_deref(), Some("2024-10-08T12:00:00+00:00"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::{macos_launcher_dirs, uwp_minecraft_dirs, LauncherManager, LauncherType};
+use minecraft_installer::updater::MinecraftUpdater;
+
+#[tokio::test]
+async fn scans_multimc_instances() {
+    let root = temp_dir("multimc-scan");
+    std::fs::write(root.join("multimc.cfg"), "InstanceDir=instances\n").unwrap();
+
+    let instance = root.join("instances").join("fabric-pack");
+    std::fs::create_dir_all(instance.join("minecraft").join("mods")).unwrap();
+    std::fs::write(instance.join("instance.cfg"), "InstanceType=OneSix\nname=Fabric Pack\n").unwrap();
+    std::fs::write(
+        instance.join("mmc-pack.json"),
+        json!({
+            "formatVersion": 1,
+            "components": [
+                { "cachedName": "Minecraft", "uid": "net.minecraft", "version": "1.20.1" },
+                { "cachedName": "Fabric Loader", "uid": "net.fabricmc.fabric-loader", "cachedVersion": "0.15.11" }
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    std::fs::write(instance.join("minecraft").join("mods").join("sodium-0.5.3.jar"), b"sodium").unwrap();
+    std::fs::write(instance.join("minecraft").join("mods").join("lithium-0.11.2.jar"), b"lithium").unwrap();
+
+    assert_eq!(LauncherManager::new().detect_launcher_type(&root).await.unwrap(), LauncherType::MultiMC);
+
+    let instances = MinecraftUpdater::new().scan_launcher(&LauncherType::MultiMC, &root).await.unwrap();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].name, "Fabric Pack");
+    assert_eq!(instances[0].launcher_type, "MultiMC");
+    assert_eq!(instances[0].minecraft_version, "1.20.1");
+    assert_eq!(instances[0].mod_loader, "Fabric");
+    assert_eq!(instances[0].mod_count, 2);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn scans_atlauncher_instances() {
+    let root = temp_dir("atlauncher-scan");
+    for dir in ["configs", "servers"] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+
+    let instance = root.join("instances").join("NeoForgePack");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    std::fs::write(
+        instance.join("instance.json"),
+        json!({
+            "id": "1.21.1",
+            "type": "release",
+            "launcher": {
+                "name": "NeoForge Pack",
+                "pack": "NeoForge Pack",
+                "version": "1.0.0",
+                "loaderVersion": { "version": "21.1.65", "type": "NeoForge" }
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    std::fs::write(instance.join("mods").join("jei-19.0.0.jar"), b"jei").unwrap();
+
+    assert_eq!(LauncherManager::new().detect_launcher_type(&root).await.unwrap(), LauncherType::ATLauncher);
+
+    let updater = MinecraftUpdater::new();
+    let instances = updater.scan_launcher(&LauncherType::ATLauncher, &root).await.unwrap();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].name, "NeoForge Pack");
+    assert_eq!(instances[0].launcher_type, "ATLauncher");
+    assert_eq!(instances[0].minecraft_version, "1.21.1");
+    assert_eq!(instances[0].mod_loader, "NeoForge");
+    assert_eq!(instances[0].mod_loader_version.as_deref(), Some("21.1.65"));
+    assert_eq!(instances[0].mod_count, 1);
+
+    // Direct analysis must not mistake ATLauncher's instance.json for XMCL's
+    let analyzed = updater.analyze_instance(&instance).await.unwrap().unwrap();
+    assert_eq!(analyzed.launcher_type, "ATLauncher");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn scans_gdlauncher_instances() {
+    let root = temp_dir("gdlauncher-scan").join("gdlauncher_next");
+    std::fs::create_dir_all(root.join("datastore").join("libraries")).unwrap();
+
+    let instance = root.join("instances").join("Fabric Pack");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    std::fs::write(
+        instance.join("config.json"),
+        json!({
+            "loader": {
+                "loaderType": "fabric",
+                "mcVersion": "1.20.1",
+                "loaderVersion": "0.15.11"
+            },
+            "timePlayed": 3600,
+            "lastPlayed": 1_700_000_000_000_i64,
+            "mods": []
+        })
+        .to_string(),
+    )
+    .unwrap();
+    std::fs::write(instance.join("mods").join("sodium-0.5.3.jar"), b"sodium").unwrap();
+
+    assert_eq!(LauncherManager::new().detect_launcher_type(&root).await.unwrap(), LauncherType::GDLauncher);
+
+    let updater = MinecraftUpdater::new();
+    let instances = updater.scan_launcher(&LauncherType::GDLauncher, &root).await.unwrap();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].name, "Fabric Pack");
+    assert_eq!(instances[0].launcher_type, "GDLauncher");
+    assert_eq!(instances[0].minecraft_version, "1.20.1");
+    assert_eq!(instances[0].mod_loader, "Fabric");
+    assert_eq!(instances[0].mod_loader_version.as_deref(), Some("0.15.11"));
+    assert_eq!(instances[0].mod_count, 1);
+    assert!(instances[0].last_updated.as_deref().unwrap().starts_with("2023-11-14"));
+
+    let analyzed = updater.analyze_instance(&instance).await.unwrap().unwrap();
+    assert_eq!(analyzed.launcher_type, "GDLauncher");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn finds_microsoft_store_launcher_data() {
+    let local_app_data = temp_dir("uwp-scan");
+    let packages = local_app_data.join("Packages");
+    let game_dir = packages
+        .join("Microsoft.4297127D64EC6_8wekyb3d8bbwe")
+        .join("LocalCache")
+        .join("Roaming")
+        .join(".minecraft");
+    std::fs::create_dir_all(game_dir.join("versions")).unwrap();
+    std::fs::write(game_dir.join("launcher_profiles_microsoft_store.json"), r#"{"profiles": {}}"#).unwrap();
+    // Other Store apps live beside it
+    std::fs::create_dir_all(packages.join("Microsoft.WindowsTerminal_8wekyb3d8bbwe").join("LocalCache")).unwrap();
+
+    assert_eq!(uwp_minecraft_dirs(&local_app_data), vec![game_dir.clone()]);
+    assert_eq!(LauncherManager::new().detect_launcher_type(&game_dir).await.unwrap(), LauncherType::Official);
+
+    std::fs::remove_dir_all(&local_app_data).unwrap();
+}
+
+#[tokio::test]
+async fn macos_application_support_launchers_are_detected() {
+    let home = temp_dir("macos-launchers");
+    let support = home.join("Library").join("Application Support");
+
+    let xmcl = support.join("XMCL");
+    std::fs::create_dir_all(xmcl.join("instances")).unwrap();
+    std::fs::write(xmcl.join("launcher_profiles.json"), "{}").unwrap();
+
+    let multimc = support.join("MultiMC");
+    std::fs::create_dir_all(multimc.join("instances")).unwrap();
+    std::fs::write(multimc.join("multimc.cfg"), "InstanceDir=instances\n").unwrap();
+
+    let atlauncher = support.join("ATLauncher");
+    for dir in ["configs", "instances", "servers"] {
+        std::fs::create_dir_all(atlauncher.join(dir)).unwrap();
+    }
+
+    let cracked = support.join("PrismLauncher-Cracked");
+    std::fs::create_dir_all(cracked.join("instances")).unwrap();
+    std::fs::write(cracked.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    std::fs::write(cracked.join("accounts.json"), r#"{"accounts": [{"type": "Offline"}]}"#).unwrap();
+
+    // LauncherManager::new() only adds these on macOS, so search them explicitly here
+    let mut manager = LauncherManager::new();
+    for dir in macos_launcher_dirs(&home) {
+        manager.add_search_path(dir);
+    }
+    let launchers = manager.detect_launchers().await;
+    for expected in [
+        (LauncherType::XMCL, xmcl),
+        (LauncherType::MultiMC, multimc),
+        (LauncherType::ATLauncher, atlauncher),
+        (LauncherType::PrismCracked, cracked),
+    ] {
+        assert!(launchers.contains(&expected), "{:?} not in {:?}", expected, launchers);
+    }
+
+    std::fs::remove_dir_all(&home).unwrap();
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::{LauncherManager, LauncherType};
+
+#[tokio::test]
+async fn custom_search_path_is_detected() {
+    let usb = temp_dir("launcher-search-path").join("PrismLauncher-portable");
+    std::fs::create_dir_all(usb.join("instances")).unwrap();
+    std::fs::write(usb.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    std::fs::write(usb.join("portable.txt"), "").unwrap();
+
+    let mut manager = LauncherManager::new();
+    assert!(!manager.detect_launchers().await.iter().any(|(_, path)| *path == usb));
+
+    manager.add_search_path(usb.clone());
+    manager.add_search_path(usb.clone());
+    let found: Vec<_> = manager.detect_launchers().await.into_iter().filter(|(_, path)| *path == usb).collect();
+    assert_eq!(found, vec![(LauncherType::Prism, usb.clone())]);
+
+    std::fs::remove_dir_all(usb.parent().unwrap()).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn symlinked_launcher_is_detected_once() {
+    let root = temp_dir("launcher-search-path");
+    let prism = root.join("other-drive").join("PrismLauncher");
+    std::fs::create_dir_all(prism.join("instances")).unwrap();
+    std::fs::write(prism.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    let link = root.join("PrismLauncher");
+    std::os::unix::fs::symlink(&prism, &link).unwrap();
+
+    let mut manager = LauncherManager::new();
+    manager.add_search_path(link.clone());
+    manager.add_search_path(prism.clone());
+    let found: Vec<_> = manager
+        .detect_launchers()
+        .await
+        .into_iter()
+        .filter(|(_, path)| path.starts_with(&root))
+        .collect();
+    assert_eq!(found, vec![(LauncherType::Prism, link)]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
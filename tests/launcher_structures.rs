@@ -1,9 +1,11 @@
+mod common;
+
 use std::path::PathBuf;
 use tokio::fs;
 use serde_json::json;
 
+use common::{sha1_hex, MockServer, Route};
 use minecraft_installer::launcher_support::{LauncherType, LauncherManager};
-use minecraft_installer::directories::DirectoryManager;
 use minecraft_installer::error::Result;
 
 /// Test launcher directory structures and instance management
@@ -472,6 +474,17 @@ async fn create_xmcl_structure(base_dir: &PathBuf) -> Result<()> {
         serde_json::to_string_pretty(&instances)?
     ).await?;
 
+    // XMCL keeps a launcher_profiles.json in its root so Forge installers can run against it
+    let profiles = json!({
+        "profiles": {},
+        "selectedProfile": ""
+    });
+
+    fs::write(
+        xmcl_dir.join("launcher_profiles.json"),
+        serde_json::to_string_pretty(&profiles)?
+    ).await?;
+
     Ok(())
 }
 
@@ -525,6 +538,9 @@ async fn create_astral_rinth_structure(base_dir: &PathBuf) -> Result<()> {
         serde_json::to_string_pretty(&settings)?
     ).await?;
 
+    // The app writes its window state next to profiles/ on first run
+    fs::write(astral_dir.join("app-window-state.json"), "{}").await?;
+
     // Create a sample profile
     create_astral_rinth_profile(&astral_dir, "vanilla-1.20.1").await?;
 
@@ -680,8 +696,12 @@ async fn test_mrpack_installation() -> Result<()> {
         fs::remove_dir_all(&test_dir).await?;
     }
 
+    // Serve the pack's one mod locally
+    let mod_jar = b"example mod jar".to_vec();
+    let server = MockServer::start(vec![Route::new("/example-mod.jar", 200, mod_jar.clone())]).await;
+
     // Create test mrpack structure
-    create_test_mrpack(&test_dir).await?;
+    create_test_mrpack(&test_dir, &server.url("/example-mod.jar"), &mod_jar).await?;
 
     // Test installation
     let launcher_manager = LauncherManager::new();
@@ -696,8 +716,8 @@ async fn test_mrpack_installation() -> Result<()> {
     // Verify installation
     let instance_dir = test_dir.join("instance");
     assert!(instance_dir.exists());
-    assert!(instance_dir.join("mods").exists());
-    assert!(instance_dir.join("config").exists());
+    assert_eq!(std::fs::read(instance_dir.join("mods").join("example-mod.jar"))?, mod_jar);
+    assert!(instance_dir.join("config").join("example.toml").exists());
 
     // Clean up
     fs::remove_dir_all(&test_dir).await?;
@@ -707,7 +727,7 @@ async fn test_mrpack_installation() -> Result<()> {
 }
 
 /// Create a test mrpack file
-async fn create_test_mrpack(test_dir: &PathBuf) -> Result<()> {
+async fn create_test_mrpack(test_dir: &PathBuf, mod_url: &str, mod_jar: &[u8]) -> Result<()> {
     use std::io::Write;
 
     fs::create_dir_all(test_dir).await?;
@@ -728,16 +748,16 @@ async fn create_test_mrpack(test_dir: &PathBuf) -> Result<()> {
             {
                 "path": "mods/example-mod.jar",
                 "hashes": {
-                    "sha1": "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+                    "sha1": sha1_hex(mod_jar)
                 },
                 "env": {
                     "client": "required",
                     "server": "required"
                 },
                 "downloads": [
-                    "https://example.com/example-mod.jar"
+                    mod_url
                 ],
-                "fileSize": 12345
+                "fileSize": mod_jar.len()
             }
         ],
         "dependencies": {
@@ -746,14 +766,14 @@ async fn create_test_mrpack(test_dir: &PathBuf) -> Result<()> {
         }
     });
 
-    zip.start_file("modrinth.index.json", zip::write::FileOptions::default())?;
+    zip.start_file("modrinth.index.json", zip::write::SimpleFileOptions::default())?;
     zip.write_all(serde_json::to_string_pretty(&index)?.as_bytes())?;
 
     // Add overrides directory with config files
-    zip.add_directory("overrides/", zip::write::FileOptions::default())?;
-    zip.add_directory("overrides/config/", zip::write::FileOptions::default())?;
+    zip.add_directory("overrides/", zip::write::SimpleFileOptions::default())?;
+    zip.add_directory("overrides/config/", zip::write::SimpleFileOptions::default())?;
 
-    zip.start_file("overrides/config/example.toml", zip::write::FileOptions::default())?;
+    zip.start_file("overrides/config/example.toml", zip::write::SimpleFileOptions::default())?;
     zip.write_all(b"# Example config file\nenabled = true\n")?;
 
     zip.finish()?;
@@ -0,0 +1,32 @@
+use minecraft_installer::launcher_support::LauncherType;
+
+#[test]
+fn display_round_trips_through_from_str() {
+    for launcher_type in LauncherType::ALL {
+        assert_eq!(launcher_type.to_string().parse::<LauncherType>().unwrap(), launcher_type);
+    }
+}
+
+#[test]
+fn accepts_legacy_cli_aliases() {
+    let cases = [
+        ("astralrinth", LauncherType::AstralRinth),
+        ("modrinth", LauncherType::ModrinthApp),
+        ("prism", LauncherType::Prism),
+        ("prismlauncher", LauncherType::Prism),
+        ("prismcracked", LauncherType::PrismCracked),
+        ("Official Minecraft", LauncherType::Official),
+        ("xmcl", LauncherType::XMCL),
+        ("multimc", LauncherType::MultiMC),
+        ("other", LauncherType::Other),
+    ];
+    for (name, expected) in cases {
+        assert_eq!(name.parse::<LauncherType>().unwrap(), expected, "{}", name);
+    }
+}
+
+#[test]
+fn rejects_unknown_names() {
+    assert!("curseforge".parse::<LauncherType>().is_err());
+    assert!("".parse::<LauncherType>().is_err());
+}
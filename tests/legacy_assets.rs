@@ -0,0 +1,89 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::path::Path;
+
+use common::{install_fake_java, seed_offline_cache, sha1_hex, temp_dir};
+use minecraft_installer::installer::MinecraftInstaller;
+
+/// Rewrite the seeded 1.20.1 asset index as a legacy one with the given flag set
+fn make_index_legacy(cache: &Path, flag: &str) {
+    let manifest_path = cache.join("version_manifest.json");
+    let mut manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+    let details_sha1 = manifest["versions"][0]["sha1"].as_str().unwrap().to_string();
+    let mut details: serde_json::Value = serde_json::from_slice(&std::fs::read(cache.join(&details_sha1)).unwrap()).unwrap();
+
+    let index_sha1 = details["assetIndex"]["sha1"].as_str().unwrap().to_string();
+    let mut index: serde_json::Value = serde_json::from_slice(&std::fs::read(cache.join(&index_sha1)).unwrap()).unwrap();
+    index[flag] = true.into();
+    let index = index.to_string();
+    let index_sha1 = sha1_hex(index.as_bytes());
+    std::fs::write(cache.join(&index_sha1), &index).unwrap();
+
+    details["assets"] = "legacy".into();
+    details["assetIndex"]["id"] = "legacy".into();
+    details["assetIndex"]["sha1"] = index_sha1.into();
+    details["assetIndex"]["size"] = index.len().into();
+    let details = details.to_string();
+    let details_sha1 = sha1_hex(details.as_bytes());
+    std::fs::write(cache.join(&details_sha1), details).unwrap();
+
+    manifest["versions"][0]["sha1"] = details_sha1.into();
+    std::fs::write(&manifest_path, manifest.to_string()).unwrap();
+}
+
+async fn install(root: &Path, flag: Option<&str>) -> (std::path::PathBuf, String) {
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (_, _, asset) = seed_offline_cache(&cache);
+    if let Some(flag) = flag {
+        make_index_legacy(&cache, flag);
+    }
+    install_fake_java(&install_dir);
+
+    MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache)
+        .install_minecraft("1.20.1", "vanilla", "stable", false)
+        .await
+        .unwrap();
+
+    (install_dir, asset)
+}
+
+#[tokio::test]
+async fn virtual_index_writes_named_copies_next_to_objects() {
+    let root = temp_dir("legacy-assets");
+    let (install_dir, asset) = install(&root, Some("virtual")).await;
+
+    let assets = install_dir.join("minecraft").join("assets");
+    let object = assets.join("objects").join(&asset[..2]).join(&asset);
+    let named = assets.join("virtual").join("legacy").join("minecraft/lang/en_us.json");
+    assert_eq!(std::fs::read(&object).unwrap(), b"asset object");
+    assert_eq!(std::fs::read(&named).unwrap(), b"asset object");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn map_to_resources_index_fills_the_resources_folder() {
+    let root = temp_dir("legacy-assets");
+    let (install_dir, _) = install(&root, Some("map_to_resources")).await;
+
+    let resources = install_dir.join("instances").join("minecraft-installer-1.20.1").join("resources");
+    assert_eq!(std::fs::read(resources.join("minecraft/lang/en_us.json")).unwrap(), b"asset object");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn modern_index_has_no_virtual_copies() {
+    let root = temp_dir("legacy-assets");
+    let (install_dir, _) = install(&root, None).await;
+
+    assert!(!install_dir.join("minecraft").join("assets").join("virtual").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use serde_json::json;
+
+use common::{sha1_hex, temp_dir, MockServer, Route};
+use minecraft_installer::directories::DirectoryManager;
+use minecraft_installer::download::{DownloadManager, VersionDetails};
+use minecraft_installer::error::MinecraftInstallerError;
+
+fn library(name: &str, path: &str, url: String, sha1: &str) -> serde_json::Value {
+    json!({
+        "name": name,
+        "downloads": { "artifact": { "path": path, "sha1": sha1, "size": 11, "url": url } }
+    })
+}
+
+#[tokio::test]
+async fn missing_required_library_fails_the_download() {
+    let root = temp_dir("library-failures");
+    let server = MockServer::start(vec![Route::new("/good.jar", 200, b"library jar".to_vec())]).await;
+    let good_sha1 = sha1_hex(b"library jar");
+
+    // Excluded by its rules on every OS, so its missing jar isn't a failure
+    let mut excluded = library("com.example:excluded:1.0", "com/example/excluded.jar", server.url("/excluded.jar"), &good_sha1);
+    excluded["rules"] = json!([{ "action": "allow", "os": { "name": "plan9" } }]);
+
+    let version: VersionDetails = serde_json::from_value(json!({
+        "id": "1.20.1",
+        "type": "release",
+        "mainClass": "net.minecraft.client.main.Main",
+        "assets": "5",
+        "assetIndex": { "id": "5", "sha1": "0", "size": 0, "totalSize": 0, "url": server.url("/5.json") },
+        "downloads": { "client": { "sha1": "0", "size": 0, "url": server.url("/client.jar") } },
+        "libraries": [
+            library("com.example:good:1.0", "com/example/good.jar", server.url("/good.jar"), &good_sha1),
+            library("com.example:missing:1.0", "com/example/missing.jar", server.url("/missing.jar"), &good_sha1),
+            excluded
+        ]
+    }))
+    .unwrap();
+
+    let dirs = DirectoryManager::new(root.clone());
+    dirs.init().await.unwrap();
+    let error = DownloadManager::new(dirs.clone()).download_libraries(&version).await.unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::DownloadFailed(_)), "{}", error);
+    let message = error.to_string();
+    assert!(message.contains("1 required library failed: com.example:missing:1.0"), "{}", message);
+    assert!(!message.contains("excluded"), "{}", message);
+    // The libraries that could be fetched are still in place for a retry
+    assert!(dirs.libraries_dir().join("com/example/good.jar").is_file());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::updater::MinecraftUpdater;
+
+#[tokio::test]
+async fn mods_serialize_with_every_field() {
+    let root = temp_dir("list-mods");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    let mods_dir = instance.join("mods");
+    std::fs::create_dir_all(&mods_dir).unwrap();
+    std::fs::write(mods_dir.join("sodium-0.5.3.jar"), b"sodium jar").unwrap();
+    std::fs::write(mods_dir.join("lithium-0.11.2.jar"), b"lithium").unwrap();
+    std::fs::write(mods_dir.join("minimap-2.1.0.jar"), b"my own mod").unwrap();
+    std::fs::write(mods_dir.join("notes.txt"), b"not a mod").unwrap();
+
+    let mods = MinecraftUpdater::new().list_instance_mods(&instance).await.unwrap();
+    let json = serde_json::to_value(&mods).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 3, "{}", json);
+
+    for entry in entries {
+        for field in ["name", "filename", "version", "mod_id", "file_size", "is_user_mod", "last_modified"] {
+            assert!(entry.get(field).is_some(), "{} missing from {}", field, entry);
+        }
+        assert!(!entry["name"].as_str().unwrap().is_empty(), "{}", entry);
+        assert!(entry["file_size"].as_u64().unwrap() > 0, "{}", entry);
+    }
+
+    assert_eq!(entries[0]["filename"], "lithium-0.11.2.jar");
+    assert_eq!(entries[1]["filename"], "minimap-2.1.0.jar");
+    assert_eq!(entries[1]["name"], "minimap");
+    assert_eq!(entries[1]["file_size"], 10);
+    assert_eq!(entries[1]["is_user_mod"], true);
+    assert_eq!(entries[2]["filename"], "sodium-0.5.3.jar");
+    assert_eq!(entries[2]["is_user_mod"], false);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn instance_without_mods_lists_nothing() {
+    let root = temp_dir("list-mods");
+    let instance = root.join("instances").join("Vanilla");
+    std::fs::create_dir_all(&instance).unwrap();
+
+    assert!(MinecraftUpdater::new().list_instance_mods(&instance).await.unwrap().is_empty());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
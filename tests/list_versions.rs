@@ -0,0 +1,56 @@
+mod common;
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::installer::MinecraftInstaller;
+
+fn manifest_version(id: &str, version_type: &str, release_time: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "type": version_type,
+        "url": format!("https://piston-meta.mojang.com/v1/packages/{}.json", id),
+        "time": release_time,
+        "releaseTime": release_time
+    })
+}
+
+#[tokio::test]
+async fn json_listing_has_expected_fields_and_respects_limit() {
+    let root = temp_dir("list-versions");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    std::fs::create_dir_all(&cache).unwrap();
+    let manifest = json!({
+        "latest": { "release": "1.21.1", "snapshot": "24w33a" },
+        "versions": [
+            manifest_version("1.21.1", "release", "2024-08-08T12:24:45+00:00"),
+            manifest_version("24w33a", "snapshot", "2024-08-07T12:24:45+00:00"),
+            manifest_version("1.21", "release", "2024-06-13T08:24:03+00:00"),
+            manifest_version("1.20.6", "release", "2024-04-29T12:24:03+00:00")
+        ]
+    });
+    std::fs::write(cache.join("version_manifest.json"), manifest.to_string()).unwrap();
+
+    let version_dir = install_dir.join("minecraft/versions/1.21");
+    std::fs::create_dir_all(&version_dir).unwrap();
+    std::fs::write(version_dir.join("1.21.jar"), b"jar").unwrap();
+    std::fs::write(version_dir.join("1.21.json"), b"{}").unwrap();
+
+    let installer = MinecraftInstaller::new(install_dir).await.unwrap().with_offline_cache(cache);
+
+    let releases = serde_json::to_value(installer.get_versions(Some("release"), 2).await.unwrap()).unwrap();
+    assert_eq!(
+        releases,
+        json!([
+            { "id": "1.21.1", "version_type": "release", "release_time": "2024-08-08T12:24:45+00:00", "installed": false },
+            { "id": "1.21", "version_type": "release", "release_time": "2024-06-13T08:24:03+00:00", "installed": true }
+        ])
+    );
+
+    assert_eq!(installer.get_versions(None, 0).await.unwrap().len(), 4);
+    assert_eq!(installer.get_versions(None, 1).await.unwrap()[0].id, "1.21.1");
+    assert!(installer.list_versions(None, 1, "xml").await.is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
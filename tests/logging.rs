@@ -0,0 +1,20 @@
+use tracing_subscriber::EnvFilter;
+
+use minecraft_installer::logging::{self, LogFormat};
+
+#[test]
+fn json_subscriber_builds_and_records_events() {
+    let subscriber = logging::subscriber(LogFormat::Json, EnvFilter::new("info"));
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(version = "1.21.1", files = 3, "Installing modpack");
+    });
+}
+
+#[test]
+fn log_format_parses_cli_values() {
+    assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+    assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+    assert_eq!("human".parse::<LogFormat>().unwrap(), LogFormat::Human);
+    assert_eq!(LogFormat::default(), LogFormat::Human);
+    assert!("yaml".parse::<LogFormat>().is_err());
+}
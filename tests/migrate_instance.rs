@@ -0,0 +1,60 @@
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::LauncherManager;
+
+#[tokio::test]
+async fn prism_instance_moves_into_astralrinth() {
+    let root = temp_dir("migrate-instance");
+    let prism = root.join("PrismLauncher");
+    std::fs::create_dir_all(prism.join("instances")).unwrap();
+    std::fs::write(prism.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let astral = root.join("AstralRinthApp");
+    std::fs::create_dir_all(astral.join("profiles")).unwrap();
+    std::fs::write(astral.join("app-window-state.json"), "{}").unwrap();
+    let conn = rusqlite::Connection::open(astral.join("app.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE profiles (
+            path TEXT PRIMARY KEY, name TEXT, game_version TEXT, mod_loader TEXT, install_stage TEXT,
+            created INTEGER, modified INTEGER, groups TEXT, override_extra_launch_args TEXT, override_custom_env_vars TEXT
+        );",
+    )
+    .unwrap();
+
+    let manager = LauncherManager::new();
+    let source = manager.create_instance(&prism, "NAHA Fabric", "1.21.1", "fabric", Some("0.16.5")).await.unwrap();
+    let game_dir = source.join(".minecraft");
+    for (path, body) in [
+        ("mods/sodium.jar", "sodium"),
+        ("config/sodium-options.json", "{}"),
+        ("saves/World/level.dat", "level"),
+        ("shaderpacks/complementary.zip", "shader"),
+    ] {
+        let file = game_dir.join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, body).unwrap();
+    }
+
+    let target = manager.migrate_instance(&prism, "NAHA Fabric", &astral).await.unwrap();
+
+    assert_eq!(target, astral.join("profiles").join("naha-fabric"));
+    let profile: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(target.join("profile.json")).unwrap()).unwrap();
+    assert_eq!(profile["name"], "NAHA Fabric");
+    assert_eq!(profile["game_version"], "1.21.1");
+    assert_eq!(profile["loader"], "fabric");
+    assert_eq!(profile["loader_version"], "0.16.5");
+    for path in ["mods/sodium.jar", "config/sodium-options.json", "saves/World/level.dat", "shaderpacks/complementary.zip"] {
+        assert!(target.join(path).is_file(), "{} missing", path);
+    }
+
+    let (name, loader): (String, String) = conn
+        .query_row("SELECT name, mod_loader FROM profiles WHERE path = 'naha-fabric'", [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap();
+    assert_eq!((name.as_str(), loader.as_str()), ("NAHA Fabric", "fabric"));
+
+    // The source is left as it was
+    assert!(game_dir.join("mods/sodium.jar").is_file());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::{minecraft_base_dir, LauncherManager};
+
+fn instance(root: &Path, name: &str, files: &[&str], dirs: &[&str]) -> PathBuf {
+    let path = root.join(name);
+    std::fs::create_dir_all(&path).unwrap();
+    for file in files {
+        std::fs::write(path.join(file), "{}").unwrap();
+    }
+    for dir in dirs {
+        std::fs::create_dir_all(path.join(dir)).unwrap();
+    }
+    path
+}
+
+#[test]
+fn each_layout_resolves_to_its_game_directory() {
+    let root = temp_dir("minecraft-base-dir");
+
+    let modrinth = instance(&root, "modrinth", &["profile.json"], &["mods"]);
+    assert_eq!(minecraft_base_dir(&modrinth), modrinth);
+
+    let xmcl = instance(&root, "xmcl", &["instance.json"], &[]);
+    assert_eq!(minecraft_base_dir(&xmcl), xmcl);
+
+    let prism = instance(&root, "prism", &["mmc-pack.json", "instance.cfg"], &[".minecraft/mods"]);
+    assert_eq!(minecraft_base_dir(&prism), prism.join(".minecraft"));
+
+    // A freshly registered Prism instance has no game directory yet
+    let fresh_prism = instance(&root, "fresh-prism", &["mmc-pack.json"], &[]);
+    assert_eq!(minecraft_base_dir(&fresh_prism), fresh_prism.join(".minecraft"));
+
+    let legacy_multimc = instance(&root, "legacy-multimc", &["instance.cfg"], &["minecraft/mods"]);
+    assert_eq!(minecraft_base_dir(&legacy_multimc), legacy_multimc.join("minecraft"));
+
+    let official = instance(&root, "official", &[], &["mods", "saves"]);
+    assert_eq!(minecraft_base_dir(&official), official);
+
+    let custom = instance(&root, "custom", &["launcher_profiles.json"], &[]);
+    assert_eq!(minecraft_base_dir(&custom), custom);
+
+    let wrapped = instance(&root, "wrapped", &[], &[".minecraft/mods"]);
+    assert_eq!(minecraft_base_dir(&wrapped), wrapped.join(".minecraft"));
+
+    let empty = instance(&root, "empty", &[], &[]);
+    assert_eq!(minecraft_base_dir(&empty), empty);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn copied_files_and_mods_lookup_agree() {
+    let root = temp_dir("minecraft-base-dir");
+    let prism = instance(&root, "prism", &["mmc-pack.json", "instance.cfg"], &[]);
+    let temp_instance = instance(&root, "temp", &[], &["mods", "config"]);
+    std::fs::write(temp_instance.join("mods").join("sodium.jar"), b"sodium").unwrap();
+    std::fs::write(temp_instance.join("servers.dat"), b"servers").unwrap();
+
+    let manager = LauncherManager::new();
+    manager.copy_instance_files(&temp_instance, &prism).await.unwrap();
+
+    let mods_dir = manager.find_mods_directory(&prism);
+    assert_eq!(mods_dir, prism.join(".minecraft").join("mods"));
+    assert!(mods_dir.join("sodium.jar").exists());
+    assert!(prism.join(".minecraft").join("servers.dat").exists());
+    assert!(!prism.join("mods").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn deep_trees_are_copied_intact() {
+    let root = temp_dir("minecraft-base-dir");
+    let temp_instance = instance(&root, "temp", &[], &[]);
+    let mut expected = Vec::new();
+    for world in 0..3 {
+        for depth in 0..4 {
+            let dir = (0..=depth).fold(temp_instance.join("saves").join(format!("world-{}", world)), |dir, level| {
+                dir.join(format!("level-{}", level))
+            });
+            std::fs::create_dir_all(&dir).unwrap();
+            for file in 0..5 {
+                let path = dir.join(format!("region-{}.mca", file));
+                let content = format!("world {} depth {} file {}", world, depth, file).repeat(depth + 1);
+                std::fs::write(&path, &content).unwrap();
+                expected.push((path.strip_prefix(&temp_instance).unwrap().to_path_buf(), content));
+            }
+        }
+    }
+    std::fs::create_dir_all(temp_instance.join("saves").join("empty-world").join("data")).unwrap();
+
+    let target = instance(&root, "target", &["profile.json"], &[]);
+    LauncherManager::new().copy_instance_files(&temp_instance, &target).await.unwrap();
+
+    assert_eq!(expected.len(), 60);
+    for (relative, content) in &expected {
+        assert_eq!(&std::fs::read_to_string(target.join(relative)).unwrap(), content, "{}", relative.display());
+    }
+    assert!(target.join("saves").join("empty-world").join("data").is_dir());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, sha1_hex, sha512_hex, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::updater::MinecraftUpdater;
+
+#[tokio::test]
+async fn mod_already_on_disk_with_the_pack_hash_is_not_downloaded() {
+    let root = temp_dir("mod-download-skip");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    let mods = instance.join("mods");
+    std::fs::create_dir_all(&mods).unwrap();
+    // An interrupted update left the new sodium next to the old one
+    std::fs::write(mods.join("sodium-0.9.0.jar"), b"old sodium").unwrap();
+    std::fs::write(mods.join("sodium-0.10.0.jar"), b"new sodium").unwrap();
+    std::fs::write(mods.join("lithium-0.11.0.jar"), b"old lithium").unwrap();
+
+    let server = MockServer::start(vec![
+        Route::new("/sodium-0.10.0.jar", 200, b"new sodium".to_vec()),
+        Route::new("/lithium-0.12.0.jar", 200, b"new lithium".to_vec()),
+    ])
+    .await;
+    let mrpack = root.join("pack.mrpack");
+    write_mrpack(&mrpack, &fabric_index(json!([
+        {
+            "path": "mods/sodium-0.10.0.jar",
+            "hashes": { "sha1": sha1_hex(b"new sodium"), "sha512": sha512_hex(b"new sodium") },
+            "downloads": [server.url("/sodium-0.10.0.jar")],
+            "fileSize": 10
+        },
+        {
+            "path": "mods/lithium-0.12.0.jar",
+            "hashes": { "sha1": sha1_hex(b"new lithium") },
+            "downloads": [server.url("/lithium-0.12.0.jar")],
+            "fileSize": 11
+        }
+    ])), &[]);
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(result.already_current, vec!["sodium-0.10.0.jar".to_string()]);
+    assert_eq!(result.updated_mods, vec!["lithium-0.11.0.jar → lithium-0.12.0.jar".to_string()]);
+    assert_eq!(server.requests(), vec!["/lithium-0.12.0.jar".to_string()]);
+    assert_eq!(std::fs::read(mods.join("sodium-0.10.0.jar")).unwrap(), b"new sodium");
+    assert!(!mods.join("sodium-0.9.0.jar").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
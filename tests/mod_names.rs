@@ -0,0 +1,41 @@
+use minecraft_installer::updater::normalize_mod_name;
+
+#[test]
+fn real_world_filenames_normalize_to_their_mod() {
+    let cases = [
+        ("sodium-extra-0.6.0.jar", "sodium-extra"),
+        ("sodium-extra-0.6.0+mc1.21.1.jar", "sodium-extra"),
+        ("sodium-fabric-0.6.0+mc1.21.1.jar", "sodium"),
+        ("chat_heads-0.14.0-neoforge-1.21.jar", "chat_heads"),
+        ("reinforced-barrels-2.6.1+1.21.1$reinforced-core-4.0.2+1.21.1$cloth-config-fabric-15.0.130-fabric.jar", "reinforced-barrels"),
+        ("kotlinforforge-5.7.0-all.jar", "kotlinforforge"),
+        ("badoptimizations-2.3.0-1.21.1.jar", "badoptimizations"),
+        ("bocchud-0.4.0+mc1.21.1.jar", "bocchud"),
+        ("iris-neoforge-1.8.0+mc1.21.1.jar", "iris"),
+        ("jei-1.21.1-neoforge-19.21.0.247.jar", "jei"),
+        ("cloth-config-15.0.140-neoforge.jar", "cloth-config"),
+        ("fabric-api-0.107.0+1.21.1.jar", "fabric-api"),
+        ("fabric-language-kotlin-1.12.3+kotlin.2.0.21.jar", "fabric-language-kotlin"),
+        ("forge-config-api-port-fabric-21.1.0.jar", "forge-config-api-port"),
+        ("yet_another_config_lib_v3-3.5.0+1.21-fabric.jar", "yet_another_config_lib"),
+        ("Xaeros_Minimap_24.5.0_NeoForge_1.21.jar", "xaeros_minimap"),
+        ("entity-model-features-2.2.6.jar", "entity-model-features"),
+        ("modmenu-11.0.2.jar.disabled", "modmenu"),
+        ("3dskinlayers-1.6.8.jar", "3dskinlayers"),
+        ("Sodium Extra", "sodium extra"),
+    ];
+
+    for (filename, expected) in cases {
+        assert_eq!(normalize_mod_name(filename), expected, "{}", filename);
+    }
+}
+
+#[test]
+fn pack_updates_keep_the_same_name() {
+    assert_eq!(normalize_mod_name("sodium-extra-0.6.0.jar"), normalize_mod_name("sodium-extra-0.7.1+mc1.21.4.jar"));
+    assert_eq!(
+        normalize_mod_name("chat_heads-0.13.9-neoforge-1.21.jar"),
+        normalize_mod_name("chat_heads-0.14.0-neoforge-1.21.1.jar")
+    );
+    assert_ne!(normalize_mod_name("sodium-fabric-0.6.0.jar"), normalize_mod_name("sodium-extra-0.6.0.jar"));
+}
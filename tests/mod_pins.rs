@@ -0,0 +1,78 @@
+mod common;
+
+use std::path::Path;
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::updater::{MinecraftUpdater, ModPin};
+
+fn write_pack(path: &Path, server: &MockServer, mods: &[&str]) {
+    let files: Vec<_> = mods
+        .iter()
+        .map(|name| {
+            json!({
+                "path": format!("mods/{}", name),
+                "hashes": { "sha1": "0000000000000000000000000000000000000000" },
+                "downloads": [server.url(&format!("/{}", name))],
+                "fileSize": 1
+            })
+        })
+        .collect();
+    write_mrpack(path, &fabric_index(json!(files)), &[]);
+}
+
+#[tokio::test]
+async fn pinned_mod_survives_an_update_and_excluded_mod_is_not_added() {
+    let root = temp_dir("mod-pins");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    let mods = instance.join("mods");
+    std::fs::create_dir_all(&mods).unwrap();
+    std::fs::write(mods.join("sodium-0.5.0.jar"), b"old sodium").unwrap();
+    std::fs::write(mods.join("lithium-0.11.0.jar"), b"old lithium").unwrap();
+    std::fs::write(
+        instance.join(".naha-pins.json"),
+        json!({ "Sodium": "pinned", "iris-1.7.0.jar": "excluded" }).to_string(),
+    )
+    .unwrap();
+
+    let server = MockServer::start(vec![
+        Route::new("/sodium-0.6.0.jar", 200, b"new sodium".to_vec()),
+        Route::new("/lithium-0.12.0.jar", 200, b"new lithium".to_vec()),
+        Route::new("/iris-1.8.0.jar", 200, b"iris".to_vec()),
+    ])
+    .await;
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack, &server, &["sodium-0.6.0.jar", "lithium-0.12.0.jar", "iris-1.8.0.jar"]);
+
+    let updater = MinecraftUpdater::new();
+    let pins = updater.load_mod_pins(&instance).await.unwrap();
+    assert_eq!(pins.get("sodium"), Some(&ModPin::Pinned));
+    assert_eq!(pins.get("iris"), Some(&ModPin::Excluded));
+
+    let result = updater.update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false).await.unwrap();
+
+    assert_eq!(result.pinned_mods, vec!["sodium-0.5.0.jar".to_string()]);
+    assert_eq!(result.updated_mods, vec!["lithium-0.11.0.jar → lithium-0.12.0.jar".to_string()]);
+    assert!(result.new_mods.is_empty(), "{:?}", result.new_mods);
+    assert_eq!(std::fs::read(mods.join("sodium-0.5.0.jar")).unwrap(), b"old sodium");
+    assert!(!mods.join("sodium-0.6.0.jar").exists());
+    assert!(!mods.join("iris-1.8.0.jar").exists());
+    assert_eq!(std::fs::read(mods.join("lithium-0.12.0.jar")).unwrap(), b"new lithium");
+    assert!(server.requests().iter().all(|r| !r.contains("sodium") && !r.contains("iris")), "{:?}", server.requests());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn malformed_pins_file_stops_the_update() {
+    let root = temp_dir("mod-pins");
+    let instance = root.join("NAHA-Fabric");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(instance.join(".naha-pins.json"), r#"{ "sodium": "frozen" }"#).unwrap();
+
+    let error = MinecraftUpdater::new().load_mod_pins(&instance).await.unwrap_err();
+    assert!(error.to_string().contains(".naha-pins.json"), "{}", error);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::LauncherManager;
+
+#[tokio::test]
+async fn disable_then_enable_renames_the_jar() {
+    let instance = temp_dir("mod-toggle").join("NAHA-Fabric");
+    let mods = instance.join(".minecraft").join("mods");
+    std::fs::create_dir_all(&mods).unwrap();
+    std::fs::write(mods.join("sodium-0.5.3.jar"), b"sodium").unwrap();
+    let manager = LauncherManager::new();
+
+    let disabled = manager.set_mod_enabled(&instance, "sodium-0.5.3.jar", false).await.unwrap();
+    assert_eq!(disabled, mods.join("sodium-0.5.3.jar.disabled"));
+    assert!(disabled.exists());
+    assert!(!mods.join("sodium-0.5.3.jar").exists());
+
+    // Already disabled: nothing to do
+    assert_eq!(manager.set_mod_enabled(&instance, "sodium-0.5.3.jar", false).await.unwrap(), disabled);
+
+    let enabled = manager.set_mod_enabled(&instance, "sodium-0.5.3.jar.disabled", true).await.unwrap();
+    assert_eq!(enabled, mods.join("sodium-0.5.3.jar"));
+    assert_eq!(std::fs::read(&enabled).unwrap(), b"sodium");
+    assert!(!disabled.exists());
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn unknown_mod_is_an_error() {
+    let instance = temp_dir("mod-toggle").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+
+    let message = LauncherManager::new()
+        .set_mod_enabled(&instance, "missing.jar", false)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("missing.jar"), "{}", message);
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
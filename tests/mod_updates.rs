@@ -0,0 +1,64 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{temp_dir, MockServer, Route};
+use minecraft_installer::modrinth::ModrinthClient;
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("modrinth")
+        .join(name);
+    std::fs::read_to_string(path).expect("missing fixture")
+}
+
+#[tokio::test]
+async fn reports_outdated_and_current_mods() {
+    let api = MockServer::start(vec![
+        Route::new("/v2/version_files", 200, fixture("version-files.json")),
+        Route::new("/v2/version_files/update", 200, fixture("version-files-update.json")),
+    ])
+    .await;
+    let instance = temp_dir("mod-updates").join("NAHA-Fabric");
+    let mods = instance.join("mods");
+    std::fs::create_dir_all(&mods).unwrap();
+    std::fs::write(mods.join("sodium-fabric-0.5.8+mc1.20.1.jar"), b"sodium-fabric-0.5.8+mc1.20.1 jar").unwrap();
+    std::fs::write(mods.join("lithium-fabric-mc1.20.1-0.11.2.jar"), b"lithium-fabric-mc1.20.1-0.11.2 jar").unwrap();
+    std::fs::write(mods.join("my-private-tweaks.jar"), b"not on modrinth").unwrap();
+
+    let updates = MinecraftUpdater::new()
+        .with_modrinth_client(ModrinthClient::with_base_url(&api.url("/v2")))
+        .check_mod_updates(&instance)
+        .await
+        .unwrap();
+
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates[0].filename, "lithium-fabric-mc1.20.1-0.11.2.jar");
+    assert_eq!(updates[0].latest_version, "mc1.20.1-0.11.2");
+    assert!(!updates[0].update_available);
+    assert_eq!(updates[1].filename, "sodium-fabric-0.5.8+mc1.20.1.jar");
+    assert_eq!(updates[1].project_id, "AANobbMI");
+    assert_eq!(updates[1].current_version, "mc1.20.1-0.5.8");
+    assert_eq!(updates[1].latest_version, "mc1.20.1-0.5.11");
+    assert!(updates[1].update_available);
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn instance_without_mods_needs_no_lookup() {
+    let instance = temp_dir("mod-updates").join("Vanilla");
+    std::fs::create_dir_all(&instance).unwrap();
+
+    let updates = MinecraftUpdater::new()
+        .with_modrinth_client(ModrinthClient::with_base_url("http://127.0.0.1:9"))
+        .check_mod_updates(&instance)
+        .await
+        .unwrap();
+    assert!(updates.is_empty());
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use serde_json::json;
+
+use common::{sha256_hex, temp_dir, MockServer, Route};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::LauncherManager;
+
+fn naha_info() -> String {
+    json!({
+        "server_name": "NAHA Fabric",
+        "server_ip": "fabric.naha.example",
+        "server_port": 25565,
+        "fingerprint": "0123456789abcdef0123456789abcdef",
+        "last_updated": "2025-10-08T00:00:00Z"
+    })
+    .to_string()
+}
+
+fn release(download_url: &str) -> String {
+    release_with_digest(download_url, None)
+}
+
+fn release_with_digest(download_url: &str, digest: Option<&str>) -> String {
+    let mut asset = json!({ "name": "NAHA-Fabric-1.21.1-0.2.5.mrpack", "browser_download_url": download_url });
+    if let Some(digest) = digest {
+        asset["digest"] = json!(digest);
+    }
+    json!({ "assets": [asset] }).to_string()
+}
+
+fn manager(server: &MockServer) -> LauncherManager {
+    let mut manager = LauncherManager::new();
+    manager.set_modpack_api_urls(server.url("/api"), server.url("/releases"));
+    manager
+}
+
+#[tokio::test]
+async fn transient_server_error_is_retried() {
+    let server = MockServer::start(vec![
+        Route::new("/api/fabric/", 200, naha_info()).failing_first(1, 503),
+        Route::new("/releases/latest", 200, release("https://example.com/NAHA-Fabric-1.21.1-0.2.5.mrpack")),
+    ])
+    .await;
+
+    let info = manager(&server).fetch_modpack_info("fabric").await.unwrap();
+
+    assert_eq!(info.server_ip, "fabric.naha.example");
+    assert_eq!(info.version, "0.2.5");
+    let requests = server.requests();
+    assert_eq!(requests.iter().filter(|r| *r == "/api/fabric/").count(), 2, "{:?}", requests);
+    assert_eq!(requests.iter().filter(|r| *r == "/releases/latest").count(), 1, "{:?}", requests);
+}
+
+#[tokio::test]
+async fn empty_download_url_is_rejected() {
+    let server = MockServer::start(vec![
+        Route::new("/api/fabric/", 200, naha_info()),
+        Route::new("/releases/latest", 200, release("")),
+    ])
+    .await;
+
+    let error = manager(&server).fetch_modpack_info("fabric").await.unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::InstallationFailed(_)), "{}", error);
+    assert!(error.to_string().contains("download_url"), "{}", error);
+}
+
+#[tokio::test]
+async fn corrupted_download_fails_the_release_digest() {
+    let published = b"the real mrpack";
+    let digest = format!("sha256:{}", sha256_hex(published));
+    let server = MockServer::start(vec![
+        Route::new("/api/fabric/", 200, naha_info()),
+        Route::new("/releases/latest", 200, release_with_digest("https://example.com/pack.mrpack", Some(&digest))),
+        Route::new("/pack.mrpack", 200, b"the real mrp".to_vec()),
+    ])
+    .await;
+
+    let manager = manager(&server);
+    let mut info = manager.fetch_modpack_info("fabric").await.unwrap();
+    assert_eq!(info.file_hash.as_deref(), Some(sha256_hex(published).as_str()));
+
+    info.download_url = server.url("/pack.mrpack");
+    let custom = temp_dir("modpack-info");
+    let error = manager
+        .install_from_modpack_info(&info, None, false, Some(&custom), None, false)
+        .await
+        .unwrap_err();
+
+    match error {
+        MinecraftInstallerError::HashMismatch { expected, actual, .. } => {
+            assert_eq!(expected, sha256_hex(published));
+            assert_eq!(actual, sha256_hex(b"the real mrp"));
+        }
+        other => panic!("expected a hash mismatch, got {}", other),
+    }
+    assert_eq!(std::fs::read_dir(&custom).unwrap().count(), 0);
+
+    std::fs::remove_dir_all(&custom).unwrap();
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::updater::{infer_modpack_type, MinecraftUpdater};
+
+fn prism_instance(root: &Path, folder: &str, components: serde_json::Value) -> PathBuf {
+    let instance = root.join("instances").join(folder);
+    std::fs::create_dir_all(instance.join("minecraft").join("mods")).unwrap();
+    std::fs::write(instance.join("instance.cfg"), format!("InstanceType=OneSix\nname={}\n", folder)).unwrap();
+    std::fs::write(instance.join("mmc-pack.json"), json!({ "formatVersion": 1, "components": components }).to_string()).unwrap();
+    instance
+}
+
+#[tokio::test]
+async fn fabric_instance_selects_the_fabric_pack() {
+    let root = temp_dir("modpack-type-inference");
+    let fabric = prism_instance(&root, "My Fabric", json!([
+        { "cachedName": "Minecraft", "uid": "net.minecraft", "version": "1.21.1" },
+        { "cachedName": "Fabric Loader", "uid": "net.fabricmc.fabric-loader", "cachedVersion": "0.16.5" }
+    ]));
+    let vanilla = prism_instance(&root, "Vanilla", json!([
+        { "cachedName": "Minecraft", "uid": "net.minecraft", "version": "1.21.1" }
+    ]));
+
+    let updater = MinecraftUpdater::new();
+    assert_eq!(updater.detect_modpack_type(&fabric).await.unwrap(), "fabric");
+
+    let error = updater.detect_modpack_type(&vanilla).await.unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::InvalidLoader(_)), "{}", error);
+    assert!(error.to_string().contains("--modpack-type"), "{}", error);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn only_naha_loaders_are_inferred() {
+    assert_eq!(infer_modpack_type("NeoForge").unwrap(), "neoforge");
+    assert_eq!(infer_modpack_type("fabric").unwrap(), "fabric");
+    // Forge can't run the NeoForge pack and no pack targets Quilt
+    assert!(infer_modpack_type("Forge").is_err());
+    assert!(infer_modpack_type("Quilt").is_err());
+}
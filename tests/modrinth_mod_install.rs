@@ -0,0 +1,114 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{temp_dir, MockServer, Route};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::LauncherManager;
+use minecraft_installer::modrinth::ModrinthClient;
+
+const FABRIC_JAR: &[u8] = b"sodium 0.6.0 fabric jar";
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("modrinth")
+        .join(name);
+    std::fs::read_to_string(path).expect("missing fixture")
+}
+
+/// Serve the recorded API responses, with file URLs pointing at a local CDN
+async fn servers(fabric_jar: &[u8]) -> (MockServer, MockServer) {
+    let cdn = MockServer::start(vec![Route::new(
+        "/data/AANobbMI/versions/sodium-fabric-0.6.0+mc1.21.1.jar",
+        200,
+        fabric_jar.to_vec(),
+    )])
+    .await;
+    let rewrite = |body: String| body.replace("https://cdn.modrinth.com", &cdn.base_url);
+    let api = MockServer::start(vec![
+        Route::new("/v2/project/sodium/version", 200, rewrite(fixture("sodium-versions.json"))),
+        Route::new("/v2/version/OihdIimA", 200, rewrite(fixture("version-OihdIimA.json"))),
+    ])
+    .await;
+    (cdn, api)
+}
+
+#[tokio::test]
+async fn latest_compatible_version_is_installed() {
+    let (_cdn, api) = servers(FABRIC_JAR).await;
+    let instance = temp_dir("modrinth-mod").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+
+    let path = LauncherManager::new()
+        .install_modrinth_mod_with_client(
+            &instance,
+            "sodium",
+            "latest",
+            "1.21.1",
+            "Fabric",
+            &ModrinthClient::with_base_url(&api.url("/v2")),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(path, instance.join("mods").join("sodium-fabric-0.6.0+mc1.21.1.jar"));
+    assert_eq!(std::fs::read(&path).unwrap(), FABRIC_JAR);
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn incompatible_requests_are_rejected() {
+    let (_cdn, api) = servers(FABRIC_JAR).await;
+    let instance = temp_dir("modrinth-mod").join("NAHA-Fabric");
+    let client = ModrinthClient::with_base_url(&api.url("/v2"));
+    let manager = LauncherManager::new();
+
+    let wrong_game = manager
+        .install_modrinth_mod_with_client(&instance, "sodium", "OihdIimA", "1.21.1", "fabric", &client)
+        .await
+        .unwrap_err();
+    assert!(matches!(wrong_game, MinecraftInstallerError::InvalidVersion(_)), "{}", wrong_game);
+    assert!(wrong_game.to_string().contains("1.21.1"), "{}", wrong_game);
+
+    let wrong_loader = manager
+        .install_modrinth_mod_with_client(&instance, "sodium", "OihdIimA", "1.21", "forge", &client)
+        .await
+        .unwrap_err();
+    assert!(matches!(wrong_loader, MinecraftInstallerError::InvalidLoader(_)), "{}", wrong_loader);
+
+    let none = manager
+        .install_modrinth_mod_with_client(&instance, "sodium", "latest", "1.20.1", "fabric", &client)
+        .await
+        .unwrap_err();
+    assert!(none.to_string().contains("No version of sodium"), "{}", none);
+    assert!(!instance.join("mods").exists());
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn hash_mismatch_is_not_written() {
+    let (_cdn, api) = servers(b"tampered jar").await;
+    let instance = temp_dir("modrinth-mod").join("NAHA-Fabric");
+
+    let error = LauncherManager::new()
+        .install_modrinth_mod_with_client(
+            &instance,
+            "sodium",
+            "latest",
+            "1.21.1",
+            "fabric",
+            &ModrinthClient::with_base_url(&api.url("/v2")),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::HashMismatch { .. }), "{}", error);
+    assert!(error.to_string().contains("Hash mismatch"), "{}", error);
+    assert!(!instance.join("mods").join("sodium-fabric-0.6.0+mc1.21.1.jar").exists());
+
+    let _ = std::fs::remove_dir_all(instance.parent().unwrap());
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use serde_json::json;
+
+use common::{sha512_hex, temp_dir, MockServer, Route};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::LauncherManager;
+use minecraft_installer::modrinth::ModrinthClient;
+
+const RESOURCE_PACK: &[u8] = b"faithful 32x resource pack zip";
+const SHADER_PACK: &[u8] = b"complementary reimagined shader zip";
+
+fn version(id: &str, project_id: &str, loaders: &[&str], url: String, filename: &str, sha512: String) -> serde_json::Value {
+    json!({
+        "id": id,
+        "project_id": project_id,
+        "name": filename,
+        "version_number": "1.0.0",
+        "game_versions": ["1.21", "1.21.1"],
+        "loaders": loaders,
+        "files": [{ "hashes": { "sha512": sha512 }, "url": url, "filename": filename, "primary": true, "size": 30 }]
+    })
+}
+
+/// An API serving one resource pack and one shader pack version, with file URLs pointing at a local CDN
+async fn servers(shader_bytes: &[u8]) -> (MockServer, MockServer) {
+    let cdn = MockServer::start(vec![
+        Route::new("/Faithful-32x-1.21.zip", 200, RESOURCE_PACK.to_vec()),
+        Route::new("/ComplementaryReimagined_r5.3.zip", 200, shader_bytes.to_vec()),
+    ])
+    .await;
+    let faithful = version(
+        "FaIth32x", "faithful-32x", &["minecraft"],
+        cdn.url("/Faithful-32x-1.21.zip"), "Faithful-32x-1.21.zip", sha512_hex(RESOURCE_PACK),
+    );
+    let shader = version(
+        "CompR15x", "complementary", &["iris", "optifine"],
+        cdn.url("/ComplementaryReimagined_r5.3.zip"), "ComplementaryReimagined_r5.3.zip", sha512_hex(SHADER_PACK),
+    );
+    let api = MockServer::start(vec![
+        Route::new("/v2/project/faithful-32x/version", 200, json!([faithful]).to_string()),
+        Route::new("/v2/version/CompR15x", 200, shader.to_string()),
+    ])
+    .await;
+    (cdn, api)
+}
+
+#[tokio::test]
+async fn resource_pack_lands_in_the_game_directory() {
+    let (_cdn, api) = servers(SHADER_PACK).await;
+    // A Prism instance keeps its game files under .minecraft
+    let instance = temp_dir("modrinth-packs").join("NAHA-Fabric");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(instance.join("mmc-pack.json"), "{}").unwrap();
+
+    let path = LauncherManager::new()
+        .install_modrinth_resourcepack_with_client(&instance, "faithful-32x", "latest", "1.21.1", &ModrinthClient::with_base_url(&api.url("/v2")))
+        .await
+        .unwrap();
+
+    assert_eq!(path, instance.join(".minecraft").join("resourcepacks").join("Faithful-32x-1.21.zip"));
+    assert_eq!(std::fs::read(&path).unwrap(), RESOURCE_PACK);
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn shader_pack_lands_in_shaderpacks() {
+    let (_cdn, api) = servers(SHADER_PACK).await;
+    let instance = temp_dir("modrinth-packs").join("naha-fabric");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(instance.join("profile.json"), "{}").unwrap();
+
+    let path = LauncherManager::new()
+        .install_modrinth_shaderpack_with_client(&instance, "complementary", "CompR15x", "1.21.1", &ModrinthClient::with_base_url(&api.url("/v2")))
+        .await
+        .unwrap();
+
+    assert_eq!(path, instance.join("shaderpacks").join("ComplementaryReimagined_r5.3.zip"));
+    assert_eq!(std::fs::read(&path).unwrap(), SHADER_PACK);
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn corrupted_pack_is_not_written() {
+    let (_cdn, api) = servers(b"truncated").await;
+    let instance = temp_dir("modrinth-packs").join("naha-fabric");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(instance.join("profile.json"), "{}").unwrap();
+    let client = ModrinthClient::with_base_url(&api.url("/v2"));
+    let manager = LauncherManager::new();
+
+    let error = manager
+        .install_modrinth_shaderpack_with_client(&instance, "complementary", "CompR15x", "1.21.1", &client)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::HashMismatch { .. }), "{}", error);
+    assert!(!instance.join("shaderpacks").exists());
+
+    let error = manager
+        .install_modrinth_shaderpack_with_client(&instance, "complementary", "CompR15x", "1.20.4", &client)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::InvalidVersion(_)), "{}", error);
+
+    std::fs::remove_dir_all(instance.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn pack_file_name_cannot_escape_the_pack_folder() {
+    let cdn = MockServer::start(vec![Route::new("/escape.zip", 200, RESOURCE_PACK.to_vec())]).await;
+    let evil = version(
+        "Ev1lPack", "faithful-32x", &["minecraft"],
+        cdn.url("/escape.zip"), "../../escape.zip", sha512_hex(RESOURCE_PACK),
+    );
+    let api = MockServer::start(vec![Route::new("/v2/version/Ev1lPack", 200, evil.to_string())]).await;
+    let root = temp_dir("modrinth-packs");
+    let instance = root.join("instances").join("naha-fabric");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(instance.join("profile.json"), "{}").unwrap();
+
+    let error = LauncherManager::new()
+        .install_modrinth_resourcepack_with_client(&instance, "faithful-32x", "Ev1lPack", "1.21.1", &ModrinthClient::with_base_url(&api.url("/v2")))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::Validation(_)), "{}", error);
+    assert!(!root.join("escape.zip").exists());
+    assert!(cdn.requests().is_empty());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::json;
+
+use common::{fabric_index, sha1_hex, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::LauncherManager;
+
+fn write_pack(path: &Path, files: Vec<serde_json::Value>) {
+    write_mrpack(path, &fabric_index(json!(files)), &[]);
+}
+
+fn mod_entry(name: &str, body: &[u8], downloads: Vec<String>) -> serde_json::Value {
+    json!({
+        "path": format!("mods/{}.jar", name),
+        "hashes": { "sha1": sha1_hex(body) },
+        "downloads": downloads,
+        "fileSize": body.len()
+    })
+}
+
+#[tokio::test]
+async fn every_mod_lands_with_bounded_concurrency() {
+    let names: Vec<String> = (0..12).map(|i| format!("mod{}", i)).collect();
+    let routes = names.iter()
+        .map(|name| Route::new(&format!("/{}.jar", name), 200, name.as_bytes().to_vec()).delayed(Duration::from_millis(150)))
+        .collect();
+    let server = MockServer::start(routes).await;
+
+    let root = temp_dir("mrpack-downloads");
+    let mrpack = root.join("pack.mrpack");
+    let files = names.iter()
+        .map(|name| mod_entry(name, name.as_bytes(), vec![server.url(&format!("/{}.jar", name))]))
+        .collect();
+    write_pack(&mrpack, files);
+
+    let instance = root.join("instance");
+    let mut manager = LauncherManager::new();
+    manager.set_download_concurrency(3);
+    manager.install_mrpack(&mrpack, &instance, "Big Pack").await.unwrap();
+
+    for name in &names {
+        assert_eq!(std::fs::read(instance.join("mods").join(format!("{}.jar", name))).unwrap(), name.as_bytes());
+    }
+    assert_eq!(server.requests().len(), 12);
+    assert!(server.peak_in_flight() > 1, "downloads ran one at a time");
+    assert!(server.peak_in_flight() <= 3, "{} downloads at once", server.peak_in_flight());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn fallback_url_and_hash_check_still_apply() {
+    let server = MockServer::start(vec![
+        Route::new("/tampered/good.jar", 200, b"tampered".to_vec()),
+        Route::new("/mirror/good.jar", 200, b"good".to_vec()),
+        Route::new("/other.jar", 200, b"other".to_vec()),
+    ])
+    .await;
+
+    let root = temp_dir("mrpack-downloads");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack, vec![
+        mod_entry("good", b"good", vec![server.url("/missing/good.jar"), server.url("/tampered/good.jar"), server.url("/mirror/good.jar")]),
+        mod_entry("broken", b"broken", vec![server.url("/missing/broken.jar")]),
+        mod_entry("other", b"other", vec![server.url("/other.jar")]),
+    ]);
+
+    let instance = root.join("instance");
+    let error = LauncherManager::new().install_mrpack(&mrpack, &instance, "Big Pack").await.unwrap_err();
+
+    // The failing file is reported after the rest of the batch has been downloaded
+    assert!(matches!(error, MinecraftInstallerError::DownloadFailed(_)), "{}", error);
+    assert!(error.to_string().contains("mods/broken.jar"), "{}", error);
+    assert_eq!(std::fs::read(instance.join("mods/good.jar")).unwrap(), b"good");
+    assert_eq!(std::fs::read(instance.join("mods/other.jar")).unwrap(), b"other");
+    assert!(!instance.join("mods/broken.jar").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn duplicate_paths_are_downloaded_once() {
+    let server = MockServer::start(vec![Route::new("/sodium.jar", 200, b"sodium".to_vec())]).await;
+
+    let root = temp_dir("mrpack-downloads");
+    let mrpack = root.join("pack.mrpack");
+    let entry = mod_entry("sodium", b"sodium", vec![server.url("/sodium.jar")]);
+    write_pack(&mrpack, vec![entry.clone(), entry]);
+
+    let instance = root.join("instance");
+    LauncherManager::new().install_mrpack(&mrpack, &instance, "Big Pack").await.unwrap();
+
+    assert_eq!(std::fs::read(instance.join("mods/sodium.jar")).unwrap(), b"sodium");
+    assert_eq!(server.requests(), vec!["/sodium.jar".to_string()]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::json;
+
+use common::{sha1_hex, sha512_hex, temp_dir, MockServer, Route};
+use minecraft_installer::launcher_support::LauncherManager;
+use minecraft_installer::modrinth::ModrinthClient;
+
+fn read_mods(mods_dir: &Path) -> BTreeMap<String, Vec<u8>> {
+    std::fs::read_dir(mods_dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .map(|p| (p.file_name().unwrap().to_string_lossy().to_string(), std::fs::read(&p).unwrap()))
+        .collect()
+}
+
+#[tokio::test]
+async fn exported_mrpack_reinstalls_same_mods() {
+    let root = temp_dir("mrpack-export");
+    let instance_path = root.join(".xmcl").join("instances").join("My Pack");
+    std::fs::create_dir_all(instance_path.join("mods")).unwrap();
+    std::fs::write(
+        instance_path.join("instance.json"),
+        json!({
+            "name": "My Pack",
+            "runtime": { "minecraft": "1.20.1", "fabricLoader": "0.15.11", "forge": "", "neoForged": "" }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let sodium = b"sodium jar bytes".to_vec();
+    let custom = b"locally built mod".to_vec();
+    std::fs::write(instance_path.join("mods").join("sodium-fabric-0.5.3.jar"), &sodium).unwrap();
+    std::fs::write(instance_path.join("mods").join("my-custom-mod.jar"), &custom).unwrap();
+    std::fs::write(instance_path.join("mods").join("ignored.txt"), b"not a mod").unwrap();
+
+    let sodium_sha512 = sha512_hex(&sodium);
+    // Jar downloads come from a separate host, like Modrinth's CDN
+    let files_server = MockServer::start(vec![Route::new("/files/sodium.jar", 200, sodium.clone())]).await;
+    let version = json!({
+        "id": "abc123",
+        "project_id": "AANobbMI",
+        "name": "Sodium 0.5.3",
+        "version_number": "mc1.20.1-0.5.3",
+        "game_versions": ["1.20.1"],
+        "loaders": ["fabric"],
+        "files": [{
+            "hashes": { "sha1": sha1_hex(&sodium), "sha512": sodium_sha512 },
+            "url": files_server.url("/files/sodium.jar"),
+            "filename": "sodium-fabric-0.5.3.jar",
+            "primary": true,
+            "size": sodium.len()
+        }]
+    });
+    let api = MockServer::start(vec![Route::new(
+        &format!("/version_file/{}", sodium_sha512),
+        200,
+        version.to_string(),
+    )])
+    .await;
+
+    let manager = LauncherManager::new();
+    let output = root.join("export").join("My Pack.mrpack");
+    let index = manager
+        .export_mrpack_with_client(&instance_path, &output, &ModrinthClient::with_base_url(&api.base_url))
+        .await
+        .unwrap();
+
+    assert_eq!(index.name, "My Pack");
+    assert_eq!(index.dependencies.get("minecraft").map(String::as_str), Some("1.20.1"));
+    assert_eq!(index.dependencies.get("fabric-loader").map(String::as_str), Some("0.15.11"));
+    assert_eq!(index.files.len(), 1);
+    assert_eq!(index.files[0].path, "mods/sodium-fabric-0.5.3.jar");
+    assert_eq!(index.files[0].hashes.get("sha512"), Some(&sodium_sha512));
+    assert_eq!(api.requests().len(), 2);
+
+    // The exported pack should pass validation and reinstall to the same mod set
+    assert!(manager.validate_mrpack(&output).unwrap().is_empty());
+
+    let reinstall_dir = root.join("reinstall");
+    let (minecraft_version, mod_loader) = manager
+        .install_mrpack(&output, &reinstall_dir, "My Pack")
+        .await
+        .unwrap();
+    assert_eq!(minecraft_version, "1.20.1");
+    assert_eq!(mod_loader, "fabric");
+
+    let mut original = read_mods(&instance_path.join("mods"));
+    original.remove("ignored.txt");
+    assert_eq!(read_mods(&reinstall_dir.join("mods")), original);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn export_requires_detectable_instance() {
+    let root = temp_dir("mrpack-export");
+
+    let result = LauncherManager::new()
+        .export_mrpack_with_client(&root, &root.join("out.mrpack"), &ModrinthClient::with_base_url("http://127.0.0.1:9"))
+        .await;
+    assert!(result.is_err());
+    assert!(!root.join("out.mrpack").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use std::path::Path;
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, sha1_hex, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::launcher_support::{
+    LauncherManager, OverrideOptions, OverrideOrder, OverridePolicy,
+};
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn write_pack(path: &Path) {
+    write_mrpack(path, &fabric_index(json!([])), &[
+        ("overrides/config/sodium-options.json", b"pack sodium"),
+        ("overrides/config/new-mod.toml", b"pack new mod"),
+    ]);
+}
+
+fn customized_instance(root: &Path) -> std::path::PathBuf {
+    let instance = root.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join("config")).unwrap();
+    std::fs::write(instance.join("config").join("sodium-options.json"), b"user sodium").unwrap();
+    instance
+}
+
+#[test]
+fn policies_and_orders_parse() {
+    assert_eq!("skip-existing".parse::<OverridePolicy>().unwrap(), OverridePolicy::SkipExisting);
+    assert_eq!("Overwrite".parse::<OverridePolicy>().unwrap(), OverridePolicy::Overwrite);
+    assert_eq!("after_mods".parse::<OverrideOrder>().unwrap(), OverrideOrder::AfterMods);
+    assert!("sometimes".parse::<OverridePolicy>().is_err());
+    assert_eq!(OverrideOptions::default(), OverrideOptions::fresh_install());
+}
+
+#[tokio::test]
+async fn fresh_install_overwrites_and_skip_existing_keeps_user_configs() {
+    let root = temp_dir("mrpack-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack);
+    let manager = LauncherManager::new();
+
+    let instance = customized_instance(&root);
+    manager
+        .install_mrpack_with_options(&mrpack, &instance, "NAHA-Fabric", OverrideOptions::update())
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(instance.join("config/sodium-options.json")).unwrap(), b"user sodium");
+    assert_eq!(std::fs::read(instance.join("config/new-mod.toml")).unwrap(), b"pack new mod");
+
+    manager.install_mrpack(&mrpack, &instance, "NAHA-Fabric").await.unwrap();
+    assert_eq!(std::fs::read(instance.join("config/sodium-options.json")).unwrap(), b"pack sodium");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn updates_keep_existing_configs_unless_told_to_overwrite() {
+    let root = temp_dir("mrpack-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack);
+    let instance = customized_instance(&root);
+
+    MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(instance.join("config/sodium-options.json")).unwrap(), b"user sodium");
+    assert_eq!(std::fs::read(instance.join("config/new-mod.toml")).unwrap(), b"pack new mod");
+
+    MinecraftUpdater::new()
+        .with_override_options(OverrideOptions::update().with_policy(OverridePolicy::Overwrite))
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(instance.join("config/sodium-options.json")).unwrap(), b"pack sodium");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn mod_shipped_in_overrides_is_not_downloaded_again() {
+    let server = MockServer::start(vec![
+        Route::new("/sodium.jar", 200, b"indexed sodium".to_vec()),
+        Route::new("/lithium.jar", 200, b"indexed lithium".to_vec()),
+    ])
+    .await;
+    let root = temp_dir("mrpack-overrides");
+    let mrpack = root.join("pack.mrpack");
+    let entry = |name: &str, body: &[u8]| json!({
+        "path": format!("mods/{}", name),
+        "hashes": { "sha1": sha1_hex(body) },
+        "downloads": [server.url(&format!("/{}", name))],
+        "fileSize": body.len()
+    });
+
+    write_mrpack(
+        &mrpack,
+        &fabric_index(json!([entry("sodium.jar", b"indexed sodium"), entry("lithium.jar", b"indexed lithium")])),
+        &[("overrides/mods/sodium.jar", b"bundled sodium")],
+    );
+
+    let instance = root.join("instance");
+    LauncherManager::new().install_mrpack(&mrpack, &instance, "NAHA-Fabric").await.unwrap();
+
+    let mods: Vec<_> = std::fs::read_dir(instance.join("mods")).unwrap().flatten().map(|e| e.file_name()).collect();
+    assert_eq!(mods.iter().filter(|name| *name == "sodium.jar").count(), 1);
+    assert_eq!(std::fs::read(instance.join("mods/sodium.jar")).unwrap(), b"bundled sodium");
+    assert_eq!(std::fs::read(instance.join("mods/lithium.jar")).unwrap(), b"indexed lithium");
+    assert_eq!(server.requests(), vec!["/lithium.jar".to_string()]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn overrides_only_install_applies_configs_without_downloading_mods() {
+    let server = MockServer::start(vec![Route::new("/lithium.jar", 200, b"indexed lithium".to_vec())]).await;
+    let root = temp_dir("mrpack-overrides");
+    let mrpack = root.join("pack.mrpack");
+
+    let index = fabric_index(json!([{
+        "path": "mods/lithium.jar",
+        "hashes": { "sha1": sha1_hex(b"indexed lithium") },
+        "downloads": [server.url("/lithium.jar")],
+        "fileSize": 15
+    }]));
+    write_mrpack(&mrpack, &index, &[
+        ("overrides/config/sodium-options.json", b"pack sodium"),
+        ("client-overrides/options.txt", b"pack options"),
+    ]);
+
+    let instance = customized_instance(&root);
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    std::fs::write(instance.join("mods/manual.jar"), b"manual mod").unwrap();
+
+    let (minecraft, loader) = LauncherManager::new()
+        .install_mrpack_overrides_only(&mrpack, &instance)
+        .await
+        .unwrap();
+
+    assert_eq!((minecraft.as_str(), loader.as_str()), ("1.21.1", "fabric"));
+    assert_eq!(std::fs::read(instance.join("config/sodium-options.json")).unwrap(), b"pack sodium");
+    assert_eq!(std::fs::read(instance.join("options.txt")).unwrap(), b"pack options");
+    assert!(!instance.join("mods/lithium.jar").exists());
+    assert_eq!(std::fs::read(instance.join("mods/manual.jar")).unwrap(), b"manual mod");
+    assert!(server.requests().is_empty());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn updates_write_overrides_into_the_prism_game_dir() {
+    let root = temp_dir("mrpack-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack);
+    let instance = root.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join(".minecraft/mods")).unwrap();
+    std::fs::write(instance.join("mmc-pack.json"), r#"{"components": [], "formatVersion": 1}"#).unwrap();
+
+    MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(instance.join(".minecraft/config/new-mod.toml")).unwrap(), b"pack new mod");
+    assert!(!instance.join("config").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn overrides_only_install_targets_the_prism_game_dir() {
+    let root = temp_dir("mrpack-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack);
+    let instance = root.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join(".minecraft/config")).unwrap();
+    std::fs::write(instance.join("instance.cfg"), "name=NAHA-Fabric\n").unwrap();
+
+    LauncherManager::new().install_mrpack_overrides_only(&mrpack, &instance).await.unwrap();
+
+    assert_eq!(std::fs::read(instance.join(".minecraft/config/sodium-options.json")).unwrap(), b"pack sodium");
+    assert!(!instance.join("config").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use common::{temp_dir, write_mrpack};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::LauncherManager;
+
+fn write_pack(index: &serde_json::Value) -> PathBuf {
+    let mrpack_path = temp_dir("mrpack-validate").join("pack.mrpack");
+    write_mrpack(&mrpack_path, index, &[]);
+    mrpack_path
+}
+
+fn valid_file(path: &str) -> serde_json::Value {
+    json!({
+        "path": path,
+        "hashes": {
+            "sha1": "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            "sha512": "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        },
+        "env": { "client": "required", "server": "optional" },
+        "downloads": [format!("https://cdn.modrinth.com/data/abc/versions/1.0/{}", path)],
+        "fileSize": 0
+    })
+}
+
+#[test]
+fn valid_mrpack_has_no_issues() {
+    let mrpack = write_pack(&json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": "0.1.0",
+        "name": "Test Pack",
+        "files": [valid_file("mods/a.jar"), valid_file("mods/b.jar")],
+        "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.15.11" }
+    }));
+
+    let issues = LauncherManager::new().validate_mrpack(&mrpack).unwrap();
+    assert!(issues.is_empty(), "unexpected issues: {:?}", issues);
+
+    std::fs::remove_dir_all(mrpack.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn reports_every_problem() {
+    let mut bad_env = valid_file("mods/env.jar");
+    bad_env["env"]["client"] = json!("sometimes");
+    let mut no_hash = valid_file("mods/nohash.jar");
+    no_hash["hashes"].as_object_mut().unwrap().remove("sha512");
+    let mut no_url = valid_file("mods/nourl.jar");
+    no_url["downloads"] = json!([""]);
+
+    let mrpack = write_pack(&json!({
+        "formatVersion": 2,
+        "game": "minecraft",
+        "versionId": "",
+        "name": "Broken Pack",
+        "files": [
+            valid_file("mods/a.jar"),
+            valid_file("mods/a.jar"),
+            valid_file("../escape.jar"),
+            bad_env,
+            no_hash,
+            no_url
+        ],
+        "dependencies": { "fabric-loader": "0.15.11" }
+    }));
+
+    let issues = LauncherManager::new().validate_mrpack(&mrpack).unwrap();
+    let expected = [
+        "unsupported formatVersion 2 (expected 1)",
+        "missing or empty versionId",
+        "dependencies does not specify a minecraft version",
+        "mods/a.jar: duplicate path",
+        "../escape.jar: path escapes the instance directory",
+        "mods/env.jar: invalid env.client value \"sometimes\"",
+        "mods/nohash.jar: missing sha512 hash",
+        "mods/nourl.jar: empty download URL",
+    ];
+    for message in expected {
+        assert!(issues.iter().any(|i| i == message), "missing {:?} in {:?}", message, issues);
+    }
+    assert_eq!(issues.len(), expected.len(), "unexpected issues: {:?}", issues);
+
+    std::fs::remove_dir_all(mrpack.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn reports_missing_index() {
+    let temp_dir = std::env::temp_dir().join(format!("mrpack-validate-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let mrpack_path = temp_dir.join("empty.mrpack");
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&mrpack_path).unwrap());
+    zip.start_file("overrides/config/a.toml", zip::write::SimpleFileOptions::default()).unwrap();
+    zip.write_all(b"a = 1").unwrap();
+    zip.finish().unwrap();
+
+    let issues = LauncherManager::new().validate_mrpack(&mrpack_path).unwrap();
+    assert_eq!(issues, vec!["modrinth.index.json not found in archive".to_string()]);
+
+    std::fs::remove_dir_all(&temp_dir).unwrap();
+}
+
+async fn install_error(format_version: u32, dependencies: serde_json::Value) -> (String, PathBuf) {
+    let mrpack = write_pack(&json!({
+        "formatVersion": format_version,
+        "game": "minecraft",
+        "versionId": "0.1.0",
+        "name": "Test Pack",
+        "files": [],
+        "dependencies": dependencies
+    }));
+    let instance = mrpack.parent().unwrap().join("instance");
+
+    let error = LauncherManager::new().install_mrpack(&mrpack, &instance, "Test Pack").await.unwrap_err();
+    assert!(matches!(error, MinecraftInstallerError::InstallationFailed(_)), "{}", error);
+    assert!(!instance.exists(), "instance created for a rejected pack");
+
+    (error.to_string(), mrpack)
+}
+
+#[tokio::test]
+async fn install_rejects_future_format_versions() {
+    let (message, mrpack) = install_error(2, json!({ "minecraft": "1.20.1", "fabric-loader": "0.15.11" })).await;
+    assert!(message.contains("formatVersion 2"), "{}", message);
+
+    std::fs::remove_dir_all(mrpack.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn install_rejects_packs_with_two_loaders() {
+    let (message, mrpack) = install_error(1, json!({ "minecraft": "1.20.1", "fabric-loader": "0.15.11", "forge": "47.2.0" })).await;
+    assert!(message.contains("more than one loader (fabric-loader, forge)"), "{}", message);
+
+    let issues = LauncherManager::new().validate_mrpack(&mrpack).unwrap();
+    assert_eq!(issues, vec!["dependencies declares more than one loader (fabric-loader, forge)".to_string()]);
+
+    std::fs::remove_dir_all(mrpack.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn install_rejects_unknown_loaders() {
+    let (message, mrpack) = install_error(1, json!({ "minecraft": "1.20.1", "liteloader": "1.12.2" })).await;
+    assert!(message.contains("unsupported dependency \"liteloader\""), "{}", message);
+
+    std::fs::remove_dir_all(mrpack.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn index_is_found_under_a_dot_slash_prefix_and_other_case() {
+    for entry in ["./modrinth.index.json", "Modrinth.Index.json"] {
+        let temp_dir = std::env::temp_dir().join(format!("mrpack-validate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let mrpack_path = temp_dir.join("pack.mrpack");
+
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&mrpack_path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("overrides/config/a.toml", options).unwrap();
+        zip.write_all(b"a = 1").unwrap();
+        zip.start_file(entry, options).unwrap();
+        let index = json!({
+            "formatVersion": 1,
+            "game": "minecraft",
+            "versionId": "0.1.0",
+            "name": "Test Pack",
+            "files": [],
+            "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.15.11" }
+        });
+        zip.write_all(index.to_string().as_bytes()).unwrap();
+        zip.finish().unwrap();
+
+        let manager = LauncherManager::new();
+        assert!(manager.validate_mrpack(&mrpack_path).unwrap().is_empty(), "{}", entry);
+        assert_eq!(manager.modpack_version(&mrpack_path).unwrap().as_deref(), Some("0.1.0"));
+
+        let instance = temp_dir.join("instance");
+        let (minecraft_version, loader) = manager.install_mrpack(&mrpack_path, &instance, "Test Pack").await.unwrap();
+        assert_eq!((minecraft_version.as_str(), loader.as_str()), ("1.20.1", "fabric"));
+        assert_eq!(std::fs::read_to_string(instance.join("config").join("a.toml")).unwrap(), "a = 1");
+
+        std::fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
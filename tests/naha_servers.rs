@@ -0,0 +1,271 @@
+mod common;
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, mrpack_bytes, temp_dir, MockServer, Route};
+use minecraft_installer::launcher_support::{
+    add_servers_to_list, parse_naha_servers, read_server_list, LauncherManager, MrpackIndex, NahaModpackInfo, NahaServer,
+    ServerListEntry,
+};
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn server(name: &str, ip: &str, port: u16, fingerprint: &str) -> NahaServer {
+    NahaServer {
+        server_name: name.to_string(),
+        server_ip: ip.to_string(),
+        server_port: port,
+        fingerprint: fingerprint.to_string(),
+    }
+}
+
+fn nbt_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// servers.dat as the game writes it: one entry with an icon and a texture prompt flag
+fn game_written_servers_dat() -> Vec<u8> {
+    let mut out = vec![10];
+    nbt_string(&mut out, "");
+    out.push(9);
+    nbt_string(&mut out, "servers");
+    out.push(10);
+    out.extend_from_slice(&1i32.to_be_bytes());
+    for (name, value) in [("icon", "iVBORw0KGgo="), ("ip", "mc.hypixel.net"), ("name", "Hypixel")] {
+        out.push(8);
+        nbt_string(&mut out, name);
+        nbt_string(&mut out, value);
+    }
+    out.push(1);
+    nbt_string(&mut out, "acceptTextures");
+    out.push(1);
+    out.push(0);
+    out.push(0);
+    out
+}
+
+#[test]
+fn single_server_and_server_lists_both_parse() {
+    let single = parse_naha_servers(&json!({
+        "server_name": "NAHA",
+        "server_ip": "play.naha.com",
+        "server_port": 25565,
+        "fingerprint": "abc"
+    }));
+    assert_eq!(single, vec![server("NAHA", "play.naha.com", 25565, "abc")]);
+
+    let list = parse_naha_servers(&json!({
+        "server_ip": "ignored.naha.com",
+        "servers": [
+            { "server_name": "NAHA Survival", "server_ip": "survival.naha.com", "fingerprint": "f1" },
+            { "server_name": "NAHA Creative", "server_ip": "creative.naha.com", "server_port": 25570, "fingerprint": "f2" }
+        ]
+    }));
+    assert_eq!(
+        list,
+        vec![
+            server("NAHA Survival", "survival.naha.com", 25565, "f1"),
+            server("NAHA Creative", "creative.naha.com", 25570, "f2"),
+        ]
+    );
+
+    assert_eq!(parse_naha_servers(&json!({})), vec![server("NAHA Server", "play.naha.com", 25565, "naha-server-fingerprint")]);
+}
+
+#[test]
+fn servers_are_appended_to_an_existing_list() {
+    let root = temp_dir("naha-servers");
+    let servers_dat = root.join("servers.dat");
+    std::fs::write(&servers_dat, game_written_servers_dat()).unwrap();
+    let servers = [
+        server("NAHA Survival", "survival.naha.com", 25565, "f1"),
+        server("NAHA Creative", "creative.naha.com", 25570, "f2"),
+    ];
+
+    assert_eq!(add_servers_to_list(&servers_dat, &servers).unwrap(), 2);
+    assert_eq!(add_servers_to_list(&servers_dat, &servers).unwrap(), 0);
+
+    let entry = |name: &str, ip: &str| ServerListEntry { name: name.to_string(), ip: ip.to_string() };
+    assert_eq!(
+        read_server_list(&servers_dat).unwrap(),
+        vec![
+            entry("Hypixel", "mc.hypixel.net"),
+            entry("NAHA Survival", "survival.naha.com"),
+            entry("NAHA Creative", "creative.naha.com:25570"),
+        ]
+    );
+
+    // The game's own entry keeps its extra tags byte for byte
+    let written = std::fs::read(&servers_dat).unwrap();
+    let original = game_written_servers_dat();
+    let entry_bytes = &original[18..original.len() - 1];
+    assert!(written.windows(entry_bytes.len()).any(|w| w == entry_bytes));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+fn empty_mrpack() -> Vec<u8> {
+    mrpack_bytes(&fabric_index(json!([])), &[])
+}
+
+#[tokio::test]
+async fn update_writes_every_server_into_known_hosts_and_server_list() {
+    let root = temp_dir("naha-servers");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    std::fs::write(instance.join("servers.dat"), game_written_servers_dat()).unwrap();
+    let mrpack = root.join("pack.mrpack");
+    std::fs::write(&mrpack, empty_mrpack()).unwrap();
+
+    let info = NahaModpackInfo {
+        server_name: "NAHA Survival".to_string(),
+        fingerprint: "f1".to_string(),
+        server_ip: "survival.naha.com".to_string(),
+        servers: vec![
+            server("NAHA Survival", "survival.naha.com", 25565, "f1"),
+            server("NAHA Creative", "creative.naha.com", 25570, "f2"),
+        ],
+        ..modpack_info()
+    };
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &info, false)
+        .await
+        .unwrap();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+    let hosts: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(instance.join("automodpack-known-hosts.json")).unwrap()).unwrap();
+    assert_eq!(hosts["hosts"], json!({ "survival.naha.com": "f1", "creative.naha.com:25570": "f2" }));
+    assert_eq!(read_server_list(&instance.join("servers.dat")).unwrap().len(), 3);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn servers_sharing_a_host_keep_their_own_known_hosts_entries() {
+    let root = temp_dir("naha-servers");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+    std::fs::write(instance.join("servers.dat"), game_written_servers_dat()).unwrap();
+    let mrpack = root.join("pack.mrpack");
+    std::fs::write(&mrpack, empty_mrpack()).unwrap();
+
+    let info = NahaModpackInfo {
+        server_name: "NAHA Survival".to_string(),
+        fingerprint: "f1".to_string(),
+        server_ip: "play.naha.com".to_string(),
+        servers: vec![
+            server("NAHA Survival", "play.naha.com", 25565, "f1"),
+            server("NAHA Creative", "play.naha.com", 25570, "f2"),
+        ],
+        ..modpack_info()
+    };
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance, &mrpack, &info, false)
+        .await
+        .unwrap();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+    let hosts: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(instance.join("automodpack-known-hosts.json")).unwrap()).unwrap();
+    assert_eq!(hosts["hosts"], json!({ "play.naha.com": "f1", "play.naha.com:25570": "f2" }));
+    let listed: Vec<String> = read_server_list(&instance.join("servers.dat")).unwrap().into_iter().map(|entry| entry.ip).collect();
+    assert_eq!(listed[1..], ["play.naha.com", "play.naha.com:25570"]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn server_config_syncs_the_files_clients_need() {
+    let root = temp_dir("naha-servers");
+    let info = NahaModpackInfo {
+        server_name: "NAHA Survival".to_string(),
+        fingerprint: "f1".to_string(),
+        server_ip: "survival.naha.com".to_string(),
+        server_port: 25570,
+        ..modpack_info()
+    };
+    let file = |path: &str, env: Option<(&str, &str)>| json!({
+        "path": path,
+        "hashes": {},
+        "env": env.map(|(client, server)| json!({ "client": client, "server": server })),
+        "downloads": [],
+        "fileSize": 1
+    });
+    let index: MrpackIndex = serde_json::from_value(json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": "0.2.0",
+        "name": "NAHA Fabric",
+        "files": [
+            file("mods/fabric-api-0.107.0+1.21.1.jar", Some(("required", "required"))),
+            file("mods/create-fabric-0.5.1.jar", None),
+            file("mods/sodium-fabric-0.6.0+mc1.21.1.jar", Some(("required", "unsupported"))),
+            file("mods/spark-1.10.109-fabric.jar", Some(("unsupported", "required"))),
+            file("mods/ferritecore-7.0.0-fabric.jar", Some(("optional", "optional")))
+        ],
+        "dependencies": { "minecraft": "1.21.1", "fabric-loader": "0.16.5" }
+    }))
+    .unwrap();
+
+    let path = LauncherManager::new()
+        .generate_automodpack_server_config(&root, &info, &index)
+        .await
+        .unwrap();
+
+    assert_eq!(path, root.join("automodpack").join("automodpack-server.json"));
+    let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(config["hostIp"], "survival.naha.com");
+    assert_eq!(config["hostPort"], 25570);
+    assert_eq!(config["syncedFiles"], json!(["/mods/fabric-api-0.107.0+1.21.1.jar", "/mods/create-fabric-0.5.1.jar"]));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn install_and_update_key_known_hosts_by_the_same_address() {
+    let mock = MockServer::start(vec![Route::new("/pack.mrpack", 200, empty_mrpack())]).await;
+    let root = temp_dir("naha-servers").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    let mut manager = LauncherManager::new();
+    manager.add_search_path(root.clone());
+
+    let info = NahaModpackInfo {
+        server_name: "NAHA Survival".to_string(),
+        fingerprint: "f1".to_string(),
+        download_url: mock.url("/pack.mrpack"),
+        server_ip: "203.0.113.7".to_string(),
+        servers: vec![
+            server("NAHA Survival", "203.0.113.7", 25565, "f1"),
+            server("NAHA Creative", "creative.naha.com", 25570, "f2"),
+        ],
+        ..modpack_info()
+    };
+    manager
+        .install_from_modpack_info(&info, Some("PrismLauncher"), true, None, Some("NAHA Fabric"), false)
+        .await
+        .unwrap();
+
+    let game_dir = root.join("instances").join("NAHA Fabric").join(".minecraft");
+    let hosts: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(game_dir.join("automodpack").join(".private").join("automodpack-known-hosts.json")).unwrap(),
+    )
+    .unwrap();
+    let expected = json!({ "203.0.113.7": "f1", "creative.naha.com:25570": "f2" });
+    assert_eq!(hosts["hosts"], expected);
+
+    let mrpack = root.join("pack.mrpack");
+    std::fs::write(&mrpack, empty_mrpack()).unwrap();
+    MinecraftUpdater::new()
+        .update_instance_from_mrpack(&root.join("instances").join("NAHA Fabric"), &mrpack, &info, false)
+        .await
+        .unwrap();
+    let hosts: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(game_dir.join("automodpack-known-hosts.json")).unwrap()).unwrap();
+    assert_eq!(hosts["hosts"], expected);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use common::{fabric_meta, launcher_manager, temp_dir};
+use minecraft_installer::launcher_support::{LauncherManager, LauncherType};
+use minecraft_installer::updater::MinecraftUpdater;
+
+/// Official launcher at `<root>/.minecraft` with one profile whose gameDir lives elsewhere
+fn official_launcher_with_custom_game_dir(root: &Path) -> (PathBuf, PathBuf) {
+    let launcher_path = root.join(".minecraft");
+    std::fs::create_dir_all(launcher_path.join("versions")).unwrap();
+    std::fs::create_dir_all(launcher_path.join("mods")).unwrap();
+    std::fs::write(launcher_path.join("mods").join("stray-1.0.jar"), b"not in the profile").unwrap();
+
+    let game_dir = root.join("games").join("modded");
+    std::fs::create_dir_all(game_dir.join("mods")).unwrap();
+    std::fs::write(game_dir.join("mods").join("sodium-0.5.3.jar"), b"sodium").unwrap();
+    std::fs::write(game_dir.join("mods").join("lithium-0.11.2.jar"), b"lithium").unwrap();
+
+    std::fs::write(
+        launcher_path.join("launcher_profiles.json"),
+        json!({
+            "profiles": {
+                "modded": {
+                    "name": "Modded",
+                    "type": "custom",
+                    "lastVersionId": "fabric-loader-0.16.5-1.21.1",
+                    "gameDir": game_dir.to_string_lossy()
+                }
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    (launcher_path, game_dir)
+}
+
+#[tokio::test]
+async fn scan_reads_mods_from_profile_game_dir() {
+    let root = temp_dir("official-game-dir");
+    let (launcher_path, game_dir) = official_launcher_with_custom_game_dir(&root);
+
+    let instances = MinecraftUpdater::new()
+        .scan_launcher(&LauncherType::Official, &launcher_path)
+        .await
+        .unwrap();
+
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].instance_path, game_dir.to_string_lossy());
+    assert_eq!(instances[0].mod_count, 2);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn created_instance_files_go_into_game_dir() {
+    let root = temp_dir("official-game-dir");
+    let launcher_path = root.join(".minecraft");
+    std::fs::create_dir_all(launcher_path.join("versions")).unwrap();
+    std::fs::write(launcher_path.join("launcher_profiles.json"), json!({ "profiles": {} }).to_string()).unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let game_dir = manager
+        .create_instance(&launcher_path, "NAHA-Fabric", "1.21.1", "fabric", None)
+        .await
+        .unwrap();
+
+    let temp_instance = root.join("temp-instance");
+    std::fs::create_dir_all(temp_instance.join("mods")).unwrap();
+    std::fs::write(temp_instance.join("mods").join("sodium-0.5.3.jar"), b"sodium").unwrap();
+    manager.copy_instance_files(&temp_instance, &game_dir).await.unwrap();
+
+    assert!(game_dir.join("mods").join("sodium-0.5.3.jar").exists());
+    assert!(!game_dir.join(".minecraft").exists());
+
+    let (found_launcher, profile_id) = manager.find_official_profile(&game_dir).await.unwrap();
+    assert_eq!(found_launcher, launcher_path);
+    assert_eq!(profile_id, "minecraft-installer-NAHA-Fabric");
+
+    let info = MinecraftUpdater::new().analyze_instance(&game_dir).await.unwrap().unwrap();
+    assert_eq!(info.launcher_type, "Official");
+    assert_eq!(info.mod_count, 1);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn microsoft_store_launcher_keeps_its_own_profiles_file() {
+    let root = temp_dir("official-store-profiles");
+    let launcher_path = root.join(".minecraft");
+    std::fs::create_dir_all(launcher_path.join("versions")).unwrap();
+    let store_profiles = launcher_path.join("launcher_profiles_microsoft_store.json");
+    std::fs::write(&store_profiles, json!({ "profiles": {} }).to_string()).unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let game_dir = manager
+        .create_instance(&launcher_path, "NAHA-Fabric", "1.21.1", "fabric", None)
+        .await
+        .unwrap();
+
+    assert!(!launcher_path.join("launcher_profiles.json").exists());
+    let profiles: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&store_profiles).unwrap()).unwrap();
+    assert!(profiles["profiles"]["minecraft-installer-NAHA-Fabric"].is_object());
+
+    let (found_launcher, profile_id) = manager.find_official_profile(&game_dir).await.unwrap();
+    assert_eq!(found_launcher, launcher_path);
+    assert_eq!(profile_id, "minecraft-installer-NAHA-Fabric");
+
+    let renamed = manager.rename_instance(&launcher_path, "NAHA-Fabric", "NAHA-Renamed").await.unwrap();
+    let profiles: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&store_profiles).unwrap()).unwrap();
+    assert!(profiles["profiles"]["minecraft-installer-NAHA-Renamed"].is_object());
+    assert!(renamed.is_dir());
+    assert!(!launcher_path.join("launcher_profiles.json").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn official_launcher_with_created_instances_is_not_xmcl() {
+    let root = temp_dir("official-detect");
+    let launcher_path = root.join(".minecraft");
+    std::fs::create_dir_all(launcher_path.join("versions")).unwrap();
+    std::fs::write(launcher_path.join("launcher_profiles.json"), json!({ "profiles": {} }).to_string()).unwrap();
+
+    let manager = LauncherManager::new();
+    manager.create_instance(&launcher_path, "NAHA-Fabric", "1.21.1", "vanilla", None).await.unwrap();
+    assert!(launcher_path.join("instances").is_dir());
+    assert_eq!(manager.detect_launcher_type(&launcher_path).await.unwrap(), LauncherType::Official);
+
+    // XMCL keeps instances/ next to a profiles file without any of this installer's profiles
+    let xmcl = root.join("xmcl");
+    std::fs::create_dir_all(xmcl.join("instances")).unwrap();
+    std::fs::write(xmcl.join("launcher_profiles.json"), json!({ "profiles": {} }).to_string()).unwrap();
+    assert_eq!(manager.detect_launcher_type(&xmcl).await.unwrap(), LauncherType::XMCL);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn game_dir_outside_the_launcher_is_found_through_detected_launchers() {
+    let root = temp_dir("official-game-dir");
+    let (launcher_path, game_dir) = official_launcher_with_custom_game_dir(&root);
+
+    let mut manager = LauncherManager::new();
+    assert_eq!(manager.find_official_profile(&game_dir).await, None);
+
+    // Adding a search path forgets the launchers detected before it
+    manager.add_search_path(launcher_path.clone());
+    let expected = Some((launcher_path, "modded".to_string()));
+    assert_eq!(manager.find_official_profile(&game_dir).await, expected);
+    assert_eq!(manager.find_official_profile(&game_dir).await, expected);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
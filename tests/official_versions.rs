@@ -0,0 +1,102 @@
+mod common;
+
+use std::path::Path;
+
+use common::{fabric_meta, launcher_manager, temp_dir};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::launcher_support::LauncherManager;
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn official_launcher(prefix: &str) -> std::path::PathBuf {
+    let root = temp_dir(prefix).join(".minecraft");
+    std::fs::create_dir_all(root.join("versions")).unwrap();
+    std::fs::write(root.join("launcher_profiles.json"), r#"{"profiles": {}}"#).unwrap();
+    root
+}
+
+#[tokio::test]
+async fn modded_official_profile_points_at_an_installed_version() {
+    let root = official_launcher("official-versions");
+    let meta = fabric_meta().await;
+
+    launcher_manager(&meta)
+        .create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5"))
+        .await
+        .unwrap();
+
+    let version_id = "fabric-loader-0.16.5-1.21.1";
+    let version_json = root.join("versions").join(version_id).join(format!("{}.json", version_id));
+    assert!(version_json.is_file(), "{} missing", version_json.display());
+    let version = read_json(&version_json);
+    assert_eq!(version["id"], version_id);
+    assert_eq!(version["inheritsFrom"], "1.21.1");
+    assert_eq!(version["mainClass"], "net.fabricmc.loader.impl.launch.knot.KnotClient");
+    let libraries: Vec<&str> = version["libraries"].as_array().unwrap().iter().map(|l| l["name"].as_str().unwrap()).collect();
+    for library in ["net.fabricmc:fabric-loader:0.16.5", "net.fabricmc:intermediary:1.21.1", "net.fabricmc:sponge-mixin:0.15.4+mixin.0.8.7", "org.ow2.asm:asm:9.7.1"] {
+        assert!(libraries.contains(&library), "{} missing from {:?}", library, libraries);
+    }
+    // The client jar and assets come from the inherited vanilla version
+    assert!(version.get("downloads").is_none(), "{}", version);
+    assert!(version.get("assetIndex").is_none(), "{}", version);
+
+    let profiles = read_json(&root.join("launcher_profiles.json"));
+    assert_eq!(profiles["profiles"]["minecraft-installer-NAHA-Fabric"]["lastVersionId"], version_id);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn forge_official_profile_needs_the_forge_installer_first() {
+    let root = official_launcher("official-versions");
+
+    let error = LauncherManager::new()
+        .create_instance(&root, "NAHA-Forge", "1.20.1", "forge", Some("47.2.0"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::InvalidLoader(_)), "{}", error);
+    assert!(error.to_string().contains("1.20.1-forge-47.2.0"), "{}", error);
+    let profiles = read_json(&root.join("launcher_profiles.json"));
+    assert!(profiles["profiles"].get("minecraft-installer-NAHA-Forge").is_none());
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn forge_official_profile_launches_the_installed_forge_version() {
+    let root = official_launcher("official-versions");
+    let version_dir = root.join("versions").join("1.20.1-forge-47.2.0");
+    std::fs::create_dir_all(&version_dir).unwrap();
+    let installed = r#"{"id": "1.20.1-forge-47.2.0", "inheritsFrom": "1.20.1"}"#;
+    std::fs::write(version_dir.join("1.20.1-forge-47.2.0.json"), installed).unwrap();
+
+    LauncherManager::new()
+        .create_instance(&root, "NAHA-Forge", "1.20.1", "forge", Some("47.2.0"))
+        .await
+        .unwrap();
+
+    let profiles = read_json(&root.join("launcher_profiles.json"));
+    assert_eq!(profiles["profiles"]["minecraft-installer-NAHA-Forge"]["lastVersionId"], "1.20.1-forge-47.2.0");
+    assert_eq!(std::fs::read_to_string(version_dir.join("1.20.1-forge-47.2.0.json")).unwrap(), installed);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn vanilla_official_profile_leaves_versions_to_the_launcher() {
+    let root = official_launcher("official-versions");
+
+    LauncherManager::new()
+        .create_instance(&root, "Vanilla", "1.21.1", "vanilla", None)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read_dir(root.join("versions")).unwrap().count(), 0);
+    let profiles = read_json(&root.join("launcher_profiles.json"));
+    assert_eq!(profiles["profiles"]["minecraft-installer-Vanilla"]["lastVersionId"], "1.21.1");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
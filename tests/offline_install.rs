@@ -0,0 +1,177 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{install_fake_java, seed_offline_cache, temp_dir};
+use minecraft_installer::download::DownloadOptions;
+use minecraft_installer::installer::MinecraftInstaller;
+
+#[tokio::test]
+async fn installs_vanilla_from_offline_cache() {
+    let root = temp_dir("offline-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (_, _, asset) = seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache);
+    installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap();
+
+    let minecraft = install_dir.join("minecraft");
+    assert_eq!(std::fs::read(minecraft.join("versions/1.20.1/1.20.1.jar")).unwrap(), b"client jar");
+    assert!(minecraft.join("versions/1.20.1/1.20.1.json").exists());
+    assert_eq!(
+        std::fs::read(minecraft.join("libraries/com/example/lib/1.0/lib-1.0.jar")).unwrap(),
+        b"library jar"
+    );
+    assert!(minecraft.join("assets/indexes/5.json").exists());
+    assert_eq!(
+        std::fs::read(minecraft.join("assets/objects").join(&asset[..2]).join(&asset)).unwrap(),
+        b"asset object"
+    );
+    assert!(installer.verify_assets("1.20.1").await.unwrap().is_intact());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn missing_cache_entries_are_listed() {
+    let root = temp_dir("offline-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (_, library, _) = seed_offline_cache(&cache);
+    std::fs::remove_file(cache.join(&library)).unwrap();
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir)
+        .await
+        .unwrap()
+        .with_offline_cache(cache);
+    let message = installer
+        .install_minecraft("1.20.1", "vanilla", "stable", false)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(message.contains("missing from offline cache"), "{}", message);
+    assert!(message.contains(&library), "{}", message);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn download_plan_lists_every_file_without_downloading() {
+    let root = temp_dir("offline-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (client, library, asset) = seed_offline_cache(&cache);
+
+    let installer = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache);
+    let plan = serde_json::to_value(installer.download_plan("1.20.1").await.unwrap()).unwrap();
+
+    let files: Vec<_> = plan["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["kind"].as_str().unwrap(), f["path"].as_str().unwrap(), f["sha1"].as_str().unwrap()))
+        .collect();
+    assert_eq!(plan["version"], "1.20.1");
+    assert_eq!(files.len(), 4);
+    assert_eq!(files[0], ("client", "versions/1.20.1/1.20.1.jar", client.as_str()));
+    assert_eq!(files[1], ("library", "libraries/com/example/lib/1.0/lib-1.0.jar", library.as_str()));
+    assert_eq!(files[2].0, "asset_index");
+    assert_eq!(files[3].0, "asset");
+    assert_eq!(files[3].2, asset);
+    assert_eq!(
+        plan["files"][3]["url"],
+        format!("https://resources.download.minecraft.net/{}/{}", &asset[..2], asset)
+    );
+    assert!(!install_dir.join("minecraft").join("versions").join("1.20.1").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn skipping_assets_never_reads_the_asset_index() {
+    let root = temp_dir("offline-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (_, _, asset) = seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    // Without the asset index in the cache, any attempt to read it fails the install
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(cache.join("version_manifest.json")).unwrap()).unwrap();
+    let version: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(cache.join(manifest["versions"][0]["sha1"].as_str().unwrap())).unwrap())
+            .unwrap();
+    std::fs::remove_file(cache.join(version["assetIndex"]["sha1"].as_str().unwrap())).unwrap();
+    std::fs::remove_file(cache.join(&asset)).unwrap();
+
+    let installer = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache);
+    let options = DownloadOptions { download_assets: false, ..Default::default() };
+    let report = installer
+        .install_minecraft_with_options("1.20.1", "vanilla", "stable", false, options)
+        .await
+        .unwrap();
+
+    let minecraft = install_dir.join("minecraft");
+    assert_eq!(std::fs::read(minecraft.join("versions/1.20.1/1.20.1.jar")).unwrap(), b"client jar");
+    assert!(minecraft.join("versions/1.20.1/1.20.1.json").exists());
+    assert!(minecraft.join("libraries/com/example/lib/1.0/lib-1.0.jar").exists());
+    assert!(!minecraft.join("assets/indexes/5.json").exists());
+    assert!(report.assets_skipped);
+    assert!(!report.libraries_skipped);
+    assert_eq!(report.asset_count, 0);
+    assert_eq!(report.library_count, 1);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn reinstall_skips_assets_already_present() {
+    let root = temp_dir("offline-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (_, _, asset) = seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache.clone());
+    installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap();
+
+    // With the object gone from the cache, any download would fail, and an object of the
+    // right size but different content would be replaced if it were hashed
+    std::fs::remove_file(cache.join(&asset)).unwrap();
+    let object = install_dir.join("minecraft/assets/objects").join(&asset[..2]).join(&asset);
+    std::fs::write(&object, b"ASSET OBJECT").unwrap();
+
+    installer.install_minecraft("1.20.1", "vanilla", "stable", true).await.unwrap();
+    assert_eq!(std::fs::read(&object).unwrap(), b"ASSET OBJECT");
+
+    let rehashing = MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache)
+        .with_asset_rehash();
+    let message = rehashing
+        .install_minecraft("1.20.1", "vanilla", "stable", true)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("missing from offline cache"), "{}", message);
+    assert!(message.contains(&asset), "{}", message);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use serde_json::json;
+
+use common::{modpack_info, MockServer, Route};
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn release(tag: &str, mrpack: &str, server: &MockServer) -> serde_json::Value {
+    json!({
+        "tag_name": tag,
+        "assets": [
+            {"name": "NAHA-NeoForge-1.21.1.mrpack", "browser_download_url": server.url("/missing.mrpack")},
+            {"name": mrpack, "browser_download_url": server.url(&format!("/{}", mrpack))}
+        ]
+    })
+}
+
+#[tokio::test]
+async fn previous_release_reads_only_the_first_page() {
+    let previous = format!("NAHA-Fabric-1.21.1-0.1.{}.mrpack", std::process::id());
+    let files = MockServer::start(vec![Route::new(&format!("/{}", previous), 200, "previous pack")]).await;
+    let releases = json!([
+        release("v0.2.0", "NAHA-Fabric-1.21.1-0.2.0.mrpack", &files),
+        release("v0.1.0", &previous, &files),
+        release("v0.0.1", "NAHA-Fabric-1.21.1-0.0.1.mrpack", &files),
+    ]);
+    let api = MockServer::start(vec![Route::new("/releases", 200, releases.to_string())]).await;
+
+    let path = MinecraftUpdater::new()
+        .with_modpack_releases_api_url(api.url("/releases"))
+        .get_previous_version_mrpack(&modpack_info())
+        .await
+        .unwrap()
+        .expect("previous release should be found");
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous pack");
+    assert_eq!(files.requests(), [format!("/{}", previous)]);
+    let requests = api.requests();
+    assert_eq!(requests, ["/releases?per_page=10"]);
+    assert!(!requests.iter().any(|r| r.contains("page=2")));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn oversized_releases_page_skips_the_comparison() {
+    let padding = "x".repeat(3 * 1024 * 1024);
+    let body = json!([{"tag_name": "v0.2.0", "body": padding, "assets": []}]).to_string();
+    let server = MockServer::start(vec![Route::new("/releases", 200, body)]).await;
+
+    let previous = MinecraftUpdater::new()
+        .with_modpack_releases_api_url(server.url("/releases"))
+        .get_previous_version_mrpack(&modpack_info())
+        .await
+        .unwrap();
+
+    assert!(previous.is_none());
+}
+
+#[tokio::test]
+async fn oversized_chunked_releases_page_skips_the_comparison() {
+    let padding = "x".repeat(3 * 1024 * 1024);
+    let body = json!([{"tag_name": "v0.2.0", "body": padding, "assets": []}]).to_string();
+    let server = MockServer::start(vec![Route::new("/releases", 200, body).chunked()]).await;
+
+    let previous = MinecraftUpdater::new()
+        .with_modpack_releases_api_url(server.url("/releases"))
+        .get_previous_version_mrpack(&modpack_info())
+        .await
+        .unwrap();
+
+    assert!(previous.is_none());
+}
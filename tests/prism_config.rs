@@ -0,0 +1,54 @@
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::{LauncherManager, LauncherType, PrismConfig};
+
+#[test]
+fn defaults_when_keys_missing() {
+    let root = temp_dir("prism-config");
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\nLanguage=en_US\n").unwrap();
+
+    let config = PrismConfig::load(&root);
+    assert_eq!(config.instance_dir, root.join("instances"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn absolute_instance_dir_is_kept() {
+    let root = temp_dir("prism-config");
+    let elsewhere = temp_dir("prism-config-instances");
+    std::fs::write(
+        root.join("prismlauncher.cfg"),
+        format!("[General]\nInstanceDir=\"{}\"\n", elsewhere.display()),
+    )
+    .unwrap();
+
+    let config = PrismConfig::load(&root);
+    assert_eq!(config.instance_dir, elsewhere);
+
+    std::fs::remove_dir_all(&root).unwrap();
+    std::fs::remove_dir_all(&elsewhere).unwrap();
+}
+
+#[tokio::test]
+async fn custom_instance_dir_is_detected_and_used() {
+    // Portable layout: data next to the executable, instances in a custom folder
+    let root = temp_dir("prism-portable");
+    std::fs::write(root.join("portable.txt"), "").unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\nInstanceDir=my-instances\n").unwrap();
+    std::fs::create_dir_all(root.join("my-instances")).unwrap();
+
+    let manager = LauncherManager::new();
+    assert_eq!(manager.detect_launcher_type(&root).await.unwrap(), LauncherType::Prism);
+
+    let instance = manager
+        .create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5"))
+        .await
+        .unwrap();
+    assert_eq!(instance, root.join("my-instances").join("NAHA-Fabric"));
+    assert!(instance.join("instance.cfg").exists());
+    assert!(!root.join("instances").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::temp_dir;
+use minecraft_installer::launcher_support::{LauncherManager, LauncherType, PRISM_DATA_DIR_ENV};
+use minecraft_installer::updater::MinecraftUpdater;
+
+// Only test in this binary: it sets PRISM_DATA_DIR for the whole process
+#[tokio::test]
+async fn portable_prism_instances_are_scanned_from_its_instance_dir() {
+    let root = temp_dir("prism-data-dir");
+    let portable = root.join("PrismLauncher-Windows-Portable");
+    let instances = root.join("Games").join("Prism Instances");
+    std::fs::create_dir_all(&portable).unwrap();
+    std::fs::write(portable.join("portable.txt"), "").unwrap();
+    std::fs::write(
+        portable.join("prismlauncher.cfg"),
+        format!("[General]\nInstanceDir={}\n", instances.display()),
+    )
+    .unwrap();
+    let instance = instances.join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join(".minecraft").join("mods")).unwrap();
+    std::fs::write(instance.join("instance.cfg"), "InstanceType=OneSix\nname=NAHA-Fabric\n").unwrap();
+    std::fs::write(
+        instance.join("mmc-pack.json"),
+        r#"{"formatVersion": 1, "components": [{"uid": "net.minecraft", "version": "1.21.1"}]}"#,
+    )
+    .unwrap();
+
+    std::env::set_var(PRISM_DATA_DIR_ENV, &portable);
+    let launchers = LauncherManager::new().detect_launchers().await;
+    assert!(launchers.contains(&(LauncherType::Prism, portable.clone())), "{:?}", launchers);
+
+    let scanned = MinecraftUpdater::new().scan_launcher(&LauncherType::Prism, &portable).await.unwrap();
+    assert_eq!(scanned.len(), 1);
+    assert_eq!(scanned[0].instance_path, instance.to_string_lossy());
+    assert_eq!(scanned[0].minecraft_version, "1.21.1");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
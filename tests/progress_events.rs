@@ -0,0 +1,216 @@
+mod common;
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::events::{Phase, PhaseTotal, ProgressEvent};
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn drain(receiver: &mut tokio::sync::mpsc::UnboundedReceiver<ProgressEvent>) -> Vec<ProgressEvent> {
+    let mut events = Vec::new();
+    while let Ok(event) = receiver.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+fn phases(events: &[ProgressEvent]) -> Vec<(&'static str, Phase)> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            ProgressEvent::PhaseStarted { phase, .. } => Some(("started", *phase)),
+            ProgressEvent::PhaseCompleted { phase, .. } => Some(("completed", *phase)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn install_streams_every_phase() {
+    use common::{install_fake_java, seed_offline_cache};
+    use minecraft_installer::installer::MinecraftInstaller;
+
+    let root = temp_dir("progress-events");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let mut installer = MinecraftInstaller::new(install_dir).await.unwrap().with_offline_cache(cache);
+    let mut receiver = installer.subscribe_progress();
+    installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap();
+    let events = drain(&mut receiver);
+
+    assert_eq!(
+        phases(&events),
+        vec![
+            ("started", Phase::Java),
+            ("completed", Phase::Java),
+            ("started", Phase::Client),
+            ("completed", Phase::Client),
+            ("started", Phase::Libraries),
+            ("completed", Phase::Libraries),
+            ("started", Phase::Assets),
+            ("completed", Phase::Assets),
+            ("started", Phase::Profile),
+            ("completed", Phase::Profile),
+        ]
+    );
+    assert!(events.contains(&ProgressEvent::Progress {
+        phase: Phase::Libraries,
+        instance: Some("1.20.1".to_string()),
+        file: Some("com.example:lib:1.0".to_string()),
+        bytes: 11,
+        current: 1,
+        total: 1,
+        percent: 100.0,
+    }));
+    assert!(events.contains(&ProgressEvent::Progress {
+        phase: Phase::Assets,
+        instance: Some("1.20.1".to_string()),
+        file: Some("minecraft/lang/en_us.json".to_string()),
+        bytes: 12,
+        current: 1,
+        total: 1,
+        percent: 100.0,
+    }));
+
+    let serialized = serde_json::to_value(&events[0]).unwrap();
+    assert_eq!(serialized, json!({ "event": "phase_started", "phase": "java", "instance": "1.20.1" }));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn install_plans_an_aggregate_of_every_phase() {
+    use common::{install_fake_java, seed_offline_cache};
+    use minecraft_installer::installer::MinecraftInstaller;
+
+    let root = temp_dir("progress-events");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let mut installer = MinecraftInstaller::new(install_dir).await.unwrap().with_offline_cache(cache);
+    let mut receiver = installer.subscribe_progress();
+    installer.install_minecraft("1.20.1", "vanilla", "stable", false).await.unwrap();
+    let events = drain(&mut receiver);
+
+    let planned: Vec<_> = events.iter().filter(|event| matches!(event, ProgressEvent::Planned { .. })).collect();
+    assert_eq!(planned.len(), 1);
+    let ProgressEvent::Planned { phases, total_bytes, .. } = planned[0] else { unreachable!() };
+
+    assert_eq!(
+        phases.iter().map(|total| total.phase).collect::<Vec<_>>(),
+        vec![Phase::Client, Phase::Libraries, Phase::Assets]
+    );
+    assert_eq!(*total_bytes, phases.iter().map(|total| total.bytes).sum::<u64>());
+    assert!(*total_bytes > 0);
+
+    // Client and library totals match the bytes their progress events report
+    for phase in [Phase::Client, Phase::Libraries] {
+        let reported: u64 = events
+            .iter()
+            .filter_map(|event| match event {
+                ProgressEvent::Progress { phase: p, bytes, .. } if *p == phase => Some(*bytes),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(phases.iter().find(|total| total.phase == phase).unwrap().bytes, reported, "{:?}", phase);
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn update_streams_overrides_and_update_phases() {
+    let root = temp_dir("progress-events");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance.join("mods")).unwrap();
+
+    let mrpack = root.join("pack.mrpack");
+    write_mrpack(&mrpack, &fabric_index(json!([])), &[("overrides/config/sodium-options.json", b"pack sodium")]);
+
+    let mut updater = MinecraftUpdater::new();
+    let mut receiver = updater.subscribe_progress();
+    updater.update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false).await.unwrap();
+    let events = drain(&mut receiver);
+
+    assert_eq!(
+        phases(&events),
+        vec![
+            ("started", Phase::Overrides),
+            ("completed", Phase::Overrides),
+            ("started", Phase::Update),
+            ("completed", Phase::Update),
+        ]
+    );
+    assert!(events.contains(&ProgressEvent::Log {
+        instance: Some("NAHA-Fabric".to_string()),
+        message: "Wrote 1 override files".to_string(),
+    }));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn update_reports_each_downloaded_mod() {
+    let root = temp_dir("progress-events");
+    let instance = root.join("instances").join("NAHA-Fabric");
+    let mods = instance.join("mods");
+    std::fs::create_dir_all(&mods).unwrap();
+    std::fs::write(mods.join("sodium-0.5.0.jar"), b"old sodium").unwrap();
+    std::fs::write(mods.join("lithium-0.12.0.jar"), b"lithium").unwrap();
+
+    let server = MockServer::start(vec![
+        Route::new("/sodium-0.6.0.jar", 200, b"new sodium".to_vec()),
+        Route::new("/iris-1.8.0.jar", 200, b"iris".to_vec()),
+    ])
+    .await;
+    let files: Vec<_> = [("sodium-0.6.0.jar", 10), ("lithium-0.12.0.jar", 7), ("iris-1.8.0.jar", 4)]
+        .iter()
+        .map(|(name, size)| {
+            json!({
+                "path": format!("mods/{}", name),
+                "hashes": { "sha1": "0000000000000000000000000000000000000000" },
+                "downloads": [server.url(&format!("/{}", name))],
+                "fileSize": size
+            })
+        })
+        .collect();
+    let mrpack = root.join("pack.mrpack");
+    write_mrpack(&mrpack, &fabric_index(json!(files)), &[]);
+
+    let mut updater = MinecraftUpdater::new();
+    let mut receiver = updater.subscribe_progress();
+    let result = updater.update_instance_from_mrpack(&instance, &mrpack, &modpack_info(), false).await.unwrap();
+    let events = drain(&mut receiver);
+
+    // lithium is already up to date, so only sodium and iris are fetched
+    let downloaded = result.updated_mods.len() + result.new_mods.len();
+    assert_eq!(downloaded, 2, "{:?}", result);
+    assert!(events.contains(&ProgressEvent::Planned {
+        instance: Some("NAHA-Fabric".to_string()),
+        phases: vec![PhaseTotal { phase: Phase::Update, bytes: 14 }],
+        total_bytes: 14,
+    }));
+    let advances: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            ProgressEvent::Progress { phase: Phase::Update, file, bytes, current, total, .. } => {
+                Some((file.clone().unwrap(), *bytes, *current, *total))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(advances.len(), downloaded);
+    assert_eq!(
+        advances,
+        vec![("sodium-0.6.0.jar".to_string(), 10, 1, 2), ("iris-1.8.0.jar".to_string(), 4, 2, 2)]
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
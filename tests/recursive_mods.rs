@@ -0,0 +1,71 @@
+mod common;
+
+use std::path::Path;
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, sha1_hex, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn write_pack(path: &Path, url: &str, jar: &[u8]) {
+    write_mrpack(path, &fabric_index(json!([{
+        "path": "mods/sodium-0.5.3.jar",
+        "hashes": { "sha1": sha1_hex(jar) },
+        "downloads": [url],
+        "fileSize": jar.len()
+    }])), &[]);
+}
+
+fn instance_with_nested_mod(root: &Path) -> std::path::PathBuf {
+    let instance_path = root.join("instances").join("NAHA-Fabric");
+    let optional = instance_path.join("mods").join("optional");
+    std::fs::create_dir_all(&optional).unwrap();
+    std::fs::write(optional.join("sodium-0.5.0.jar"), b"old sodium").unwrap();
+    std::fs::write(instance_path.join("mods").join("lithium-0.11.0.jar"), b"lithium").unwrap();
+    instance_path
+}
+
+#[tokio::test]
+async fn subfolder_mods_are_ignored_by_default() {
+    let root = temp_dir("recursive-mods");
+    let instance_path = instance_with_nested_mod(&root);
+    let mrpack_path = root.join("pack.mrpack");
+    write_pack(&mrpack_path, "http://127.0.0.1:9/sodium.jar", b"new sodium");
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance_path, &mrpack_path, &modpack_info(), true)
+        .await
+        .unwrap();
+
+    assert!(result.updated_mods.is_empty());
+    assert_eq!(result.new_mods, vec!["sodium-0.5.3.jar".to_string()]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn subfolder_mods_are_updated_in_place() {
+    let root = temp_dir("recursive-mods");
+    let instance_path = instance_with_nested_mod(&root);
+    let jar = b"new sodium".to_vec();
+    let server = MockServer::start(vec![Route::new("/sodium.jar", 200, jar.clone())]).await;
+    let mrpack_path = root.join("pack.mrpack");
+    write_pack(&mrpack_path, &server.url("/sodium.jar"), &jar);
+
+    let result = MinecraftUpdater::new()
+        .with_recursive_mods(true)
+        .update_instance_from_mrpack(&instance_path, &mrpack_path, &modpack_info(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(result.updated_mods, vec!["optional/sodium-0.5.0.jar → sodium-0.5.3.jar".to_string()]);
+    assert!(result.new_mods.is_empty());
+
+    let mods_dir = instance_path.join("mods");
+    assert_eq!(std::fs::read(mods_dir.join("optional").join("sodium-0.5.3.jar")).unwrap(), jar);
+    assert!(!mods_dir.join("optional").join("sodium-0.5.0.jar").exists());
+    assert!(!mods_dir.join("sodium-0.5.3.jar").exists());
+    assert!(mods_dir.join("lithium-0.11.0.jar").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
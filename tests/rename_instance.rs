@@ -0,0 +1,97 @@
+mod common;
+
+use std::path::Path;
+
+use common::{fabric_meta, launcher_manager, temp_dir};
+
+fn read_cfg_name(instance: &Path) -> Vec<String> {
+    std::fs::read_to_string(instance.join("instance.cfg"))
+        .unwrap()
+        .lines()
+        .filter(|line| line.starts_with("name="))
+        .map(str::to_string)
+        .collect()
+}
+
+#[tokio::test]
+async fn prism_rename_moves_the_folder_and_name() {
+    let root = temp_dir("rename-instance").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let source = manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5")).await.unwrap();
+    let mods = source.join(".minecraft").join("mods");
+    std::fs::create_dir_all(&mods).unwrap();
+    std::fs::write(mods.join("sodium.jar"), b"sodium").unwrap();
+
+    let renamed = manager.rename_instance(&root, "NAHA-Fabric", "NAHA: Survival").await.unwrap();
+
+    assert_eq!(renamed, root.join("instances").join("NAHA_ Survival"));
+    assert!(!source.exists());
+    assert_eq!(read_cfg_name(&renamed), vec!["name=NAHA: Survival".to_string()]);
+    assert_eq!(std::fs::read(renamed.join(".minecraft/mods/sodium.jar")).unwrap(), b"sodium");
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn rename_onto_an_existing_instance_fails() {
+    let root = temp_dir("rename-instance").join("PrismLauncher");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("prismlauncher.cfg"), "[General]\n").unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    let first = manager.create_instance(&root, "First", "1.21.1", "vanilla", None).await.unwrap();
+    let second = manager.create_instance(&root, "Second", "1.21.1", "vanilla", None).await.unwrap();
+
+    let error = manager.rename_instance(&root, "First", "Second").await.unwrap_err();
+    assert!(error.to_string().contains("already exists"), "{}", error);
+    assert_eq!(read_cfg_name(&first), vec!["name=First".to_string()]);
+    assert_eq!(read_cfg_name(&second), vec!["name=Second".to_string()]);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn official_rename_rekeys_the_profile() {
+    let root = temp_dir("rename-instance").join(".minecraft");
+    std::fs::create_dir_all(root.join("versions")).unwrap();
+    std::fs::write(root.join("launcher_profiles.json"), r#"{"profiles": {}}"#).unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    manager.create_instance(&root, "Vanilla", "1.21.1", "vanilla", None).await.unwrap();
+    let renamed = manager.rename_instance(&root, "Vanilla", "Vanilla Plus").await.unwrap();
+
+    let profiles: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(root.join("launcher_profiles.json")).unwrap()).unwrap();
+    assert!(profiles["profiles"].get("minecraft-installer-Vanilla").is_none());
+    let profile = &profiles["profiles"]["minecraft-installer-Vanilla Plus"];
+    assert_eq!(profile["name"], "Vanilla Plus");
+    assert_eq!(profile["gameDir"], renamed.to_string_lossy().as_ref());
+    assert_eq!(renamed, root.join("instances").join("Vanilla Plus"));
+    assert!(renamed.join("mods").is_dir());
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn multimc_rename_replaces_the_headerless_name() {
+    let root = temp_dir("rename-instance").join("MultiMC");
+    std::fs::create_dir_all(root.join("instances")).unwrap();
+    std::fs::write(root.join("multimc.cfg"), "InstanceDir=instances\n").unwrap();
+
+    let meta = fabric_meta().await;
+    let manager = launcher_manager(&meta);
+    manager.create_instance(&root, "NAHA-Fabric", "1.21.1", "fabric", Some("0.16.5")).await.unwrap();
+    let renamed = manager.rename_instance(&root, "NAHA-Fabric", "NAHA Survival").await.unwrap();
+
+    assert_eq!(read_cfg_name(&renamed), vec!["name=NAHA Survival".to_string()]);
+    let cfg = std::fs::read_to_string(renamed.join("instance.cfg")).unwrap();
+    assert!(cfg.contains("notes=Created by Minecraft Installer"), "{}", cfg);
+
+    std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+}
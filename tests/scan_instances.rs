@@ -0,0 +1,70 @@
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::json;
+
+use common::temp_dir;
+use minecraft_installer::updater::InstanceInfo;
+
+fn write_instance(instances_dir: &Path, folder: &str, loader: (&str, &str)) {
+    let instance = instances_dir.join(folder);
+    std::fs::create_dir_all(instance.join("minecraft").join("mods")).unwrap();
+    std::fs::write(instance.join("instance.cfg"), format!("InstanceType=OneSix\nname={}\n", folder)).unwrap();
+    std::fs::write(
+        instance.join("mmc-pack.json"),
+        json!({
+            "formatVersion": 1,
+            "components": [
+                { "cachedName": "Minecraft", "uid": "net.minecraft", "version": "1.21.1" },
+                { "cachedName": loader.0, "uid": loader.1, "cachedVersion": "0.16.5" }
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    std::fs::write(instance.join("minecraft").join("mods").join("sodium-0.6.0.jar"), b"sodium").unwrap();
+}
+
+#[test]
+fn scan_instances_prints_every_launcher_as_json() {
+    let home = temp_dir("scan-instances");
+    let prism = home.join(".local/share/PrismLauncher");
+    std::fs::create_dir_all(&prism).unwrap();
+    std::fs::write(prism.join("prismlauncher.cfg"), "[General]\n").unwrap();
+    write_instance(&prism.join("instances"), "NAHA-Fabric", ("Fabric Loader", "net.fabricmc.fabric-loader"));
+
+    // A launcher outside the default locations, found only through --launcher-path
+    let multimc = home.join("Portable").join("MultiMC");
+    std::fs::create_dir_all(&multimc).unwrap();
+    std::fs::write(multimc.join("multimc.cfg"), "InstanceDir=instances\n").unwrap();
+    write_instance(&multimc.join("instances"), "Quilt Pack", ("Quilt Loader", "org.quiltmc.quilt-loader"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_minecraft-installer"))
+        .arg("--scan-instances")
+        .arg("--launcher-path")
+        .arg(&multimc)
+        .env("HOME", &home)
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("PRISM_DATA_DIR")
+        .env_remove("MINECRAFT_INSTALLER_CONFIG")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let mut instances: Vec<InstanceInfo> = serde_json::from_slice(&output.stdout)
+        .unwrap_or_else(|e| panic!("{}: {}", e, String::from_utf8_lossy(&output.stdout)));
+    instances.sort_by(|a, b| a.name.cmp(&b.name));
+    let summary: Vec<_> = instances
+        .iter()
+        .map(|i| (i.name.as_str(), i.launcher_type.as_str(), i.mod_loader.as_str(), i.mod_count))
+        .collect();
+    assert_eq!(summary, [
+        ("NAHA-Fabric", "PrismLauncher", "Fabric", 1),
+        ("Quilt Pack", "MultiMC", "Quilt", 1),
+    ]);
+
+    std::fs::remove_dir_all(&home).unwrap();
+}
@@ -0,0 +1,95 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::path::Path;
+
+use common::{install_fake_java, seed_offline_cache, sha1_hex, temp_dir};
+use minecraft_installer::error::MinecraftInstallerError;
+use minecraft_installer::installer::MinecraftInstaller;
+
+/// Give the seeded 1.20.1 a server download served from the cache
+fn add_server_download(cache: &Path, server_jar: &[u8]) {
+    let server_sha1 = sha1_hex(server_jar);
+    std::fs::write(cache.join(&server_sha1), server_jar).unwrap();
+
+    let manifest_path = cache.join("version_manifest.json");
+    let mut manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+    let entry = &mut manifest["versions"][0];
+    let mut details: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(cache.join(entry["sha1"].as_str().unwrap())).unwrap()).unwrap();
+    details["downloads"]["server"] = serde_json::json!({
+        "sha1": server_sha1,
+        "size": server_jar.len(),
+        "url": "https://piston-data.mojang.com/v1/objects/server.jar"
+    });
+
+    let details = details.to_string();
+    let details_sha1 = sha1_hex(details.as_bytes());
+    std::fs::write(cache.join(&details_sha1), details).unwrap();
+    entry["sha1"] = details_sha1.into();
+    std::fs::write(&manifest_path, manifest.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn server_jar_is_downloaded_and_verified() {
+    let root = temp_dir("server-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (_, _, asset) = seed_offline_cache(&cache);
+    add_server_download(&cache, b"server jar");
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir.clone()).await.unwrap().with_offline_cache(cache);
+    let server_jar = installer.install_server("1.20.1").await.unwrap();
+
+    let version_dir = install_dir.join("minecraft/versions/1.20.1");
+    assert_eq!(server_jar, version_dir.join("server.jar"));
+    assert_eq!(sha1_hex(&std::fs::read(&server_jar).unwrap()), sha1_hex(b"server jar"));
+    assert!(std::fs::read_to_string(version_dir.join("eula.txt")).unwrap().contains("eula=false"));
+    assert!(std::fs::read_to_string(version_dir.join("server.properties")).unwrap().contains("server-port=25565"));
+
+    // Client-only files are skipped
+    assert!(!version_dir.join("1.20.1.jar").exists());
+    assert!(!install_dir.join("minecraft/assets/objects").join(&asset[..2]).join(&asset).exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn accepted_eula_is_kept_on_reinstall() {
+    let root = temp_dir("server-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    add_server_download(&cache, b"server jar");
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir.clone()).await.unwrap().with_offline_cache(cache);
+    installer.install_server("1.20.1").await.unwrap();
+    let eula = install_dir.join("minecraft/versions/1.20.1/eula.txt");
+    std::fs::write(&eula, "eula=true\n").unwrap();
+    installer.install_server("1.20.1").await.unwrap();
+
+    assert_eq!(std::fs::read_to_string(&eula).unwrap(), "eula=true\n");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn version_without_a_server_download_is_rejected() {
+    let root = temp_dir("server-install");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    let installer = MinecraftInstaller::new(install_dir.clone()).await.unwrap().with_offline_cache(cache);
+    let error = installer.install_server("1.20.1").await.unwrap_err();
+
+    assert!(matches!(error, MinecraftInstallerError::InvalidVersion(_)), "{}", error);
+    assert!(error.to_string().contains("no server download"), "{}", error);
+    assert!(!install_dir.join("minecraft/versions/1.20.1/server.jar").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,63 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::os::unix::fs::MetadataExt;
+use common::{install_fake_java, seed_offline_cache, temp_dir};
+use minecraft_installer::installer::MinecraftInstaller;
+
+#[tokio::test]
+async fn install_dirs_share_one_copy_of_each_asset() {
+    let root = temp_dir("shared-assets");
+    let cache = root.join("cache");
+    let store = root.join("store");
+    let (_, _, asset) = seed_offline_cache(&cache);
+
+    let mut objects = Vec::new();
+    for name in ["install-a", "install-b"] {
+        let install_dir = root.join(name);
+        install_fake_java(&install_dir);
+        MinecraftInstaller::new(install_dir.clone())
+            .await
+            .unwrap()
+            .with_offline_cache(cache.clone())
+            .with_shared_assets_at(store.clone())
+            .install_minecraft("1.20.1", "vanilla", "stable", false)
+            .await
+            .unwrap();
+        assert!(!install_dir.join("shared-assets").exists());
+        objects.push(install_dir.join("minecraft/assets/objects").join(&asset[..2]).join(&asset));
+    }
+
+    let stored_meta = std::fs::metadata(store.join(&asset)).unwrap();
+    assert_eq!(stored_meta.nlink(), 3);
+    for object in &objects {
+        assert_eq!(std::fs::metadata(object).unwrap().ino(), stored_meta.ino());
+        assert_eq!(std::fs::read(object).unwrap(), b"asset object");
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn assets_are_plain_files_without_the_option() {
+    let root = temp_dir("shared-assets");
+    let cache = root.join("cache");
+    let install_dir = root.join("install");
+    let (_, _, asset) = seed_offline_cache(&cache);
+    install_fake_java(&install_dir);
+
+    MinecraftInstaller::new(install_dir.clone())
+        .await
+        .unwrap()
+        .with_offline_cache(cache)
+        .install_minecraft("1.20.1", "vanilla", "stable", false)
+        .await
+        .unwrap();
+
+    let object = install_dir.join("minecraft/assets/objects").join(&asset[..2]).join(&asset);
+    assert_eq!(std::fs::metadata(&object).unwrap().nlink(), 1);
+    assert!(!install_dir.join("shared-assets").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
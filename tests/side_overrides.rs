@@ -0,0 +1,126 @@
+mod common;
+
+use std::path::Path;
+
+use serde_json::json;
+
+use common::{fabric_index, sha1_hex, temp_dir, write_mrpack, MockServer, Route};
+use minecraft_installer::launcher_support::{LauncherManager, OverrideOptions, OverridePolicy, PackSide};
+
+fn write_pack(path: &Path) {
+    write_mrpack(path, &fabric_index(json!([])), &[
+        ("overrides/config/sodium-options.json", b"shared sodium"),
+        ("overrides/config/shared.toml", b"shared"),
+        ("client-overrides/config/sodium-options.json", b"client sodium"),
+        ("client-overrides/options.txt", b"client options"),
+        ("server-overrides/server.properties", b"motd=NAHA"),
+    ]);
+}
+
+#[tokio::test]
+async fn client_install_gets_client_overrides_on_top() {
+    let root = temp_dir("side-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack);
+    let instance = root.join("client");
+
+    LauncherManager::new().install_mrpack(&mrpack, &instance, "client").await.unwrap();
+
+    assert_eq!(std::fs::read_to_string(instance.join("config/sodium-options.json")).unwrap(), "client sodium");
+    assert_eq!(std::fs::read_to_string(instance.join("config/shared.toml")).unwrap(), "shared");
+    assert_eq!(std::fs::read_to_string(instance.join("options.txt")).unwrap(), "client options");
+    assert!(!instance.join("server.properties").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn server_install_skips_client_overrides() {
+    let root = temp_dir("side-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack);
+    let instance = root.join("server");
+
+    LauncherManager::new()
+        .install_mrpack_with_options(&mrpack, &instance, "server", OverrideOptions::fresh_install().with_side(PackSide::Server))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read_to_string(instance.join("config/sodium-options.json")).unwrap(), "shared sodium");
+    assert_eq!(std::fs::read_to_string(instance.join("server.properties")).unwrap(), "motd=NAHA");
+    assert!(!instance.join("options.txt").exists());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn client_overrides_still_win_when_keeping_existing_files() {
+    let root = temp_dir("side-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack(&mrpack);
+    let instance = root.join("client");
+    std::fs::create_dir_all(&instance).unwrap();
+    std::fs::write(instance.join("options.txt"), "user options").unwrap();
+
+    let written = LauncherManager::new()
+        .apply_mrpack_overrides(&mrpack, &instance, OverridePolicy::SkipExisting)
+        .await
+        .unwrap();
+
+    assert_eq!(written, 2);
+    assert_eq!(std::fs::read_to_string(instance.join("config/sodium-options.json")).unwrap(), "client sodium");
+    assert_eq!(std::fs::read_to_string(instance.join("options.txt")).unwrap(), "user options");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+fn write_pack_with_mods(path: &Path, server: &MockServer) {
+    let file = |name: &str, data: &[u8], env: serde_json::Value| json!({
+        "path": format!("mods/{}", name),
+        "hashes": { "sha1": sha1_hex(data) },
+        "downloads": [server.url(&format!("/{}", name))],
+        "fileSize": data.len(),
+        "env": env
+    });
+    // Sodium is wrongly marked as required on the server, as many packs do
+    let required = json!({ "client": "required", "server": "required" });
+    let files = json!([
+        file("sodium-fabric-0.6.0+mc1.21.1.jar", b"sodium", required.clone()),
+        file("lithium-fabric-0.14.0.jar", b"lithium", required)
+    ]);
+    write_mrpack(path, &fabric_index(files), &[]);
+}
+
+#[tokio::test]
+async fn server_install_drops_known_client_only_mods() {
+    let server = MockServer::start(vec![
+        Route::new("/sodium-fabric-0.6.0+mc1.21.1.jar", 200, b"sodium".to_vec()),
+        Route::new("/lithium-fabric-0.14.0.jar", 200, b"lithium".to_vec()),
+    ])
+    .await;
+    let root = temp_dir("side-overrides");
+    let mrpack = root.join("pack.mrpack");
+    write_pack_with_mods(&mrpack, &server);
+    let instance = root.join("server");
+
+    LauncherManager::new()
+        .install_mrpack_with_options(&mrpack, &instance, "server", OverrideOptions::fresh_install().with_side(PackSide::Server))
+        .await
+        .unwrap();
+
+    assert!(!instance.join("mods/sodium-fabric-0.6.0+mc1.21.1.jar").exists());
+    assert!(instance.join("mods/lithium-fabric-0.14.0.jar").is_file());
+    assert_eq!(server.requests(), ["/lithium-fabric-0.14.0.jar"]);
+
+    // With the list cleared, only the pack's env decides
+    let mut manager = LauncherManager::new();
+    manager.set_client_only_mods(Vec::new());
+    let trusting = root.join("trusting");
+    manager
+        .install_mrpack_with_options(&mrpack, &trusting, "server", OverrideOptions::fresh_install().with_side(PackSide::Server))
+        .await
+        .unwrap();
+    assert!(trusting.join("mods/sodium-fabric-0.6.0+mc1.21.1.jar").is_file());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
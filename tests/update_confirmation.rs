@@ -0,0 +1,84 @@
+mod common;
+
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, temp_dir, write_mrpack};
+use minecraft_installer::updater::{confirm_update, ConfirmPrompt, MinecraftUpdater, UpdateResult};
+
+/// Answers every question the same way and remembers what it was asked
+struct RecordingPrompt {
+    answer: bool,
+    questions: Vec<String>,
+}
+
+impl ConfirmPrompt for RecordingPrompt {
+    fn confirm(&mut self, question: &str) -> bool {
+        self.questions.push(question.to_string());
+        self.answer
+    }
+}
+
+fn mrpack_file(path: &str) -> serde_json::Value {
+    json!({
+        "path": path,
+        "hashes": { "sha1": "0000000000000000000000000000000000000000" },
+        "downloads": [format!("http://127.0.0.1:9/{}", path)],
+        "fileSize": 1
+    })
+}
+
+async fn planned_update() -> UpdateResult {
+    let root = temp_dir("update-confirmation");
+    let instance_path = root.join("instances").join("NAHA-Fabric");
+    let mods_dir = instance_path.join("mods");
+    std::fs::create_dir_all(&mods_dir).unwrap();
+    std::fs::write(mods_dir.join("sodium-0.5.0.jar"), b"old sodium").unwrap();
+    std::fs::write(mods_dir.join("lithium-0.11.0.jar"), b"old lithium").unwrap();
+    std::fs::write(mods_dir.join("entityculling-1.6.0.jar"), b"current").unwrap();
+    std::fs::write(mods_dir.join("mycoolmod-1.0.jar"), b"user mod").unwrap();
+
+    let mrpack_path = root.join("pack.mrpack");
+    write_mrpack(&mrpack_path, &fabric_index(json!([
+        mrpack_file("mods/sodium-0.5.3.jar"),
+        mrpack_file("mods/lithium-0.11.2.jar"),
+        mrpack_file("mods/entityculling-1.6.0.jar"),
+        mrpack_file("mods/iris-1.7.0.jar")
+    ])), &[]);
+
+    let plan = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance_path, &mrpack_path, &modpack_info(), true)
+        .await
+        .unwrap();
+    std::fs::remove_dir_all(&root).unwrap();
+    plan
+}
+
+#[tokio::test]
+async fn summary_counts_match_the_plan() {
+    let plan = planned_update().await;
+    let mut summary = plan.summary();
+    summary.updated.sort();
+    summary.removed.sort();
+
+    assert_eq!(summary.added, vec!["iris-1.7.0.jar".to_string()]);
+    assert_eq!(summary.updated, vec!["lithium-0.11.2.jar".to_string(), "sodium-0.5.3.jar".to_string()]);
+    assert_eq!(summary.removed, vec!["lithium-0.11.0.jar".to_string(), "sodium-0.5.0.jar".to_string()]);
+    assert_eq!(summary.preserved, vec!["mycoolmod-1.0.jar".to_string()]);
+}
+
+#[tokio::test]
+async fn prompt_decides_unless_yes_is_given() {
+    let plan = planned_update().await;
+
+    let mut declining = RecordingPrompt { answer: false, questions: Vec::new() };
+    assert!(!confirm_update(&plan, &mut declining, false));
+    assert_eq!(declining.questions.len(), 1);
+
+    let mut accepting = RecordingPrompt { answer: true, questions: Vec::new() };
+    assert!(confirm_update(&plan, &mut accepting, false));
+
+    let mut unused = RecordingPrompt { answer: false, questions: Vec::new() };
+    assert!(confirm_update(&plan, &mut unused, true));
+    assert!(unused.questions.is_empty());
+}
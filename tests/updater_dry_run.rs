@@ -0,0 +1,68 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use common::{fabric_index, modpack_info, temp_dir, write_mrpack};
+use minecraft_installer::updater::MinecraftUpdater;
+
+fn snapshot(root: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+    let mut files = BTreeMap::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.insert(path.clone(), Vec::new());
+                stack.push(path);
+            } else {
+                files.insert(path.clone(), std::fs::read(&path).unwrap());
+            }
+        }
+    }
+    files
+}
+
+fn mrpack_file(path: &str) -> serde_json::Value {
+    json!({
+        "path": path,
+        "hashes": { "sha1": "0000000000000000000000000000000000000000" },
+        "downloads": [format!("http://127.0.0.1:9/{}", path)],
+        "fileSize": 1
+    })
+}
+
+#[tokio::test]
+async fn dry_run_leaves_instance_untouched() {
+    let root = temp_dir("updater-dry-run");
+    let instance_path = root.join("instances").join("NAHA-Fabric");
+    std::fs::create_dir_all(instance_path.join("mods")).unwrap();
+    std::fs::write(instance_path.join("mods").join("sodium-0.5.0.jar"), b"old sodium").unwrap();
+    std::fs::write(instance_path.join("mods").join("lithium-0.11.0.jar"), b"lithium").unwrap();
+    std::fs::write(instance_path.join("mods").join("mycoolmod-1.0.jar"), b"user mod").unwrap();
+
+    let mrpack_path = root.join("pack.mrpack");
+    write_mrpack(&mrpack_path, &fabric_index(json!([
+        mrpack_file("mods/sodium-0.5.3.jar"),
+        mrpack_file("mods/lithium-0.11.0.jar"),
+        mrpack_file("mods/iris-1.7.0.jar")
+    ])), &[]);
+
+    let before = snapshot(&instance_path);
+
+    let result = MinecraftUpdater::new()
+        .update_instance_from_mrpack(&instance_path, &mrpack_path, &modpack_info(), true)
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot(&instance_path), before);
+    assert!(result.dry_run);
+    assert!(result.success);
+    assert_eq!(result.updated_mods, vec!["sodium-0.5.0.jar → sodium-0.5.3.jar".to_string()]);
+    assert_eq!(result.new_mods, vec!["iris-1.7.0.jar".to_string()]);
+    assert_eq!(result.preserved_mods, vec!["mycoolmod-1.0.jar".to_string()]);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}